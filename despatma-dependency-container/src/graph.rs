@@ -0,0 +1,291 @@
+use std::{cell::RefCell, rc::Rc};
+
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::{ext::IdentExt, Type};
+
+use crate::processing::{self, Lifetime};
+
+/// The wiring of the container which can be rendered as a DOT or Mermaid diagram
+#[cfg_attr(test, derive(Eq, PartialEq, Debug))]
+pub struct Graph {
+    name: String,
+    nodes: Vec<Node>,
+    edges: Vec<Edge>,
+}
+
+#[cfg_attr(test, derive(Eq, PartialEq, Debug))]
+struct Node {
+    name: String,
+    lifetime: &'static str,
+    is_async: bool,
+    is_boxed: bool,
+}
+
+/// The `argument` of the dependency `from` requesting the dependency `to`. Deferred edges are through a `Lazy` or
+/// `Provider` handle.
+#[cfg_attr(test, derive(Eq, PartialEq, Debug))]
+struct Edge {
+    from: String,
+    to: String,
+    argument: String,
+    is_deferred: bool,
+}
+
+impl Graph {
+    pub fn new(self_ty: &Type, dependencies: &[Rc<RefCell<processing::Dependency>>]) -> Self {
+        let name = match self_ty {
+            Type::Path(path) => path
+                .path
+                .segments
+                .last()
+                .map(|segment| segment.ident.unraw().to_string()),
+            _ => None,
+        }
+        .unwrap_or_else(|| "Container".to_string());
+
+        let mut nodes: Vec<Node> = dependencies
+            .iter()
+            .map(|dependency| {
+                let dependency = dependency.borrow();
+
+                Node {
+                    name: dependency.sig.ident.unraw().to_string(),
+                    lifetime: match dependency.lifetime {
+                        Lifetime::Transient(_) => "Transient",
+                        Lifetime::Scoped(_) => "Scoped",
+                        Lifetime::Singleton(_) => "Singleton",
+                        Lifetime::Embedded(_) => "Embedded",
+                    },
+                    is_async: dependency.is_async,
+                    is_boxed: dependency.pointer.is_some(),
+                }
+            })
+            .collect();
+
+        // The `#[cfg]` alternatives of a dependency are drawn as one node
+        let mut names = Vec::new();
+        nodes.retain(|node| {
+            let is_first = !names.contains(&node.name);
+            names.push(node.name.clone());
+            is_first
+        });
+
+        let edges = dependencies
+            .iter()
+            .flat_map(|dependency| {
+                let dependency = dependency.borrow();
+                let from = dependency.sig.ident.unraw().to_string();
+
+                let children = dependency
+                    .dependencies
+                    .iter()
+                    .map(|child| (&child.inner, &child.ident, false));
+                let deferred = dependency
+                    .deferred
+                    .iter()
+                    .map(|deferred| (&deferred.inner, &deferred.ident, true));
+
+                children
+                    .chain(deferred)
+                    .map(|(inner, argument, is_deferred)| Edge {
+                        from: from.clone(),
+                        to: inner.borrow().sig.ident.unraw().to_string(),
+                        argument: argument.unraw().to_string(),
+                        is_deferred,
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+
+        Self { name, nodes, edges }
+    }
+
+    fn to_dot(&self) -> String {
+        let mut dot = format!("digraph {} {{\n    node [shape=box];\n", self.name);
+
+        for node in &self.nodes {
+            dot.push_str(&format!(
+                "    \"{}\" [label=\"{}\\n{}\"];\n",
+                node.name,
+                node.name,
+                node.description()
+            ));
+        }
+
+        for edge in &self.edges {
+            let mut attributes = Vec::new();
+
+            if edge.argument != edge.to {
+                attributes.push(format!("label=\"{}\"", edge.argument));
+            }
+
+            if edge.is_deferred {
+                attributes.push("style=dashed".to_string());
+            }
+
+            if attributes.is_empty() {
+                dot.push_str(&format!("    \"{}\" -> \"{}\";\n", edge.from, edge.to));
+            } else {
+                dot.push_str(&format!(
+                    "    \"{}\" -> \"{}\" [{}];\n",
+                    edge.from,
+                    edge.to,
+                    attributes.join(", ")
+                ));
+            }
+        }
+
+        dot.push_str("}\n");
+
+        dot
+    }
+
+    fn to_mermaid(&self) -> String {
+        let mut mermaid = "graph TD\n".to_string();
+
+        for node in &self.nodes {
+            mermaid.push_str(&format!(
+                "    {}[\"{}<br/>{}\"]\n",
+                node.name,
+                node.name,
+                node.description()
+            ));
+        }
+
+        for edge in &self.edges {
+            let arrow = match (edge.argument == edge.to, edge.is_deferred) {
+                (true, false) => "-->".to_string(),
+                (true, true) => "-.->".to_string(),
+                (false, false) => format!("-- {} -->", edge.argument),
+                (false, true) => format!("-. {} .->", edge.argument),
+            };
+
+            mermaid.push_str(&format!("    {} {} {}\n", edge.from, arrow, edge.to));
+        }
+
+        mermaid
+    }
+}
+
+impl Node {
+    fn description(&self) -> String {
+        let mut description = self.lifetime.to_string();
+
+        if self.is_async {
+            description.push_str(", async");
+        }
+
+        if self.is_boxed {
+            description.push_str(", boxed");
+        }
+
+        description
+    }
+}
+
+impl ToTokens for Graph {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let dot = self.to_dot();
+        let mermaid = self.to_mermaid();
+        let dot_doc =
+            " The dependencies of this container and how they are wired together in the DOT format";
+        let mermaid_doc =
+            " The dependencies of this container and how they are wired together as a Mermaid flowchart";
+
+        tokens.extend(quote! {
+            #[doc = #dot_doc]
+            pub fn dependency_graph_dot() -> &'static str {
+                #dot
+            }
+
+            #[doc = #mermaid_doc]
+            pub fn dependency_graph_mermaid() -> &'static str {
+                #mermaid
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use syn::parse_quote;
+
+    use crate::input;
+
+    use super::*;
+
+    fn graph() -> Graph {
+        let mut container: processing::Container = input::Container::from_item_impl(parse_quote!(
+            impl DependencyContainer {
+                fn new(config: Config) {}
+
+                #[Singleton]
+                async fn db(&self, config: &Config) -> Db {
+                    Db::connect(config).await
+                }
+
+                #[Scoped]
+                fn data_layer(&self, db: &Db) -> Box<dyn DataLayer> {
+                    Box::new(Sqlite::new(db))
+                }
+
+                fn service(&self, #[from(data_layer)] source: &Box<dyn DataLayer>) -> Service {
+                    Service::new(source)
+                }
+
+                fn report(&self, service: Lazy<Service>, #[from(db)] fresh: Provider<Db>) -> Report {
+                    Report::new(service, fresh)
+                }
+            }
+        ))
+        .into();
+
+        // The handles are types of `despatma`
+        container.options.despatma = true;
+        container.process();
+
+        Graph::new(&parse_quote!(DependencyContainer), &container.dependencies)
+    }
+
+    #[test]
+    fn to_dot() {
+        assert_eq!(
+            graph().to_dot(),
+            r#"digraph DependencyContainer {
+    node [shape=box];
+    "db" [label="db\nSingleton, async"];
+    "data_layer" [label="data_layer\nScoped, async, boxed"];
+    "service" [label="service\nTransient, async"];
+    "report" [label="report\nTransient"];
+    "config" [label="config\nEmbedded"];
+    "db" -> "config";
+    "data_layer" -> "db";
+    "service" -> "data_layer" [label="source"];
+    "report" -> "service" [style=dashed];
+    "report" -> "db" [label="fresh", style=dashed];
+}
+"#
+        );
+    }
+
+    #[test]
+    fn to_mermaid() {
+        assert_eq!(
+            graph().to_mermaid(),
+            r#"graph TD
+    db["db<br/>Singleton, async"]
+    data_layer["data_layer<br/>Scoped, async, boxed"]
+    service["service<br/>Transient, async"]
+    report["report<br/>Transient"]
+    config["config<br/>Embedded"]
+    db --> config
+    data_layer --> db
+    service -- source --> data_layer
+    report -.-> service
+    report -. fresh .-> db
+"#
+        );
+    }
+}
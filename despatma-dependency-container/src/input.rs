@@ -1,14 +1,95 @@
 use proc_macro_error2::emit_error;
-use syn::{Attribute, ImplItem, ImplItemFn, ItemImpl, Type, Visibility};
+use syn::{
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    Attribute, Ident, ImplItem, ImplItemFn, ItemImpl, Meta, Path, Token, Type, Visibility,
+};
 
 #[cfg_attr(test, derive(Eq, PartialEq, Debug))]
 pub struct Container {
     pub(crate) vis: Visibility,
+    pub(crate) options: Options,
     pub(crate) attrs: Vec<Attribute>,
     pub(crate) self_ty: Type,
     pub(crate) dependencies: Vec<ImplItemFn>,
 }
 
+/// The arguments of the attribute itself. Ie `pub, sync` in `#[dependency_container(pub, sync)]`
+#[cfg_attr(test, derive(Eq, PartialEq, Debug))]
+pub struct Arguments {
+    pub(crate) vis: Visibility,
+    pub(crate) options: Options,
+}
+
+/// Options changing how the container is generated
+#[derive(Clone, Default)]
+#[cfg_attr(test, derive(Eq, PartialEq, Debug))]
+pub struct Options {
+    /// Generate a container that can be shared between threads
+    pub(crate) sync: bool,
+    /// Fall back to finding a dependency by its type when no dependency has the name of an argument
+    pub(crate) by_type: bool,
+    /// The named scopes of the container from the outermost to the innermost. Ie `tenant, request` in
+    /// `scopes(tenant, request)`
+    pub(crate) scopes: Vec<Ident>,
+    /// Smart pointers of the user which are handled like `Arc` and `Rc`. Ie `Shared` in `pointers(Shared)`
+    pub(crate) pointers: Vec<Path>,
+    /// Hand out owned `Arc` handles to every singleton and scoped dependency so that the container can be `'static`
+    pub(crate) shared: bool,
+    /// Leave out the generated `Debug` implementation so that the user can write their own
+    pub(crate) no_debug: bool,
+    /// Implement the `Provides` traits of `despatma` for the type of every dependency
+    pub(crate) provides: bool,
+    /// Generate `override_` methods to replace how dependencies are created, ie in tests
+    pub(crate) overrides: bool,
+    /// Count how many times each dependency is created for the `constructions` method
+    pub(crate) constructions: bool,
+    /// Whether the macro is called through `despatma`. So the container can use the types of `despatma::container`
+    pub(crate) despatma: bool,
+}
+
+impl Parse for Arguments {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let vis: Visibility = input.parse()?;
+
+        if !matches!(vis, Visibility::Inherited) && !input.is_empty() {
+            input.parse::<Token![,]>()?;
+        }
+
+        let mut options = Options::default();
+
+        for meta in Punctuated::<Meta, Token![,]>::parse_terminated(input)? {
+            match meta {
+                Meta::Path(path) if path.is_ident("sync") => options.sync = true,
+                Meta::Path(path) if path.is_ident("by_type") => options.by_type = true,
+                Meta::Path(path) if path.is_ident("shared") => options.shared = true,
+                Meta::Path(path) if path.is_ident("no_debug") => options.no_debug = true,
+                Meta::Path(path) if path.is_ident("provides") => options.provides = true,
+                Meta::Path(path) if path.is_ident("overrides") => options.overrides = true,
+                Meta::Path(path) if path.is_ident("constructions") => options.constructions = true,
+                Meta::List(list) if list.path.is_ident("scopes") => {
+                    options.scopes = list
+                        .parse_args_with(Punctuated::<Ident, Token![,]>::parse_terminated)?
+                        .into_iter()
+                        .collect();
+                }
+                Meta::List(list) if list.path.is_ident("pointers") => {
+                    options.pointers = list
+                        .parse_args_with(Punctuated::<Path, Token![,]>::parse_terminated)?
+                        .into_iter()
+                        .collect();
+                }
+                meta => emit_error!(
+                    meta, "This option is not supported";
+                    hint = "The supported options are: `sync`, `by_type`, `shared`, `no_debug`, `provides`, `overrides`, `constructions`, `scopes(...)`, `pointers(...)`"
+                ),
+            }
+        }
+
+        Ok(Self { vis, options })
+    }
+}
+
 impl Container {
     pub fn from_item_impl(item_impl: ItemImpl) -> Self {
         let dependencies = item_impl
@@ -25,6 +106,7 @@ impl Container {
 
         Self {
             vis: Visibility::Inherited,
+            options: Default::default(),
             attrs: item_impl.attrs,
             self_ty: item_impl.self_ty.as_ref().clone(),
             dependencies,
@@ -34,6 +116,10 @@ impl Container {
     pub fn set_visibility(&mut self, vis: Visibility) {
         self.vis = vis;
     }
+
+    pub fn set_options(&mut self, options: Options) {
+        self.options = options;
+    }
 }
 
 #[cfg(test)]
@@ -85,6 +171,7 @@ mod tests {
         ));
         let expected = Container {
             vis: Visibility::Inherited,
+            options: Default::default(),
             attrs: vec![],
             self_ty: parse_quote!(DependencyContainer),
             dependencies: vec![
@@ -136,4 +223,235 @@ mod tests {
 
         assert_eq!(container, expected);
     }
+
+    #[test]
+    fn parse_arguments() {
+        let arguments: Arguments = parse_quote!();
+        let expected = Arguments {
+            vis: Visibility::Inherited,
+            options: Options {
+                sync: false,
+                by_type: false,
+                scopes: vec![],
+                pointers: vec![],
+                shared: false,
+                no_debug: false,
+                provides: false,
+                overrides: false,
+                constructions: false,
+                despatma: false,
+            },
+        };
+
+        assert_eq!(arguments, expected);
+
+        let arguments: Arguments = parse_quote!(pub(crate));
+        let expected = Arguments {
+            vis: parse_quote!(pub(crate)),
+            options: Options {
+                sync: false,
+                by_type: false,
+                scopes: vec![],
+                pointers: vec![],
+                shared: false,
+                no_debug: false,
+                provides: false,
+                overrides: false,
+                constructions: false,
+                despatma: false,
+            },
+        };
+
+        assert_eq!(arguments, expected);
+
+        let arguments: Arguments = parse_quote!(sync);
+        let expected = Arguments {
+            vis: Visibility::Inherited,
+            options: Options {
+                sync: true,
+                by_type: false,
+                scopes: vec![],
+                pointers: vec![],
+                shared: false,
+                no_debug: false,
+                provides: false,
+                overrides: false,
+                constructions: false,
+                despatma: false,
+            },
+        };
+
+        assert_eq!(arguments, expected);
+
+        let arguments: Arguments = parse_quote!(pub, sync);
+        let expected = Arguments {
+            vis: parse_quote!(pub),
+            options: Options {
+                sync: true,
+                by_type: false,
+                scopes: vec![],
+                pointers: vec![],
+                shared: false,
+                no_debug: false,
+                provides: false,
+                overrides: false,
+                constructions: false,
+                despatma: false,
+            },
+        };
+
+        assert_eq!(arguments, expected);
+
+        let arguments: Arguments = parse_quote!(pub, sync, by_type);
+        let expected = Arguments {
+            vis: parse_quote!(pub),
+            options: Options {
+                sync: true,
+                by_type: true,
+                scopes: vec![],
+                pointers: vec![],
+                shared: false,
+                no_debug: false,
+                provides: false,
+                overrides: false,
+                constructions: false,
+                despatma: false,
+            },
+        };
+
+        assert_eq!(arguments, expected);
+
+        let arguments: Arguments = parse_quote!(sync, scopes(tenant, request));
+        let expected = Arguments {
+            vis: Visibility::Inherited,
+            options: Options {
+                sync: true,
+                by_type: false,
+                scopes: vec![parse_quote!(tenant), parse_quote!(request)],
+                pointers: vec![],
+                shared: false,
+                no_debug: false,
+                provides: false,
+                overrides: false,
+                constructions: false,
+                despatma: false,
+            },
+        };
+
+        assert_eq!(arguments, expected);
+
+        let arguments: Arguments = parse_quote!(pointers(Shared, crate::Handle));
+        let expected = Arguments {
+            vis: Visibility::Inherited,
+            options: Options {
+                sync: false,
+                by_type: false,
+                scopes: vec![],
+                pointers: vec![parse_quote!(Shared), parse_quote!(crate::Handle)],
+                shared: false,
+                no_debug: false,
+                provides: false,
+                overrides: false,
+                constructions: false,
+                despatma: false,
+            },
+        };
+
+        assert_eq!(arguments, expected);
+
+        let arguments: Arguments = parse_quote!(sync, shared);
+        let expected = Arguments {
+            vis: Visibility::Inherited,
+            options: Options {
+                sync: true,
+                by_type: false,
+                scopes: vec![],
+                pointers: vec![],
+                shared: true,
+                no_debug: false,
+                provides: false,
+                overrides: false,
+                constructions: false,
+                despatma: false,
+            },
+        };
+
+        assert_eq!(arguments, expected);
+
+        let arguments: Arguments = parse_quote!(no_debug);
+        let expected = Arguments {
+            vis: Visibility::Inherited,
+            options: Options {
+                sync: false,
+                by_type: false,
+                scopes: vec![],
+                pointers: vec![],
+                shared: false,
+                no_debug: true,
+                provides: false,
+                overrides: false,
+                constructions: false,
+                despatma: false,
+            },
+        };
+
+        assert_eq!(arguments, expected);
+
+        let arguments: Arguments = parse_quote!(provides);
+        let expected = Arguments {
+            vis: Visibility::Inherited,
+            options: Options {
+                sync: false,
+                by_type: false,
+                scopes: vec![],
+                pointers: vec![],
+                shared: false,
+                no_debug: false,
+                provides: true,
+                overrides: false,
+                constructions: false,
+                despatma: false,
+            },
+        };
+
+        assert_eq!(arguments, expected);
+
+        let arguments: Arguments = parse_quote!(overrides);
+        let expected = Arguments {
+            vis: Visibility::Inherited,
+            options: Options {
+                sync: false,
+                by_type: false,
+                scopes: vec![],
+                pointers: vec![],
+                shared: false,
+                no_debug: false,
+                provides: false,
+                overrides: true,
+                constructions: false,
+                despatma: false,
+            },
+        };
+
+        assert_eq!(arguments, expected);
+
+        let arguments: Arguments = parse_quote!(constructions);
+        let expected = Arguments {
+            vis: Visibility::Inherited,
+            options: Options {
+                sync: false,
+                by_type: false,
+                scopes: vec![],
+                pointers: vec![],
+                shared: false,
+                no_debug: false,
+                provides: false,
+                overrides: false,
+                constructions: true,
+                despatma: false,
+            },
+        };
+
+        assert_eq!(arguments, expected);
+    }
 }
@@ -1,22 +1,47 @@
 #![doc = include_str!("../README.md")]
 
 use proc_macro::TokenStream;
-use proc_macro_error2::proc_macro_error;
+use proc_macro_error2::{emit_call_site_error, proc_macro_error};
 use quote::quote;
 use syn::{parse_macro_input, ItemImpl};
 
+mod graph;
 mod input;
+mod metadata;
 mod output;
 mod processing;
 
 #[proc_macro_error]
 #[proc_macro_attribute]
 pub fn dependency_container(tokens: TokenStream, impl_expr: TokenStream) -> TokenStream {
+    expand(tokens, impl_expr, false)
+}
+
+/// The `dependency_container` macro as re-exported by `despatma`. The generated container can then also use the types
+/// of `despatma::container`, which a standalone user of this crate does not have.
+#[doc(hidden)]
+#[proc_macro_error]
+#[proc_macro_attribute]
+pub fn despatma_dependency_container(tokens: TokenStream, impl_expr: TokenStream) -> TokenStream {
+    expand(tokens, impl_expr, true)
+}
+
+fn expand(tokens: TokenStream, impl_expr: TokenStream, despatma: bool) -> TokenStream {
     let input = parse_macro_input!(impl_expr as ItemImpl);
     let mut input = input::Container::from_item_impl(input);
 
-    let visibility = parse_macro_input!(tokens as syn::Visibility);
-    input.set_visibility(visibility);
+    let input::Arguments { vis, mut options } = parse_macro_input!(tokens as input::Arguments);
+
+    if options.provides && !despatma {
+        emit_call_site_error!(
+            "The `Provides` traits are types of the `despatma` crate";
+            hint = "Generate the container with `#[despatma::dependency_container(provides)]` to implement them"
+        );
+    }
+
+    options.despatma = despatma;
+    input.set_visibility(vis);
+    input.set_options(options);
 
     let mut processing: processing::Container = input.into();
     processing.process();
@@ -0,0 +1,208 @@
+use std::{cell::RefCell, rc::Rc};
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote, ToTokens};
+use syn::{ext::IdentExt, parse_str, Attribute, Path, ReturnType, Type};
+
+use crate::{output::CONTAINER_PATH, processing};
+
+/// The `METADATA` constant describing every dependency for tooling to check
+#[cfg_attr(test, derive(Eq, PartialEq, Debug))]
+pub struct Metadata {
+    dependencies: Vec<DependencyInfo>,
+}
+
+#[cfg_attr(test, derive(Eq, PartialEq, Debug))]
+struct DependencyInfo {
+    cfgs: Vec<Attribute>,
+    name: String,
+    ty: String,
+    field_ty: String,
+    lifetime: &'static str,
+    is_async: bool,
+    is_boxed: bool,
+    dependencies: Vec<String>,
+}
+
+impl Metadata {
+    pub fn new(dependencies: &[Rc<RefCell<processing::Dependency>>]) -> Self {
+        let dependencies = dependencies
+            .iter()
+            .map(|dependency| {
+                let dependency = dependency.borrow();
+
+                let ty = match &dependency.sig.output {
+                    ReturnType::Default => "()".to_string(),
+                    ReturnType::Type(_, ty) => type_to_string(ty),
+                };
+
+                DependencyInfo {
+                    cfgs: dependency.cfgs.clone(),
+                    name: dependency.sig.ident.unraw().to_string(),
+                    ty,
+                    field_ty: type_to_string(&dependency.field_ty),
+                    lifetime: dependency.lifetime.name(),
+                    is_async: dependency.is_async,
+                    is_boxed: dependency.pointer.is_some(),
+                    dependencies: dependency
+                        .dependencies
+                        .iter()
+                        .map(|child| child.inner.borrow().sig.ident.unraw().to_string())
+                        .collect(),
+                }
+            })
+            .collect();
+
+        Self { dependencies }
+    }
+}
+
+/// Print a type the way it would be written by hand. Ie `Box<dyn DAL>` rather than `Box < dyn DAL >`
+fn type_to_string(ty: &Type) -> String {
+    let mut string = ty.to_token_stream().to_string();
+
+    for (from, to) in [
+        (" < ", "<"),
+        ("< ", "<"),
+        (" <", "<"),
+        (" >", ">"),
+        (" :: ", "::"),
+        (":: ", "::"),
+        (" ,", ","),
+        ("& ", "&"),
+        (" (", "("),
+        ("( ", "("),
+        (" )", ")"),
+        ("[ ", "["),
+        (" ]", "]"),
+        (" ;", ";"),
+    ] {
+        string = string.replace(from, to);
+    }
+
+    string
+}
+
+impl ToTokens for Metadata {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let container: Path = parse_str(CONTAINER_PATH).expect("CONTAINER_PATH to be a path");
+        let dependencies = self.dependencies.iter().map(
+            |DependencyInfo {
+                 cfgs,
+                 name,
+                 ty,
+                 field_ty,
+                 lifetime,
+                 is_async,
+                 is_boxed,
+                 dependencies,
+             }| {
+                let lifetime = format_ident!("{}", lifetime);
+
+                quote! {
+                    #(#cfgs)*
+                    #container::DependencyInfo {
+                        name: #name,
+                        ty: #ty,
+                        field_ty: #field_ty,
+                        lifetime: #container::Lifetime::#lifetime,
+                        is_async: #is_async,
+                        is_boxed: #is_boxed,
+                        dependencies: &[#(#dependencies),*],
+                    }
+                }
+            },
+        );
+
+        tokens.extend(quote! {
+            /// Describes every dependency of this container so that tooling can check how it is wired together
+            pub const METADATA: &'static [#container::DependencyInfo] = &[
+                #(#dependencies),*
+            ];
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use syn::parse_quote;
+
+    use crate::input;
+
+    use super::*;
+
+    #[test]
+    fn new() {
+        let mut container: processing::Container = input::Container::from_item_impl(parse_quote!(
+            impl DependencyContainer {
+                fn new(config: Config) {}
+
+                #[Singleton]
+                async fn db(&self, config: &Config) -> Result<Db, DbError> {
+                    Db::connect(config).await
+                }
+
+                #[Scoped(Sqlite)]
+                fn data_layer(&self, db: &Db) -> impl DataLayer {
+                    Sqlite::new(db)
+                }
+
+                fn service(&self, #[from(data_layer)] source: impl DataLayer) -> Box<dyn Service> {
+                    Box::new(ServiceImpl::new(source))
+                }
+            }
+        ))
+        .into();
+
+        container.process();
+
+        let metadata = Metadata::new(&container.dependencies);
+
+        assert_eq!(
+            metadata.dependencies,
+            vec![
+                DependencyInfo {
+                    cfgs: vec![],
+                    name: "db".to_string(),
+                    ty: "Result<Db, DbError>".to_string(),
+                    field_ty: "Db".to_string(),
+                    lifetime: "Singleton",
+                    is_async: true,
+                    is_boxed: false,
+                    dependencies: vec!["config".to_string()],
+                },
+                DependencyInfo {
+                    cfgs: vec![],
+                    name: "data_layer".to_string(),
+                    ty: "impl DataLayer".to_string(),
+                    field_ty: "Sqlite".to_string(),
+                    lifetime: "Scoped",
+                    is_async: true,
+                    is_boxed: false,
+                    dependencies: vec!["db".to_string()],
+                },
+                DependencyInfo {
+                    cfgs: vec![],
+                    name: "service".to_string(),
+                    ty: "Box<dyn Service>".to_string(),
+                    field_ty: "std::boxed::Box<dyn Service>".to_string(),
+                    lifetime: "Transient",
+                    is_async: true,
+                    is_boxed: true,
+                    dependencies: vec!["data_layer".to_string()],
+                },
+                DependencyInfo {
+                    cfgs: vec![],
+                    name: "config".to_string(),
+                    ty: "Config".to_string(),
+                    field_ty: "Config".to_string(),
+                    lifetime: "Embedded",
+                    is_async: false,
+                    is_boxed: false,
+                    dependencies: vec![],
+                },
+            ]
+        );
+    }
+}
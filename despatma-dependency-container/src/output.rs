@@ -1,15 +1,22 @@
 use std::{cell::RefCell, rc::Rc};
 
-use crate::processing::{self, Lifetime};
-use proc_macro2::TokenStream;
-use quote::{quote, ToTokens};
+use crate::graph::Graph;
+use crate::input::Options;
+use crate::metadata::Metadata;
+use crate::processing::{self, to_pascal_case, Argument, Handle, Lifetime, Pointer};
+use proc_macro2::{Group, Literal, Span, TokenStream, TokenTree};
+use quote::{format_ident, quote, quote_spanned, ToTokens};
+use syn::ext::IdentExt;
+use syn::spanned::Spanned;
 use syn::token::Comma;
 use syn::{
     parse_quote, parse_str,
     punctuated::Punctuated,
     token::{Async, Fn, Paren},
-    Attribute, Block, Field, FieldValue, FieldsNamed, FnArg, Ident, PatType, Path, Signature, Stmt,
-    Token, Type, Visibility,
+    visit::Visit,
+    visit_mut::{visit_type_reference_mut, VisitMut},
+    Attribute, Block, Expr, Field, FieldValue, FieldsNamed, FnArg, Ident, PatType, Path,
+    ReturnType, Signature, Stmt, Token, Type, TypeImplTrait, TypeReference, Visibility,
 };
 
 #[cfg(any(test, feature = "standalone"))]
@@ -18,21 +25,142 @@ const ASYNC_ONCE_CELL_PATH: &str = "async_once_cell::OnceCell";
 #[cfg(not(any(test, feature = "standalone")))]
 const ASYNC_ONCE_CELL_PATH: &str = "despatma::async_once_cell::OnceCell";
 
+/// The module of `despatma` with the types the container uses for metadata, handles and the `Provides` traits.
+/// Standalone users of this crate do not have it. So these are only generated when the macro is called through `despatma`.
+pub(crate) const CONTAINER_PATH: &str = "despatma::container";
+
+#[cfg(all(feature = "tracing", any(test, feature = "standalone")))]
+const TRACING_PATH: &str = "tracing";
+
+#[cfg(all(feature = "tracing", not(any(test, feature = "standalone"))))]
+const TRACING_PATH: &str = "despatma::tracing";
+
 #[cfg_attr(test, derive(Eq, PartialEq, Debug))]
 pub struct Container {
     vis: Visibility,
     attrs: Vec<Attribute>,
     self_ty: Type,
+    /// Whether the struct can be `'static` since none of its fields borrow anything. So it has no lifetime parameter
+    is_static: bool,
     constructor_arguments: Punctuated<FnArg, Comma>,
     fields: Punctuated<Field, Token![,]>,
     constructors: Punctuated<FieldValue, Token![,]>,
     scope_constructors: Punctuated<FieldValue, Token![,]>,
+    named_scopes: Vec<NamedScope>,
+    send_sync_types: Vec<(Vec<Attribute>, Type)>,
+    builder: Option<Builder>,
+    profiles: Option<Profiles>,
+    init: Option<Init>,
+    shutdown: Option<Shutdown>,
+    introspection: Introspection,
+    graph: Graph,
+    metadata: Option<Metadata>,
     dependencies: Vec<Dependency>,
+    provides: Vec<Provide>,
+}
+
+/// The methods and `Debug` implementation to inspect which dependencies have been created without creating them
+#[cfg_attr(test, derive(Eq, PartialEq, Debug))]
+pub struct Introspection {
+    self_ty: Type,
+    is_static: bool,
+    is_sync: bool,
+    /// Whether to implement `Debug`, unless the user wants to write their own
+    has_debug: bool,
+    /// Whether the constructions of the dependencies are counted for a `constructions` method
+    has_constructions: bool,
+    dependencies: Vec<IntrospectedDependency>,
+}
+
+#[cfg_attr(test, derive(Eq, PartialEq, Debug))]
+struct IntrospectedDependency {
+    cfgs: Vec<Attribute>,
+    ident: Ident,
+    /// Whether the dependency is kept in a cell which can be checked
+    is_managed: bool,
+}
+
+/// An implementation of `Provides` or `ProvidesAsync` for the type of a dependency
+#[cfg_attr(test, derive(Eq, PartialEq, Debug))]
+pub struct Provide {
+    cfgs: Vec<Attribute>,
+    ident: Ident,
+    ty: Type,
+    is_async: bool,
+}
+
+/// A named scope of the container which gets its own method to create it
+#[cfg_attr(test, derive(Eq, PartialEq, Debug))]
+pub struct NamedScope {
+    ident: Ident,
+    is_static: bool,
+    constructors: Punctuated<FieldValue, Token![,]>,
+}
+
+/// The enum of the runtime profiles the container can be created with
+#[cfg_attr(test, derive(Eq, PartialEq, Debug))]
+pub struct Profiles {
+    vis: Visibility,
+    self_ty: Type,
+    ident: Ident,
+    variants: Vec<Ident>,
+}
+
+/// A typed builder to set the constructor arguments of the container by name
+#[cfg_attr(test, derive(Eq, PartialEq, Debug))]
+pub struct Builder {
+    vis: Visibility,
+    self_ty: Type,
+    is_static: bool,
+    ident: Ident,
+    arguments: Vec<BuilderArgument>,
+}
+
+#[cfg_attr(test, derive(Eq, PartialEq, Debug))]
+struct BuilderArgument {
+    ident: Ident,
+    ty: Type,
+    default: Option<Expr>,
+}
+
+/// The `init` method to create the eager singletons up front
+#[cfg_attr(test, derive(Eq, PartialEq, Debug))]
+pub struct Init {
+    is_sync: bool,
+    error_ty: Option<Type>,
+    dependencies: Vec<EagerDependency>,
+}
+
+#[cfg_attr(test, derive(Eq, PartialEq, Debug))]
+struct EagerDependency {
+    cfgs: Vec<Attribute>,
+    ident: Ident,
+    is_async: bool,
+    is_fallible: bool,
+}
+
+/// The `shutdown` method to dispose the managed dependencies which have a dispose hook
+#[cfg_attr(test, derive(Eq, PartialEq, Debug))]
+pub struct Shutdown {
+    is_sync: bool,
+    disposals: Vec<Disposal>,
+}
+
+#[cfg_attr(test, derive(Eq, PartialEq, Debug))]
+struct Disposal {
+    cfgs: Vec<Attribute>,
+    ident: Ident,
+    is_singleton: bool,
+    /// The index of the named scope of a scoped dependency
+    scope: Option<usize>,
+    asyncness: Option<Async>,
+    method: Ident,
 }
 
 #[cfg_attr(test, derive(Eq, PartialEq, Debug, Clone))]
 pub struct Dependency {
     attrs: Vec<Attribute>,
+    cfgs: Vec<Attribute>,
     block: Block,
     asyncness: Option<Async>,
     fn_token: Fn,
@@ -40,19 +168,68 @@ pub struct Dependency {
     paren_token: Paren,
     inputs: Punctuated<FnArg, Token![,]>,
     ty: Type,
+    field_ty: Type,
+    error_ty: Option<Type>,
+    override_ty: Option<Type>,
+    /// The type returned by the block creating the dependency, unless it is opaque and cannot be named
+    #[cfg_attr(not(feature = "tracing"), allow(dead_code))]
+    value_ty: Option<Type>,
+    create_ty: Option<Type>,
     create_asyncness: Option<Async>,
+    is_fallible: bool,
     is_managed: bool,
     is_embedded: bool,
-    dependencies: Vec<Dependency>,
+    /// Whether the dependency is imported from another container, which creates it
+    is_import: bool,
+    /// Whether the container counts how many times this dependency has been created. An imported dependency is created
+    /// by the container it is imported from, so it is counted there instead.
+    is_counted: bool,
+    /// Whether the cell holds an `Arc` which is cloned out rather than borrowed
+    is_shared: bool,
+    #[cfg_attr(not(feature = "tracing"), allow(dead_code))]
+    lifetime: &'static str,
+    is_sync: bool,
+    arguments: Vec<Argument>,
+    dependencies: Vec<ChildDependency>,
+    deferred: Vec<DeferredDependency>,
+}
+
+/// A dependency requested by another dependency under the name of the argument.
+/// It is created by calling the method of the dependency rather than repeating how it is created.
+/// The `dependency` is the private `create_` method when the public method hides the concrete type.
+#[cfg_attr(test, derive(Eq, PartialEq, Debug, Clone))]
+pub struct ChildDependency {
+    ident: Ident,
+    dependency: Ident,
+    is_async: bool,
+    is_fallible: bool,
+    /// Whether a shared pointer is requested by value. So it is cloned out of the container instead
+    is_cloned: bool,
+    /// The field and requested type of a shared dependency which is requested by reference. So it is borrowed from the
+    /// cell of the container rather than from the `Arc` handed out
+    borrowed: Option<(Ident, Type)>,
+}
+
+/// A dependency requested through a `Lazy` or `Provider` handle. The handle calls the method of the dependency once it
+/// is used. Or a dependency taking runtime arguments, which is requested as a function to call the method with them.
+#[cfg_attr(test, derive(Eq, PartialEq, Debug, Clone))]
+pub struct DeferredDependency {
+    ident: Ident,
+    dependency: Ident,
+    is_async: bool,
+    handle: Handle,
+    arguments: Vec<Argument>,
 }
 
 impl From<processing::Container> for Container {
     fn from(container: processing::Container) -> Self {
         let processing::Container {
             vis,
+            options,
             attrs,
             self_ty,
             dependencies,
+            profiles,
         } = container;
 
         let managed_dependencies: Vec<_> = dependencies
@@ -61,32 +238,306 @@ impl From<processing::Container> for Container {
             .cloned()
             .collect();
 
-        let fields = get_struct_fields(&managed_dependencies);
+        let is_static = options.shared
+            && !managed_dependencies
+                .iter()
+                .any(|d| names_lifetime(&d.borrow().field_ty));
 
-        let constructors = get_struct_field_constructors(&managed_dependencies);
+        let mut fields = get_struct_fields(&managed_dependencies, options.sync);
 
-        let scope_constructors = get_new_scope_constructors(&managed_dependencies);
+        let mut constructors = get_struct_field_constructors(&managed_dependencies);
+
+        let mut scope_constructors =
+            get_new_scope_constructors(&managed_dependencies, &options.scopes, 0);
+
+        let mut named_scopes: Vec<_> = options
+            .scopes
+            .iter()
+            .enumerate()
+            .map(|(index, ident)| NamedScope {
+                ident: ident.clone(),
+                is_static,
+                constructors: get_new_scope_constructors(
+                    &managed_dependencies,
+                    &options.scopes,
+                    index,
+                ),
+            })
+            .collect();
 
         let constructor_arguments = get_constructor_arguments(&managed_dependencies);
 
-        let dependencies = dependencies
+        let builder = get_builder(&vis, &self_ty, is_static, &managed_dependencies);
+
+        let profiles = profiles.map(|profiles| Profiles {
+            vis: vis.clone(),
+            self_ty: self_ty.clone(),
+            ident: profiles.ident,
+            variants: profiles.variants,
+        });
+
+        let init = get_init(&dependencies, options.sync);
+
+        let shutdown = get_shutdown(&dependencies, &options.scopes, options.sync);
+
+        let graph = Graph::new(&self_ty, &dependencies);
+
+        let metadata = options.despatma.then(|| Metadata::new(&dependencies));
+
+        let send_sync_types = if options.sync {
+            managed_dependencies
+                .iter()
+                .map(|d| {
+                    let dep_ref = d.borrow();
+
+                    // A rewritten field type has no span of its own, so point it at the return type instead
+                    let ty = match &dep_ref.sig.output {
+                        ReturnType::Type(_, ty) if **ty == dep_ref.field_ty => ty.as_ref().clone(),
+                        ReturnType::Type(_, ty) => {
+                            let field_ty = respan(dep_ref.field_ty.to_token_stream(), ty.span());
+                            parse_quote!(#field_ty)
+                        }
+                        ReturnType::Default => dep_ref.field_ty.clone(),
+                    };
+
+                    (dep_ref.cfgs.clone(), ty)
+                })
+                .collect()
+        } else {
+            Default::default()
+        };
+
+        let profile_providers: Vec<_> = dependencies
+            .iter()
+            .filter(|d| d.borrow().profile.is_some())
+            .map(|d| d.borrow().sig.ident.clone())
+            .collect();
+
+        let dependencies: Vec<_> = dependencies
             .into_iter()
-            .map(|d| d.borrow().clone().into())
+            .map(|d| Dependency::new(d.borrow().clone(), &options))
             .collect();
 
+        // The `Provides` traits are only reachable through `despatma`
+        let provides = if options.despatma && options.provides {
+            get_provides(&dependencies, &profile_providers)
+        } else {
+            Vec::new()
+        };
+
+        let introspection = get_introspection(&self_ty, is_static, &dependencies, &options);
+
+        fields.extend(get_override_fields(&dependencies, options.sync));
+        constructors.extend(get_override_constructors(&dependencies, |_| {
+            quote! { None }
+        }));
+        scope_constructors.extend(get_override_constructors(&dependencies, |ident| {
+            quote! { self.#ident.clone() }
+        }));
+        for named_scope in named_scopes.iter_mut() {
+            named_scope
+                .constructors
+                .extend(get_override_constructors(&dependencies, |ident| {
+                    quote! { self.#ident.clone() }
+                }));
+        }
+
+        // Scopes count the constructions of the container they were created from
+        fields.extend(get_constructions_fields(&dependencies, options.sync));
+        constructors.extend(get_constructions_constructors(&dependencies, |_| {
+            quote! { Default::default() }
+        }));
+        scope_constructors.extend(get_constructions_constructors(&dependencies, |ident| {
+            quote! { self.#ident.clone() }
+        }));
+        for named_scope in named_scopes.iter_mut() {
+            named_scope
+                .constructors
+                .extend(get_constructions_constructors(&dependencies, |ident| {
+                    quote! { self.#ident.clone() }
+                }));
+        }
+
+        // Scopes share the singletons of their parent. So only the root container should dispose them
+        if shutdown
+            .as_ref()
+            .is_some_and(|shutdown| shutdown.disposals.iter().any(|d| d.is_singleton))
+        {
+            let field: FieldsNamed = parse_quote!({ _is_scope: bool });
+
+            fields.extend(field.named);
+            constructors.push(parse_quote!(_is_scope: false));
+            scope_constructors.push(parse_quote!(_is_scope: true));
+            for named_scope in named_scopes.iter_mut() {
+                named_scope.constructors.push(parse_quote!(_is_scope: true));
+            }
+        }
+
+        // Named scopes share the dependencies of the scopes around them. So a scope only disposes those it created
+        if shutdown
+            .as_ref()
+            .is_some_and(|shutdown| shutdown.disposals.iter().any(|d| d.scope.is_some()))
+        {
+            let field: FieldsNamed = parse_quote!({ _scope_depth: usize });
+
+            fields.extend(field.named);
+            constructors.push(parse_quote!(_scope_depth: 0));
+            scope_constructors.push(parse_quote!(_scope_depth: 1));
+            for (index, named_scope) in named_scopes.iter_mut().enumerate() {
+                let depth = Literal::usize_unsuffixed(index + 1);
+
+                named_scope
+                    .constructors
+                    .push(parse_quote!(_scope_depth: #depth));
+            }
+        }
+
+        // The generated struct continues after these
+        if !fields.empty_or_trailing() {
+            fields.push_punct(Default::default());
+            constructors.push_punct(Default::default());
+            scope_constructors.push_punct(Default::default());
+            for named_scope in named_scopes.iter_mut() {
+                named_scope.constructors.push_punct(Default::default());
+            }
+        }
+
         Self {
             vis,
             attrs,
             self_ty,
+            is_static,
             constructor_arguments,
             fields,
             constructors,
             scope_constructors,
+            named_scopes,
+            send_sync_types,
+            builder,
+            profiles,
+            init,
+            shutdown,
+            introspection,
+            graph,
+            metadata,
             dependencies,
+            provides,
         }
     }
 }
 
+fn get_introspection(
+    self_ty: &Type,
+    is_static: bool,
+    dependencies: &[Dependency],
+    options: &Options,
+) -> Introspection {
+    let dependencies = dependencies
+        .iter()
+        .filter(|dependency| !dependency.is_embedded && !dependency.is_import)
+        .map(|dependency| IntrospectedDependency {
+            cfgs: dependency.cfgs.clone(),
+            ident: dependency.ident.clone(),
+            is_managed: dependency.is_managed,
+        })
+        .collect();
+
+    Introspection {
+        self_ty: self_ty.clone(),
+        is_static,
+        is_sync: options.sync,
+        has_debug: !options.no_debug,
+        has_constructions: options.constructions,
+        dependencies,
+    }
+}
+
+/// The types which can be provided through the `Provides` traits. A type returned by more than one dependency is
+/// skipped since the container could provide either of them. Unless those are `#[cfg]` alternatives of the same one.
+/// The providers for a profile are skipped too, since they are only created for the dependency picking between them.
+///
+/// The types are those handed out by the resolvers. These are built from the normalised `ty` after `ExtractBoxType` and
+/// `WrapBoxType`, so a smart pointer is spelled the same way however it was declared.
+fn get_provides(dependencies: &[Dependency], profile_providers: &[Ident]) -> Vec<Provide> {
+    let mut provides: Vec<_> = dependencies
+        .iter()
+        .filter(|dependency| {
+            dependency.arguments.is_empty()
+                && !is_opaque(&dependency.ty)
+                && !profile_providers.contains(&dependency.ident)
+        })
+        .map(|dependency| {
+            let mut ty = dependency.ty.clone();
+
+            // The container hands out references for its own lifetime
+            NameLifetimes.visit_type_mut(&mut ty);
+
+            Provide {
+                cfgs: dependency.cfgs.clone(),
+                ident: dependency.ident.clone(),
+                ty,
+                is_async: dependency.asyncness.is_some(),
+            }
+        })
+        .collect();
+
+    let duplicates: Vec<_> = provides
+        .iter()
+        .filter(|provide| {
+            provides
+                .iter()
+                .any(|p| p.ty == provide.ty && p.ident != provide.ident)
+        })
+        .map(|provide| provide.ty.clone())
+        .collect();
+
+    provides.retain(|provide| !duplicates.contains(&provide.ty));
+
+    provides
+}
+
+/// Replaces any elided or wildcard lifetimes with the `'a` lifetime of the container
+struct NameLifetimes;
+
+impl VisitMut for NameLifetimes {
+    fn visit_type_reference_mut(&mut self, type_reference: &mut TypeReference) {
+        if type_reference.lifetime.is_none() {
+            type_reference.lifetime = Some(parse_quote!('a));
+        }
+
+        visit_type_reference_mut(self, type_reference);
+    }
+
+    fn visit_lifetime_mut(&mut self, lifetime: &mut syn::Lifetime) {
+        if lifetime.ident == "_" {
+            *lifetime = parse_quote!('a);
+        }
+    }
+}
+
+/// Whether the type borrows something for the `'a` lifetime of the container
+fn names_lifetime(ty: &Type) -> bool {
+    struct Checker {
+        names_lifetime: bool,
+    }
+
+    impl<'ast> Visit<'ast> for Checker {
+        fn visit_lifetime(&mut self, lifetime: &'ast syn::Lifetime) {
+            if lifetime.ident == "a" {
+                self.names_lifetime = true;
+            }
+        }
+    }
+
+    let mut checker = Checker {
+        names_lifetime: false,
+    };
+
+    checker.visit_type(ty);
+
+    checker.names_lifetime
+}
+
 fn get_constructor_arguments(
     managed_dependencies: &[Rc<RefCell<processing::Dependency>>],
 ) -> Punctuated<FnArg, Token![,]> {
@@ -112,8 +563,161 @@ fn get_constructor_arguments(
     parse_quote!(#(#embedded_dependencies,)*)
 }
 
+fn get_builder(
+    vis: &Visibility,
+    self_ty: &Type,
+    is_static: bool,
+    managed_dependencies: &[Rc<RefCell<processing::Dependency>>],
+) -> Option<Builder> {
+    let Type::Path(path) = self_ty else {
+        return None;
+    };
+
+    let arguments: Vec<_> = managed_dependencies
+        .iter()
+        .filter(|d| d.borrow().lifetime.is_embedded())
+        .map(|d| {
+            let dep_ref = d.borrow();
+
+            BuilderArgument {
+                ident: dep_ref.sig.ident.clone(),
+                ty: dep_ref.field_ty.clone(),
+                default: dep_ref.default.clone(),
+            }
+        })
+        .collect();
+
+    if arguments.is_empty() {
+        return None;
+    }
+
+    Some(Builder {
+        vis: vis.clone(),
+        self_ty: self_ty.clone(),
+        is_static,
+        ident: format_ident!("{}Builder", path.path.segments.last()?.ident),
+        arguments,
+    })
+}
+
+/// Sort the dependencies so that every dependency comes after the dependencies it is created from
+fn topological_order(
+    dependencies: &[Rc<RefCell<processing::Dependency>>],
+) -> Vec<Rc<RefCell<processing::Dependency>>> {
+    fn visit(
+        dependency: &Rc<RefCell<processing::Dependency>>,
+        order: &mut Vec<Rc<RefCell<processing::Dependency>>>,
+    ) {
+        if order.iter().any(|d| Rc::ptr_eq(d, dependency)) {
+            return;
+        }
+
+        for candidate in dependency
+            .borrow()
+            .dependencies
+            .iter()
+            .flat_map(|child| child.candidates())
+        {
+            visit(candidate, order);
+        }
+
+        order.push(dependency.clone());
+    }
+
+    let mut order = Vec::new();
+
+    for dependency in dependencies {
+        visit(dependency, &mut order);
+    }
+
+    order
+}
+
+/// Get the eager dependencies in the order they should be created
+fn get_init(dependencies: &[Rc<RefCell<processing::Dependency>>], is_sync: bool) -> Option<Init> {
+    let eager_dependencies: Vec<_> = topological_order(dependencies)
+        .into_iter()
+        .filter(|dependency| dependency.borrow().is_eager)
+        .collect();
+
+    if eager_dependencies.is_empty() {
+        return None;
+    }
+
+    // Any eager dependencies failing with a different error type have already been reported
+    let error_ty = eager_dependencies
+        .iter()
+        .find_map(|dependency| dependency.borrow().error_ty.clone());
+
+    let dependencies = eager_dependencies
+        .iter()
+        .map(|dependency| {
+            let dep_ref = dependency.borrow();
+
+            EagerDependency {
+                cfgs: dep_ref.cfgs.clone(),
+                ident: dep_ref.sig.ident.clone(),
+                is_async: dep_ref.is_async,
+                is_fallible: dep_ref.error_ty.is_some(),
+            }
+        })
+        .collect();
+
+    Some(Init {
+        is_sync,
+        error_ty,
+        dependencies,
+    })
+}
+
+/// Get the dispose hooks in the order they should be called.
+/// Which is the reverse topological order so that a dependency is disposed before the dependencies it was created from.
+fn get_shutdown(
+    dependencies: &[Rc<RefCell<processing::Dependency>>],
+    scopes: &[Ident],
+    is_sync: bool,
+) -> Option<Shutdown> {
+    let order = topological_order(dependencies);
+
+    let disposals: Vec<_> = order
+        .iter()
+        .rev()
+        .filter_map(|dependency| {
+            let dep_ref = dependency.borrow();
+            let dispose = dep_ref.dispose.as_ref()?;
+
+            let is_singleton = match dep_ref.lifetime {
+                Lifetime::Singleton(_) => true,
+                Lifetime::Scoped(_) => false,
+                Lifetime::Transient(_) | Lifetime::Embedded(_) => return None,
+            };
+
+            let scope = dep_ref
+                .scope
+                .as_ref()
+                .and_then(|scope| scopes.iter().position(|s| s == scope));
+
+            Some(Disposal {
+                cfgs: dep_ref.cfgs.clone(),
+                ident: dep_ref.sig.ident.clone(),
+                is_singleton,
+                scope,
+                asyncness: dispose.asyncness,
+                method: dispose.method.clone(),
+            })
+        })
+        .collect();
+
+    if disposals.is_empty() {
+        return None;
+    }
+
+    Some(Shutdown { is_sync, disposals })
+}
+
 fn get_struct_fields(
     managed_dependencies: &[Rc<RefCell<processing::Dependency>>],
+    sync: bool,
 ) -> Punctuated<Field, Token![,]> {
     if managed_dependencies.is_empty() {
         Default::default()
@@ -122,8 +726,14 @@ fn get_struct_fields(
             .iter()
             .map(|dep| {
                 let dep_ref = dep.borrow();
+                let cfgs = &dep_ref.cfgs;
                 let ident = &dep_ref.sig.ident;
                 let field_ty = &dep_ref.field_ty;
+                let field_ty: Type = if dep_ref.is_shared {
+                    parse_quote! { std::sync::Arc<#field_ty> }
+                } else {
+                    field_ty.clone()
+                };
 
                 let wrapper_ty = match &dep_ref.lifetime {
                     Lifetime::Singleton(_) | Lifetime::Scoped(_) => {
@@ -131,6 +741,8 @@ fn get_struct_fields(
                             let once_cell_path: Path = parse_str(ASYNC_ONCE_CELL_PATH)
                                 .expect("ASYNC_ONCE_CELL_PATH to be a path");
                             quote! { std::sync::Arc<#once_cell_path<#field_ty>> }
+                        } else if sync {
+                            quote! { std::sync::Arc<std::sync::OnceLock<#field_ty>> }
                         } else {
                             quote! { std::rc::Rc<std::cell::OnceCell<#field_ty>> }
                         }
@@ -144,6 +756,7 @@ fn get_struct_fields(
                 };
 
                 parse_quote! {
+                    #(#cfgs)*
                     #ident: #wrapper_ty
                 }
             })
@@ -171,13 +784,16 @@ fn get_struct_field_constructors(
             .iter()
             .map(|dep| {
                 let dep_ref = dep.borrow();
+                let cfgs = &dep_ref.cfgs;
                 let ident = &dep_ref.sig.ident;
 
                 match dep_ref.lifetime {
                     Lifetime::Singleton(_) | Lifetime::Scoped(_) => parse_quote! {
+                        #(#cfgs)*
                         #ident: Default::default()
                     },
                     Lifetime::Embedded(_) => parse_quote! {
+                        #(#cfgs)*
                         #ident: std::sync::Arc::new(#ident)
                     },
                     Lifetime::Transient(_) => unreachable!(
@@ -191,8 +807,12 @@ fn get_struct_field_constructors(
     }
 }
 
+/// The fields of a new scope which creates the scoped dependencies of the named scope at `index` and any scope inside
+/// it again. Scoped dependencies without a named scope are always created again.
 fn get_new_scope_constructors(
     managed_dependencies: &[Rc<RefCell<processing::Dependency>>],
+    scopes: &[Ident],
+    index: usize,
 ) -> Punctuated<FieldValue, Token![,]> {
     if managed_dependencies.is_empty() {
         Default::default()
@@ -201,11 +821,25 @@ fn get_new_scope_constructors(
             .iter()
             .map(|dep| {
                 let dep_ref = dep.borrow();
+                let cfgs = &dep_ref.cfgs;
                 let ident = &dep_ref.sig.ident;
                 let init = match dep_ref.lifetime {
+                    // Imported containers get a new scope of their own too
+                    Lifetime::Embedded(_) if dep_ref.is_import => {
+                        quote! { std::sync::Arc::new(self.#ident.new_scope()) }
+                    }
                     Lifetime::Singleton(_) | Lifetime::Embedded(_) => {
                         quote! { self.#ident.clone() }
                     }
+                    Lifetime::Scoped(_)
+                        if dep_ref
+                            .scope
+                            .as_ref()
+                            .and_then(|scope| scopes.iter().position(|s| s == scope))
+                            .is_some_and(|position| position < index) =>
+                    {
+                        quote! { self.#ident.clone() }
+                    }
                     Lifetime::Scoped(_) => quote! { Default::default() },
                     Lifetime::Transient(_) => {
                         unreachable!(
@@ -215,6 +849,7 @@ fn get_new_scope_constructors(
                 };
 
                 parse_quote! {
+                    #(#cfgs)*
                     #ident: #init
                 }
             })
@@ -224,18 +859,184 @@ fn get_new_scope_constructors(
     }
 }
 
-impl From<processing::Dependency> for Dependency {
-    fn from(dependency: processing::Dependency) -> Self {
+/// Fields to hold the functions replacing any overridden dependencies
+fn get_override_fields(dependencies: &[Dependency], sync: bool) -> Vec<Field> {
+    dependencies
+        .iter()
+        .filter_map(|dep| {
+            let override_ty = dep.override_ty.as_ref()?;
+            let cfgs = &dep.cfgs;
+            let ident = dep.override_field_ident();
+            let argument_tys = dep.arguments.iter().map(|argument| &argument.ty);
+
+            let field: FieldsNamed = if sync {
+                parse_quote! {
+                    { #(#cfgs)* #ident: Option<std::sync::Arc<dyn Fn(#(#argument_tys),*) -> #override_ty + Send + Sync>> }
+                }
+            } else {
+                parse_quote! {
+                    { #(#cfgs)* #ident: Option<std::rc::Rc<dyn Fn(#(#argument_tys),*) -> #override_ty>> }
+                }
+            };
+
+            field.named.into_iter().next()
+        })
+        .collect()
+}
+
+fn get_override_constructors(
+    dependencies: &[Dependency],
+    init: fn(&Ident) -> TokenStream,
+) -> Vec<FieldValue> {
+    dependencies
+        .iter()
+        .filter(|dep| dep.override_ty.is_some())
+        .map(|dep| {
+            let cfgs = &dep.cfgs;
+            let ident = dep.override_field_ident();
+            let init = init(&ident);
+
+            parse_quote! {
+                #(#cfgs)*
+                #ident: #init
+            }
+        })
+        .collect()
+}
+
+/// Fields to count how many times each dependency has been created. Embedded dependencies are never created.
+fn get_constructions_fields(dependencies: &[Dependency], sync: bool) -> Vec<Field> {
+    dependencies
+        .iter()
+        .filter(|dep| dep.is_counted)
+        .filter_map(|dep| {
+            let cfgs = &dep.cfgs;
+            let ident = constructions_ident(&dep.ident);
+
+            let field: FieldsNamed = if sync {
+                parse_quote! {
+                    { #(#cfgs)* #ident: std::sync::Arc<std::sync::atomic::AtomicUsize> }
+                }
+            } else {
+                parse_quote! {
+                    { #(#cfgs)* #ident: std::rc::Rc<std::cell::Cell<usize>> }
+                }
+            };
+
+            field.named.into_iter().next()
+        })
+        .collect()
+}
+
+fn get_constructions_constructors(
+    dependencies: &[Dependency],
+    init: fn(&Ident) -> TokenStream,
+) -> Vec<FieldValue> {
+    dependencies
+        .iter()
+        .filter(|dep| dep.is_counted)
+        .map(|dep| {
+            let cfgs = &dep.cfgs;
+            let ident = constructions_ident(&dep.ident);
+            let init = init(&ident);
+
+            parse_quote! {
+                #(#cfgs)*
+                #ident: #init
+            }
+        })
+        .collect()
+}
+
+/// Whether the type hides the concrete type behind an `impl Trait`
+fn is_opaque(ty: &Type) -> bool {
+    struct Checker {
+        is_opaque: bool,
+    }
+
+    impl<'ast> Visit<'ast> for Checker {
+        fn visit_type_impl_trait(&mut self, _: &'ast TypeImplTrait) {
+            self.is_opaque = true;
+        }
+    }
+
+    let mut checker = Checker { is_opaque: false };
+
+    checker.visit_type(ty);
+
+    checker.is_opaque
+}
+
+/// Whether the dependency needs a private method which returns its concrete type for other dependencies to call.
+/// Calling the public method would give them the opaque `impl Trait` instead.
+fn needs_create_method(dependency: &processing::Dependency) -> bool {
+    !dependency.lifetime.is_embedded()
+        && is_opaque(&dependency.ty)
+        && !is_opaque(&dependency.field_ty)
+}
+
+/// Whether a dependency of this type can be replaced by a function stored on the container.
+/// The function cannot return anything borrowed from the container and has to name the concrete type it returns.
+/// Otherwise, the container would no longer be covariant over its lifetime.
+///
+/// Other dependencies are left without an `override_` method. This cannot be reported since warnings are not stable
+/// for proc macros, so it is documented on the `dependency_container` macro instead.
+fn is_overridable(ty: &Type) -> bool {
+    struct Checker {
+        is_overridable: bool,
+    }
+
+    impl<'ast> Visit<'ast> for Checker {
+        fn visit_type_reference(&mut self, _: &'ast TypeReference) {
+            self.is_overridable = false;
+        }
+
+        fn visit_type_impl_trait(&mut self, _: &'ast TypeImplTrait) {
+            self.is_overridable = false;
+        }
+
+        fn visit_lifetime(&mut self, _: &'ast syn::Lifetime) {
+            self.is_overridable = false;
+        }
+    }
+
+    let mut checker = Checker {
+        is_overridable: true,
+    };
+
+    checker.visit_type(ty);
+
+    checker.is_overridable
+}
+
+impl Dependency {
+    fn new(dependency: processing::Dependency, options: &Options) -> Self {
+        let has_create_method = needs_create_method(&dependency);
         let processing::Dependency {
             attrs,
+            cfgs,
             sig,
             block,
             is_async,
-            is_boxed: _,
+            pointer: _,
+            is_fallible,
+            is_eager: _,
+            is_stateful: _,
+            is_shared,
             lifetime,
+            scope: _,
+            qualifier: _,
+            default: _,
+            is_import,
+            dispose: _,
+            group: _,
+            profile: _,
+            arguments,
             ty,
-            field_ty: _,
+            field_ty,
+            error_ty,
             dependencies,
+            deferred,
         } = dependency;
 
         let Signature {
@@ -262,16 +1063,112 @@ impl From<processing::Dependency> for Dependency {
 
         let is_managed = lifetime.is_managed();
         let is_embedded = lifetime.is_embedded();
-        let ty = if is_managed { parse_quote!(&#ty) } else { ty };
+        let lifetime = lifetime.name();
+
+        // The block of a managed dependency fills its cell while that of a transient dependency is returned as is
+        let value_ty = if is_managed { &field_ty } else { &ty };
+        let value_ty: Option<Type> = match &error_ty {
+            _ if is_embedded || is_opaque(value_ty) => None,
+            Some(error_ty) if is_fallible => {
+                Some(parse_quote!(std::result::Result<#value_ty, #error_ty>))
+            }
+            _ => Some(value_ty.clone()),
+        };
+
+        // The override replaces the block of the dependency. So it should return whatever the block returns
+        let override_ty = match &error_ty {
+            _ if !options.overrides || is_embedded || !is_overridable(&field_ty) => None,
+            Some(error_ty) if is_fallible => {
+                Some(parse_quote!(std::result::Result<#field_ty, #error_ty>))
+            }
+            _ => Some(field_ty.clone()),
+        };
+
+        let create_ty = if has_create_method {
+            let create_ty: Type = if is_shared {
+                parse_quote!(std::sync::Arc<#field_ty>)
+            } else if is_managed {
+                parse_quote!(&#field_ty)
+            } else {
+                field_ty.clone()
+            };
+
+            Some(if let Some(error_ty) = &error_ty {
+                parse_quote!(std::result::Result<#create_ty, #error_ty>)
+            } else {
+                create_ty
+            })
+        } else {
+            None
+        };
+
+        let ty = if is_shared {
+            parse_quote!(std::sync::Arc<#ty>)
+        } else if is_managed {
+            parse_quote!(&#ty)
+        } else {
+            ty
+        };
+        let ty = if let Some(error_ty) = &error_ty {
+            parse_quote!(std::result::Result<#ty, #error_ty>)
+        } else {
+            ty
+        };
 
         let dependencies = dependencies
             .into_iter()
-            .map(|d| d.inner.borrow().clone().into())
+            .map(|d| {
+                let inner = d.inner.borrow();
+
+                let dependency = if needs_create_method(&inner) {
+                    create_ident(&inner.sig.ident)
+                } else {
+                    inner.sig.ident.clone()
+                };
+
+                let is_cloned = inner.lifetime.is_managed()
+                    && !inner.is_shared
+                    && inner.pointer.as_ref().is_some_and(Pointer::is_shared)
+                    && matches!(d.ty, Type::Path(_));
+
+                let borrowed = (inner.is_shared && matches!(d.ty, Type::Reference(_)))
+                    .then(|| (inner.sig.ident.clone(), d.ty.clone()));
+
+                ChildDependency {
+                    ident: d.ident,
+                    dependency,
+                    is_async: inner.is_async,
+                    is_fallible: inner.error_ty.is_some(),
+                    is_cloned,
+                    borrowed,
+                }
+            })
+            .collect();
+
+        let deferred = deferred
+            .into_iter()
+            .map(|d| {
+                let inner = d.inner.borrow();
+                let dependency = if needs_create_method(&inner) {
+                    create_ident(&inner.sig.ident)
+                } else {
+                    inner.sig.ident.clone()
+                };
+
+                DeferredDependency {
+                    ident: d.ident,
+                    dependency,
+                    is_async: inner.is_async,
+                    handle: d.handle,
+                    arguments: inner.arguments.clone(),
+                }
+            })
             .collect();
 
         Self {
             create_asyncness,
             attrs,
+            cfgs,
             block,
             asyncness,
             fn_token,
@@ -279,70 +1176,726 @@ impl From<processing::Dependency> for Dependency {
             paren_token,
             inputs,
             ty,
+            field_ty,
+            error_ty,
+            override_ty,
+            value_ty,
+            create_ty,
+            is_fallible,
             is_managed,
             is_embedded,
+            is_import,
+            is_counted: options.constructions && !is_embedded && !is_import,
+            is_shared,
+            lifetime,
+            is_sync: options.sync,
+            arguments,
             dependencies,
+            deferred,
         }
     }
+
+    fn override_ident(&self) -> Ident {
+        format_ident!(
+            "override_{}",
+            self.ident.to_string().trim_start_matches('_')
+        )
+    }
+
+    fn override_field_ident(&self) -> Ident {
+        format_ident!("{}_override", self.ident)
+    }
+}
+
+/// The lifetime parameter of the container, unless it is `'static`
+fn generics(is_static: bool) -> Option<TokenStream> {
+    (!is_static).then(|| quote! { <'a> })
+}
+
+/// The constructor of the marker field holding the lifetime of the container, unless it is `'static`
+fn phantom_constructor(is_static: bool) -> Option<TokenStream> {
+    (!is_static).then(|| quote! { _phantom: Default::default(), })
+}
+
+/// The private method creating a dependency with its concrete type
+fn create_ident(ident: &Ident) -> Ident {
+    format_ident!("create_{}", ident.unraw())
+}
+
+/// The field counting how many times a dependency has been created
+fn constructions_ident(ident: &Ident) -> Ident {
+    format_ident!("{}_constructions", ident.unraw())
+}
+
+/// Moves every token onto `span`, since interpolated tokens keep their own spans even inside `quote_spanned!`
+fn respan(tokens: TokenStream, span: Span) -> TokenStream {
+    tokens
+        .into_iter()
+        .map(|token| match token {
+            TokenTree::Group(group) => {
+                let mut respanned = Group::new(group.delimiter(), respan(group.stream(), span));
+                respanned.set_span(span);
+                TokenTree::Group(respanned)
+            }
+            mut token => {
+                token.set_span(span);
+                token
+            }
+        })
+        .collect()
 }
 
 impl ToTokens for Container {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let Self {
             vis,
-            attrs,
+            attrs,
+            self_ty,
+            is_static,
+            constructor_arguments,
+            fields,
+            constructors,
+            scope_constructors,
+            named_scopes,
+            send_sync_types,
+            builder,
+            profiles,
+            init,
+            shutdown,
+            introspection,
+            graph,
+            metadata,
+            dependencies,
+            provides,
+        } = self;
+
+        // The methods still borrow the container for `'a`, even when the struct itself does not have the lifetime
+        let generics = generics(*is_static);
+        let phantom_field =
+            (!is_static).then(|| quote! { _phantom: std::marker::PhantomData<&'a ()>, });
+        let phantom = phantom_constructor(*is_static);
+
+        tokens.extend(quote! {
+            #(#attrs)*
+            #[derive(core::clone::Clone)]
+            #vis struct #self_ty #generics {
+                #fields
+                #phantom_field
+            }
+
+            impl <'a> #self_ty #generics {
+                pub fn new(#constructor_arguments) -> Self {
+                    Self {
+                        #constructors
+                        #phantom
+                    }
+                }
+
+                pub fn new_scope(&self) -> Self {
+                    Self {
+                        #scope_constructors
+                        #phantom
+                    }
+                }
+
+                #(#named_scopes)*
+
+                #init
+
+                #shutdown
+
+                #introspection
+
+                #graph
+
+                #metadata
+
+                #(#dependencies)*
+            }
+        });
+
+        builder.to_tokens(tokens);
+        profiles.to_tokens(tokens);
+        introspection.debug_to_tokens(tokens);
+
+        let container: Path = parse_str(CONTAINER_PATH).expect("CONTAINER_PATH to be a path");
+
+        for Provide {
+            cfgs,
+            ident,
+            ty,
+            is_async,
+        } in provides
+        {
+            if *is_async {
+                tokens.extend(quote! {
+                    #(#cfgs)*
+                    impl<'a> #container::ProvidesAsync<'a, #ty> for #self_ty #generics {
+                        fn provide(&'a self) -> impl std::future::Future<Output = #ty> {
+                            self.#ident()
+                        }
+                    }
+                });
+            } else {
+                tokens.extend(quote! {
+                    #(#cfgs)*
+                    impl<'a> #container::Provides<'a, #ty> for #self_ty #generics {
+                        fn provide(&'a self) -> #ty {
+                            self.#ident()
+                        }
+                    }
+                });
+            }
+        }
+
+        if !send_sync_types.is_empty() {
+            let assertions = send_sync_types.iter().map(|(cfgs, ty)| {
+                quote_spanned! { ty.span() =>
+                    #(#cfgs)*
+                    assert_send_sync::<#ty>();
+                }
+            });
+
+            // Point any errors at the dependency which cannot be shared between threads
+            tokens.extend(quote! {
+                const _: () = {
+                    fn assert_send_sync<T: ?Sized + Send + Sync>() {}
+
+                    #[allow(dead_code)]
+                    fn assert_dependencies<'a>() {
+                        #(#assertions)*
+                    }
+                };
+            });
+        }
+    }
+}
+
+impl ToTokens for Init {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let Self {
+            is_sync,
+            error_ty,
+            dependencies,
+        } = self;
+
+        let stmts = dependencies.iter().map(
+            |EagerDependency {
+                 cfgs,
+                 ident,
+                 is_async,
+                 is_fallible,
+             }| {
+                let await_token = is_async.then(|| quote! { .await });
+                let try_token = is_fallible.then(|| quote! { ? });
+
+                quote! {
+                    #(#cfgs)*
+                    self.#ident()#await_token #try_token;
+                }
+            },
+        );
+
+        let (output, ok) = match error_ty {
+            Some(error_ty) => (
+                quote! { std::result::Result<(), #error_ty> },
+                quote! { Ok(()) },
+            ),
+            None => (quote! { () }, quote! {}),
+        };
+
+        let doc = " Create the eager singletons now rather than when they are first used. Their dependencies are created first.";
+
+        let is_async = dependencies.iter().any(|d| d.is_async);
+
+        if is_async && *is_sync {
+            // Spell out the future so that it is guaranteed to be `Send`
+            tokens.extend(quote! {
+                #[doc = #doc]
+                pub fn init(&'a self) -> impl std::future::Future<Output = #output> + Send + 'a {
+                    async move {
+                        #(#stmts)*
+                        #ok
+                    }
+                }
+            });
+        } else if is_async {
+            tokens.extend(quote! {
+                #[doc = #doc]
+                pub async fn init(&'a self) -> #output {
+                    #(#stmts)*
+                    #ok
+                }
+            });
+        } else {
+            tokens.extend(quote! {
+                #[doc = #doc]
+                pub fn init(&'a self) -> #output {
+                    #(#stmts)*
+                    #ok
+                }
+            });
+        }
+    }
+}
+
+impl ToTokens for NamedScope {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let Self {
+            ident,
+            is_static,
+            constructors,
+        } = self;
+
+        let method = format_ident!("new_{}_scope", ident.unraw());
+        let phantom = phantom_constructor(*is_static);
+        let doc = format!(
+            " Create a new `{ident}` scope. The scoped dependencies of this scope and the scopes inside it are created again, while the others are shared with this container."
+        );
+
+        tokens.extend(quote! {
+            #[doc = #doc]
+            pub fn #method(&self) -> Self {
+                Self {
+                    #constructors
+                    #phantom
+                }
+            }
+        });
+    }
+}
+
+impl ToTokens for Shutdown {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let Self { is_sync, disposals } = self;
+
+        let stmts = disposals.iter().map(
+            |Disposal {
+                 cfgs,
+                 ident,
+                 is_singleton,
+                 scope,
+                 asyncness,
+                 method,
+             }| {
+                let await_token = asyncness.map(|_| quote! { .await });
+                let dispose = quote! {
+                    if let Some(#ident) = self.#ident.get() {
+                        #ident.#method()#await_token;
+                    }
+                };
+
+                if *is_singleton {
+                    quote! {
+                        #(#cfgs)*
+                        if !self._is_scope {
+                            #dispose
+                        }
+                    }
+                } else if let Some(index) = scope {
+                    let depth = Literal::usize_unsuffixed(index + 1);
+
+                    quote! {
+                        #(#cfgs)*
+                        if self._scope_depth <= #depth {
+                            #dispose
+                        }
+                    }
+                } else {
+                    quote! {
+                        #(#cfgs)*
+                        #dispose
+                    }
+                }
+            },
+        );
+
+        let doc = [
+            " Call the dispose hooks of the dependencies which have been created, starting with those that depend on the others.",
+            " A scope only disposes its own scoped dependencies.",
+        ];
+
+        let is_async = disposals.iter().any(|d| d.asyncness.is_some());
+
+        if is_async && *is_sync {
+            // Spell out the future so that it is guaranteed to be `Send`
+            tokens.extend(quote! {
+                #(#[doc = #doc])*
+                pub fn shutdown(self) -> impl std::future::Future<Output = ()> + Send + 'a {
+                    async move {
+                        #(#stmts)*
+                    }
+                }
+            });
+        } else if is_async {
+            tokens.extend(quote! {
+                #(#[doc = #doc])*
+                pub async fn shutdown(self) {
+                    #(#stmts)*
+                }
+            });
+        } else {
+            tokens.extend(quote! {
+                #(#[doc = #doc])*
+                pub fn shutdown(self) {
+                    #(#stmts)*
+                }
+            });
+        }
+    }
+}
+
+impl ToTokens for Introspection {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let Self {
+            self_ty: _,
+            is_static: _,
+            is_sync,
+            has_debug: _,
+            has_constructions,
+            dependencies,
+        } = self;
+
+        let initialized = dependencies.iter().filter(|d| d.is_managed).map(
+            |IntrospectedDependency { cfgs, ident, .. }| {
+                let name = ident.unraw().to_string();
+
+                quote! {
+                    #(#cfgs)*
+                    if self.#ident.get().is_some() {
+                        initialized.push(#name);
+                    }
+                }
+            },
+        );
+
+        let constructions =
+            dependencies
+                .iter()
+                .map(|IntrospectedDependency { cfgs, ident, .. }| {
+                    let name = ident.unraw().to_string();
+                    let field = constructions_ident(ident);
+                    let count = if *is_sync {
+                        quote! { self.#field.load(std::sync::atomic::Ordering::Relaxed) }
+                    } else {
+                        quote! { self.#field.get() }
+                    };
+
+                    quote! {
+                        #(#cfgs)*
+                        #name => Some(#count),
+                    }
+                });
+
+        let initialized_doc = " The names of the singleton and scoped dependencies which have been created, without creating any others";
+        let constructions_doc = [
+            " How many times the dependency with the given name has been created by this container and its scopes.",
+            " Or `None` if the container does not have such a dependency.",
+        ];
+
+        tokens.extend(quote! {
+            #[doc = #initialized_doc]
+            pub fn initialized(&self) -> Vec<&'static str> {
+                #[allow(unused_mut)]
+                let mut initialized = Vec::new();
+
+                #(#initialized)*
+
+                initialized
+            }
+
+        });
+
+        if *has_constructions {
+            tokens.extend(quote! {
+                #(#[doc = #constructions_doc])*
+                pub fn constructions(&self, dependency: &str) -> Option<usize> {
+                    match dependency {
+                        #(#constructions)*
+                        _ => None,
+                    }
+                }
+            });
+        }
+    }
+}
+
+impl Introspection {
+    /// Output a `Debug` implementation showing which managed dependencies have been created.
+    /// It does not require the dependencies to implement `Debug` themselves.
+    fn debug_to_tokens(&self, tokens: &mut TokenStream) {
+        let Self {
+            self_ty,
+            is_static,
+            is_sync: _,
+            has_debug,
+            has_constructions: _,
+            dependencies,
+        } = self;
+
+        if !has_debug {
+            return;
+        }
+
+        let container_name = self_ty.to_token_stream().to_string();
+        let generics = generics(*is_static);
+        let fields = dependencies.iter().filter(|d| d.is_managed).map(
+            |IntrospectedDependency { cfgs, ident, .. }| {
+                let name = ident.unraw().to_string();
+
+                quote! {
+                    #(#cfgs)*
+                    debug.field(
+                        #name,
+                        &format_args!(
+                            "{}",
+                            if self.#ident.get().is_some() { "initialized" } else { "uninitialized" }
+                        ),
+                    );
+                }
+            },
+        );
+
+        tokens.extend(quote! {
+            impl #generics core::fmt::Debug for #self_ty #generics {
+                fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                    #[allow(unused_mut)]
+                    let mut debug = f.debug_struct(#container_name);
+
+                    #(#fields)*
+
+                    debug.finish_non_exhaustive()
+                }
+            }
+        });
+    }
+}
+
+impl ToTokens for Profiles {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let Self {
+            vis,
+            self_ty,
+            ident,
+            variants,
+        } = self;
+
+        let container_name = self_ty.to_token_stream().to_string();
+        let doc = format!(
+            " The runtime profiles of [`{container_name}`]. The active profile picks the providers of its dependencies."
+        );
+
+        tokens.extend(quote! {
+            #[doc = #doc]
+            #[derive(core::clone::Clone, core::marker::Copy, core::fmt::Debug, core::cmp::PartialEq, core::cmp::Eq)]
+            #vis enum #ident {
+                #(#variants,)*
+            }
+        });
+    }
+}
+
+impl ToTokens for Builder {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let Self {
+            vis,
             self_ty,
-            constructor_arguments,
-            fields,
-            constructors,
-            scope_constructors,
-            dependencies,
+            is_static,
+            ident: builder,
+            arguments,
         } = self;
+        let generics = generics(*is_static);
 
-        tokens.extend(quote! {
-            #(#attrs)*
-            #[derive(core::clone::Clone)]
-            #vis struct #self_ty <'a> {
-                #fields
-                _phantom: std::marker::PhantomData<&'a ()>,
+        // Each required argument is tracked with a generic type, which stays `()` until the argument is set
+        let params: Vec<_> = arguments
+            .iter()
+            .filter(|argument| argument.default.is_none())
+            .map(BuilderArgument::param)
+            .collect();
+
+        let fields = arguments.iter().map(|argument| {
+            let BuilderArgument { ident, ty, default } = argument;
+
+            if default.is_some() {
+                quote! { #ident: Option<#ty> }
+            } else {
+                let param = argument.param();
+                quote! { #ident: #param }
             }
+        });
 
-            impl <'a> #self_ty <'a> {
-                pub fn new(#constructor_arguments) -> Self {
-                    Self {
-                        #constructors
+        let unset_params = params.iter().map(|_| quote! { () });
+        let unset_fields = arguments.iter().map(|argument| {
+            let ident = &argument.ident;
+
+            if argument.default.is_some() {
+                quote! { #ident: None }
+            } else {
+                quote! { #ident: () }
+            }
+        });
+
+        let setters = arguments.iter().map(|argument| {
+            let BuilderArgument { ident, ty, default } = argument;
+
+            if default.is_some() {
+                return quote! {
+                    pub fn #ident(mut self, #ident: #ty) -> Self {
+                        self.#ident = Some(#ident);
+                        self
+                    }
+                };
+            }
+
+            let param = argument.param();
+            let set_params = params.iter().map(|other| {
+                if *other == param {
+                    quote! { (#ty,) }
+                } else {
+                    quote! { #other }
+                }
+            });
+            let moved_fields = arguments.iter().map(|other| {
+                let other = &other.ident;
+
+                if other == ident {
+                    quote! { #ident: (#ident,) }
+                } else {
+                    quote! { #other: self.#other }
+                }
+            });
+
+            quote! {
+                pub fn #ident(self, #ident: #ty) -> #builder<'a, #(#set_params),*> {
+                    #builder {
+                        #(#moved_fields,)*
                         _phantom: Default::default(),
                     }
                 }
+            }
+        });
 
-                pub fn new_scope(&self) -> Self {
-                    Self {
-                        #scope_constructors
+        let container_name = self_ty.to_token_stream().to_string();
+        let requirements = arguments
+            .iter()
+            .filter(|argument| argument.default.is_none())
+            .map(|argument| {
+                let BuilderArgument { ident, ty, .. } = argument;
+                let param = argument.param();
+                let requirement = argument.requirement(builder);
+                let message = format!(
+                    "The `{ident}` constructor argument of `{container_name}` has not been set"
+                );
+                let label = format!("`{ident}` is missing");
+                let note = format!("Set it with `.{ident}(...)` before calling `.build()`");
+
+                let definition = quote! {
+                    #[doc(hidden)]
+                    #[diagnostic::on_unimplemented(message = #message, label = #label, note = #note)]
+                    #vis trait #requirement<'a> {
+                        fn into_argument(self) -> #ty;
+                    }
+
+                    impl<'a> #requirement<'a> for (#ty,) {
+                        fn into_argument(self) -> #ty {
+                            self.0
+                        }
+                    }
+                };
+
+                (definition, quote! { #param: #requirement<'a> })
+            });
+        let (definitions, bounds): (Vec<_>, Vec<_>) = requirements.unzip();
+
+        let constructor_arguments = arguments.iter().map(|argument| {
+            let BuilderArgument { ident, default, .. } = argument;
+
+            match default {
+                Some(default) => quote! { self.#ident.unwrap_or_else(|| #default) },
+                None => {
+                    let requirement = argument.requirement(builder);
+                    quote! { #requirement::into_argument(self.#ident) }
+                }
+            }
+        });
+
+        let doc =
+            format!(" Builder to set the constructor arguments of [`{container_name}`] by name.");
+
+        tokens.extend(quote! {
+            #[doc = #doc]
+            #[must_use]
+            #vis struct #builder<'a, #(#params),*> {
+                #(#fields,)*
+                _phantom: std::marker::PhantomData<&'a ()>,
+            }
+
+            impl<'a> #self_ty #generics {
+                pub fn builder() -> #builder<'a, #(#unset_params),*> {
+                    #builder {
+                        #(#unset_fields,)*
                         _phantom: Default::default(),
                     }
                 }
+            }
 
-                #(#dependencies)*
+            impl<'a, #(#params),*> #builder<'a, #(#params),*> {
+                #(#setters)*
+
+                pub fn build(self) -> #self_ty #generics
+                where
+                    #(#bounds,)*
+                {
+                    #self_ty::new(#(#constructor_arguments),*)
+                }
             }
+
+            #(#definitions)*
         });
     }
 }
 
+impl BuilderArgument {
+    /// The generic type tracking whether this argument has been set
+    fn param(&self) -> Ident {
+        format_ident!("__{}", to_pascal_case(&self.ident))
+    }
+
+    /// The trait a set argument satisfies
+    fn requirement(&self, builder: &Ident) -> Ident {
+        format_ident!("{}Has{}", builder, to_pascal_case(&self.ident))
+    }
+}
+
 impl ToTokens for Dependency {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let Self {
             attrs,
+            cfgs,
             block: _,
             asyncness,
-            fn_token,
+            fn_token: _,
             ident,
             paren_token,
             inputs,
             ty,
+            field_ty: _,
+            error_ty: _,
+            override_ty,
+            value_ty: _,
+            create_ty,
             create_asyncness: _,
+            is_fallible: _,
             is_managed: _,
             is_embedded: _,
+            is_import: _,
+            is_counted: _,
+            is_shared: _,
+            lifetime: _,
+            is_sync,
+            arguments,
             dependencies: _,
+            deferred: _,
         } = self;
 
         // Do the same thing `syn` does for the paren_token
@@ -354,59 +1907,422 @@ impl ToTokens for Dependency {
 
         let stmts = self.to_stmts();
 
-        tokens.extend(quote!(
-            #(#attrs)*
-            pub #asyncness #fn_token #ident(&'a self) -> #ty {
-                #(#stmts);*
-            }
-        ));
+        if let Some(create_ty) = create_ty {
+            // Other dependencies call the private method so that they get the concrete type
+            let create_ident = create_ident(ident);
+            let await_token = asyncness.is_some().then(|| quote! { .await });
+            let arguments = arguments.iter().map(|argument| &argument.ident);
+
+            self.method_to_tokens(
+                tokens,
+                quote! { #(#cfgs)* #(#attrs)* pub },
+                ident,
+                ty,
+                quote! { self.#create_ident(#(#arguments),*)#await_token },
+            );
+            self.method_to_tokens(
+                tokens,
+                quote! { #(#cfgs)* },
+                &create_ident,
+                create_ty,
+                self.trace_body(quote! { #(#stmts);* }),
+            );
+        } else {
+            self.method_to_tokens(
+                tokens,
+                quote! { #(#cfgs)* #(#attrs)* pub },
+                ident,
+                ty,
+                self.trace_body(quote! { #(#stmts);* }),
+            );
+        }
+
+        if let Some(override_ty) = override_ty {
+            let override_ident = self.override_ident();
+            let override_field_ident = self.override_field_ident();
+            let doc = format!(
+                " Replace how the `{ident}` dependency is created. Useful to swap in a fake during tests."
+            );
+
+            let argument_tys: Vec<_> = arguments.iter().map(|argument| &argument.ty).collect();
+
+            let (create_ty, wrapper) = if *is_sync {
+                (
+                    quote! { impl Fn(#(#argument_tys),*) -> #override_ty + Send + Sync + 'static },
+                    quote! { std::sync::Arc },
+                )
+            } else {
+                (
+                    quote! { impl Fn(#(#argument_tys),*) -> #override_ty + 'static },
+                    quote! { std::rc::Rc },
+                )
+            };
+
+            tokens.extend(quote!(
+                #(#cfgs)*
+                #[doc = #doc]
+                pub fn #override_ident(mut self, create: #create_ty) -> Self {
+                    self.#override_field_ident = Some(#wrapper::new(create));
+                    self
+                }
+            ));
+        }
     }
 }
 
 impl Dependency {
+    /// Output a method of this dependency with the given signature parts and body
+    fn method_to_tokens(
+        &self,
+        tokens: &mut TokenStream,
+        prefix: TokenStream,
+        ident: &Ident,
+        ty: &Type,
+        body: TokenStream,
+    ) {
+        let Self {
+            asyncness,
+            fn_token,
+            is_sync,
+            arguments,
+            ..
+        } = self;
+        let arguments = arguments
+            .iter()
+            .map(|Argument { ident, ty }| quote! { , #ident: #ty });
+
+        if *is_sync && asyncness.is_some() {
+            // Spell out the future so that it is guaranteed to be `Send`
+            tokens.extend(quote!(
+                #prefix #fn_token #ident(&'a self #(#arguments)*) -> impl std::future::Future<Output = #ty> + Send + 'a {
+                    async move {
+                        #body
+                    }
+                }
+            ));
+        } else {
+            tokens.extend(quote!(
+                #prefix #asyncness #fn_token #ident(&'a self #(#arguments)*) -> #ty {
+                    #body
+                }
+            ));
+        }
+    }
+
     fn to_stmts(&self) -> Vec<Stmt> {
         let Self {
             attrs: _,
+            cfgs: _,
             block,
             asyncness: _,
             fn_token: _,
-            ident,
+            ident: _,
             paren_token: _,
             inputs: _,
             ty: _,
-            create_asyncness,
-            is_managed,
-            is_embedded,
+            field_ty: _,
+            error_ty: _,
+            override_ty,
+            value_ty: _,
+            create_ty: _,
+            create_asyncness: _,
+            is_fallible: _,
+            is_managed: _,
+            is_embedded: _,
+            is_import: _,
+            is_counted: _,
+            is_shared: _,
+            lifetime: _,
+            is_sync,
+            arguments,
             dependencies,
+            deferred,
         } = self;
 
         let create_dependencies: Vec<_> = dependencies
             .iter()
-            .map(|child_dependency| {
-                let stmts = child_dependency.to_stmts();
-                let ident = &child_dependency.ident;
+            .map(
+                |ChildDependency {
+                     ident,
+                     dependency,
+                     is_async,
+                     is_fallible,
+                     is_cloned,
+                     borrowed,
+                 }| {
+                    let await_token = is_async.then(|| quote! { .await });
+                    let try_token = is_fallible.then(|| quote! { ? });
+                    let clone_token = is_cloned.then(|| quote! { .clone() });
+
+                    // The cell keeps the `Arc` alive for as long as the container. So a reference can borrow from it
+                    if let Some((field, ty)) = borrowed {
+                        let get = quote! { self.#field.get().expect("the dependency to have been created") };
+
+                        return if is_opaque(ty) {
+                            quote! {
+                                let #ident = { self.#dependency()#await_token #try_token; &**#get };
+                            }
+                        } else {
+                            quote! {
+                                let #ident: #ty = { self.#dependency()#await_token #try_token; #get };
+                            }
+                        };
+                    }
+
+                    quote! {
+                        let #ident = self.#dependency()#await_token #try_token #clone_token;
+                    }
+                },
+            )
+            .chain(deferred.iter().map(
+                |DeferredDependency {
+                     ident,
+                     dependency,
+                     is_async,
+                     handle,
+                     arguments,
+                 }| {
+                    let handle = match (handle, is_async) {
+                        (Handle::Lazy, false) => quote! { Lazy },
+                        (Handle::Lazy, true) => quote! { AsyncLazy },
+                        (Handle::Provider, false) => quote! { Provider },
+                        (Handle::Provider, true) => quote! { AsyncProvider },
+                        (Handle::Factory, _) => {
+                            // Requested as a plain function which takes the runtime arguments
+                            let params = arguments.iter().map(|Argument { ident, ty }| quote! { #ident: #ty });
+                            let arguments = arguments.iter().map(|argument| &argument.ident);
+
+                            return quote! {
+                                let #ident = move |#(#params),*| self.#dependency(#(#arguments),*);
+                            };
+                        }
+                    };
+
+                    // Only containers generated through `despatma` have deferred dependencies
+                    let container: Path = parse_str(CONTAINER_PATH).expect("CONTAINER_PATH to be a path");
+
+                    // The handles of `sync` containers have to be shareable between threads too
+                    let (resolver, resolve) = match (is_async, is_sync) {
+                        (false, false) => (
+                            quote! { dyn Fn() -> _ + 'a },
+                            quote! { self.#dependency() },
+                        ),
+                        (false, true) => (
+                            quote! { dyn Fn() -> _ + Send + Sync + 'a },
+                            quote! { self.#dependency() },
+                        ),
+                        (true, false) => (
+                            quote! { dyn Fn() -> #container::BoxFuture<'a, _> + 'a },
+                            quote! { Box::pin(self.#dependency()) },
+                        ),
+                        (true, true) => (
+                            quote! {
+                                dyn Fn() -> std::pin::Pin<Box<dyn std::future::Future<Output = _> + Send + 'a>>
+                                    + Send
+                                    + Sync
+                                    + 'a
+                            },
+                            quote! { Box::pin(self.#dependency()) },
+                        ),
+                    };
+
+                    quote! {
+                        let #ident = #container::#handle::<_, #resolver>::new(Box::new(move || #resolve));
+                    }
+                },
+            ))
+            .collect();
 
-                let block = if stmts.len() == 1 {
-                    let stmt = &stmts[0];
+        let final_stmt = self.final_stmt(block);
 
-                    quote! { #stmt }
-                } else {
-                    quote! { {  #(#stmts);* } }
-                };
+        if override_ty.is_none() {
+            return parse_quote! {
+                #(#create_dependencies)*
 
-                let create_stmt = quote! {
-                    let #ident = #block;
-                };
+                #final_stmt
+            };
+        }
 
-                create_stmt
-            })
-            .collect();
+        // Check for an override before creating any of the children, since the override will not need them
+        let override_field_ident = self.override_field_ident();
+        let arguments = arguments.iter().map(|argument| &argument.ident);
+        let override_stmt = self.final_stmt(&parse_quote!({ create(#(#arguments),*) }));
+
+        parse_quote! {
+            if let Some(create) = &self.#override_field_ident {
+                #override_stmt
+            } else {
+                #(#create_dependencies)*
+
+                #final_stmt
+            }
+        }
+    }
+
+    /// Wrap the body which creates this dependency in a span with the name and lifetime of the dependency.
+    /// So that a slow dependency can be found in the traces of a slow startup.
+    #[cfg(feature = "tracing")]
+    fn trace_body(&self, body: TokenStream) -> TokenStream {
+        let Self {
+            ident,
+            asyncness,
+            is_managed,
+            lifetime,
+            ..
+        } = self;
+        let tracing: Path = parse_str(TRACING_PATH).expect("TRACING_PATH to be a path");
+        let name = ident.unraw().to_string();
+
+        // Use hygienic names so that the span cannot clash with the arguments of the dependency
+        let span = Ident::new("span", proc_macro2::Span::mixed_site());
+        let entered = Ident::new("_entered", proc_macro2::Span::mixed_site());
+
+        // A managed dependency is served from its cell unless the block creating it runs
+        let span_stmt = quote! {
+            let #span = #tracing::debug_span!(
+                "resolve",
+                dependency = #name,
+                lifetime = #lifetime,
+                cached = #is_managed,
+                duration = #tracing::field::Empty,
+            );
+        };
+
+        if asyncness.is_some() {
+            quote! {
+                #span_stmt
+                #tracing::Instrument::instrument(async move { #body }, #span).await
+            }
+        } else {
+            quote! {
+                #span_stmt
+                let #entered = #span.enter();
+                #body
+            }
+        }
+    }
+
+    #[cfg(not(feature = "tracing"))]
+    fn trace_body(&self, body: TokenStream) -> TokenStream {
+        body
+    }
+
+    /// Record on the span of the dependency how long the given block took to create it
+    #[cfg(feature = "tracing")]
+    fn time_block(&self, block: &Block) -> Block {
+        let tracing: Path = parse_str(TRACING_PATH).expect("TRACING_PATH to be a path");
+        let start = Ident::new("start", proc_macro2::Span::mixed_site());
+        let value = Ident::new("value", proc_macro2::Span::mixed_site());
+
+        // The block only runs when the value is missing from the cell of a managed dependency
+        let record_cached = self.is_managed.then(|| quote! { .record("cached", false) });
+
+        // Keep the type the block returns so that its value can still be coerced, like to a `Box<dyn Trait>`
+        let value_ty = self.value_ty.as_ref().map(|ty| quote! { : #ty });
+
+        parse_quote! {{
+            let #start = std::time::Instant::now();
+            let #value #value_ty = #block;
+
+            #tracing::Span::current()
+                #record_cached
+                .record("duration", #tracing::field::debug(#start.elapsed()));
+
+            #value
+        }}
+    }
+
+    #[cfg(not(feature = "tracing"))]
+    fn time_block(&self, block: &Block) -> Block {
+        block.clone()
+    }
+
+    /// Count every time the given block runs to create this dependency
+    fn count_block(&self, block: &Block) -> Block {
+        if !self.is_counted {
+            return block.clone();
+        }
+
+        let field = constructions_ident(&self.ident);
+        let count: Stmt = if self.is_sync {
+            parse_quote! {
+                self.#field.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            }
+        } else {
+            parse_quote! {
+                self.#field.set(self.#field.get() + 1);
+            }
+        };
+
+        let mut block = block.clone();
+        block.stmts.insert(0, count);
+
+        block
+    }
+
+    /// Figure out the correct final statement to create this dependency with the given block
+    fn final_stmt(&self, block: &Block) -> TokenStream {
+        let Self {
+            ident,
+            field_ty,
+            error_ty,
+            create_asyncness,
+            is_fallible,
+            is_managed,
+            is_embedded,
+            is_shared,
+            ..
+        } = self;
+        let block = &self.time_block(&self.count_block(block));
 
-        // Figure out the correct final statement
-        let final_stmt = if *is_managed && !is_embedded {
+        let final_stmt = if *is_shared {
+            // The cell holds an `Arc` so that the caller gets an owned handle
+            if create_asyncness.is_some() {
+                if *is_fallible {
+                    quote! {
+                        self.#ident.get_or_try_init(async { (async #block ).await.map(std::sync::Arc::new) }).await.cloned()
+                    }
+                } else {
+                    quote! {
+                        self.#ident.get_or_init(async { std::sync::Arc::new(#block) }).await.clone()
+                    }
+                }
+            } else if *is_fallible {
+                quote! {
+                    match self.#ident.get() {
+                        Some(#ident) => Ok(#ident.clone()),
+                        None => {
+                            let #ident = (|| -> std::result::Result<#field_ty, #error_ty> #block)()?;
+                            Ok(self.#ident.get_or_init(|| std::sync::Arc::new(#ident)).clone())
+                        }
+                    }
+                }
+            } else {
+                quote! {
+                    self.#ident.get_or_init(|| std::sync::Arc::new(#block)).clone()
+                }
+            }
+        } else if *is_managed && !is_embedded {
             if create_asyncness.is_some() {
+                if *is_fallible {
+                    quote! {
+                        self.#ident.get_or_try_init(async #block ).await
+                    }
+                } else {
+                    quote! {
+                        self.#ident.get_or_init(async #block ).await
+                    }
+                }
+            } else if *is_fallible {
+                // Only cache the value once it was created successfully so that creating it is retried next time
                 quote! {
-                    self.#ident.get_or_init(async #block ).await
+                    match self.#ident.get() {
+                        Some(#ident) => Ok(#ident),
+                        None => {
+                            let #ident = (|| -> std::result::Result<#field_ty, #error_ty> #block)()?;
+                            Ok(self.#ident.get_or_init(|| #ident))
+                        }
+                    }
                 }
             } else {
                 quote! {
@@ -424,10 +2340,13 @@ impl Dependency {
             }
         };
 
-        parse_quote! {
-            #(#create_dependencies)*
-
-            #final_stmt
+        // Dependencies which can fail because of their children still need to return a `Result`
+        if error_ty.is_some() && !is_fallible {
+            quote! {
+                Ok({ #final_stmt })
+            }
+        } else {
+            final_stmt
         }
     }
 }
@@ -440,7 +2359,8 @@ mod tests {
     use proc_macro2::Span;
     use syn::parse_quote;
 
-    use crate::processing::{self, Lifetime};
+    use crate::input;
+    use crate::processing::{self, Lifetime, Pointer};
 
     use super::*;
 
@@ -448,77 +2368,153 @@ mod tests {
     fn from_processing_container() {
         let embedded = Rc::new(RefCell::new(processing::Dependency {
             attrs: vec![],
+            cfgs: vec![],
             sig: parse_quote!(fn embedded(&self) -> Embedded),
             block: parse_quote!({}),
             is_async: false,
-            is_boxed: false,
+            pointer: None,
+            is_fallible: false,
+            is_eager: false,
+            is_stateful: false,
+            is_shared: false,
             lifetime: Lifetime::Embedded(Span::call_site()),
+            scope: None,
+            qualifier: None,
+            default: None,
+            is_import: false,
+            dispose: None,
+            group: None,
+            profile: None,
+            arguments: vec![],
             ty: parse_quote! { Embedded },
             field_ty: parse_quote! { Embedded },
+            error_ty: None,
             dependencies: vec![],
+            deferred: vec![],
         }));
 
         let config = Rc::new(RefCell::new(processing::Dependency {
             attrs: vec![],
+            cfgs: vec![],
             sig: parse_quote! {
                 async fn config(&self) -> Config
             },
             block: parse_quote!({ Config::new().await }),
             is_async: true,
-            is_boxed: false,
+            pointer: None,
+            is_fallible: false,
+            is_eager: false,
+            is_stateful: false,
+            is_shared: false,
             lifetime: Lifetime::Singleton(Span::call_site()),
+            scope: None,
+            qualifier: None,
+            default: None,
+            is_import: false,
+            dispose: None,
+            group: None,
+            profile: None,
+            arguments: vec![],
             ty: parse_quote! { Config },
             field_ty: parse_quote! { Config },
+            error_ty: None,
             dependencies: vec![],
+            deferred: vec![],
         }));
         let db = Rc::new(RefCell::new(processing::Dependency {
             attrs: vec![],
+            cfgs: vec![],
             sig: parse_quote! {
                 fn db(&self, config: &Config, embedded: &Embedded) -> Sqlite
             },
             block: parse_quote!({ Sqlite::new(config.conn_str, embedded.some_val) }),
             is_async: true,
-            is_boxed: false,
+            pointer: None,
+            is_fallible: false,
+            is_eager: false,
+            is_stateful: false,
+            is_shared: false,
             lifetime: Lifetime::Singleton(Span::call_site()),
+            scope: None,
+            qualifier: None,
+            default: None,
+            is_import: false,
+            dispose: None,
+            group: None,
+            profile: None,
+            arguments: vec![],
             ty: parse_quote! { Sqlite },
             field_ty: parse_quote! { Sqlite },
+            error_ty: None,
             dependencies: vec![
                 processing::ChildDependency {
                     inner: config.clone(),
+                    alternatives: vec![],
+                    ident: parse_quote!(config),
                     ty: parse_quote!(&Config),
                 },
                 processing::ChildDependency {
                     inner: embedded.clone(),
+                    alternatives: vec![],
+                    ident: parse_quote!(embedded),
                     ty: parse_quote!(&Embedded),
                 },
             ],
+            deferred: vec![],
         }));
         let container = processing::Container {
             vis: syn::Visibility::Inherited,
+            options: input::Options {
+                despatma: true,
+                provides: true,
+                overrides: true,
+                constructions: true,
+                ..Default::default()
+            },
             attrs: vec![],
             self_ty: parse_quote! { Container },
+            profiles: None,
             dependencies: vec![
                 config,
                 db.clone(),
                 Rc::new(RefCell::new(processing::Dependency {
                     attrs: vec![],
+                    cfgs: vec![],
                     sig: parse_quote! {
                         fn service(&self, db: &Sqlite) -> Service
                     },
                     block: parse_quote!({ Service::new(db) }),
                     is_async: true,
-                    is_boxed: false,
+                    pointer: None,
+                    is_fallible: false,
+                    is_eager: false,
+                    is_stateful: false,
+                    is_shared: false,
                     lifetime: Lifetime::Transient(None),
+                    scope: None,
+                    qualifier: None,
+                    default: None,
+                    is_import: false,
+                    dispose: None,
+                    group: None,
+                    profile: None,
+                    arguments: vec![],
                     ty: parse_quote! { Service },
                     field_ty: parse_quote! { Service },
+                    error_ty: None,
                     dependencies: vec![processing::ChildDependency {
                         inner: db,
+                        alternatives: vec![],
+                        ident: parse_quote!(db),
                         ty: parse_quote!(&Sqlite),
                     }],
+                    deferred: vec![],
                 })),
                 embedded,
             ],
         };
+        let graph = Graph::new(&container.self_ty, &container.dependencies);
+        let metadata = Metadata::new(&container.dependencies);
         let container: super::Container = container.into();
 
         let fields = {
@@ -527,12 +2523,19 @@ mod tests {
                     config: std::sync::Arc<async_once_cell::OnceCell<Config>>,
                     db: std::rc::Rc<std::cell::OnceCell<Sqlite>>,
                     embedded: std::sync::Arc<Embedded>,
+                    config_override: Option<std::rc::Rc<dyn Fn() -> Config>>,
+                    db_override: Option<std::rc::Rc<dyn Fn() -> Sqlite>>,
+                    service_override: Option<std::rc::Rc<dyn Fn() -> Service>>,
+                    config_constructions: std::rc::Rc<std::cell::Cell<usize>>,
+                    db_constructions: std::rc::Rc<std::cell::Cell<usize>>,
+                    service_constructions: std::rc::Rc<std::cell::Cell<usize>>,
                 }
             };
             named.named
         };
         let config = Dependency {
             attrs: vec![],
+            cfgs: vec![],
             block: parse_quote!({ Config::new().await }),
             asyncness: Some(parse_quote!(async)),
             fn_token: parse_quote!(fn),
@@ -540,27 +2543,55 @@ mod tests {
             paren_token: Default::default(),
             inputs: parse_quote!(&self),
             ty: parse_quote!(&Config),
+            field_ty: parse_quote!(Config),
+            error_ty: None,
+            override_ty: Some(parse_quote!(Config)),
+            value_ty: Some(parse_quote!(Config)),
+            create_ty: None,
             create_asyncness: Some(parse_quote!(async)),
+            is_fallible: false,
             is_managed: true,
             is_embedded: false,
+            is_import: false,
+            is_counted: true,
+            is_shared: false,
+            lifetime: "Singleton",
+            is_sync: false,
+            arguments: vec![],
             dependencies: vec![],
+            deferred: vec![],
         };
         let embedded = Dependency {
             attrs: vec![],
+            cfgs: vec![],
             block: parse_quote!({}),
             asyncness: None,
             fn_token: parse_quote!(fn),
             ident: parse_quote!(embedded),
             paren_token: Default::default(),
             ty: parse_quote! { &Embedded },
+            field_ty: parse_quote!(Embedded),
+            error_ty: None,
+            override_ty: None,
+            value_ty: None,
+            create_ty: None,
             create_asyncness: None,
             dependencies: vec![],
             inputs: parse_quote!(&self),
+            is_fallible: false,
             is_managed: true,
             is_embedded: true,
+            is_import: false,
+            is_counted: false,
+            is_shared: false,
+            lifetime: "Embedded",
+            is_sync: false,
+            arguments: vec![],
+            deferred: vec![],
         };
         let db = Dependency {
             attrs: vec![],
+            cfgs: vec![],
             block: parse_quote!({ Sqlite::new(config.conn_str, embedded.some_val) }),
             asyncness: Some(parse_quote!(async)),
             fn_token: parse_quote!(fn),
@@ -568,24 +2599,118 @@ mod tests {
             paren_token: Default::default(),
             inputs: parse_quote!(&self, config: &Config, embedded: &Embedded),
             ty: parse_quote!(&Sqlite),
+            field_ty: parse_quote!(Sqlite),
+            error_ty: None,
+            override_ty: Some(parse_quote!(Sqlite)),
+            value_ty: Some(parse_quote!(Sqlite)),
+            create_ty: None,
             create_asyncness: None,
+            is_fallible: false,
             is_managed: true,
             is_embedded: false,
-            dependencies: vec![config.clone(), embedded.clone()],
+            is_import: false,
+            is_counted: true,
+            is_shared: false,
+            lifetime: "Singleton",
+            is_sync: false,
+            arguments: vec![],
+            dependencies: vec![
+                ChildDependency {
+                    ident: parse_quote!(config),
+                    dependency: parse_quote!(config),
+                    is_async: true,
+                    is_fallible: false,
+                    is_cloned: false,
+                    borrowed: None,
+                },
+                ChildDependency {
+                    ident: parse_quote!(embedded),
+                    dependency: parse_quote!(embedded),
+                    is_async: false,
+                    is_fallible: false,
+                    is_cloned: false,
+                    borrowed: None,
+                },
+            ],
+            deferred: vec![],
         };
         let expected = super::Container {
             vis: Visibility::Inherited,
             attrs: vec![],
             self_ty: parse_quote! { Container },
+            is_static: false,
             constructor_arguments: parse_quote!(embedded: Embedded,),
             fields,
-            constructors: parse_quote!( config: Default::default(), db: Default::default(), embedded: std::sync::Arc::new(embedded), ),
-            scope_constructors: parse_quote!( config: self.config.clone(), db: self.db.clone(), embedded: self.embedded.clone(), ),
+            constructors: parse_quote!(
+                config: Default::default(),
+                db: Default::default(),
+                embedded: std::sync::Arc::new(embedded),
+                config_override: None,
+                db_override: None,
+                service_override: None,
+                config_constructions: Default::default(),
+                db_constructions: Default::default(),
+                service_constructions: Default::default(),
+            ),
+            scope_constructors: parse_quote!(
+                config: self.config.clone(),
+                db: self.db.clone(),
+                embedded: self.embedded.clone(),
+                config_override: self.config_override.clone(),
+                db_override: self.db_override.clone(),
+                service_override: self.service_override.clone(),
+                config_constructions: self.config_constructions.clone(),
+                db_constructions: self.db_constructions.clone(),
+                service_constructions: self.service_constructions.clone(),
+            ),
+            named_scopes: vec![],
+            send_sync_types: vec![],
+            builder: Some(Builder {
+                vis: Visibility::Inherited,
+                self_ty: parse_quote! { Container },
+                is_static: false,
+                ident: parse_quote!(ContainerBuilder),
+                arguments: vec![BuilderArgument {
+                    ident: parse_quote!(embedded),
+                    ty: parse_quote!(Embedded),
+                    default: None,
+                }],
+            }),
+            profiles: None,
+            init: None,
+            shutdown: None,
+            introspection: Introspection {
+                self_ty: parse_quote! { Container },
+                is_static: false,
+                is_sync: false,
+                has_debug: true,
+                has_constructions: true,
+                dependencies: vec![
+                    IntrospectedDependency {
+                        cfgs: vec![],
+                        ident: parse_quote!(config),
+                        is_managed: true,
+                    },
+                    IntrospectedDependency {
+                        cfgs: vec![],
+                        ident: parse_quote!(db),
+                        is_managed: true,
+                    },
+                    IntrospectedDependency {
+                        cfgs: vec![],
+                        ident: parse_quote!(service),
+                        is_managed: false,
+                    },
+                ],
+            },
+            graph,
+            metadata: Some(metadata),
             dependencies: vec![
                 config,
                 db.clone(),
                 Dependency {
                     attrs: vec![],
+                    cfgs: vec![],
                     block: parse_quote!({ Service::new(db) }),
                     asyncness: Some(parse_quote!(async)),
                     fn_token: parse_quote!(fn),
@@ -593,13 +2718,59 @@ mod tests {
                     paren_token: Default::default(),
                     inputs: parse_quote!(&self, db: &Sqlite),
                     ty: parse_quote!(Service),
+                    field_ty: parse_quote!(Service),
+                    error_ty: None,
+                    override_ty: Some(parse_quote!(Service)),
+                    value_ty: Some(parse_quote!(Service)),
+                    create_ty: None,
                     create_asyncness: None,
+                    is_fallible: false,
                     is_managed: false,
                     is_embedded: false,
-                    dependencies: vec![db],
+                    is_import: false,
+                    is_counted: true,
+                    is_shared: false,
+                    lifetime: "Transient",
+                    is_sync: false,
+                    arguments: vec![],
+                    dependencies: vec![ChildDependency {
+                        ident: parse_quote!(db),
+                        dependency: parse_quote!(db),
+                        is_async: true,
+                        is_fallible: false,
+                        is_cloned: false,
+                        borrowed: None,
+                    }],
+                    deferred: vec![],
                 },
                 embedded,
             ],
+            provides: vec![
+                Provide {
+                    cfgs: vec![],
+                    ident: parse_quote!(config),
+                    ty: parse_quote!(&'a Config),
+                    is_async: true,
+                },
+                Provide {
+                    cfgs: vec![],
+                    ident: parse_quote!(db),
+                    ty: parse_quote!(&'a Sqlite),
+                    is_async: true,
+                },
+                Provide {
+                    cfgs: vec![],
+                    ident: parse_quote!(service),
+                    ty: parse_quote!(Service),
+                    is_async: true,
+                },
+                Provide {
+                    cfgs: vec![],
+                    ident: parse_quote!(embedded),
+                    ty: parse_quote!(&'a Embedded),
+                    is_async: false,
+                },
+            ],
         };
 
         assert_eq!(container, expected);
@@ -609,21 +2780,37 @@ mod tests {
     fn from_processing_dependency() {
         let dependency = processing::Dependency {
             attrs: vec![],
+            cfgs: vec![],
             sig: parse_quote! {
                 fn db(&self) -> Box<dyn DB>
             },
             block: parse_quote!({ Box::new(Sqlite::new()) }),
             is_async: false,
-            is_boxed: true,
+            pointer: Some(Pointer::Box),
+            is_fallible: false,
+            is_eager: false,
+            is_stateful: false,
+            is_shared: false,
             lifetime: Lifetime::Scoped(Span::call_site()),
+            scope: None,
+            qualifier: None,
+            default: None,
+            is_import: false,
+            dispose: None,
+            group: None,
+            profile: None,
+            arguments: vec![],
             ty: parse_quote! { std::boxed::Box<dyn DB + 'a> },
             field_ty: parse_quote! { std::boxed::Box<dyn DB + 'a> },
+            error_ty: None,
             dependencies: vec![],
+            deferred: vec![],
         };
-        let dependency: Dependency = dependency.into();
+        let dependency = Dependency::new(dependency, &Default::default());
 
         let expected = Dependency {
             attrs: vec![],
+            cfgs: vec![],
             block: parse_quote!({ Box::new(Sqlite::new()) }),
             asyncness: None,
             fn_token: parse_quote!(fn),
@@ -631,12 +2818,137 @@ mod tests {
             paren_token: Default::default(),
             inputs: parse_quote!(&self),
             ty: parse_quote!(&std::boxed::Box<dyn DB + 'a>),
+            field_ty: parse_quote!(std::boxed::Box<dyn DB + 'a>),
+            error_ty: None,
+            override_ty: None,
+            value_ty: Some(parse_quote!(std::boxed::Box<dyn DB + 'a>)),
+            create_ty: None,
             create_asyncness: None,
+            is_fallible: false,
             is_managed: true,
             is_embedded: false,
+            is_import: false,
+            is_counted: false,
+            is_shared: false,
+            lifetime: "Scoped",
+            is_sync: false,
+            arguments: vec![],
             dependencies: vec![],
+            deferred: vec![],
         };
 
         assert_eq!(dependency, expected);
     }
+
+    #[cfg(feature = "tracing")]
+    fn tracing_dependency(is_async: bool, lifetime: Lifetime) -> Dependency {
+        let dependency = processing::Dependency {
+            attrs: vec![],
+            cfgs: vec![],
+            sig: parse_quote! {
+                fn config(&self) -> Config
+            },
+            block: parse_quote!({ Config }),
+            is_async,
+            pointer: None,
+            is_fallible: false,
+            is_eager: false,
+            is_stateful: false,
+            is_shared: false,
+            lifetime,
+            scope: None,
+            qualifier: None,
+            default: None,
+            is_import: false,
+            dispose: None,
+            group: None,
+            profile: None,
+            arguments: vec![],
+            ty: parse_quote! { Config },
+            field_ty: parse_quote! { Config },
+            error_ty: None,
+            dependencies: vec![],
+            deferred: vec![],
+        };
+
+        Dependency::new(dependency, &Default::default())
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn trace_body() {
+        let dependency = tracing_dependency(false, Lifetime::Singleton(Span::call_site()));
+
+        assert_eq!(
+            dependency.trace_body(quote! { Config }).to_string(),
+            quote! {
+                let span = tracing::debug_span!(
+                    "resolve",
+                    dependency = "config",
+                    lifetime = "Singleton",
+                    cached = true,
+                    duration = tracing::field::Empty,
+                );
+                let _entered = span.enter();
+                Config
+            }
+            .to_string()
+        );
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn trace_body_async() {
+        let dependency = tracing_dependency(true, Lifetime::Transient(None));
+
+        assert_eq!(
+            dependency.trace_body(quote! { Config }).to_string(),
+            quote! {
+                let span = tracing::debug_span!(
+                    "resolve",
+                    dependency = "config",
+                    lifetime = "Transient",
+                    cached = false,
+                    duration = tracing::field::Empty,
+                );
+                tracing::Instrument::instrument(async move { Config }, span).await
+            }
+            .to_string()
+        );
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn time_block() {
+        let dependency = tracing_dependency(false, Lifetime::Scoped(Span::call_site()));
+        let expected: Block = parse_quote! {{
+            let start = std::time::Instant::now();
+            let value: Config = { Config };
+
+            tracing::Span::current()
+                .record("cached", false)
+                .record("duration", tracing::field::debug(start.elapsed()));
+
+            value
+        }};
+
+        assert_eq!(dependency.time_block(&parse_quote!({ Config })), expected);
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn time_block_transient() {
+        let dependency = tracing_dependency(true, Lifetime::Transient(None));
+        let expected: Block = parse_quote! {{
+            let start = std::time::Instant::now();
+            let value: Config = { Config };
+
+            tracing::Span::current()
+                .record("duration", tracing::field::debug(start.elapsed()));
+
+            value
+        }};
+
+        assert_eq!(dependency.time_block(&parse_quote!({ Config })), expected);
+    }
 }
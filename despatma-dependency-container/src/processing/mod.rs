@@ -1,18 +1,22 @@
 use std::{cell::RefCell, rc::Rc};
 
-use crate::input;
+use crate::input::{self, Options};
 use proc_macro2::Span;
+use syn::parse::{Parse, ParseStream};
 use syn::spanned::Spanned;
 use syn::{
-    parse_quote, Attribute, Block, ImplItemFn, Pat, PatType, ReturnType, Signature, Type,
-    Visibility,
+    parse_quote, Attribute, Block, Expr, Ident, ImplItemFn, Pat, PatType, Path, ReturnType,
+    Signature, Token, Type, Visibility,
 };
 
 use self::visitor::{
-    AddWildcardLifetime, ErrorVisitorMut, ExtractAsync, ExtractBoxType, ExtractEmbeddedDependency,
-    ExtractLifetime, ImplTraitButRegisteredConcrete, ImplTraitFields, LinkDependencies,
-    OwningManagedDependency, ReplaceImplGenericsWithConcrete, UnsupportedRegisteredTypes,
-    VisitableMut, WrapBoxType,
+    AddGroupDependencies, AddProfileDependencies, AddWildcardLifetime, CaptiveDependencies,
+    CyclicDependencies, EagerErrorTypes, ErrorVisitorMut, ExtractArguments, ExtractAsync,
+    ExtractBoxType, ExtractCfg, ExtractDispose, ExtractEmbeddedDependency, ExtractErrorType,
+    ExtractGroup, ExtractImports, ExtractLifetime, ExtractQualifier, ExtractResultType,
+    ImplTraitButRegisteredConcrete, ImplTraitFields, LinkDependencies, MismatchedAlternatives,
+    OwningManagedDependency, ReplaceImplGenericsWithConcrete, ReservedNames, UnmanagedDispose,
+    UnsupportedRegisteredTypes, VisitableMut, WrapBoxType,
 };
 
 mod visitor;
@@ -20,32 +24,147 @@ mod visitor;
 #[cfg_attr(test, derive(Eq, PartialEq, Debug))]
 pub struct Container {
     pub(crate) vis: Visibility,
+    pub(crate) options: Options,
     pub(crate) attrs: Vec<Attribute>,
     pub(crate) self_ty: Type,
     pub(crate) dependencies: Vec<Rc<RefCell<Dependency>>>,
+    pub(crate) profiles: Option<Profiles>,
+}
+
+/// The enum of the runtime profiles picking between the providers of a dependency. Ie `dev` in `#[Profile(dev)]`
+#[cfg_attr(test, derive(Eq, PartialEq, Debug))]
+pub struct Profiles {
+    pub(crate) ident: Ident,
+    pub(crate) variants: Vec<Ident>,
 }
 
 #[derive(Clone)]
 #[cfg_attr(test, derive(Eq, PartialEq, Debug))]
 pub struct Dependency {
     pub(crate) attrs: Vec<Attribute>,
+    /// The `#[cfg(...)]` attributes deciding whether the dependency is compiled in
+    pub(crate) cfgs: Vec<Attribute>,
     pub(crate) sig: Signature,
     pub(crate) block: Block,
     pub(crate) is_async: bool,
-    pub(crate) is_boxed: bool,
+    /// The smart pointer the returned type was taken out of
+    pub(crate) pointer: Option<Pointer>,
+    pub(crate) is_fallible: bool,
+    pub(crate) is_eager: bool,
+    /// Whether the transient dependency has state meant for a single use. So it should not be kept by a managed
+    /// dependency
+    pub(crate) is_stateful: bool,
+    /// Whether the managed dependency is handed out as an owned `Arc` handle rather than a reference
+    pub(crate) is_shared: bool,
     pub(crate) lifetime: Lifetime,
+    pub(crate) scope: Option<Ident>,
+    pub(crate) qualifier: Option<Ident>,
     pub(crate) ty: Type,
     pub(crate) field_ty: Type,
+    pub(crate) error_ty: Option<Type>,
+    pub(crate) default: Option<Expr>,
+    /// Whether the dependency is a container to import dependencies from, or one of the dependencies imported from it
+    pub(crate) is_import: bool,
+    pub(crate) dispose: Option<Dispose>,
+    pub(crate) group: Option<Ident>,
+    /// The profile this dependency provides another dependency for
+    pub(crate) profile: Option<Ident>,
+    pub(crate) arguments: Vec<Argument>,
     pub(crate) dependencies: Vec<ChildDependency>,
+    pub(crate) deferred: Vec<DeferredDependency>,
+}
+
+/// The hook to call when the container is shut down. Ie `async close` in `#[Dispose(async close)]`
+#[derive(Clone)]
+#[cfg_attr(test, derive(Eq, PartialEq, Debug))]
+pub struct Dispose {
+    pub(crate) asyncness: Option<Token![async]>,
+    pub(crate) method: Ident,
+}
+
+impl Parse for Dispose {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        Ok(Self {
+            asyncness: input.parse()?,
+            method: input.parse()?,
+        })
+    }
+}
+
+/// An argument the caller passes in to create the dependency. Ie `order_id` in `#[arg] order_id: OrderId`
+#[derive(Clone)]
+#[cfg_attr(test, derive(Eq, PartialEq, Debug))]
+pub struct Argument {
+    pub(crate) ident: Ident,
+    pub(crate) ty: Type,
 }
 
 #[derive(Clone)]
 #[cfg_attr(test, derive(Eq, PartialEq, Debug))]
 pub struct ChildDependency {
     pub(crate) inner: Rc<RefCell<Dependency>>,
+    /// The other `#[cfg]` alternatives of the dependency. Only one of them is compiled in, but it could be any of them
+    pub(crate) alternatives: Vec<Rc<RefCell<Dependency>>>,
+    pub(crate) ident: Ident,
     pub(crate) ty: Type,
 }
 
+impl ChildDependency {
+    /// Every alternative the dependency could be created with, starting with the one it is linked to
+    pub fn candidates(&self) -> impl Iterator<Item = &Rc<RefCell<Dependency>>> {
+        std::iter::once(&self.inner).chain(self.alternatives.iter())
+    }
+}
+
+/// A dependency requested through a `Lazy` or `Provider` handle. It is only created once the handle is used. So it is
+/// not needed to create the dependency requesting it and is left out of the recursive visitors.
+#[derive(Clone)]
+#[cfg_attr(test, derive(Eq, PartialEq, Debug))]
+pub struct DeferredDependency {
+    pub(crate) inner: Rc<RefCell<Dependency>>,
+    pub(crate) ident: Ident,
+    pub(crate) handle: Handle,
+}
+
+#[derive(Clone, Copy)]
+#[cfg_attr(test, derive(Eq, PartialEq, Debug))]
+pub enum Handle {
+    /// Creates the dependency the first time it is used
+    Lazy,
+    /// Creates the dependency every time it is used
+    Provider,
+    /// Creates the dependency from the runtime arguments it is called with
+    Factory,
+}
+
+/// A smart pointer around the type of a dependency. Ie `Arc` in `Arc<dyn Repo>`
+#[derive(Clone)]
+#[cfg_attr(test, derive(Eq, PartialEq, Debug))]
+pub enum Pointer {
+    Box,
+    Arc,
+    Rc,
+    /// A pointer declared with the `pointers(...)` option of the container
+    Custom(Path),
+}
+
+impl Pointer {
+    /// The path to wrap the type in this pointer again
+    pub fn path(&self) -> Path {
+        match self {
+            Pointer::Box => parse_quote!(std::boxed::Box),
+            Pointer::Arc => parse_quote!(std::sync::Arc),
+            Pointer::Rc => parse_quote!(std::rc::Rc),
+            Pointer::Custom(path) => path.clone(),
+        }
+    }
+
+    /// Whether cloning the pointer shares what it points to. So that it can be handed out from a managed dependency
+    pub fn is_shared(&self) -> bool {
+        !matches!(self, Pointer::Box)
+    }
+}
+
 #[derive(Clone)]
 #[cfg_attr(test, derive(Debug))]
 pub enum Lifetime {
@@ -80,12 +199,23 @@ impl Lifetime {
     pub fn is_embedded(&self) -> bool {
         matches!(self, Lifetime::Embedded(_))
     }
+
+    /// The name of the lifetime as it is written in the attribute
+    pub fn name(&self) -> &'static str {
+        match self {
+            Lifetime::Transient(_) => "Transient",
+            Lifetime::Scoped(_) => "Scoped",
+            Lifetime::Singleton(_) => "Singleton",
+            Lifetime::Embedded(_) => "Embedded",
+        }
+    }
 }
 
 impl From<input::Container> for Container {
     fn from(input: input::Container) -> Self {
         let input::Container {
             vis,
+            options,
             attrs,
             self_ty,
             dependencies,
@@ -100,9 +230,11 @@ impl From<input::Container> for Container {
 
         Self {
             vis,
+            options,
             attrs,
             self_ty,
             dependencies,
+            profiles: None,
         }
     }
 }
@@ -124,14 +256,29 @@ impl From<ImplItemFn> for Dependency {
 
         Self {
             attrs,
+            cfgs: vec![],
             sig,
             block,
             is_async: false,
-            is_boxed: false,
+            pointer: None,
+            is_fallible: false,
+            is_eager: false,
+            is_stateful: false,
+            is_shared: false,
             lifetime: Lifetime::Transient(None),
+            scope: None,
+            qualifier: None,
             field_ty: ty.clone(),
             ty,
+            error_ty: None,
+            default: None,
+            is_import: false,
+            dispose: None,
+            group: None,
+            profile: None,
+            arguments: vec![],
             dependencies: vec![],
+            deferred: vec![],
         }
     }
 }
@@ -146,24 +293,72 @@ impl From<&PatType> for Dependency {
 
         Self {
             attrs: vec![],
+            cfgs: vec![],
             sig: parse_quote! { fn #ident(&self) -> #ty },
             block: parse_quote!({}),
             is_async: false,
-            is_boxed: false,
+            pointer: None,
+            is_fallible: false,
+            is_eager: false,
+            is_stateful: false,
+            is_shared: false,
             lifetime: Lifetime::Embedded(ty.span()),
+            scope: None,
+            qualifier: None,
             field_ty: ty.clone(),
             ty: ty.clone(),
+            error_ty: None,
+            default: None,
+            is_import: false,
+            dispose: None,
+            group: None,
+            profile: None,
+            arguments: vec![],
             dependencies: vec![],
+            deferred: vec![],
         }
     }
 }
 
 impl Container {
     pub fn process(&mut self) {
+        // Needs to happen before result types are extracted
+        self.process_visitor::<ExtractImports>();
+
+        // Needs to happen before any other attributes are extracted, since the providers hand theirs over
+        self.process_visitor::<AddProfileDependencies>();
+
+        // Has to happen before dependencies are linked, so that alternatives are known
+        self.process_visitor::<ExtractCfg>();
+
+        // Needs to happen before any type hints (lifetimes) are extracted
+        self.process_visitor::<ExtractQualifier>();
+        self.process_visitor::<ExtractDispose>();
+        self.process_visitor::<ExtractGroup>();
+        self.process_visitor::<ExtractResultType>();
         self.process_visitor::<ExtractLifetime>();
         self.process_visitor::<ExtractEmbeddedDependency>();
+
+        // Needs dispose hooks, lifetimes and constructor arguments to be extracted first
+        self.process_visitor::<ReservedNames>();
+
+        // Needs lifetimes to be extracted first
+        self.process_visitor::<UnmanagedDispose>();
+        self.process_visitor::<ExtractArguments>();
+
+        // Needs groups and lifetimes to be extracted first
+        // And has to happen before dependencies are linked
+        self.process_visitor::<AddGroupDependencies>();
+
         self.process_visitor::<LinkDependencies>();
 
+        // Needs dependencies to be linked first
+        // And has to happen before any of the recursive visitors below
+        self.process_visitor::<CyclicDependencies>();
+
+        // Needs lifetimes to be extracted, dependencies to be linked and cycles to be removed
+        self.process_visitor::<CaptiveDependencies>();
+
         // Needs field types (lifetimes) to be extracted and dependencies to be linked first
         self.process_visitor::<ReplaceImplGenericsWithConcrete>();
 
@@ -179,6 +374,11 @@ impl Container {
 
         // Needs dependencies to be linked first
         self.process_visitor::<ExtractAsync>();
+        self.process_visitor::<ExtractErrorType>();
+
+        // Needs error types to be extracted first
+        self.process_visitor::<EagerErrorTypes>();
+        self.process_visitor::<MismatchedAlternatives>();
 
         self.process_visitor::<ExtractBoxType>();
         self.process_visitor::<UnsupportedRegisteredTypes>();
@@ -199,3 +399,20 @@ impl Container {
         visitor.emit_errors();
     }
 }
+
+/// Turn a `snake_case` name into `PascalCase`. Ie `dev_local` into `DevLocal`
+pub(crate) fn to_pascal_case(ident: &Ident) -> String {
+    ident
+        .to_string()
+        .split('_')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,399 @@
+use std::{cell::RefCell, rc::Rc};
+
+use proc_macro_error2::emit_error;
+use syn::{parse_quote, GenericArgument, Ident, ImplItemFn, PathArguments, Type};
+
+use crate::processing::{Container, Dependency};
+
+use super::{ErrorVisitorMut, VisitorMut};
+
+/// Adds a dependency for every group which collects all the dependencies in the group in the order they are declared.
+/// So `handlers` will be a `Vec<Box<dyn Handler>>` for transient dependencies in the `handlers` group. Or a
+/// `Vec<&dyn Handler>` when the dependencies in the group are managed by the container.
+///
+/// Needs groups and lifetimes to be extracted first. But has to happen before dependencies are linked.
+pub struct AddGroupDependencies {
+    errors: Vec<Error>,
+}
+
+#[cfg_attr(test, derive(Eq, PartialEq, Debug))]
+enum Error {
+    /// The dependency does not return a boxed trait object
+    NotBoxed { dependency: Ident, ty: Type },
+
+    /// The dependency returns another type than the first dependency in the group
+    MismatchedType {
+        group: Ident,
+        ty: Type,
+        first: Ident,
+    },
+
+    /// Some dependencies in the group are transient while others are managed
+    MixedLifetimes {
+        group: Ident,
+        transient: Ident,
+        managed: Ident,
+    },
+
+    /// The name of the group is already the name of another dependency
+    Duplicate { group: Ident, existing: Ident },
+}
+
+/// The dependencies in a group in the order they are declared
+struct Group {
+    ident: Ident,
+    members: Vec<Rc<RefCell<Dependency>>>,
+}
+
+impl VisitorMut for AddGroupDependencies {
+    fn visit_container_mut(&mut self, container: &mut Container) {
+        let mut groups: Vec<Group> = Vec::new();
+
+        for dependency in container.dependencies.iter() {
+            let Some(ident) = dependency.borrow().group.clone() else {
+                continue;
+            };
+
+            match groups.iter_mut().find(|group| group.ident == ident) {
+                Some(group) => group.members.push(dependency.clone()),
+                None => groups.push(Group {
+                    ident,
+                    members: vec![dependency.clone()],
+                }),
+            }
+        }
+
+        for group in groups {
+            if let Some(dependency) = self.create_dependency(&group, &container.dependencies) {
+                container
+                    .dependencies
+                    .push(Rc::new(RefCell::new(dependency)));
+            }
+        }
+    }
+}
+
+impl AddGroupDependencies {
+    /// Create the dependency collecting all the members of the group. Or record why it cannot be created.
+    fn create_dependency(
+        &mut self,
+        group: &Group,
+        dependencies: &[Rc<RefCell<Dependency>>],
+    ) -> Option<Dependency> {
+        let Group { ident, members } = group;
+        let errors = self.errors.len();
+
+        if let Some(existing) = dependencies
+            .iter()
+            .find(|dependency| &dependency.borrow().sig.ident == ident)
+        {
+            self.errors.push(Error::Duplicate {
+                group: ident.clone(),
+                existing: existing.borrow().sig.ident.clone(),
+            });
+        }
+
+        let first = members[0].borrow();
+
+        for member in members.iter() {
+            let member = member.borrow();
+
+            if get_trait_object(&member.ty).is_none() {
+                self.errors.push(Error::NotBoxed {
+                    dependency: member.sig.ident.clone(),
+                    ty: member.ty.clone(),
+                });
+            } else if get_trait_object(&first.ty).is_some() && member.ty != first.ty {
+                self.errors.push(Error::MismatchedType {
+                    group: ident.clone(),
+                    ty: member.ty.clone(),
+                    first: first.sig.ident.clone(),
+                });
+            }
+        }
+
+        let (transient, managed): (Vec<_>, Vec<_>) = members
+            .iter()
+            .partition(|member| !member.borrow().lifetime.is_managed());
+
+        if let (Some(transient), Some(managed)) = (transient.first(), managed.first()) {
+            self.errors.push(Error::MixedLifetimes {
+                group: ident.clone(),
+                transient: transient.borrow().sig.ident.clone(),
+                managed: managed.borrow().sig.ident.clone(),
+            });
+        }
+
+        if self.errors.len() > errors {
+            return None;
+        }
+
+        let box_ty = &first.ty;
+        let trait_object: Type = match get_trait_object(box_ty)? {
+            // `&dyn Route + Send` is ambiguous, so it has to be `&(dyn Route + Send)`
+            Type::TraitObject(trait_object) if trait_object.bounds.len() > 1 => {
+                parse_quote!((#trait_object))
+            }
+            trait_object => trait_object.clone(),
+        };
+        let arguments: Vec<_> = members
+            .iter()
+            .map(|member| member.borrow().sig.ident.clone())
+            .collect();
+        let doc = format!(" All the dependencies in the `{ident}` group");
+
+        let impl_item_fn: ImplItemFn = if managed.is_empty() {
+            parse_quote! {
+                #[doc = #doc]
+                fn #ident(&self, #(#arguments: #box_ty),*) -> Vec<#box_ty> {
+                    vec![#(#arguments),*]
+                }
+            }
+        } else {
+            parse_quote! {
+                #[doc = #doc]
+                fn #ident(&self, #(#arguments: &#box_ty),*) -> Vec<&#trait_object> {
+                    vec![#(&**#arguments),*]
+                }
+            }
+        };
+
+        Some(Dependency::from(impl_item_fn))
+    }
+}
+
+/// Get the `dyn Trait` inside a `Box<dyn Trait>`
+fn get_trait_object(ty: &Type) -> Option<&Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+
+    let last_segment = path.path.segments.last()?;
+
+    if last_segment.ident != "Box" {
+        return None;
+    }
+
+    let PathArguments::AngleBracketed(generics) = &last_segment.arguments else {
+        return None;
+    };
+
+    match generics.args.first() {
+        Some(GenericArgument::Type(ty @ Type::TraitObject(_))) if generics.args.len() == 1 => {
+            Some(ty)
+        }
+        _ => None,
+    }
+}
+
+impl ErrorVisitorMut for AddGroupDependencies {
+    fn new() -> Self {
+        Self {
+            errors: Default::default(),
+        }
+    }
+
+    fn emit_errors(self) {
+        for error in self.errors {
+            match error {
+                Error::NotBoxed { dependency, ty } => emit_error!(
+                    ty,
+                    "The '{}' dependency has to return a `Box<dyn Trait>` to be part of a group",
+                    dependency;
+                    hint = "Return the dependency as a `Box<dyn Trait>` of the trait shared by the group"
+                ),
+                Error::MismatchedType { group, ty, first } => emit_error!(
+                    ty,
+                    "Every dependency in the '{}' group has to return the same type",
+                    group;
+                    hint = first.span() => format!("`{first}` is the first dependency in the group")
+                ),
+                Error::MixedLifetimes {
+                    group,
+                    transient,
+                    managed,
+                } => emit_error!(
+                    transient,
+                    "The '{}' group has both transient and managed dependencies",
+                    group;
+                    hint = managed.span() => format!("`{managed}` is managed by the container");
+                    help = "Make every dependency in the group transient, or make every dependency singleton or scoped"
+                ),
+                Error::Duplicate { group, existing } => emit_error!(
+                    group,
+                    "The '{}' group is already the name of another dependency",
+                    group;
+                    hint = existing.span() => format!("`{existing}` is registered here")
+                ),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use syn::parse_quote;
+
+    use crate::{
+        input,
+        processing::{
+            self,
+            visitor::{ExtractGroup, ExtractLifetime, VisitableMut},
+        },
+    };
+
+    use super::*;
+
+    fn add_group_dependencies(container: &mut processing::Container) -> AddGroupDependencies {
+        // Test needs groups and lifetimes to be extracted
+        container.apply_mut(&mut ExtractGroup::new());
+        container.apply_mut(&mut ExtractLifetime::new());
+
+        let mut visitor = AddGroupDependencies::new();
+
+        container.apply_mut(&mut visitor);
+
+        visitor
+    }
+
+    #[test]
+    fn add_group_dependencies_transient() {
+        let mut container: processing::Container = input::Container::from_item_impl(parse_quote!(
+            impl Container {
+                #[Group(handlers)]
+                fn ping(&self) -> Box<dyn Handler> {
+                    Box::new(Ping)
+                }
+
+                fn config(&self) -> Config {
+                    Config
+                }
+
+                #[Group(handlers)]
+                fn echo(&self, config: Config) -> Box<dyn Handler> {
+                    Box::new(Echo::new(config))
+                }
+            }
+        ))
+        .into();
+
+        let visitor = add_group_dependencies(&mut container);
+
+        let expected: ImplItemFn = parse_quote! {
+            #[doc = " All the dependencies in the `handlers` group"]
+            fn handlers(&self, ping: Box<dyn Handler>, echo: Box<dyn Handler>) -> Vec<Box<dyn Handler>> {
+                vec![ping, echo]
+            }
+        };
+
+        assert_eq!(visitor.errors, vec![]);
+        assert_eq!(container.dependencies.len(), 4);
+        assert_eq!(
+            *container.dependencies[3].borrow(),
+            Dependency::from(expected)
+        );
+    }
+
+    #[test]
+    fn add_group_dependencies_managed() {
+        let mut container: processing::Container = input::Container::from_item_impl(parse_quote!(
+            impl Container {
+                #[Singleton]
+                #[Group(routes)]
+                fn index(&self) -> Box<dyn Route + Send> {
+                    Box::new(Index)
+                }
+
+                #[Scoped]
+                #[Group(routes)]
+                fn login(&self) -> Box<dyn Route + Send> {
+                    Box::new(Login)
+                }
+            }
+        ))
+        .into();
+
+        let visitor = add_group_dependencies(&mut container);
+
+        let expected: ImplItemFn = parse_quote! {
+            #[doc = " All the dependencies in the `routes` group"]
+            fn routes(&self, index: &Box<dyn Route + Send>, login: &Box<dyn Route + Send>) -> Vec<&(dyn Route + Send)> {
+                vec![&**index, &**login]
+            }
+        };
+
+        assert_eq!(visitor.errors, vec![]);
+        assert_eq!(container.dependencies.len(), 3);
+        assert_eq!(
+            *container.dependencies[2].borrow(),
+            Dependency::from(expected)
+        );
+    }
+
+    #[test]
+    fn add_group_dependencies_errors() {
+        let mut container: processing::Container = input::Container::from_item_impl(parse_quote!(
+            impl Container {
+                #[Group(handlers)]
+                fn ping(&self) -> Box<dyn Handler> {
+                    Box::new(Ping)
+                }
+
+                #[Group(handlers)]
+                fn echo(&self) -> Echo {
+                    Echo
+                }
+
+                #[Group(handlers)]
+                fn other(&self) -> Box<dyn Other> {
+                    Box::new(Other)
+                }
+
+                #[Singleton]
+                #[Group(handlers)]
+                fn cached(&self) -> Box<dyn Handler> {
+                    Box::new(Cached)
+                }
+
+                #[Group(config)]
+                fn local(&self) -> Box<dyn Source> {
+                    Box::new(Local)
+                }
+
+                fn config(&self) -> Config {
+                    Config
+                }
+            }
+        ))
+        .into();
+
+        let visitor = add_group_dependencies(&mut container);
+
+        // No groups should be added when they have errors
+        assert_eq!(container.dependencies.len(), 6);
+        assert_eq!(
+            visitor.errors,
+            vec![
+                Error::NotBoxed {
+                    dependency: parse_quote!(echo),
+                    ty: parse_quote!(Echo),
+                },
+                Error::MismatchedType {
+                    group: parse_quote!(handlers),
+                    ty: parse_quote!(Box<dyn Other>),
+                    first: parse_quote!(ping),
+                },
+                Error::MixedLifetimes {
+                    group: parse_quote!(handlers),
+                    transient: parse_quote!(ping),
+                    managed: parse_quote!(cached),
+                },
+                Error::Duplicate {
+                    group: parse_quote!(config),
+                    existing: parse_quote!(config),
+                },
+            ]
+        );
+    }
+}
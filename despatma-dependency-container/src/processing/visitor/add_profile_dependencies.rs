@@ -0,0 +1,576 @@
+use std::{cell::RefCell, rc::Rc};
+
+use proc_macro_error2::emit_error;
+use quote::{format_ident, quote};
+use syn::{ext::IdentExt, parse_quote, Attribute, FnArg, Ident, ImplItemFn, Pat, ReturnType, Type};
+
+use crate::processing::{to_pascal_case, Container, Dependency, Profiles};
+
+use super::{ErrorVisitorMut, VisitorMut};
+
+/// Adds a dependency for every dependency with providers for runtime profiles, like `#[Profile(dev)]` and
+/// `#[Profile(prod)]`. The providers are renamed to `repository_dev` and `repository_prod`, and the added `repository`
+/// dependency asks the `profile` dependency which of them to create. So only the provider of the active profile, and
+/// the dependencies it needs, are ever created.
+///
+/// The lifetime and other attributes of the providers move to the added dependency. So a singleton only creates the
+/// provider of the active profile once.
+///
+/// The added dependencies request the providers through `Provider` handles, which are types of `despatma`. So profiles
+/// are reported when the macro is not called through it.
+///
+/// Needs to happen before any other attributes are extracted.
+pub struct AddProfileDependencies {
+    errors: Vec<Error>,
+}
+
+#[cfg_attr(test, derive(Eq, PartialEq, Debug))]
+enum Error {
+    /// The profile attribute does not name a profile
+    InvalidAttribute(Attribute),
+
+    /// More than one provider of the dependency is for the same profile
+    Duplicate { dependency: Ident, profile: Ident },
+
+    /// The dependency has no provider for a profile used elsewhere in the container
+    Missing { dependency: Ident, profile: Ident },
+
+    /// The providers of the dependency differ in more than how they create it
+    Mismatched {
+        dependency: Ident,
+        first: Ident,
+        difference: &'static str,
+    },
+
+    /// Nothing tells which profile is active
+    NoProfileDependency { profile: Ident, ty: Ident },
+
+    /// The macro is not called through `despatma`, which has the handles to pick a provider with
+    Standalone(Ident),
+}
+
+/// The provider of a dependency for one profile
+struct Provider {
+    profile: Ident,
+    dependency: Rc<RefCell<Dependency>>,
+}
+
+impl VisitorMut for AddProfileDependencies {
+    fn visit_container_mut(&mut self, container: &mut Container) {
+        let mut dependencies: Vec<(Ident, Vec<Provider>)> = Vec::new();
+
+        for dependency in container.dependencies.iter() {
+            let Some(profile) = self.extract_profile(&mut dependency.borrow_mut()) else {
+                continue;
+            };
+            let ident = dependency.borrow().sig.ident.clone();
+            let provider = Provider {
+                profile,
+                dependency: dependency.clone(),
+            };
+
+            match dependencies.iter_mut().find(|(i, _)| *i == ident) {
+                Some((_, providers)) => providers.push(provider),
+                None => dependencies.push((ident, vec![provider])),
+            }
+        }
+
+        let mut profiles: Vec<Ident> = Vec::new();
+
+        for (_, providers) in dependencies.iter() {
+            for Provider { profile, .. } in providers {
+                if !profiles.contains(profile) {
+                    profiles.push(profile.clone());
+                }
+            }
+        }
+
+        let Some(first_profile) = profiles.first() else {
+            return;
+        };
+
+        if !container.options.despatma {
+            self.errors.push(Error::Standalone(first_profile.clone()));
+
+            // Keep only the first provider of every dependency so that nothing else is reported for the others
+            for (_, providers) in dependencies {
+                for provider in providers.iter().skip(1) {
+                    container
+                        .dependencies
+                        .retain(|d| !Rc::ptr_eq(d, &provider.dependency));
+                }
+
+                providers[0].dependency.borrow_mut().profile = None;
+            }
+
+            return;
+        }
+
+        let Type::Path(path) = &container.self_ty else {
+            return;
+        };
+
+        let Some(last_segment) = path.path.segments.last() else {
+            return;
+        };
+
+        let profile_ty = format_ident!("{}Profile", last_segment.ident);
+        let profile_param = match get_profile_lifetime(&container.dependencies) {
+            Some(true) => Some(quote! { profile: &#profile_ty, }),
+            Some(false) => Some(quote! { profile: #profile_ty, }),
+            None => {
+                self.errors.push(Error::NoProfileDependency {
+                    profile: first_profile.clone(),
+                    ty: profile_ty.clone(),
+                });
+                None
+            }
+        };
+
+        for (ident, providers) in dependencies {
+            if let Some(dependency) =
+                self.create_dependency(&ident, &providers, &profiles, &profile_ty, &profile_param)
+            {
+                container
+                    .dependencies
+                    .push(Rc::new(RefCell::new(dependency)));
+            }
+        }
+
+        container.profiles = Some(Profiles {
+            ident: profile_ty,
+            variants: profiles
+                .iter()
+                .map(|profile| Ident::new(&to_pascal_case(profile), profile.span()))
+                .collect(),
+        });
+    }
+}
+
+impl AddProfileDependencies {
+    /// Move the profile named by the `#[Profile(dev)]` attribute of the dependency to its `profile`
+    fn extract_profile(&mut self, dependency: &mut Dependency) -> Option<Ident> {
+        let mut profile = None;
+
+        dependency.attrs.retain(|attr| {
+            if !attr.path().is_ident("Profile") {
+                return true;
+            }
+
+            match attr.parse_args::<Ident>() {
+                Ok(ident) => profile = Some(ident),
+                Err(_) => self.errors.push(Error::InvalidAttribute(attr.clone())),
+            }
+
+            false
+        });
+
+        dependency.profile.clone_from(&profile);
+
+        profile
+    }
+
+    /// Create the dependency picking the provider for the active profile. Or record why it cannot be created.
+    fn create_dependency(
+        &mut self,
+        ident: &Ident,
+        providers: &[Provider],
+        profiles: &[Ident],
+        profile_ty: &Ident,
+        profile_param: &Option<proc_macro2::TokenStream>,
+    ) -> Option<Dependency> {
+        let errors = self.errors.len();
+        let first = providers[0].dependency.borrow();
+        let first_attrs = get_shared_attrs(&first.attrs);
+
+        for (
+            index,
+            Provider {
+                profile,
+                dependency,
+            },
+        ) in providers.iter().enumerate()
+        {
+            if providers[..index].iter().any(|p| p.profile == *profile) {
+                self.errors.push(Error::Duplicate {
+                    dependency: dependency.borrow().sig.ident.clone(),
+                    profile: profile.clone(),
+                });
+            }
+
+            let dependency = dependency.borrow();
+            let difference = if dependency.sig.output != first.sig.output {
+                Some("return type")
+            } else if dependency.sig.asyncness.is_some() != first.sig.asyncness.is_some() {
+                Some("asyncness")
+            } else if get_shared_attrs(&dependency.attrs) != first_attrs {
+                Some("attributes")
+            } else {
+                None
+            };
+
+            if let Some(difference) = difference {
+                self.errors.push(Error::Mismatched {
+                    dependency: dependency.sig.ident.clone(),
+                    first: first.sig.ident.clone(),
+                    difference,
+                });
+            }
+        }
+
+        for profile in profiles {
+            if !providers.iter().any(|p| p.profile == *profile) {
+                self.errors.push(Error::Missing {
+                    dependency: first.sig.ident.clone(),
+                    profile: profile.clone(),
+                });
+            }
+        }
+
+        if self.errors.len() > errors {
+            return None;
+        }
+
+        let asyncness = first.sig.asyncness;
+        let await_token = asyncness.map(|_| quote! { .await });
+        let ty = match &first.sig.output {
+            ReturnType::Type(_, ty) => ty.as_ref().clone(),
+            ReturnType::Default => parse_quote! { () },
+        };
+        drop(first);
+
+        let mut arguments = Vec::new();
+        let mut arms = Vec::new();
+
+        for profile in profiles {
+            let Some(Provider { dependency, .. }) =
+                providers.iter().find(|p| p.profile == *profile)
+            else {
+                continue;
+            };
+            let mut dependency = dependency.borrow_mut();
+            let argument = format_ident!(
+                "{}_{}",
+                ident.unraw(),
+                profile,
+                span = dependency.sig.ident.span()
+            );
+            let variant = Ident::new(&to_pascal_case(profile), profile.span());
+
+            // The provider is created by the added dependency, which takes over its lifetime and other attributes
+            dependency.sig.ident = argument.clone();
+            dependency
+                .attrs
+                .retain(|attr| attr.path().is_ident("doc") || attr.path().is_ident("cfg"));
+
+            arms.push(quote! { #profile_ty::#variant => #argument.get()#await_token });
+            arguments.push(argument);
+        }
+
+        let doc =
+            format!(" Creates the `{ident}` dependency with the provider of the active profile");
+
+        let impl_item_fn: ImplItemFn = parse_quote! {
+            #(#first_attrs)*
+            #[doc = #doc]
+            #asyncness fn #ident(&self, #profile_param #(#arguments: Provider<#ty>),*) -> #ty {
+                match profile {
+                    #(#arms,)*
+                }
+            }
+        };
+
+        Some(Dependency::from(impl_item_fn))
+    }
+}
+
+/// The attributes, other than docs and `#[cfg]`, which every provider of a dependency needs to agree on
+fn get_shared_attrs(attrs: &[Attribute]) -> Vec<Attribute> {
+    attrs
+        .iter()
+        .filter(|attr| !attr.path().is_ident("doc") && !attr.path().is_ident("cfg"))
+        .cloned()
+        .collect()
+}
+
+/// Whether the `profile` dependency is managed by the container, if there is one. It is managed when it is a
+/// constructor argument or a singleton or scoped dependency.
+fn get_profile_lifetime(dependencies: &[Rc<RefCell<Dependency>>]) -> Option<bool> {
+    for dependency in dependencies {
+        let dependency = dependency.borrow();
+
+        if dependency.sig.ident == "new" {
+            let is_argument = dependency.sig.inputs.iter().any(|fn_arg| match fn_arg {
+                FnArg::Typed(pat_type) => {
+                    matches!(pat_type.pat.as_ref(), Pat::Ident(pat) if pat.ident == "profile")
+                }
+                FnArg::Receiver(_) => false,
+            });
+
+            if is_argument {
+                return Some(true);
+            }
+        } else if dependency.sig.ident == "profile" {
+            return Some(
+                dependency.attrs.iter().any(|attr| {
+                    attr.path().is_ident("Singleton") || attr.path().is_ident("Scoped")
+                }),
+            );
+        }
+    }
+
+    None
+}
+
+impl ErrorVisitorMut for AddProfileDependencies {
+    fn new() -> Self {
+        Self {
+            errors: Default::default(),
+        }
+    }
+
+    fn emit_errors(self) {
+        for error in self.errors {
+            match error {
+                Error::InvalidAttribute(attr) => emit_error!(
+                    attr, "Expected the name of the profile this provides the dependency for";
+                    hint = "Try `#[Profile(dev)]`"
+                ),
+                Error::Duplicate {
+                    dependency,
+                    profile,
+                } => emit_error!(
+                    profile,
+                    "The '{}' dependency already has a provider for the '{}' profile",
+                    dependency,
+                    profile
+                ),
+                Error::Missing {
+                    dependency,
+                    profile,
+                } => emit_error!(
+                    dependency,
+                    "The '{}' dependency has no provider for the '{}' profile",
+                    dependency,
+                    profile;
+                    hint = "Add a provider with `#[Profile({})]`", profile
+                ),
+                Error::Mismatched {
+                    dependency,
+                    first,
+                    difference,
+                } => emit_error!(
+                    dependency,
+                    "Every provider of the '{}' dependency needs the same {}",
+                    dependency,
+                    difference;
+                    hint = first.span() => format!("`{first}` is first provided here")
+                ),
+                Error::NoProfileDependency { profile, ty } => emit_error!(
+                    profile,
+                    "The container needs a `profile` dependency to pick the active profile";
+                    hint = "Add a `profile: {}` argument to `new`, or a `profile` dependency returning it", ty
+                ),
+                Error::Standalone(profile) => emit_error!(
+                    profile,
+                    "Runtime profiles pick a provider with the `Provider` handle of the `despatma` crate";
+                    hint = "Generate the container with `#[despatma::dependency_container]` to use them"
+                ),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use syn::parse_quote;
+
+    use crate::{
+        input,
+        processing::{self, visitor::VisitableMut},
+    };
+
+    use super::*;
+
+    #[test]
+    fn add_profile_dependencies() {
+        let mut container: processing::Container = input::Container::from_item_impl(parse_quote!(
+            impl DependencyContainer {
+                fn new(profile: DependencyContainerProfile) {}
+
+                /// Keeps everything in memory
+                #[Profile(dev)]
+                #[Singleton]
+                fn repository(&self) -> Box<dyn Repository> {
+                    Box::new(InMemory)
+                }
+
+                #[Profile(prod)]
+                #[Singleton]
+                fn repository(&self, pool: &Pool) -> Box<dyn Repository> {
+                    Box::new(Postgres::new(pool))
+                }
+
+                fn service(&self, repository: &Box<dyn Repository>) -> Service {
+                    Service::new(repository)
+                }
+            }
+        ))
+        .into();
+
+        container.options.despatma = true;
+
+        let mut visitor = AddProfileDependencies::new();
+
+        container.apply_mut(&mut visitor);
+
+        let dev: ImplItemFn = parse_quote! {
+            /// Keeps everything in memory
+            fn repository_dev(&self) -> Box<dyn Repository> {
+                Box::new(InMemory)
+            }
+        };
+        let prod: ImplItemFn = parse_quote! {
+            fn repository_prod(&self, pool: &Pool) -> Box<dyn Repository> {
+                Box::new(Postgres::new(pool))
+            }
+        };
+        let expected: ImplItemFn = parse_quote! {
+            #[Singleton]
+            #[doc = " Creates the `repository` dependency with the provider of the active profile"]
+            fn repository(
+                &self,
+                profile: &DependencyContainerProfile,
+                repository_dev: Provider<Box<dyn Repository>>,
+                repository_prod: Provider<Box<dyn Repository>>
+            ) -> Box<dyn Repository> {
+                match profile {
+                    DependencyContainerProfile::Dev => repository_dev.get(),
+                    DependencyContainerProfile::Prod => repository_prod.get(),
+                }
+            }
+        };
+
+        assert_eq!(visitor.errors, vec![]);
+        assert_eq!(container.dependencies.len(), 5);
+        assert_eq!(
+            *container.dependencies[1].borrow(),
+            Dependency {
+                profile: Some(parse_quote!(dev)),
+                ..Dependency::from(dev)
+            }
+        );
+        assert_eq!(
+            *container.dependencies[2].borrow(),
+            Dependency {
+                profile: Some(parse_quote!(prod)),
+                ..Dependency::from(prod)
+            }
+        );
+        assert_eq!(
+            *container.dependencies[4].borrow(),
+            Dependency::from(expected)
+        );
+        assert_eq!(
+            container.profiles,
+            Some(Profiles {
+                ident: parse_quote!(DependencyContainerProfile),
+                variants: vec![parse_quote!(Dev), parse_quote!(Prod)],
+            })
+        );
+    }
+
+    #[test]
+    fn add_profile_dependencies_errors() {
+        let mut container: processing::Container = input::Container::from_item_impl(parse_quote!(
+            impl DependencyContainer {
+                #[Profile(dev)]
+                fn repository(&self) -> Box<dyn Repository> {
+                    Box::new(InMemory)
+                }
+
+                #[Profile(dev)]
+                fn repository(&self) -> Box<dyn Repository> {
+                    Box::new(Other)
+                }
+
+                #[Profile(prod)]
+                fn repository(&self) -> Repository {
+                    Postgres
+                }
+
+                #[Profile(dev)]
+                fn mailer(&self) -> Box<dyn Mailer> {
+                    Box::new(Fake)
+                }
+
+                #[Profile("prod")]
+                fn mailer(&self) -> Box<dyn Mailer> {
+                    Box::new(Smtp)
+                }
+            }
+        ))
+        .into();
+
+        container.options.despatma = true;
+
+        let mut visitor = AddProfileDependencies::new();
+
+        container.apply_mut(&mut visitor);
+
+        // No dependencies should be added when they have errors
+        assert_eq!(container.dependencies.len(), 5);
+        assert_eq!(
+            visitor.errors,
+            vec![
+                Error::InvalidAttribute(parse_quote!(#[Profile("prod")])),
+                Error::NoProfileDependency {
+                    profile: parse_quote!(dev),
+                    ty: parse_quote!(DependencyContainerProfile),
+                },
+                Error::Duplicate {
+                    dependency: parse_quote!(repository),
+                    profile: parse_quote!(dev),
+                },
+                Error::Mismatched {
+                    dependency: parse_quote!(repository),
+                    first: parse_quote!(repository),
+                    difference: "return type",
+                },
+                Error::Missing {
+                    dependency: parse_quote!(mailer),
+                    profile: parse_quote!(prod),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn add_profile_dependencies_standalone() {
+        let mut container: processing::Container = input::Container::from_item_impl(parse_quote!(
+            impl DependencyContainer {
+                #[Profile(dev)]
+                fn repository(&self) -> Box<dyn Repository> {
+                    Box::new(InMemory)
+                }
+
+                #[Profile(prod)]
+                fn repository(&self) -> Box<dyn Repository> {
+                    Box::new(Postgres)
+                }
+            }
+        ))
+        .into();
+
+        let mut visitor = AddProfileDependencies::new();
+
+        container.apply_mut(&mut visitor);
+
+        // Only the first provider is kept as a plain dependency
+        assert_eq!(container.dependencies.len(), 1);
+        assert_eq!(container.dependencies[0].borrow().profile, None);
+        assert_eq!(container.profiles, None);
+        assert_eq!(visitor.errors, vec![Error::Standalone(parse_quote!(dev))]);
+    }
+}
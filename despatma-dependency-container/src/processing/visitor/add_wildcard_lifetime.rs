@@ -13,6 +13,7 @@ use super::{ErrorVisitorMut, VisitorMut};
 
 /// Add the wildcard lifetime to any return types that might need it.
 /// This is for dependencies which requests and returns an impl Trait dependency which has a managed lifetime.
+/// Shared dependencies are handed out as owned `Arc` handles. So they do not borrow from the container.
 ///
 /// Needs to be called after lifetimes are extracted.
 /// And after dependencies are linked.
@@ -23,12 +24,12 @@ pub struct AddWildcardLifetime {
 
 impl VisitorMut for AddWildcardLifetime {
     fn visit_dependency_mut(&mut self, dependency: &mut Dependency) {
-        if dependency.lifetime.is_managed() {
+        if dependency.lifetime.is_managed() && !dependency.is_shared {
             if let Type::ImplTrait(type_impl_trait) = &dependency.ty {
                 self.adder.to_add.insert(type_impl_trait.bounds.clone());
                 return;
             }
-            // Also fix up `dyn Trait` that was exctracted from boxes
+            // Also fix up `dyn Trait` that was extracted from smart pointers
             if let Type::TraitObject(type_trait_object) = &dependency.ty {
                 self.adder.to_add.insert(type_trait_object.bounds.clone());
                 return;
@@ -115,8 +116,8 @@ mod tests {
         ))
         .into();
 
-        container.apply_mut(&mut ExtractBoxType);
-        container.apply_mut(&mut ExtractLifetime);
+        container.apply_mut(&mut ExtractBoxType::new());
+        container.apply_mut(&mut ExtractLifetime::new());
         container.apply_mut(&mut LinkDependencies::new());
 
         assert_eq!(container.dependencies[0].borrow().ty, parse_quote!(dyn DAL));
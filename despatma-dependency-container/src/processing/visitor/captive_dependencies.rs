@@ -0,0 +1,522 @@
+use std::{cell::RefCell, rc::Rc};
+
+use proc_macro_error2::{Diagnostic, Level};
+use syn::{FnArg, Ident, Pat};
+
+use crate::processing::{Container, Dependency, Lifetime};
+
+use super::{visit_container_mut, ErrorVisitorMut, VisitorMut};
+
+/// Reports on any singleton which depends on a scoped dependency. The singleton is shared with every scope, so it
+/// would hold on to the scoped dependency of the first scope it was created in forever. This includes scoped
+/// dependencies reached through transient dependencies, since those are created again for the singleton.
+///
+/// The same goes for a dependency of an outer named scope which depends on a dependency of an inner scope. Scoped
+/// dependencies without a named scope are created again by every scope, just like those of the innermost scope.
+///
+/// A transient dependency marked with `#[Transient(stateful)]` has state meant for a single use, like a transaction. So
+/// any singleton or scoped dependency keeping it, directly or through other transient dependencies, is reported too.
+/// Other transients are taken to be stateless helpers which are fine to keep.
+///
+/// Every `#[cfg]` alternative of a dependency is checked, since any of them could be the one compiled in.
+///
+/// An argument marked with `#[captive]` is skipped for the cases where capturing the dependency is deliberate.
+///
+/// Needs lifetimes to be extracted and dependencies to be linked first. And cycles should already be removed.
+pub struct CaptiveDependencies {
+    scopes: Vec<Ident>,
+    captives: Vec<Captive>,
+}
+
+#[cfg_attr(test, derive(Eq, PartialEq, Debug))]
+struct Captive {
+    dependency: Ident,
+    /// The named scope of the dependency, or `None` when it is a singleton
+    scope: Option<Ident>,
+    argument: Ident,
+    /// The transient dependencies between the singleton and the scoped dependency
+    through: Vec<Ident>,
+    /// The scoped dependency, or the stateful transient dependency, which is kept
+    scoped: Ident,
+    scoped_lifetime: Lifetime,
+    scoped_scope: Option<Ident>,
+}
+
+impl VisitorMut for CaptiveDependencies {
+    fn visit_container_mut(&mut self, container: &mut Container) {
+        self.scopes = container.options.scopes.clone();
+
+        visit_container_mut(self, container);
+    }
+
+    fn visit_dependency_mut(&mut self, dependency: &mut Dependency) {
+        let Some(depth) = self.depth(dependency) else {
+            return;
+        };
+
+        for child in dependency.dependencies.iter() {
+            if is_captive(dependency, &child.ident) {
+                continue;
+            }
+
+            for candidate in child.candidates() {
+                let mut through = Vec::new();
+
+                let Some(scoped) = self.find_scoped(candidate, depth, &mut through) else {
+                    continue;
+                };
+                let scoped = scoped.borrow();
+
+                self.captives.push(Captive {
+                    dependency: dependency.sig.ident.clone(),
+                    scope: dependency.scope.clone(),
+                    argument: child.ident.clone(),
+                    through,
+                    scoped: scoped.sig.ident.clone(),
+                    scoped_lifetime: scoped.lifetime.clone(),
+                    scoped_scope: scoped.scope.clone(),
+                });
+                break;
+            }
+        }
+    }
+}
+
+impl CaptiveDependencies {
+    /// How deep the dependency is kept. Singletons are kept by the root container, followed by every named scope from
+    /// the outermost to the innermost. Scoped dependencies without a named scope are kept as deep as the innermost
+    /// scope. Transient dependencies are not kept.
+    fn depth(&self, dependency: &Dependency) -> Option<usize> {
+        match dependency.lifetime {
+            Lifetime::Singleton(_) | Lifetime::Embedded(_) => Some(0),
+            Lifetime::Scoped(_) => Some(match &dependency.scope {
+                Some(scope) => self.scopes.iter().position(|s| s == scope)? + 1,
+                None => self.scopes.len().max(1),
+            }),
+            Lifetime::Transient(_) => None,
+        }
+    }
+
+    /// Find a dependency kept deeper than the given depth, or a stateful transient dependency, which is either this
+    /// dependency or reached through transient dependencies
+    fn find_scoped(
+        &self,
+        dependency: &Rc<RefCell<Dependency>>,
+        depth: usize,
+        through: &mut Vec<Ident>,
+    ) -> Option<Rc<RefCell<Dependency>>> {
+        let dependency_ref = dependency.borrow();
+
+        if let Some(child_depth) = self.depth(&dependency_ref) {
+            return (child_depth > depth).then(|| dependency.clone());
+        }
+
+        if dependency_ref.is_stateful {
+            return Some(dependency.clone());
+        }
+
+        through.push(dependency_ref.sig.ident.clone());
+
+        for child in dependency_ref.dependencies.iter() {
+            if is_captive(&dependency_ref, &child.ident) {
+                continue;
+            }
+
+            for candidate in child.candidates() {
+                if let Some(scoped) = self.find_scoped(candidate, depth, through) {
+                    return Some(scoped);
+                }
+            }
+        }
+
+        through.pop();
+
+        None
+    }
+}
+
+/// Whether the argument is marked with `#[captive]`
+fn is_captive(dependency: &Dependency, argument: &Ident) -> bool {
+    dependency.sig.inputs.iter().any(|fn_arg| {
+        let FnArg::Typed(pat_type) = fn_arg else {
+            return false;
+        };
+
+        let Pat::Ident(pat) = pat_type.pat.as_ref() else {
+            return false;
+        };
+
+        &pat.ident == argument
+            && pat_type
+                .attrs
+                .iter()
+                .any(|attr| attr.path().is_ident("captive"))
+    })
+}
+
+impl ErrorVisitorMut for CaptiveDependencies {
+    fn new() -> Self {
+        Self {
+            scopes: Default::default(),
+            captives: Default::default(),
+        }
+    }
+
+    fn emit_errors(self) {
+        for Captive {
+            dependency,
+            scope,
+            argument,
+            through,
+            scoped,
+            scoped_lifetime,
+            scoped_scope,
+        } in self.captives
+        {
+            let through = if through.is_empty() {
+                String::new()
+            } else {
+                let through = through
+                    .iter()
+                    .map(|ident| format!("`{ident}`"))
+                    .collect::<Vec<_>>()
+                    .join(" -> ");
+
+                format!(" through {through}")
+            };
+
+            let (message, help) = match (&scope, &scoped_scope) {
+                _ if matches!(scoped_lifetime, Lifetime::Transient(_)) => (
+                    format!(
+                        "The '{dependency}' dependency depends on the stateful '{scoped}' transient{through}. So it will keep using the same '{scoped}' rather than a new one each time"
+                    ),
+                    format!(
+                        "Make `{dependency}` transient too, or mark `{argument}` with `#[captive]` if this is deliberate"
+                    ),
+                ),
+                (None, _) => (
+                    format!(
+                        "The '{dependency}' singleton depends on the scoped '{scoped}' dependency{through}. So it will keep using the '{scoped}' of the first scope it is created in"
+                    ),
+                    format!(
+                        "Make `{dependency}` scoped too, or mark `{argument}` with `#[captive]` if this is deliberate"
+                    ),
+                ),
+                (Some(scope), Some(scoped_scope)) => (
+                    format!(
+                        "The '{dependency}' dependency of the '{scope}' scope depends on the '{scoped}' dependency of the inner '{scoped_scope}' scope{through}. So it will keep using the '{scoped}' of the first '{scoped_scope}' scope it is created in"
+                    ),
+                    format!(
+                        "Move `{dependency}` to the '{scoped_scope}' scope too, or mark `{argument}` with `#[captive]` if this is deliberate"
+                    ),
+                ),
+                (Some(scope), None) => (
+                    format!(
+                        "The '{dependency}' dependency of the '{scope}' scope depends on the scoped '{scoped}' dependency{through}, which is not part of a named scope. So it will keep using the '{scoped}' of the first scope it is created in"
+                    ),
+                    format!(
+                        "Move `{scoped}` to the '{scope}' scope, or mark `{argument}` with `#[captive]` if this is deliberate"
+                    ),
+                ),
+            };
+
+            let mut diagnostic = Diagnostic::spanned(argument.span(), Level::Error, message);
+
+            match scoped_lifetime {
+                Lifetime::Scoped(span) => {
+                    diagnostic =
+                        diagnostic.span_error(span, format!("`{scoped}` is made scoped here"));
+                }
+                Lifetime::Transient(Some(span)) => {
+                    diagnostic =
+                        diagnostic.span_error(span, format!("`{scoped}` is made stateful here"));
+                }
+                _ => {}
+            }
+
+            diagnostic.help(help).emit();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use proc_macro2::Span;
+    use syn::parse_quote;
+
+    use crate::{
+        input,
+        processing::{
+            self,
+            visitor::{ExtractLifetime, LinkDependencies, VisitableMut},
+        },
+    };
+
+    use super::*;
+
+    fn ident(name: &str) -> Ident {
+        Ident::new(name, Span::call_site())
+    }
+
+    #[test]
+    fn captive_dependencies() {
+        let mut container: processing::Container = input::Container::from_item_impl(parse_quote!(
+            impl Container {
+                #[Scoped]
+                fn transaction(&self) -> Transaction {
+                    Transaction::begin()
+                }
+
+                fn repository(&self, transaction: &Transaction) -> Repository {
+                    Repository::new(transaction)
+                }
+
+                #[Singleton]
+                fn config(&self) -> Config {
+                    Config
+                }
+
+                #[Singleton]
+                fn cache(&self, transaction: &Transaction, config: &Config) -> Cache {
+                    Cache::new(transaction, config)
+                }
+
+                #[Singleton]
+                fn service(&self, repository: Repository) -> Service {
+                    Service::new(repository)
+                }
+
+                #[Singleton]
+                fn audit(&self, #[captive] transaction: &Transaction) -> Audit {
+                    Audit::new(transaction)
+                }
+
+                #[Scoped]
+                fn handler(&self, repository: Repository, config: &Config) -> Handler {
+                    Handler::new(repository, config)
+                }
+            }
+        ))
+        .into();
+
+        // Test needs lifetimes to be extracted and dependencies to be linked
+        container.apply_mut(&mut ExtractLifetime::new());
+        container.apply_mut(&mut LinkDependencies::new());
+
+        let mut visitor = CaptiveDependencies::new();
+
+        container.apply_mut(&mut visitor);
+
+        assert_eq!(
+            visitor.captives,
+            vec![
+                Captive {
+                    dependency: ident("cache"),
+                    scope: None,
+                    argument: ident("transaction"),
+                    through: vec![],
+                    scoped: ident("transaction"),
+                    scoped_lifetime: Lifetime::Scoped(Span::call_site()),
+                    scoped_scope: None,
+                },
+                Captive {
+                    dependency: ident("service"),
+                    scope: None,
+                    argument: ident("repository"),
+                    through: vec![ident("repository")],
+                    scoped: ident("transaction"),
+                    scoped_lifetime: Lifetime::Scoped(Span::call_site()),
+                    scoped_scope: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn captive_stateful_transients() {
+        let mut container: processing::Container = input::Container::from_item_impl(parse_quote!(
+            impl Container {
+                #[Transient(stateful)]
+                fn transaction(&self) -> Transaction {
+                    Transaction::begin()
+                }
+
+                fn repository(&self, transaction: Transaction) -> Repository {
+                    Repository::new(transaction)
+                }
+
+                #[Singleton]
+                fn service(&self, repository: Repository) -> Service {
+                    Service::new(repository)
+                }
+
+                #[Scoped]
+                fn handler(&self, transaction: Transaction) -> Handler {
+                    Handler::new(transaction)
+                }
+
+                #[Singleton]
+                fn audit(&self, #[captive] transaction: Transaction) -> Audit {
+                    Audit::new(transaction)
+                }
+
+                fn request(&self, transaction: Transaction) -> Request {
+                    Request::new(transaction)
+                }
+            }
+        ))
+        .into();
+
+        container.apply_mut(&mut ExtractLifetime::new());
+        container.apply_mut(&mut LinkDependencies::new());
+
+        let mut visitor = CaptiveDependencies::new();
+
+        container.apply_mut(&mut visitor);
+
+        assert_eq!(
+            visitor.captives,
+            vec![
+                Captive {
+                    dependency: ident("service"),
+                    scope: None,
+                    argument: ident("repository"),
+                    through: vec![ident("repository")],
+                    scoped: ident("transaction"),
+                    scoped_lifetime: Lifetime::Transient(Some(Span::call_site())),
+                    scoped_scope: None,
+                },
+                Captive {
+                    dependency: ident("handler"),
+                    scope: None,
+                    argument: ident("transaction"),
+                    through: vec![],
+                    scoped: ident("transaction"),
+                    scoped_lifetime: Lifetime::Transient(Some(Span::call_site())),
+                    scoped_scope: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn captive_named_scopes() {
+        let mut container: processing::Container = input::Container::from_item_impl(parse_quote!(
+            impl Container {
+                #[Scoped(tenant)]
+                fn tenant(&self) -> Tenant {
+                    Tenant
+                }
+
+                #[Scoped(request)]
+                fn request(&self, tenant: &Tenant) -> Request {
+                    Request::new(tenant)
+                }
+
+                #[Scoped]
+                fn transaction(&self) -> Transaction {
+                    Transaction::begin()
+                }
+
+                fn handler(&self, request: &Request) -> Handler {
+                    Handler::new(request)
+                }
+
+                #[Scoped(tenant)]
+                fn billing(&self, handler: Handler, transaction: &Transaction) -> Billing {
+                    Billing::new(handler, transaction)
+                }
+
+                // Dependencies without a named scope are created again with the innermost scope too
+                #[Scoped(request)]
+                fn audit(&self, transaction: &Transaction) -> Audit {
+                    Audit::new(transaction)
+                }
+            }
+        ))
+        .into();
+
+        container.options.scopes = vec![ident("tenant"), ident("request")];
+
+        // Test needs lifetimes to be extracted and dependencies to be linked
+        container.apply_mut(&mut ExtractLifetime::new());
+        container.apply_mut(&mut LinkDependencies::new());
+
+        let mut visitor = CaptiveDependencies::new();
+
+        container.apply_mut(&mut visitor);
+
+        assert_eq!(
+            visitor.captives,
+            vec![
+                Captive {
+                    dependency: ident("billing"),
+                    scope: Some(ident("tenant")),
+                    argument: ident("handler"),
+                    through: vec![ident("handler")],
+                    scoped: ident("request"),
+                    scoped_lifetime: Lifetime::Scoped(Span::call_site()),
+                    scoped_scope: Some(ident("request")),
+                },
+                Captive {
+                    dependency: ident("billing"),
+                    scope: Some(ident("tenant")),
+                    argument: ident("transaction"),
+                    through: vec![],
+                    scoped: ident("transaction"),
+                    scoped_lifetime: Lifetime::Scoped(Span::call_site()),
+                    scoped_scope: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn captive_through_cfg_alternatives() {
+        let mut container: processing::Container = input::Container::from_item_impl(parse_quote!(
+            impl Container {
+                #[Scoped]
+                fn transaction(&self) -> Transaction {
+                    Transaction::begin()
+                }
+
+                #[cfg(not(test))]
+                fn repository(&self) -> Repository {
+                    Repository::default()
+                }
+
+                #[cfg(test)]
+                fn repository(&self, transaction: &Transaction) -> Repository {
+                    Repository::new(transaction)
+                }
+
+                #[Singleton]
+                fn service(&self, repository: Repository) -> Service {
+                    Service::new(repository)
+                }
+            }
+        ))
+        .into();
+
+        container.apply_mut(&mut ExtractLifetime::new());
+        container.apply_mut(&mut LinkDependencies::new());
+
+        let mut visitor = CaptiveDependencies::new();
+
+        container.apply_mut(&mut visitor);
+
+        assert_eq!(
+            visitor.captives,
+            vec![Captive {
+                dependency: ident("service"),
+                scope: None,
+                argument: ident("repository"),
+                through: vec![ident("repository")],
+                scoped: ident("transaction"),
+                scoped_lifetime: Lifetime::Scoped(Span::call_site()),
+                scoped_scope: None,
+            }]
+        );
+    }
+}
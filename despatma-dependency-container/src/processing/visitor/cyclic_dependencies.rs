@@ -0,0 +1,526 @@
+use std::{cell::RefCell, rc::Rc};
+
+use proc_macro_error2::{Diagnostic, Level};
+use syn::Ident;
+
+use crate::processing::{Container, Dependency};
+
+use super::{ErrorVisitorMut, VisitorMut};
+
+/// Reports on any dependencies that end up depending on themselves.
+/// So if `a` has a dependency on `b` and `b` has a dependency on `a`, this visitor will emit an error for the
+/// `a -> b -> a` cycle. Every cycle is reported, even when it shares dependencies with another one. So all of them can
+/// be fixed in one go.
+///
+/// A link to a dependency with `#[cfg]` alternatives is followed to every alternative, since any of them could be the
+/// one compiled in. The links from a profile dispatcher to its providers are followed too, since the dispatcher
+/// creates the provider of the active profile right away even though it requests them through `Provider` handles.
+///
+/// Every link inside a cycle is removed after it is reported so that the recursive visitors which follow can still
+/// complete.
+///
+/// Needs to happen right after child dependencies have been linked.
+pub struct CyclicDependencies {
+    cycles: Vec<Cycle>,
+}
+
+#[cfg_attr(test, derive(Eq, PartialEq, Debug))]
+struct Cycle {
+    start: Ident,
+    links: Vec<Link>,
+}
+
+/// A single step in a cycle: the `argument` requesting the next `dependency` in the chain.
+#[cfg_attr(test, derive(Eq, PartialEq, Debug))]
+struct Link {
+    argument: Ident,
+    dependency: Ident,
+}
+
+impl VisitorMut for CyclicDependencies {
+    fn visit_container_mut(&mut self, container: &mut Container) {
+        let dependencies = &container.dependencies;
+        let graph: Vec<Vec<usize>> = dependencies
+            .iter()
+            .map(|dependency| {
+                let mut links = Vec::new();
+
+                // Follow a dependency once, even when many arguments request it. Else its cycles are reported twice
+                for (_, candidate) in construction_links(&dependency.borrow()) {
+                    if let Some(index) = index_of(dependencies, candidate) {
+                        if !links.contains(&index) {
+                            links.push(index);
+                        }
+                    }
+                }
+
+                links
+            })
+            .collect();
+
+        let mut components = strongly_connected_components(&graph);
+
+        // Report the cycles in the order in which the dependencies were declared
+        components.iter_mut().for_each(|component| component.sort());
+        components.sort();
+
+        for component in components {
+            // A lone dependency has no cycles, unless it depends on itself
+            for path in elementary_cycles(&graph, &component) {
+                let links = path
+                    .windows(2)
+                    .map(|pair| {
+                        let from = dependencies[pair[0]].borrow();
+                        let to = &dependencies[pair[1]];
+                        let argument = construction_links(&from)
+                            .into_iter()
+                            .find(|(_, candidate)| Rc::ptr_eq(candidate, to))
+                            .map(|(argument, _)| argument.clone())
+                            .expect("path to only follow linked dependencies");
+
+                        Link {
+                            argument,
+                            dependency: to.borrow().sig.ident.clone(),
+                        }
+                    })
+                    .collect();
+
+                self.cycles.push(Cycle {
+                    start: dependencies[path[0]].borrow().sig.ident.clone(),
+                    links,
+                });
+            }
+
+            for &index in &component {
+                dependencies[index]
+                    .borrow_mut()
+                    .dependencies
+                    .retain(|child| {
+                        !child.candidates().any(|candidate| {
+                            index_of(dependencies, candidate)
+                                .is_some_and(|child_index| component.contains(&child_index))
+                        })
+                    });
+            }
+        }
+    }
+}
+
+impl ErrorVisitorMut for CyclicDependencies {
+    fn new() -> Self {
+        Self {
+            cycles: Default::default(),
+        }
+    }
+
+    fn emit_errors(self) {
+        for Cycle { start, links } in self.cycles {
+            let chain = std::iter::once(start.to_string())
+                .chain(links.iter().map(|link| link.dependency.to_string()))
+                .collect::<Vec<_>>()
+                .join(" -> ");
+
+            let mut diagnostic = Diagnostic::spanned(
+                links[0].argument.span(),
+                Level::Error,
+                format!("Dependency cycle detected: {chain}"),
+            );
+
+            let mut requester = &links[0].dependency;
+
+            for link in links.iter().skip(1) {
+                diagnostic = diagnostic.span_error(
+                    link.argument.span(),
+                    format!(
+                        "`{}` requests `{}` here as part of the cycle",
+                        requester, link.dependency
+                    ),
+                );
+                requester = &link.dependency;
+            }
+
+            diagnostic
+                .help("Break the cycle by removing one of these dependencies".to_string())
+                .emit();
+        }
+    }
+}
+
+/// Every dependency needed to create `dependency` along with the argument requesting it
+fn construction_links(dependency: &Dependency) -> Vec<(&Ident, &Rc<RefCell<Dependency>>)> {
+    let children = dependency.dependencies.iter().flat_map(|child| {
+        child
+            .candidates()
+            .map(move |candidate| (&child.ident, candidate))
+    });
+    let providers = dependency
+        .deferred
+        .iter()
+        .filter(|deferred| deferred.inner.borrow().profile.is_some())
+        .map(|deferred| (&deferred.ident, &deferred.inner));
+
+    children.chain(providers).collect()
+}
+
+fn index_of(
+    dependencies: &[Rc<RefCell<Dependency>>],
+    needle: &Rc<RefCell<Dependency>>,
+) -> Option<usize> {
+    dependencies.iter().position(|d| Rc::ptr_eq(d, needle))
+}
+
+/// Tarjan's algorithm to find all the strongly connected components in the dependency graph
+fn strongly_connected_components(graph: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    struct Tarjan<'g> {
+        graph: &'g [Vec<usize>],
+        index: usize,
+        indices: Vec<Option<usize>>,
+        low_links: Vec<usize>,
+        on_stack: Vec<bool>,
+        stack: Vec<usize>,
+        components: Vec<Vec<usize>>,
+    }
+
+    impl Tarjan<'_> {
+        fn connect(&mut self, node: usize) {
+            self.indices[node] = Some(self.index);
+            self.low_links[node] = self.index;
+            self.index += 1;
+            self.stack.push(node);
+            self.on_stack[node] = true;
+
+            for &next in &self.graph[node] {
+                match self.indices[next] {
+                    None => {
+                        self.connect(next);
+                        self.low_links[node] = self.low_links[node].min(self.low_links[next]);
+                    }
+                    Some(next_index) if self.on_stack[next] => {
+                        self.low_links[node] = self.low_links[node].min(next_index);
+                    }
+                    Some(_) => {}
+                }
+            }
+
+            if Some(self.low_links[node]) == self.indices[node] {
+                let mut component = Vec::new();
+
+                while let Some(member) = self.stack.pop() {
+                    self.on_stack[member] = false;
+                    component.push(member);
+
+                    if member == node {
+                        break;
+                    }
+                }
+
+                self.components.push(component);
+            }
+        }
+    }
+
+    let mut tarjan = Tarjan {
+        graph,
+        index: 0,
+        indices: vec![None; graph.len()],
+        low_links: vec![0; graph.len()],
+        on_stack: vec![false; graph.len()],
+        stack: Vec::new(),
+        components: Vec::new(),
+    };
+
+    for node in 0..graph.len() {
+        if tarjan.indices[node].is_none() {
+            tarjan.connect(node);
+        }
+    }
+
+    tarjan.components
+}
+
+/// Find every elementary cycle in the (sorted) component, ie every path back to its start which does not pass through
+/// the same node twice. A cycle is only followed from its first node in the component so that it is found only once.
+fn elementary_cycles(graph: &[Vec<usize>], component: &[usize]) -> Vec<Vec<usize>> {
+    fn extend(
+        graph: &[Vec<usize>],
+        allowed: &[usize],
+        path: &mut Vec<usize>,
+        cycles: &mut Vec<Vec<usize>>,
+    ) {
+        let node = *path.last().expect("path to have a start");
+
+        for &next in &graph[node] {
+            if next == path[0] {
+                let mut cycle = path.clone();
+
+                cycle.push(next);
+                cycles.push(cycle);
+            } else if allowed.contains(&next) && !path.contains(&next) {
+                path.push(next);
+                extend(graph, allowed, path, cycles);
+                path.pop();
+            }
+        }
+    }
+
+    let mut cycles = Vec::new();
+
+    for (position, &start) in component.iter().enumerate() {
+        extend(
+            graph,
+            &component[position + 1..],
+            &mut vec![start],
+            &mut cycles,
+        );
+    }
+
+    cycles
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use proc_macro2::Span;
+    use syn::parse_quote;
+
+    use crate::{
+        input,
+        processing::{
+            self,
+            visitor::{AddProfileDependencies, LinkDependencies, VisitableMut},
+        },
+    };
+
+    use super::*;
+
+    fn ident(name: &str) -> Ident {
+        Ident::new(name, Span::call_site())
+    }
+
+    #[test]
+    fn cyclic_dependencies() {
+        let mut container: processing::Container = input::Container::from_item_impl(parse_quote!(
+            impl Container {
+                fn service(&self, repo: &Repo, config: &Config) -> Service {
+                    Service::new(repo, config)
+                }
+
+                fn config(&self) -> Config {
+                    Config
+                }
+
+                fn repo(&self, db: &Db) -> Repo {
+                    Repo::new(db)
+                }
+
+                fn db(&self, config: &Config, service: &Service) -> Db {
+                    Db::new(config, service)
+                }
+
+                fn recursive(&self, recursive: Recursive) -> Recursive {
+                    recursive
+                }
+            }
+        ))
+        .into();
+
+        // Test needs them to be linked
+        container.apply_mut(&mut LinkDependencies::new());
+
+        let mut visitor = CyclicDependencies::new();
+
+        container.apply_mut(&mut visitor);
+
+        assert_eq!(
+            visitor.cycles,
+            vec![
+                Cycle {
+                    start: ident("service"),
+                    links: vec![
+                        Link {
+                            argument: ident("repo"),
+                            dependency: ident("repo"),
+                        },
+                        Link {
+                            argument: ident("db"),
+                            dependency: ident("db"),
+                        },
+                        Link {
+                            argument: ident("service"),
+                            dependency: ident("service"),
+                        },
+                    ],
+                },
+                Cycle {
+                    start: ident("recursive"),
+                    links: vec![Link {
+                        argument: ident("recursive"),
+                        dependency: ident("recursive"),
+                    }],
+                },
+            ]
+        );
+
+        let children = |index: usize| -> Vec<Ident> {
+            container.dependencies[index]
+                .borrow()
+                .dependencies
+                .iter()
+                .map(|child| child.ident.clone())
+                .collect()
+        };
+
+        // Only the links inside the cycles should be removed
+        assert_eq!(children(0), vec![ident("config")]);
+        assert_eq!(children(2), Vec::<Ident>::new());
+        assert_eq!(children(3), vec![ident("config")]);
+        assert_eq!(children(4), Vec::<Ident>::new());
+    }
+
+    #[test]
+    fn every_cycle_in_a_component() {
+        let mut container: processing::Container = input::Container::from_item_impl(parse_quote!(
+            impl Container {
+                fn service(&self, repo: &Repo, cache: &Cache) -> Service {
+                    Service::new(repo, cache)
+                }
+
+                fn repo(&self, service: &Service, cache: &Cache) -> Repo {
+                    Repo::new(service, cache)
+                }
+
+                fn cache(&self, service: &Service) -> Cache {
+                    Cache::new(service)
+                }
+            }
+        ))
+        .into();
+
+        container.apply_mut(&mut LinkDependencies::new());
+
+        let mut visitor = CyclicDependencies::new();
+
+        container.apply_mut(&mut visitor);
+
+        let link = |name: &str| Link {
+            argument: ident(name),
+            dependency: ident(name),
+        };
+
+        assert_eq!(
+            visitor.cycles,
+            vec![
+                Cycle {
+                    start: ident("service"),
+                    links: vec![link("repo"), link("service")],
+                },
+                Cycle {
+                    start: ident("service"),
+                    links: vec![link("repo"), link("cache"), link("service")],
+                },
+                Cycle {
+                    start: ident("service"),
+                    links: vec![link("cache"), link("service")],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn cyclic_dependencies_through_cfg_alternatives() {
+        let mut container: processing::Container = input::Container::from_item_impl(parse_quote!(
+            impl Container {
+                #[cfg(not(debug_assertions))]
+                fn a(&self) -> A {
+                    A
+                }
+
+                #[cfg(debug_assertions)]
+                fn a(&self, b: B) -> A {
+                    A::new(b)
+                }
+
+                fn b(&self, a: A) -> B {
+                    B::new(a)
+                }
+            }
+        ))
+        .into();
+
+        container.apply_mut(&mut LinkDependencies::new());
+
+        let mut visitor = CyclicDependencies::new();
+
+        container.apply_mut(&mut visitor);
+
+        assert_eq!(
+            visitor.cycles,
+            vec![Cycle {
+                start: ident("a"),
+                links: vec![
+                    Link {
+                        argument: ident("b"),
+                        dependency: ident("b"),
+                    },
+                    Link {
+                        argument: ident("a"),
+                        dependency: ident("a"),
+                    },
+                ],
+            }]
+        );
+    }
+
+    #[test]
+    fn cyclic_dependencies_through_profiles() {
+        let mut container: processing::Container = input::Container::from_item_impl(parse_quote!(
+            impl DependencyContainer {
+                fn new(profile: DependencyContainerProfile) {}
+
+                #[Profile(dev)]
+                fn repository(&self) -> Box<dyn Repo> {
+                    Box::new(InMemory)
+                }
+
+                #[Profile(prod)]
+                fn repository(&self, service: Service) -> Box<dyn Repo> {
+                    Box::new(Postgres::new(service))
+                }
+
+                fn service(&self, repository: Box<dyn Repo>) -> Service {
+                    Service::new(repository)
+                }
+            }
+        ))
+        .into();
+        container.options.despatma = true;
+
+        container.apply_mut(&mut AddProfileDependencies::new());
+        container.apply_mut(&mut LinkDependencies::new());
+
+        let mut visitor = CyclicDependencies::new();
+
+        container.apply_mut(&mut visitor);
+
+        assert_eq!(
+            visitor.cycles,
+            vec![Cycle {
+                start: ident("repository_prod"),
+                links: vec![
+                    Link {
+                        argument: ident("service"),
+                        dependency: ident("service"),
+                    },
+                    Link {
+                        argument: ident("repository"),
+                        dependency: ident("repository"),
+                    },
+                    Link {
+                        argument: ident("repository_prod"),
+                        dependency: ident("repository_prod"),
+                    },
+                ],
+            }]
+        );
+    }
+}
@@ -0,0 +1,147 @@
+use proc_macro_error2::emit_error;
+use quote::ToTokens;
+use syn::{Ident, Type};
+
+use crate::processing::Container;
+
+use super::{ErrorVisitorMut, VisitorMut};
+
+/// Reports when eager dependencies can fail with different errors. Since `init` creates all of them, it can only
+/// return one error type.
+///
+/// Needs error types to be extracted first.
+pub struct EagerErrorTypes {
+    errors: Vec<Error>,
+}
+
+#[cfg_attr(test, derive(Eq, PartialEq, Debug))]
+struct Error {
+    dependency: Ident,
+    error_ty: Type,
+    expected_error_ty: Type,
+}
+
+impl VisitorMut for EagerErrorTypes {
+    fn visit_container_mut(&mut self, container: &mut Container) {
+        let mut expected_error_ty: Option<Type> = None;
+
+        for dependency in container.dependencies.iter() {
+            let dependency = dependency.borrow();
+
+            if !dependency.is_eager {
+                continue;
+            }
+
+            let Some(error_ty) = &dependency.error_ty else {
+                continue;
+            };
+
+            match &expected_error_ty {
+                None => expected_error_ty = Some(error_ty.clone()),
+                Some(expected_error_ty) if expected_error_ty == error_ty => {}
+                Some(expected_error_ty) => self.errors.push(Error {
+                    dependency: dependency.sig.ident.clone(),
+                    error_ty: error_ty.clone(),
+                    expected_error_ty: expected_error_ty.clone(),
+                }),
+            }
+        }
+    }
+}
+
+impl ErrorVisitorMut for EagerErrorTypes {
+    fn new() -> Self {
+        Self {
+            errors: Default::default(),
+        }
+    }
+
+    fn emit_errors(self) {
+        for Error {
+            dependency,
+            error_ty,
+            expected_error_ty,
+        } in self.errors
+        {
+            emit_error!(
+                dependency,
+                "The eager '{}' dependency can fail with `{}`, but other eager dependencies fail with `{}`",
+                dependency,
+                error_ty.to_token_stream(),
+                expected_error_ty.to_token_stream();
+                hint = "`init` can only return one error type, so eager dependencies should all fail with the same error"
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use proc_macro2::Span;
+    use syn::parse_quote;
+
+    use crate::{
+        input,
+        processing::{
+            self,
+            visitor::{
+                ExtractErrorType, ExtractLifetime, ExtractResultType, LinkDependencies,
+                VisitableMut,
+            },
+        },
+    };
+
+    use super::*;
+
+    #[test]
+    fn eager_error_types() {
+        let mut container: processing::Container = input::Container::from_item_impl(parse_quote!(
+            impl Container {
+                #[Singleton(eager)]
+                fn db(&self) -> Result<Db, DbError> {
+                    Db::connect()
+                }
+
+                #[Singleton(eager)]
+                fn repository(&self, db: &Db) -> Repository {
+                    Repository::new(db)
+                }
+
+                #[Singleton(eager)]
+                fn config(&self) -> Config {
+                    Config
+                }
+
+                #[Singleton(eager)]
+                fn settings(&self) -> Result<Settings, SettingsError> {
+                    Settings::read()
+                }
+
+                fn lazy(&self) -> Result<Lazy, LazyError> {
+                    Lazy::load()
+                }
+            }
+        ))
+        .into();
+
+        // Test needs the error types to be extracted
+        container.apply_mut(&mut ExtractResultType);
+        container.apply_mut(&mut ExtractLifetime::new());
+        container.apply_mut(&mut LinkDependencies::new());
+        container.apply_mut(&mut ExtractErrorType::new());
+
+        let mut visitor = EagerErrorTypes::new();
+
+        container.apply_mut(&mut visitor);
+
+        assert_eq!(
+            visitor.errors,
+            vec![Error {
+                dependency: Ident::new("settings", Span::call_site()),
+                error_ty: parse_quote!(SettingsError),
+                expected_error_ty: parse_quote!(DbError),
+            }]
+        );
+    }
+}
@@ -0,0 +1,151 @@
+use proc_macro_error2::emit_error;
+use syn::{FnArg, Ident, Pat};
+
+use crate::processing::{Argument, Dependency};
+
+use super::{ErrorVisitorMut, VisitorMut};
+
+/// Get the arguments marked with `#[arg]` which the caller passes in to create the dependency.
+/// Reports on any managed dependency taking such arguments, since it is only created once for every caller.
+///
+/// Needs lifetimes to be extracted first. And has to happen before dependencies are linked.
+pub struct ExtractArguments {
+    errors: Vec<Error>,
+}
+
+#[cfg_attr(test, derive(Eq, PartialEq, Debug))]
+struct Error {
+    dependency: Ident,
+    argument: Ident,
+}
+
+impl VisitorMut for ExtractArguments {
+    fn visit_dependency_mut(&mut self, dependency: &mut Dependency) {
+        dependency.arguments = dependency
+            .sig
+            .inputs
+            .iter()
+            .filter_map(|fn_arg| {
+                let FnArg::Typed(pat_type) = fn_arg else {
+                    return None;
+                };
+
+                let Pat::Ident(pat) = pat_type.pat.as_ref() else {
+                    return None;
+                };
+
+                if !pat_type
+                    .attrs
+                    .iter()
+                    .any(|attr| attr.path().is_ident("arg"))
+                {
+                    return None;
+                }
+
+                Some(Argument {
+                    ident: pat.ident.clone(),
+                    ty: pat_type.ty.as_ref().clone(),
+                })
+            })
+            .collect();
+
+        if dependency.lifetime.is_managed() {
+            for argument in dependency.arguments.drain(..) {
+                self.errors.push(Error {
+                    dependency: dependency.sig.ident.clone(),
+                    argument: argument.ident,
+                });
+            }
+        }
+    }
+}
+
+impl ErrorVisitorMut for ExtractArguments {
+    fn new() -> Self {
+        Self {
+            errors: Default::default(),
+        }
+    }
+
+    fn emit_errors(self) {
+        for Error {
+            dependency,
+            argument,
+        } in self.errors
+        {
+            emit_error!(
+                argument,
+                "Only transient dependencies can take runtime arguments";
+                hint = "The '{}' dependency is shared by every caller, so it cannot be created from the arguments of each of them", dependency
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use syn::parse_quote;
+
+    use crate::{
+        input,
+        processing::{
+            self,
+            visitor::{ExtractLifetime, VisitableMut},
+        },
+    };
+
+    use super::*;
+
+    #[test]
+    fn extract_arguments() {
+        let mut container: processing::Container = input::Container::from_item_impl(parse_quote!(
+            impl Container {
+                fn repo(&self) -> Repo {
+                    Repo
+                }
+
+                fn order_handler(&self, repo: &Repo, #[arg] order_id: OrderId, #[arg] user: &str) -> OrderHandler {
+                    OrderHandler::new(repo, order_id, user)
+                }
+
+                #[Singleton]
+                fn cache(&self, #[arg] size: usize) -> Cache {
+                    Cache::new(size)
+                }
+            }
+        ))
+        .into();
+
+        // Test needs lifetimes to be extracted
+        container.apply_mut(&mut ExtractLifetime::new());
+
+        let mut visitor = ExtractArguments::new();
+
+        container.apply_mut(&mut visitor);
+
+        assert_eq!(container.dependencies[0].borrow().arguments, vec![]);
+        assert_eq!(
+            container.dependencies[1].borrow().arguments,
+            vec![
+                Argument {
+                    ident: parse_quote!(order_id),
+                    ty: parse_quote!(OrderId),
+                },
+                Argument {
+                    ident: parse_quote!(user),
+                    ty: parse_quote!(&str),
+                },
+            ]
+        );
+        assert_eq!(container.dependencies[2].borrow().arguments, vec![]);
+
+        assert_eq!(
+            visitor.errors,
+            vec![Error {
+                dependency: parse_quote!(cache),
+                argument: parse_quote!(size),
+            }]
+        );
+    }
+}
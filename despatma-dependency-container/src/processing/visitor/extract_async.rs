@@ -14,10 +14,11 @@ impl VisitorMut for ExtractAsync {
         dependency.is_async = dependency.sig.asyncness.is_some();
 
         for child in dependency.dependencies.iter_mut() {
-            let child = &child.inner;
-            self.visit_dependency_mut(&mut child.borrow_mut());
+            for candidate in child.candidates() {
+                self.visit_dependency_mut(&mut candidate.borrow_mut());
 
-            dependency.is_async |= child.borrow().is_async;
+                dependency.is_async |= candidate.borrow().is_async;
+            }
         }
     }
 }
@@ -1,48 +1,84 @@
-use syn::{GenericArgument, PathArguments, Type};
+use syn::{GenericArgument, Path, PathArguments, Type};
 
-use crate::processing::Dependency;
+use crate::processing::{Container, Dependency, Pointer};
 
-use super::{ErrorVisitorMut, VisitorMut};
+use super::{visit_container_mut, ErrorVisitorMut, VisitorMut};
 
-/// Extracts the internal type of any boxed dependency
+/// Extracts the internal type of any dependency in a smart pointer. Ie a `Box`, `Arc`, `Rc` or any pointer declared
+/// with the `pointers(...)` option of the container.
 /// This is needed to fix the Rust lifetimes on any singleton and scoped dependencies
-pub struct ExtractBoxType;
+pub struct ExtractBoxType {
+    pointers: Vec<Path>,
+}
 
 impl VisitorMut for ExtractBoxType {
+    fn visit_container_mut(&mut self, container: &mut Container) {
+        self.pointers = container.options.pointers.clone();
+
+        visit_container_mut(self, container);
+    }
+
     fn visit_dependency_mut(&mut self, dependency: &mut Dependency) {
-        let Type::Path(path) = &dependency.ty else {
+        let Some((pointer, ty)) = get_pointer(&dependency.ty, &self.pointers) else {
             return;
         };
 
-        let Some(last_segment) = path.path.segments.last() else {
-            return;
-        };
+        dependency.pointer = Some(pointer);
+        dependency.ty = ty;
+    }
+}
 
-        if last_segment.ident != "Box" {
-            return;
-        }
+/// Get the smart pointer around a type and the type it points to
+pub(super) fn get_pointer(ty: &Type, pointers: &[Path]) -> Option<(Pointer, Type)> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
 
-        let PathArguments::AngleBracketed(generics) = &last_segment.arguments else {
-            return;
-        };
+    let last_segment = path.path.segments.last()?;
+
+    let pointer = if last_segment.ident == "Box" {
+        Pointer::Box
+    } else if last_segment.ident == "Arc" {
+        Pointer::Arc
+    } else if last_segment.ident == "Rc" {
+        Pointer::Rc
+    } else {
+        let path = pointers.iter().find(|pointer| {
+            pointer
+                .segments
+                .last()
+                .is_some_and(|segment| segment.ident == last_segment.ident)
+        })?;
+
+        Pointer::Custom(path.clone())
+    };
 
-        // A box can only have one generic type
-        if generics.args.len() != 1 {
-            return;
-        }
+    let PathArguments::AngleBracketed(generics) = &last_segment.arguments else {
+        return None;
+    };
 
-        let GenericArgument::Type(ty) = &generics.args[0] else {
-            return;
-        };
+    // A smart pointer can only have one generic type
+    if generics.args.len() != 1 {
+        return None;
+    }
 
-        dependency.is_boxed = true;
-        dependency.ty = ty.clone();
+    let GenericArgument::Type(ty) = &generics.args[0] else {
+        return None;
+    };
+
+    // Only the trait objects in shared pointers need their lifetimes fixed. Other types can stay as they are
+    if pointer.is_shared() && !matches!(ty, Type::TraitObject(_)) {
+        return None;
     }
+
+    Some((pointer, ty.clone()))
 }
 
 impl ErrorVisitorMut for ExtractBoxType {
     fn new() -> Self {
-        Self
+        Self {
+            pointers: Default::default(),
+        }
     }
 }
 
@@ -69,26 +105,79 @@ mod tests {
                 fn dal(&self) -> std::boxed::Box<dyn DAL> {
                     Box::new(Sqlite)
                 }
+
+                #[Singleton]
+                fn repo(&self) -> Arc<dyn Repo> {
+                    Arc::new(PostgresRepo)
+                }
+
+                fn cache(&self) -> std::rc::Rc<dyn Cache> {
+                    Rc::new(MemoryCache)
+                }
+
+                fn handle(&self) -> Shared<dyn Handle> {
+                    Shared::new(Handler)
+                }
+
+                fn config(&self) -> Arc<Config> {
+                    Arc::new(Config)
+                }
             }
         ))
         .into();
+        container.options.pointers = vec![parse_quote!(crate::Shared)];
 
-        assert!(!container.dependencies[0].borrow().is_boxed);
+        assert_eq!(container.dependencies[0].borrow().pointer, None);
         assert_eq!(
             container.dependencies[0].borrow().ty,
             parse_quote!(Box<dyn DAL>),
         );
-        assert!(!container.dependencies[1].borrow().is_boxed);
+        assert_eq!(container.dependencies[1].borrow().pointer, None);
         assert_eq!(
             container.dependencies[1].borrow().ty,
             parse_quote!(std::boxed::Box<dyn DAL>),
         );
 
-        container.apply_mut(&mut ExtractBoxType);
+        container.apply_mut(&mut ExtractBoxType::new());
 
-        assert!(container.dependencies[0].borrow().is_boxed);
+        assert_eq!(
+            container.dependencies[0].borrow().pointer,
+            Some(Pointer::Box)
+        );
         assert_eq!(container.dependencies[0].borrow().ty, parse_quote!(dyn DAL));
-        assert!(container.dependencies[1].borrow().is_boxed);
+        assert_eq!(
+            container.dependencies[1].borrow().pointer,
+            Some(Pointer::Box)
+        );
         assert_eq!(container.dependencies[1].borrow().ty, parse_quote!(dyn DAL));
+        assert_eq!(
+            container.dependencies[2].borrow().pointer,
+            Some(Pointer::Arc)
+        );
+        assert_eq!(
+            container.dependencies[2].borrow().ty,
+            parse_quote!(dyn Repo)
+        );
+        assert_eq!(
+            container.dependencies[3].borrow().pointer,
+            Some(Pointer::Rc)
+        );
+        assert_eq!(
+            container.dependencies[3].borrow().ty,
+            parse_quote!(dyn Cache)
+        );
+        assert_eq!(
+            container.dependencies[4].borrow().pointer,
+            Some(Pointer::Custom(parse_quote!(crate::Shared)))
+        );
+        assert_eq!(
+            container.dependencies[4].borrow().ty,
+            parse_quote!(dyn Handle)
+        );
+        assert_eq!(container.dependencies[5].borrow().pointer, None);
+        assert_eq!(
+            container.dependencies[5].borrow().ty,
+            parse_quote!(Arc<Config>)
+        );
     }
 }
@@ -0,0 +1,166 @@
+use proc_macro_error2::emit_error;
+use syn::Ident;
+
+use crate::processing::Container;
+
+use super::{ErrorVisitorMut, VisitorMut};
+
+/// Moves the `#[cfg(...)]` attributes of a dependency out of its other attributes. So that they can be put on
+/// everything generated for the dependency rather than only on its method.
+///
+/// A dependency can be registered more than once when every registration has a `#[cfg(...)]` attribute. The
+/// registrations are then alternatives of which the compiler picks the active one. Any other dependency registered
+/// more than once is reported.
+pub struct ExtractCfg {
+    errors: Vec<Error>,
+}
+
+#[cfg_attr(test, derive(Eq, PartialEq, Debug))]
+struct Error {
+    dependency: Ident,
+    existing: Ident,
+}
+
+impl VisitorMut for ExtractCfg {
+    fn visit_container_mut(&mut self, container: &mut Container) {
+        for dependency in container.dependencies.iter() {
+            let mut dependency = dependency.borrow_mut();
+            let (cfgs, attrs) = dependency
+                .attrs
+                .drain(..)
+                .partition(|attr| attr.path().is_ident("cfg"));
+
+            dependency.cfgs = cfgs;
+            dependency.attrs = attrs;
+        }
+
+        for (index, dependency) in container.dependencies.iter().enumerate() {
+            let dependency = dependency.borrow();
+
+            // The providers for a profile are checked by the dependency picking between them
+            if dependency.profile.is_some() {
+                continue;
+            }
+
+            let Some(existing) = container.dependencies[..index].iter().find(|existing| {
+                let existing = existing.borrow();
+                existing.sig.ident == dependency.sig.ident && existing.profile.is_none()
+            }) else {
+                continue;
+            };
+
+            let existing = existing.borrow();
+
+            if dependency.cfgs.is_empty() || existing.cfgs.is_empty() {
+                self.errors.push(Error {
+                    dependency: dependency.sig.ident.clone(),
+                    existing: existing.sig.ident.clone(),
+                });
+            }
+        }
+    }
+}
+
+impl ErrorVisitorMut for ExtractCfg {
+    fn new() -> Self {
+        Self {
+            errors: Default::default(),
+        }
+    }
+
+    fn emit_errors(self) {
+        for Error {
+            dependency,
+            existing,
+        } in self.errors
+        {
+            emit_error!(
+                dependency,
+                "The '{}' dependency is registered more than once",
+                dependency;
+                hint = existing.span() => format!("`{existing}` is first registered here");
+                help = "Put mutually exclusive `#[cfg(...)]` attributes on every registration to pick one at compile time"
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use syn::parse_quote;
+
+    use crate::{
+        input,
+        processing::{self, visitor::VisitableMut},
+    };
+
+    use super::*;
+
+    #[test]
+    fn extract_cfg() {
+        let mut container: processing::Container = input::Container::from_item_impl(parse_quote!(
+            impl Container {
+                #[Singleton]
+                #[cfg(feature = "redis")]
+                fn cache(&self) -> Box<dyn Cache> {
+                    Box::new(Redis)
+                }
+
+                #[cfg(not(feature = "redis"))]
+                #[Singleton]
+                fn cache(&self) -> Box<dyn Cache> {
+                    Box::new(Memory)
+                }
+
+                fn config(&self) -> Config {
+                    Config
+                }
+
+                #[cfg(test)]
+                fn config(&self) -> Config {
+                    Config::test()
+                }
+            }
+        ))
+        .into();
+
+        let mut visitor = ExtractCfg::new();
+
+        container.apply_mut(&mut visitor);
+
+        let cfgs: Vec<_> = container
+            .dependencies
+            .iter()
+            .map(|d| d.borrow().cfgs.clone())
+            .collect();
+
+        assert_eq!(
+            cfgs,
+            vec![
+                vec![parse_quote!(#[cfg(feature = "redis")])],
+                vec![parse_quote!(#[cfg(not(feature = "redis"))])],
+                vec![],
+                vec![parse_quote!(#[cfg(test)])],
+            ]
+        );
+
+        // The lifetime attributes should be kept for later visitors
+        assert_eq!(
+            container.dependencies[0].borrow().attrs,
+            vec![parse_quote!(#[Singleton])]
+        );
+        assert_eq!(
+            container.dependencies[1].borrow().attrs,
+            vec![parse_quote!(#[Singleton])]
+        );
+
+        assert_eq!(
+            visitor.errors,
+            vec![Error {
+                dependency: parse_quote!(config),
+                existing: parse_quote!(config),
+            }]
+        );
+    }
+}
@@ -0,0 +1,128 @@
+use proc_macro_error2::emit_error;
+use syn::Attribute;
+
+use crate::processing::{Dependency, Dispose};
+
+use super::{ErrorVisitorMut, VisitorMut};
+
+/// Get the hook to dispose a dependency with from the `#[Dispose(close)]` or `#[Dispose(async close)]` attribute.
+///
+/// Needs to happen before lifetimes are extracted, since those treat any attribute with arguments as a type hint.
+pub struct ExtractDispose {
+    errors: Vec<Attribute>,
+}
+
+impl VisitorMut for ExtractDispose {
+    fn visit_dependency_mut(&mut self, dependency: &mut Dependency) {
+        dependency.attrs.retain(|attr| {
+            if !attr.path().is_ident("Dispose") {
+                return true;
+            }
+
+            match attr.parse_args::<Dispose>() {
+                Ok(dispose) => dependency.dispose = Some(dispose),
+                Err(_) => self.errors.push(attr.clone()),
+            }
+
+            false
+        });
+    }
+}
+
+impl ErrorVisitorMut for ExtractDispose {
+    fn new() -> Self {
+        Self {
+            errors: Default::default(),
+        }
+    }
+
+    fn emit_errors(self) {
+        for attr in self.errors {
+            emit_error!(
+                attr, "Expected the name of the method to dispose the dependency with";
+                hint = "Try `#[Dispose(close)]` or `#[Dispose(async close)]`"
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use syn::parse_quote;
+
+    use crate::{
+        input,
+        processing::{self, visitor::VisitableMut},
+    };
+
+    use super::*;
+
+    #[test]
+    fn extract_dispose() {
+        let mut container: processing::Container = input::Container::from_item_impl(parse_quote!(
+            impl Container {
+                #[Singleton]
+                #[Dispose(close)]
+                fn pool(&self) -> Pool {
+                    Pool::new()
+                }
+
+                #[Dispose(async stop)]
+                #[Scoped]
+                fn worker(&self) -> Worker {
+                    Worker::start()
+                }
+
+                fn config(&self) -> Config {
+                    Config
+                }
+
+                #[Dispose(pool.close())]
+                fn invalid(&self) -> Invalid {
+                    Invalid
+                }
+            }
+        ))
+        .into();
+
+        let mut visitor = ExtractDispose::new();
+
+        container.apply_mut(&mut visitor);
+
+        let disposals: Vec<_> = container
+            .dependencies
+            .iter()
+            .map(|d| d.borrow().dispose.clone())
+            .collect();
+
+        assert_eq!(
+            disposals,
+            vec![
+                Some(Dispose {
+                    asyncness: None,
+                    method: parse_quote!(close),
+                }),
+                Some(Dispose {
+                    asyncness: Some(Default::default()),
+                    method: parse_quote!(stop),
+                }),
+                None,
+                None,
+            ]
+        );
+
+        // The lifetime attributes should be kept for later visitors
+        assert_eq!(
+            container.dependencies[0].borrow().attrs,
+            vec![parse_quote!(#[Singleton])]
+        );
+        assert_eq!(
+            container.dependencies[1].borrow().attrs,
+            vec![parse_quote!(#[Scoped])]
+        );
+        assert!(container.dependencies[3].borrow().attrs.is_empty());
+
+        assert_eq!(visitor.errors, vec![parse_quote!(#[Dispose(pool.close())])]);
+    }
+}
@@ -1,11 +1,16 @@
 use crate::processing::visitor::{ErrorVisitorMut, VisitorMut};
 use crate::processing::{Container, Dependency};
+use proc_macro_error2::emit_error;
 use std::cell::RefCell;
 use std::rc::Rc;
-use syn::{FnArg, Pat, Signature};
+use syn::{parse_quote, Attribute, Expr, FnArg, Meta, Pat, Signature};
 
-/// Creates new dependencies based on constructor arguments
-pub struct ExtractEmbeddedDependency;
+/// Creates new dependencies based on constructor arguments.
+/// A constructor argument can have a default value with `#[default]` or `#[default(expr)]`. Or be another container
+/// to import dependencies from with `#[import(...)]`.
+pub struct ExtractEmbeddedDependency {
+    errors: Vec<Attribute>,
+}
 
 impl VisitorMut for ExtractEmbeddedDependency {
     fn visit_container_mut(&mut self, container: &mut Container) {
@@ -39,16 +44,56 @@ impl VisitorMut for ExtractEmbeddedDependency {
                     }
                     _ => None,
                 })
-                .map(Dependency::from)
+                .map(|pat_type| {
+                    let mut dependency = Dependency::from(pat_type);
+                    dependency.default = self.get_default(&pat_type.attrs);
+                    dependency.is_import = pat_type
+                        .attrs
+                        .iter()
+                        .any(|attr| attr.path().is_ident("import"));
+                    dependency
+                })
                 .map(RefCell::new)
                 .map(Rc::new),
         );
     }
 }
 
+impl ExtractEmbeddedDependency {
+    fn get_default(&mut self, attrs: &[Attribute]) -> Option<Expr> {
+        let attr = attrs.iter().find(|attr| attr.path().is_ident("default"))?;
+
+        match &attr.meta {
+            Meta::Path(_) => Some(parse_quote!(Default::default())),
+            Meta::List(_) => match attr.parse_args() {
+                Ok(expr) => Some(expr),
+                Err(_) => {
+                    self.errors.push(attr.clone());
+                    None
+                }
+            },
+            Meta::NameValue(_) => {
+                self.errors.push(attr.clone());
+                None
+            }
+        }
+    }
+}
+
 impl ErrorVisitorMut for ExtractEmbeddedDependency {
     fn new() -> Self {
-        Self {}
+        Self {
+            errors: Default::default(),
+        }
+    }
+
+    fn emit_errors(self) {
+        for attr in self.errors {
+            emit_error!(
+                attr, "Expected the default to be an expression";
+                hint = "Use `#[default]` to use `Default::default()` or `#[default(expr)]` to use `expr`"
+            );
+        }
     }
 }
 
@@ -64,7 +109,7 @@ mod tests {
     fn extract_input_dependency() {
         let mut container: Container = input::Container::from_item_impl(parse_quote!(
             impl DependencyContainer {
-                fn new(config: Config) -> Self {
+                fn new(config: Config, #[default] clock: Clock, #[default(8080)] port: u16, #[default = 1] invalid: u8, #[import(db: Db)] db_container: DbContainer) -> Self {
                     Self
                 }
 
@@ -78,7 +123,7 @@ mod tests {
         let mut visitor = ExtractEmbeddedDependency::new();
         container.apply_mut(&mut visitor);
 
-        assert_eq!(container.dependencies.len(), 2);
+        assert_eq!(container.dependencies.len(), 6);
         let service = container.dependencies[0].clone();
         let config = container.dependencies[1].clone();
 
@@ -90,5 +135,24 @@ mod tests {
         );
         assert_eq!(config.borrow().ty, parse_quote!(Config));
         assert_eq!(config.borrow().field_ty, parse_quote!(Config));
+        assert_eq!(config.borrow().default, None);
+        assert!(!config.borrow().is_import);
+
+        let defaults: Vec<_> = container.dependencies[2..]
+            .iter()
+            .map(|d| d.borrow().default.clone())
+            .collect();
+
+        assert_eq!(
+            defaults,
+            vec![
+                Some(parse_quote!(Default::default())),
+                Some(parse_quote!(8080)),
+                None,
+                None
+            ]
+        );
+        assert!(container.dependencies[5].borrow().is_import);
+        assert_eq!(visitor.errors, vec![parse_quote!(#[default = 1])]);
     }
 }
@@ -0,0 +1,184 @@
+use std::collections::HashSet;
+
+use proc_macro_error2::emit_error;
+use quote::ToTokens;
+use syn::{Ident, Type};
+
+use crate::processing::Dependency;
+
+use super::{ErrorVisitorMut, VisitorMut};
+
+/// Fix up the error type of any dependency which depends on a fallible dependency. Because if a child dependency can
+/// fail to be created, then the parent dependency can also fail and has to return a `Result` too.
+///
+/// A dependency which returns its own `Result` keeps its error type and the errors of any children will be converted
+/// into it. Otherwise, the error type of the fallible children is used. Which is only possible when all of them fail
+/// with the same error type.
+///
+/// Requires child dependencies to be linked first.
+pub struct ExtractErrorType {
+    visited: HashSet<Ident>,
+    errors: Vec<Error>,
+}
+
+#[cfg_attr(test, derive(Eq, PartialEq, Debug))]
+struct Error {
+    dependency: Ident,
+    error_types: Vec<Type>,
+}
+
+impl VisitorMut for ExtractErrorType {
+    fn visit_dependency_mut(&mut self, dependency: &mut Dependency) {
+        if !self.visited.insert(dependency.sig.ident.clone()) {
+            return;
+        }
+
+        // Children need to know their error types first
+        for child in dependency.dependencies.iter_mut() {
+            self.visit_dependency_mut(&mut child.inner.borrow_mut());
+        }
+
+        if dependency.is_fallible {
+            return;
+        }
+
+        let mut error_types: Vec<Type> = Vec::new();
+
+        for child in dependency.dependencies.iter() {
+            if let Some(error_ty) = &child.inner.borrow().error_ty {
+                if !error_types.contains(error_ty) {
+                    error_types.push(error_ty.clone());
+                }
+            }
+        }
+
+        match error_types.len() {
+            0 => {}
+            1 => dependency.error_ty = error_types.pop(),
+            _ => self.errors.push(Error {
+                dependency: dependency.sig.ident.clone(),
+                error_types,
+            }),
+        }
+    }
+}
+
+impl ErrorVisitorMut for ExtractErrorType {
+    fn new() -> Self {
+        Self {
+            visited: Default::default(),
+            errors: Default::default(),
+        }
+    }
+
+    fn emit_errors(self) {
+        for Error {
+            dependency,
+            error_types,
+        } in self.errors
+        {
+            let error_types = error_types
+                .iter()
+                .map(|ty| format!("`{}`", ty.to_token_stream()))
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            emit_error!(
+                dependency,
+                "The '{}' dependency depends on dependencies which can fail with different errors: {}",
+                dependency,
+                error_types;
+                hint = "Return a `Result<_, E>` from `{}` where `E` can be converted from each of these errors", dependency
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use proc_macro2::Span;
+    use syn::parse_quote;
+
+    use crate::{
+        input,
+        processing::{
+            self,
+            visitor::{ExtractResultType, LinkDependencies, VisitableMut},
+        },
+    };
+
+    use super::*;
+
+    #[test]
+    fn extract_error_type() {
+        let mut container: processing::Container = input::Container::from_item_impl(parse_quote!(
+            impl Container {
+                // Have this first to make sure child dependencies are considered correctly
+                fn service(&self, repository: Repository) -> Service {
+                    Service::new(repository)
+                }
+
+                fn repository(&self, db: &Db, config: &Config) -> Repository {
+                    Repository::new(db, config)
+                }
+
+                fn db(&self) -> Result<Db, DbError> {
+                    Db::connect()
+                }
+
+                fn config(&self) -> Config {
+                    Config
+                }
+
+                fn app(&self, service: Service, settings: Settings) -> Result<App, AppError> {
+                    Ok(App::new(service, settings))
+                }
+
+                fn settings(&self) -> Result<Settings, SettingsError> {
+                    Settings::read()
+                }
+
+                fn handler(&self, service: Service, settings: Settings) -> Handler {
+                    Handler::new(service, settings)
+                }
+            }
+        ))
+        .into();
+
+        // Test needs them to be linked and the results to be extracted
+        container.apply_mut(&mut ExtractResultType);
+        container.apply_mut(&mut LinkDependencies::new());
+
+        let mut visitor = ExtractErrorType::new();
+
+        container.apply_mut(&mut visitor);
+
+        let error_types: Vec<_> = container
+            .dependencies
+            .iter()
+            .map(|d| d.borrow().error_ty.clone())
+            .collect();
+
+        assert_eq!(
+            error_types,
+            vec![
+                Some(parse_quote!(DbError)),
+                Some(parse_quote!(DbError)),
+                Some(parse_quote!(DbError)),
+                None,
+                Some(parse_quote!(AppError)),
+                Some(parse_quote!(SettingsError)),
+                None,
+            ]
+        );
+
+        assert_eq!(
+            visitor.errors,
+            vec![Error {
+                dependency: Ident::new("handler", Span::call_site()),
+                error_types: vec![parse_quote!(DbError), parse_quote!(SettingsError)],
+            }]
+        );
+    }
+}
@@ -0,0 +1,118 @@
+use proc_macro_error2::emit_error;
+use syn::{Attribute, Ident};
+
+use crate::processing::Dependency;
+
+use super::{ErrorVisitorMut, VisitorMut};
+
+/// Get the group a dependency is part of from the `#[Group(handlers)]` attribute.
+///
+/// Needs to happen before lifetimes are extracted, since those treat any attribute with arguments as a type hint.
+pub struct ExtractGroup {
+    errors: Vec<Attribute>,
+}
+
+impl VisitorMut for ExtractGroup {
+    fn visit_dependency_mut(&mut self, dependency: &mut Dependency) {
+        dependency.attrs.retain(|attr| {
+            if !attr.path().is_ident("Group") {
+                return true;
+            }
+
+            match attr.parse_args::<Ident>() {
+                Ok(group) => dependency.group = Some(group),
+                Err(_) => self.errors.push(attr.clone()),
+            }
+
+            false
+        });
+    }
+}
+
+impl ErrorVisitorMut for ExtractGroup {
+    fn new() -> Self {
+        Self {
+            errors: Default::default(),
+        }
+    }
+
+    fn emit_errors(self) {
+        for attr in self.errors {
+            emit_error!(
+                attr, "Expected the name of the group to add the dependency to";
+                hint = "Try `#[Group(handlers)]`"
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use syn::parse_quote;
+
+    use crate::{
+        input,
+        processing::{self, visitor::VisitableMut},
+    };
+
+    use super::*;
+
+    #[test]
+    fn extract_group() {
+        let mut container: processing::Container = input::Container::from_item_impl(parse_quote!(
+            impl Container {
+                #[Group(handlers)]
+                fn ping(&self) -> Box<dyn Handler> {
+                    Box::new(Ping)
+                }
+
+                #[Singleton]
+                #[Group(handlers)]
+                fn echo(&self) -> Box<dyn Handler> {
+                    Box::new(Echo)
+                }
+
+                fn config(&self) -> Config {
+                    Config
+                }
+
+                #[Group("handlers")]
+                fn invalid(&self) -> Box<dyn Handler> {
+                    Box::new(Invalid)
+                }
+            }
+        ))
+        .into();
+
+        let mut visitor = ExtractGroup::new();
+
+        container.apply_mut(&mut visitor);
+
+        let groups: Vec<_> = container
+            .dependencies
+            .iter()
+            .map(|d| d.borrow().group.clone())
+            .collect();
+
+        assert_eq!(
+            groups,
+            vec![
+                Some(parse_quote!(handlers)),
+                Some(parse_quote!(handlers)),
+                None,
+                None,
+            ]
+        );
+
+        // The lifetime attributes should be kept for later visitors
+        assert!(container.dependencies[0].borrow().attrs.is_empty());
+        assert_eq!(
+            container.dependencies[1].borrow().attrs,
+            vec![parse_quote!(#[Singleton])]
+        );
+        assert!(container.dependencies[3].borrow().attrs.is_empty());
+
+        assert_eq!(visitor.errors, vec![parse_quote!(#[Group("handlers")])]);
+    }
+}
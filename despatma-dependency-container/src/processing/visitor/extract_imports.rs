@@ -0,0 +1,220 @@
+use std::{cell::RefCell, rc::Rc};
+
+use proc_macro_error2::emit_error;
+use quote::quote;
+use syn::{
+    parse::{Parse, ParseStream},
+    parse_quote,
+    punctuated::Punctuated,
+    Attribute, FnArg, Ident, ImplItemFn, Meta, Pat, Token, Type,
+};
+
+use crate::processing::{Container, Dependency};
+
+use super::{ErrorVisitorMut, VisitorMut};
+
+/// Adds a dependency for everything imported from another container with `#[import(config: Config)]` on a
+/// constructor argument. The added dependency asks the other container for it, so it can be requested like any
+/// dependency registered in this container.
+///
+/// A bare `#[import]` is reported, since a macro only sees the container it is on. So it cannot know what the other
+/// container provides.
+///
+/// Needs to happen before result types are extracted, so that a fallible import is also fallible in this container.
+pub struct ExtractImports {
+    errors: Vec<Error>,
+}
+
+#[cfg_attr(test, derive(Debug))]
+enum Error {
+    Invalid(syn::Error),
+    /// `#[import]` without the dependencies to import
+    Bare {
+        attr: Box<Attribute>,
+        container: Ident,
+    },
+}
+
+/// A dependency imported from another container
+struct Import {
+    asyncness: Option<Token![async]>,
+    ident: Ident,
+    ty: Type,
+}
+
+impl Parse for Import {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let asyncness = input.parse()?;
+        let ident = input.parse()?;
+        input.parse::<Token![:]>()?;
+        let ty = input.parse()?;
+
+        Ok(Self {
+            asyncness,
+            ident,
+            ty,
+        })
+    }
+}
+
+impl VisitorMut for ExtractImports {
+    fn visit_container_mut(&mut self, container: &mut Container) {
+        let Some(new_fn) = container
+            .dependencies
+            .iter()
+            .find(|dependency| dependency.borrow().sig.ident == "new")
+            .cloned()
+        else {
+            return;
+        };
+
+        for fn_arg in new_fn.borrow().sig.inputs.iter() {
+            let FnArg::Typed(pat_type) = fn_arg else {
+                continue;
+            };
+
+            let Pat::Ident(pat) = pat_type.pat.as_ref() else {
+                continue;
+            };
+
+            let Some(imports) = self.get_imports(&pat_type.attrs, &pat.ident) else {
+                continue;
+            };
+
+            let container_ident = &pat.ident;
+            let container_ty = &pat_type.ty;
+            let doc = format!(" Imported from the `{container_ident}` container");
+
+            container
+                .dependencies
+                .extend(imports.into_iter().map(|import| {
+                    let Import {
+                        asyncness,
+                        ident,
+                        ty,
+                    } = import;
+                    let await_token = asyncness.map(|_| quote! { .await });
+
+                    let impl_item_fn: ImplItemFn = parse_quote! {
+                        #[doc = #doc]
+                        #asyncness fn #ident(&self, #container_ident: &#container_ty) -> #ty {
+                            #container_ident.#ident()#await_token
+                        }
+                    };
+
+                    let mut dependency = Dependency::from(impl_item_fn);
+                    dependency.is_import = true;
+
+                    Rc::new(RefCell::new(dependency))
+                }));
+        }
+    }
+}
+
+impl ExtractImports {
+    /// Get the dependencies listed in the `#[import(...)]` attribute, if any
+    fn get_imports(&mut self, attrs: &[Attribute], container: &Ident) -> Option<Vec<Import>> {
+        let attr = attrs.iter().find(|attr| attr.path().is_ident("import"))?;
+
+        if let Meta::Path(_) = attr.meta {
+            self.errors.push(Error::Bare {
+                attr: Box::new(attr.clone()),
+                container: container.clone(),
+            });
+            return None;
+        }
+
+        match attr.parse_args_with(Punctuated::<Import, Token![,]>::parse_terminated) {
+            Ok(imports) => Some(imports.into_iter().collect()),
+            Err(error) => {
+                self.errors.push(Error::Invalid(error));
+                None
+            }
+        }
+    }
+}
+
+impl ErrorVisitorMut for ExtractImports {
+    fn new() -> Self {
+        Self {
+            errors: Default::default(),
+        }
+    }
+
+    fn emit_errors(self) {
+        for error in self.errors {
+            match error {
+                Error::Invalid(error) => emit_error!(
+                    error.span(), "Expected the dependencies to import as `name: Type`";
+                    hint = "Try `#[import(config: Config, async db: Db)]`"
+                ),
+                Error::Bare { attr, container } => emit_error!(
+                    attr,
+                    "The dependencies to import from `{}` have to be listed, since the macro cannot see what another container provides",
+                    container;
+                    hint = "Try `#[import(config: Config, async db: Db)]`"
+                ),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use crate::{input, processing::visitor::VisitableMut};
+
+    use super::*;
+
+    #[test]
+    fn extract_imports() {
+        let mut container: Container = input::Container::from_item_impl(parse_quote!(
+            impl DependencyContainer {
+                fn new(
+                    #[import(config: Config, async db: Result<&Db, DbError>)] config_container: ConfigContainer,
+                    #[import(config)] invalid: InvalidContainer,
+                    #[import] bare: BareContainer,
+                    other: Other,
+                ) -> Self {
+                    Self
+                }
+
+                fn service(&self, config: Config) -> Service {
+                    Service::new(config)
+                }
+            }
+        ))
+        .into();
+
+        let mut visitor = ExtractImports::new();
+        container.apply_mut(&mut visitor);
+
+        let config: ImplItemFn = parse_quote! {
+            #[doc = " Imported from the `config_container` container"]
+            fn config(&self, config_container: &ConfigContainer) -> Config {
+                config_container.config()
+            }
+        };
+        let db: ImplItemFn = parse_quote! {
+            #[doc = " Imported from the `config_container` container"]
+            async fn db(&self, config_container: &ConfigContainer) -> Result<&Db, DbError> {
+                config_container.db().await
+            }
+        };
+
+        let imported = |impl_item_fn: ImplItemFn| {
+            let mut dependency = Dependency::from(impl_item_fn);
+            dependency.is_import = true;
+            dependency
+        };
+
+        assert_eq!(container.dependencies.len(), 4);
+        assert_eq!(*container.dependencies[2].borrow(), imported(config));
+        assert_eq!(*container.dependencies[3].borrow(), imported(db));
+        assert!(matches!(
+            &visitor.errors[..],
+            [Error::Invalid(_), Error::Bare { container, .. }] if container == "bare"
+        ));
+    }
+}
@@ -1,26 +1,52 @@
-use syn::{parse_quote, Meta};
+use proc_macro_error2::emit_error;
+use syn::{
+    parse::{ParseStream, Parser},
+    Ident, Meta, Token, Type,
+};
 
-use crate::processing::{Dependency, Lifetime};
+use crate::processing::{Container, Dependency, Lifetime};
 
-use super::{ErrorVisitorMut, VisitorMut};
+use super::{link_dependencies::get_best_match, visit_container_mut, ErrorVisitorMut, VisitorMut};
 
-/// Get the lifetime of a dependency from the function attributes
-pub struct ExtractLifetime;
+/// Get the lifetime of a dependency from the function attributes.
+/// A singleton can also be marked to be created eagerly with `#[Singleton(eager)]`. And a scoped dependency can be
+/// part of one of the named scopes of the container with `#[Scoped(request)]`. A transient with state meant for a
+/// single use is marked with `#[Transient(stateful)]`. Singleton and scoped dependencies
+/// marked with `#[Singleton(shared)]`, or all of them when the container has the `shared` option, hand out `Arc`
+/// handles.
+///
+/// When the container declares named scopes, any other lowercase name in the attribute is reported as an unknown
+/// scope rather than being taken for a type hint.
+pub struct ExtractLifetime {
+    scopes: Vec<Ident>,
+    shared: bool,
+    errors: Vec<Error>,
+}
+
+#[cfg_attr(test, derive(Debug))]
+enum Error {
+    Invalid(syn::Error),
+    EagerNotSingleton(Ident),
+    StatefulNotTransient(Ident),
+    ScopeNotScoped(Ident),
+    SharedTransient(Ident),
+    UnknownScope(Ident),
+}
 
 impl VisitorMut for ExtractLifetime {
+    fn visit_container_mut(&mut self, container: &mut Container) {
+        self.scopes = container.options.scopes.clone();
+        self.shared = container.options.shared;
+
+        visit_container_mut(self, container);
+    }
+
     fn visit_dependency_mut(&mut self, dependency: &mut Dependency) {
         // Remove all lifetime attributes
         dependency.attrs.retain(|attr| {
             let path = match &attr.meta {
                 Meta::Path(path) => path,
-                Meta::List(meta_list) => {
-                    let custom_type = &meta_list.tokens;
-                    let custom_type = parse_quote!(#custom_type);
-
-                    dependency.field_ty = custom_type;
-
-                    &meta_list.path
-                }
+                Meta::List(meta_list) => &meta_list.path,
                 Meta::NameValue(_) => return true,
             };
 
@@ -28,29 +54,175 @@ impl VisitorMut for ExtractLifetime {
                 return true;
             }
 
-            match path.segments[0].ident.to_string().as_str() {
-                "Scoped" => {
-                    dependency.lifetime = Lifetime::Scoped(path.segments[0].ident.span());
-                    false
-                }
+            let ident = &path.segments[0].ident;
+            let span = ident.span();
 
-                "Singleton" => {
-                    dependency.lifetime = Lifetime::Singleton(path.segments[0].ident.span());
-                    false
-                }
-                "Transient" => {
-                    dependency.lifetime = Lifetime::Transient(Some(path.segments[0].ident.span()));
-                    false
+            match ident.to_string().as_str() {
+                "Scoped" => dependency.lifetime = Lifetime::Scoped(span),
+                "Singleton" => dependency.lifetime = Lifetime::Singleton(span),
+                "Transient" => dependency.lifetime = Lifetime::Transient(Some(span)),
+                _ => return true,
+            }
+
+            let Meta::List(meta_list) = &attr.meta else {
+                return false;
+            };
+
+            let parser = |input: ParseStream| parse_arguments(input, &self.scopes);
+
+            match parser.parse2(meta_list.tokens.clone()) {
+                Ok((flags, custom_type)) => {
+                    if let Some(custom_type) = custom_type {
+                        dependency.field_ty = custom_type;
+                    }
+
+                    for flag in flags {
+                        match flag {
+                            eager if eager == "eager" && ident != "Singleton" => {
+                                self.errors.push(Error::EagerNotSingleton(eager))
+                            }
+                            eager if eager == "eager" => dependency.is_eager = true,
+                            stateful if stateful == "stateful" && ident != "Transient" => {
+                                self.errors.push(Error::StatefulNotTransient(stateful))
+                            }
+                            stateful if stateful == "stateful" => dependency.is_stateful = true,
+                            shared if shared == "shared" && ident == "Transient" => {
+                                self.errors.push(Error::SharedTransient(shared))
+                            }
+                            shared if shared == "shared" => dependency.is_shared = true,
+                            scope if !self.scopes.contains(&scope) => {
+                                self.errors.push(Error::UnknownScope(scope))
+                            }
+                            scope if ident != "Scoped" => {
+                                self.errors.push(Error::ScopeNotScoped(scope))
+                            }
+                            scope => dependency.scope = Some(scope),
+                        }
+                    }
                 }
-                _ => true,
+                Err(error) => self.errors.push(Error::Invalid(error)),
             }
+
+            false
         });
+
+        if self.shared
+            && matches!(
+                dependency.lifetime,
+                Lifetime::Singleton(_) | Lifetime::Scoped(_)
+            )
+        {
+            dependency.is_shared = true;
+        }
+    }
+}
+
+/// Split the arguments of a lifetime attribute into the `eager`, `shared` and `stateful` flags or the name of the
+/// scope, and the type hint. Ie `#[Singleton(eager)]`, `#[Singleton(Sqlite)]`, `#[Singleton(eager, shared, Sqlite)]`,
+/// `#[Scoped(request, Sqlite)]` or `#[Transient(stateful)]`.
+fn parse_arguments(
+    input: ParseStream,
+    scopes: &[Ident],
+) -> syn::Result<(Vec<Ident>, Option<Type>)> {
+    let mut flags = Vec::new();
+
+    loop {
+        let fork = input.fork();
+        let is_flag = fork.parse::<Ident>().is_ok_and(|ident| {
+            ident == "eager"
+                || ident == "shared"
+                || ident == "stateful"
+                || scopes.contains(&ident)
+                || (!scopes.is_empty() && is_scope_name(&ident))
+        }) && (fork.is_empty() || fork.peek(Token![,]));
+
+        if !is_flag {
+            break;
+        }
+
+        flags.push(input.parse()?);
+
+        if !input.is_empty() {
+            input.parse::<Token![,]>()?;
+        }
     }
+
+    let custom_type = if input.is_empty() {
+        None
+    } else {
+        Some(input.parse()?)
+    };
+
+    Ok((flags, custom_type))
+}
+
+/// Whether the identifier looks like the name of a scope rather than a type. Ie `tenant` and not `Tenant` or `u32`
+fn is_scope_name(ident: &Ident) -> bool {
+    const PRIMITIVES: &[&str] = &[
+        "bool", "char", "str", "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32",
+        "i64", "i128", "isize", "f32", "f64",
+    ];
+
+    let name = ident.to_string();
+
+    name.starts_with(|c: char| c.is_ascii_lowercase() || c == '_')
+        && !PRIMITIVES.contains(&name.as_str())
 }
 
 impl ErrorVisitorMut for ExtractLifetime {
     fn new() -> Self {
-        Self
+        Self {
+            scopes: Default::default(),
+            shared: false,
+            errors: Default::default(),
+        }
+    }
+
+    fn emit_errors(self) {
+        let Self { scopes, errors, .. } = self;
+
+        for error in errors {
+            match error {
+                Error::Invalid(error) => emit_error!(
+                    error.span(), "Expected a type hint: {}", error;
+                    hint = "Try `#[Singleton(Sqlite)]` or `#[Singleton(eager, Sqlite)]`"
+                ),
+                Error::EagerNotSingleton(eager) => emit_error!(
+                    eager, "Only Singleton dependencies can be created eagerly";
+                    hint = "Change the lifetime to `#[Singleton(eager)]`"
+                ),
+                Error::StatefulNotTransient(stateful) => emit_error!(
+                    stateful, "Only Transient dependencies can be stateful";
+                    hint = "Change the lifetime to `#[Transient(stateful)]`"
+                ),
+                Error::ScopeNotScoped(scope) => emit_error!(
+                    scope, "Only Scoped dependencies can be part of a named scope";
+                    hint = "Change the lifetime to `#[Scoped({})]`", scope
+                ),
+                Error::SharedTransient(shared) => emit_error!(
+                    shared, "Only Singleton and Scoped dependencies can be shared";
+                    hint = "Change the lifetime to `#[Singleton(shared)]`"
+                ),
+                Error::UnknownScope(scope) => {
+                    let declared = scopes
+                        .iter()
+                        .map(|scope| format!("`{scope}`"))
+                        .collect::<Vec<_>>()
+                        .join(", ");
+
+                    match get_best_match(&scopes.iter().collect::<Vec<_>>(), &scope.to_string()) {
+                        Some(best_match) => emit_error!(
+                            scope, "`{}` is not a declared scope. The declared scopes are {}", scope, declared;
+                            hint = "Did you mean `{}`?", best_match
+                        ),
+                        None => emit_error!(
+                            scope, "`{}` is not a declared scope. The declared scopes are {}", scope, declared;
+                            hint = "Add it to the `scopes(...)` option of the container"
+                        ),
+                    }
+                }
+            }
+        }
     }
 }
 
@@ -158,7 +330,7 @@ mod tests {
             parse_quote!(impl ScopedTrait)
         );
 
-        container.apply_mut(&mut ExtractLifetime);
+        container.apply_mut(&mut ExtractLifetime::new());
 
         assert_eq!(container.dependencies[0].borrow().attrs.len(), 0);
         assert_eq!(
@@ -215,4 +387,285 @@ mod tests {
             parse_quote!(ScopedStruct)
         );
     }
+
+    #[test]
+    fn extract_eager() {
+        let mut container: processing::Container = input::Container::from_item_impl(parse_quote!(
+            impl Container {
+                #[Singleton(eager)]
+                fn eager(&self) -> Eager {
+                    Eager
+                }
+
+                #[Singleton(eager, EagerStruct)]
+                fn eager_impl_trait(&self) -> impl EagerTrait {
+                    EagerStruct
+                }
+
+                #[Singleton]
+                fn lazy(&self) -> Lazy {
+                    Lazy
+                }
+
+                #[Scoped(eager)]
+                fn scoped(&self) -> Scoped {
+                    Scoped
+                }
+            }
+        ))
+        .into();
+
+        let mut visitor = ExtractLifetime::new();
+
+        container.apply_mut(&mut visitor);
+
+        let eager: Vec<_> = container
+            .dependencies
+            .iter()
+            .map(|d| d.borrow().is_eager)
+            .collect();
+
+        assert_eq!(eager, vec![true, true, false, false]);
+        assert_eq!(
+            container.dependencies[0].borrow().field_ty,
+            parse_quote!(Eager)
+        );
+        assert_eq!(
+            container.dependencies[1].borrow().field_ty,
+            parse_quote!(EagerStruct)
+        );
+        assert_eq!(
+            container.dependencies[3].borrow().field_ty,
+            parse_quote!(Scoped)
+        );
+
+        assert!(matches!(
+            &visitor.errors[..],
+            [Error::EagerNotSingleton(eager)] if eager == "eager"
+        ));
+    }
+
+    #[test]
+    fn extract_named_scope() {
+        let mut container: processing::Container = input::Container::from_item_impl(parse_quote!(
+            impl Container {
+                #[Scoped(tenant)]
+                fn tenant(&self) -> Tenant {
+                    Tenant
+                }
+
+                #[Scoped(request, RequestStruct)]
+                fn request(&self) -> impl Request {
+                    RequestStruct
+                }
+
+                #[Scoped(Session)]
+                fn session(&self) -> impl SessionTrait {
+                    Session
+                }
+
+                #[Singleton(tenant)]
+                fn config(&self) -> Config {
+                    Config
+                }
+
+                #[Scoped(tenat)]
+                fn billing(&self) -> Billing {
+                    Billing
+                }
+
+                #[Scoped(u32)]
+                fn count(&self) -> impl Count {
+                    42
+                }
+            }
+        ))
+        .into();
+
+        container.options.scopes = vec![parse_quote!(tenant), parse_quote!(request)];
+
+        let mut visitor = ExtractLifetime::new();
+
+        container.apply_mut(&mut visitor);
+
+        let scopes: Vec<_> = container
+            .dependencies
+            .iter()
+            .map(|d| d.borrow().scope.clone())
+            .collect();
+
+        assert_eq!(
+            scopes,
+            vec![
+                Some(parse_quote!(tenant)),
+                Some(parse_quote!(request)),
+                None,
+                None,
+                None,
+                None
+            ]
+        );
+        assert_eq!(
+            container.dependencies[5].borrow().field_ty,
+            parse_quote!(u32)
+        );
+        assert_eq!(
+            container.dependencies[1].borrow().field_ty,
+            parse_quote!(RequestStruct)
+        );
+        assert_eq!(
+            container.dependencies[2].borrow().field_ty,
+            parse_quote!(Session)
+        );
+
+        assert!(matches!(
+            &visitor.errors[..],
+            [Error::ScopeNotScoped(scope), Error::UnknownScope(unknown)]
+                if scope == "tenant" && unknown == "tenat"
+        ));
+    }
+
+    #[test]
+    fn extract_shared() {
+        let mut container: processing::Container = input::Container::from_item_impl(parse_quote!(
+            impl Container {
+                #[Singleton(shared)]
+                fn shared(&self) -> Shared {
+                    Shared
+                }
+
+                #[Singleton(eager, shared, SharedStruct)]
+                fn shared_impl_trait(&self) -> impl SharedTrait {
+                    SharedStruct
+                }
+
+                #[Scoped(request, shared)]
+                fn request(&self) -> Request {
+                    Request
+                }
+
+                #[Singleton]
+                fn borrowed(&self) -> Borrowed {
+                    Borrowed
+                }
+
+                #[Transient(shared)]
+                fn transient(&self) -> Transient {
+                    Transient
+                }
+            }
+        ))
+        .into();
+
+        container.options.scopes = vec![parse_quote!(request)];
+
+        let mut visitor = ExtractLifetime::new();
+
+        container.apply_mut(&mut visitor);
+
+        let shared: Vec<_> = container
+            .dependencies
+            .iter()
+            .map(|d| d.borrow().is_shared)
+            .collect();
+
+        assert_eq!(shared, vec![true, true, true, false, false]);
+        assert!(container.dependencies[1].borrow().is_eager);
+        assert_eq!(
+            container.dependencies[1].borrow().field_ty,
+            parse_quote!(SharedStruct)
+        );
+        assert_eq!(
+            container.dependencies[2].borrow().scope,
+            Some(parse_quote!(request))
+        );
+
+        assert!(matches!(
+            &visitor.errors[..],
+            [Error::SharedTransient(shared)] if shared == "shared"
+        ));
+    }
+
+    #[test]
+    fn extract_stateful() {
+        let mut container: processing::Container = input::Container::from_item_impl(parse_quote!(
+            impl Container {
+                #[Transient(stateful)]
+                fn transaction(&self) -> Transaction {
+                    Transaction
+                }
+
+                #[Transient(stateful, Buffered)]
+                fn writer(&self) -> impl Writer {
+                    Buffered
+                }
+
+                fn stateless(&self) -> Stateless {
+                    Stateless
+                }
+
+                #[Singleton(stateful)]
+                fn singleton(&self) -> Singleton {
+                    Singleton
+                }
+            }
+        ))
+        .into();
+
+        let mut visitor = ExtractLifetime::new();
+
+        container.apply_mut(&mut visitor);
+
+        let stateful: Vec<_> = container
+            .dependencies
+            .iter()
+            .map(|d| d.borrow().is_stateful)
+            .collect();
+
+        assert_eq!(stateful, vec![true, true, false, false]);
+        assert_eq!(
+            container.dependencies[1].borrow().field_ty,
+            parse_quote!(Buffered)
+        );
+
+        assert!(matches!(
+            &visitor.errors[..],
+            [Error::StatefulNotTransient(stateful)] if stateful == "stateful"
+        ));
+    }
+
+    #[test]
+    fn extract_shared_container() {
+        let mut container: processing::Container = input::Container::from_item_impl(parse_quote!(
+            impl Container {
+                #[Singleton]
+                fn singleton(&self) -> Singleton {
+                    Singleton
+                }
+
+                #[Scoped]
+                fn scoped(&self) -> Scoped {
+                    Scoped
+                }
+
+                #[Transient]
+                fn transient(&self) -> Transient {
+                    Transient
+                }
+            }
+        ))
+        .into();
+
+        container.options.shared = true;
+
+        container.apply_mut(&mut ExtractLifetime::new());
+
+        let shared: Vec<_> = container
+            .dependencies
+            .iter()
+            .map(|d| d.borrow().is_shared)
+            .collect();
+
+        assert_eq!(shared, vec![true, true, false]);
+    }
 }
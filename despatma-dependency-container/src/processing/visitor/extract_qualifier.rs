@@ -0,0 +1,126 @@
+use proc_macro_error2::emit_error;
+use syn::{Ident, LitStr};
+
+use crate::processing::Dependency;
+
+use super::{ErrorVisitorMut, VisitorMut};
+
+/// Get the qualifier of a dependency from the `#[Named("...")]` attribute.
+/// The qualifier is an extra name other dependencies can use to request this dependency.
+///
+/// Needs to happen before lifetimes are extracted, since those treat any attribute with arguments as a type hint.
+pub struct ExtractQualifier {
+    errors: Vec<Error>,
+}
+
+#[cfg_attr(test, derive(Debug))]
+struct Error {
+    attr: syn::Attribute,
+    message: String,
+}
+
+impl VisitorMut for ExtractQualifier {
+    fn visit_dependency_mut(&mut self, dependency: &mut Dependency) {
+        dependency.attrs.retain(|attr| {
+            if !attr.path().is_ident("Named") {
+                return true;
+            }
+
+            match attr
+                .parse_args::<LitStr>()
+                .and_then(|name| name.parse::<Ident>())
+            {
+                Ok(qualifier) => dependency.qualifier = Some(qualifier),
+                Err(error) => self.errors.push(Error {
+                    attr: attr.clone(),
+                    message: error.to_string(),
+                }),
+            }
+
+            false
+        });
+    }
+}
+
+impl ErrorVisitorMut for ExtractQualifier {
+    fn new() -> Self {
+        Self {
+            errors: Default::default(),
+        }
+    }
+
+    fn emit_errors(self) {
+        for Error { attr, message } in self.errors {
+            emit_error!(
+                attr, "The qualifier should be a valid identifier: {}", message;
+                hint = "Try `#[Named(\"replica\")]`"
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use syn::parse_quote;
+
+    use crate::{
+        input,
+        processing::{self, visitor::VisitableMut},
+    };
+
+    use super::*;
+
+    #[test]
+    fn extract_qualifier() {
+        let mut container: processing::Container = input::Container::from_item_impl(parse_quote!(
+            impl Container {
+                #[Singleton]
+                #[Named("replica")]
+                fn replica_db(&self) -> Db {
+                    Db::new("replica")
+                }
+
+                fn db(&self) -> Db {
+                    Db::new("primary")
+                }
+
+                #[Named("not valid")]
+                fn invalid(&self) -> Invalid {
+                    Invalid
+                }
+
+                #[Named(unquoted)]
+                fn unquoted(&self) -> Unquoted {
+                    Unquoted
+                }
+            }
+        ))
+        .into();
+
+        let mut visitor = ExtractQualifier::new();
+
+        container.apply_mut(&mut visitor);
+
+        let qualifiers: Vec<_> = container
+            .dependencies
+            .iter()
+            .map(|d| d.borrow().qualifier.clone())
+            .collect();
+
+        assert_eq!(
+            qualifiers,
+            vec![Some(parse_quote!(replica)), None, None, None]
+        );
+
+        // The lifetime attribute should be kept for later visitors
+        assert_eq!(
+            container.dependencies[0].borrow().attrs,
+            vec![parse_quote!(#[Singleton])]
+        );
+        assert!(container.dependencies[2].borrow().attrs.is_empty());
+        assert!(container.dependencies[3].borrow().attrs.is_empty());
+
+        assert_eq!(visitor.errors.len(), 2);
+    }
+}
@@ -0,0 +1,112 @@
+use syn::{GenericArgument, PathArguments, Type};
+
+use crate::processing::Dependency;
+
+use super::{ErrorVisitorMut, VisitorMut};
+
+/// Extracts the success and error types of any dependency returning a `Result<T, E>`.
+/// The container should manage the `T` and only hand out `E` when the dependency failed to be created.
+///
+/// Needs to happen before type hints (lifetimes) are extracted.
+pub struct ExtractResultType;
+
+impl VisitorMut for ExtractResultType {
+    fn visit_dependency_mut(&mut self, dependency: &mut Dependency) {
+        let Type::Path(path) = &dependency.ty else {
+            return;
+        };
+
+        let Some(last_segment) = path.path.segments.last() else {
+            return;
+        };
+
+        if last_segment.ident != "Result" {
+            return;
+        }
+
+        let PathArguments::AngleBracketed(generics) = &last_segment.arguments else {
+            return;
+        };
+
+        // Only a fully spelled out `Result<T, E>` says what the error type is
+        let [GenericArgument::Type(ty), GenericArgument::Type(error_ty)] =
+            generics.args.iter().collect::<Vec<_>>()[..]
+        else {
+            return;
+        };
+
+        dependency.is_fallible = true;
+        dependency.error_ty = Some(error_ty.clone());
+        dependency.field_ty = ty.clone();
+        dependency.ty = ty.clone();
+    }
+}
+
+impl ErrorVisitorMut for ExtractResultType {
+    fn new() -> Self {
+        Self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use syn::parse_quote;
+
+    use crate::{
+        input,
+        processing::{self, visitor::VisitableMut},
+    };
+
+    use super::*;
+
+    #[test]
+    fn extract_result_type() {
+        let mut container: processing::Container = input::Container::from_item_impl(parse_quote!(
+            impl Container {
+                #[Singleton]
+                fn db(&self) -> Result<Db, DbError> {
+                    Db::connect()
+                }
+
+                fn dal(&self) -> std::result::Result<Box<dyn DAL>, std::io::Error> {
+                    Ok(Box::new(Sqlite))
+                }
+
+                fn config(&self) -> std::io::Result<Config> {
+                    Config::read()
+                }
+
+                fn service(&self) -> Service {
+                    Service
+                }
+            }
+        ))
+        .into();
+
+        container.apply_mut(&mut ExtractResultType);
+
+        let db = container.dependencies[0].borrow();
+        assert!(db.is_fallible);
+        assert_eq!(db.ty, parse_quote!(Db));
+        assert_eq!(db.field_ty, parse_quote!(Db));
+        assert_eq!(db.error_ty, Some(parse_quote!(DbError)));
+
+        let dal = container.dependencies[1].borrow();
+        assert!(dal.is_fallible);
+        assert_eq!(dal.ty, parse_quote!(Box<dyn DAL>));
+        assert_eq!(dal.field_ty, parse_quote!(Box<dyn DAL>));
+        assert_eq!(dal.error_ty, Some(parse_quote!(std::io::Error)));
+
+        // The error type is not known for type aliases
+        let config = container.dependencies[2].borrow();
+        assert!(!config.is_fallible);
+        assert_eq!(config.ty, parse_quote!(std::io::Result<Config>));
+        assert_eq!(config.error_ty, None);
+
+        let service = container.dependencies[3].borrow();
+        assert!(!service.is_fallible);
+        assert_eq!(service.ty, parse_quote!(Service));
+        assert_eq!(service.error_ty, None);
+    }
+}
@@ -44,8 +44,8 @@ impl VisitorMut for ImplTraitButRegisteredConcrete {
             let Some(child_dependency) = dependency
                 .dependencies
                 .iter()
+                .find(|d| d.ident == child_ident)
                 .map(|d| &d.inner)
-                .find(|d| d.borrow().sig.ident == child_ident)
             else {
                 continue;
             };
@@ -139,7 +139,7 @@ mod tests {
         ))
         .into();
 
-        container.apply_mut(&mut ExtractLifetime);
+        container.apply_mut(&mut ExtractLifetime::new());
 
         let mut visitor = ImplTraitFields::new();
         container.apply_mut(&mut visitor);
@@ -1,19 +1,53 @@
-use std::{cell::RefCell, collections::HashMap, rc::Rc};
+use std::{
+    cell::RefCell,
+    collections::{hash_map::Entry, HashMap},
+    rc::Rc,
+};
 
 use proc_macro_error2::emit_error;
+use quote::ToTokens;
 use strsim::levenshtein;
-use syn::{FnArg, Ident, Pat};
+use syn::{Attribute, FnArg, GenericArgument, Ident, Pat, Path, PathArguments, Type};
 
-use crate::processing::{ChildDependency, Container, Dependency};
+use crate::processing::{ChildDependency, Container, DeferredDependency, Dependency, Handle};
 
-use super::{visit_container_mut, ErrorVisitorMut, VisitorMut};
+use super::{extract_box_type::get_pointer, visit_container_mut, ErrorVisitorMut, VisitorMut};
 
 /// Extracts any child dependencies correctly from the registered dependencies and report on any requested dependencies that are not registered.
 /// So if `a` has a dependency on `b`, this visitor will check if `b` has been registered in the container.
 /// If not, it will emit an error.
+///
+/// A dependency can be requested by its function name or by its qualifier (`#[Named("...")]`). The argument name is
+/// used by default, unless the argument asks for another dependency with `#[from(name)]`.
+///
+/// Arguments marked with `#[inject]` are instead matched to the only dependency returning the requested type. The
+/// `by_type` option does the same for any argument not matching the name of a dependency. Types are compared on the
+/// declared return type and not the type hint. The hint of an `impl Trait` does not make its concrete type requestable,
+/// as `ImplTraitButRegisteredConcrete` reports.
+///
+/// An argument of type `Lazy<T>` or `Provider<T>` is linked as a deferred dependency instead, since it is only created
+/// when the handle is used. So is any dependency taking runtime arguments, which is requested as a function to create
+/// it with. Arguments marked with `#[arg]` are runtime arguments themselves and are skipped. The handles are types of
+/// `despatma`, so they are reported when the macro is not called through it.
+///
+/// A dependency with `#[cfg]` alternatives is linked to the first of them, and the others are kept alongside it. So
+/// that the visitors following the links can check every alternative which could be compiled in.
+///
+/// Needs qualifiers and runtime arguments to be extracted first.
 pub struct LinkDependencies {
     dependencies: HashMap<Ident, Rc<RefCell<Dependency>>>,
+    alternatives: HashMap<Ident, Vec<Rc<RefCell<Dependency>>>>,
+    types: Vec<(Ident, Type, Rc<RefCell<Dependency>>)>,
+    pointers: Vec<Path>,
+    by_type: bool,
+    despatma: bool,
     errors: Vec<Error>,
+    missing_types: Vec<MissingType>,
+    ambiguities: Vec<Ambiguity>,
+    duplicates: Vec<Duplicate>,
+    handled_factories: Vec<HandledFactory>,
+    standalone_handles: Vec<Type>,
+    invalid_attributes: Vec<syn::Error>,
 }
 
 #[cfg_attr(test, derive(Eq, PartialEq, Debug))]
@@ -22,18 +56,96 @@ struct Error {
     best_match: Option<Ident>,
 }
 
+/// No dependency returns the type requested with `#[inject]`
+#[cfg_attr(test, derive(Eq, PartialEq, Debug))]
+struct MissingType {
+    requested: Ident,
+    ty: Type,
+}
+
+/// More than one dependency returns the requested type
+#[cfg_attr(test, derive(Eq, PartialEq, Debug))]
+struct Ambiguity {
+    ty: Type,
+    candidates: Vec<Ident>,
+}
+
+/// A qualifier which is already the name of another dependency
+#[cfg_attr(test, derive(Eq, PartialEq, Debug))]
+struct Duplicate {
+    qualifier: Ident,
+    existing: Ident,
+}
+
+/// A dependency taking runtime arguments which is requested through a `Lazy` or `Provider` handle
+#[cfg_attr(test, derive(Eq, PartialEq, Debug))]
+struct HandledFactory {
+    argument: Ident,
+    dependency: Ident,
+}
+
 impl VisitorMut for LinkDependencies {
     fn visit_container_mut(&mut self, container: &mut Container) {
-        self.dependencies = HashMap::from_iter(container.dependencies.iter().map(|d| {
-            let d_ref = d.borrow();
-            (d_ref.sig.ident.clone(), d.clone())
-        }));
+        self.by_type = container.options.by_type;
+        self.despatma = container.options.despatma;
+        self.pointers = container.options.pointers.clone();
+        // The `#[cfg]` alternatives of a dependency are all called the same way. So the first is linked to and the
+        // others are kept alongside it for the visitors following the links
+        for dependency in container.dependencies.iter() {
+            match self
+                .dependencies
+                .entry(dependency.borrow().sig.ident.clone())
+            {
+                Entry::Occupied(first) => self
+                    .alternatives
+                    .entry(first.key().clone())
+                    .or_default()
+                    .push(dependency.clone()),
+                Entry::Vacant(entry) => {
+                    entry.insert(dependency.clone());
+                }
+            }
+        }
+        // Providers for a profile are only requested by the dependency picking between them
+        self.types = container
+            .dependencies
+            .iter()
+            .filter(|d| d.borrow().profile.is_none())
+            .map(|d| {
+                let d_ref = d.borrow();
+                (
+                    d_ref.sig.ident.clone(),
+                    normalize_type(&d_ref.ty, &self.pointers),
+                    d.clone(),
+                )
+            })
+            .collect();
+
+        for dependency in container.dependencies.iter() {
+            let Some(qualifier) = dependency.borrow().qualifier.clone() else {
+                continue;
+            };
+
+            if let Some(existing) = self.dependencies.get(&qualifier) {
+                if existing.borrow().sig.ident == dependency.borrow().sig.ident {
+                    continue;
+                }
+
+                self.duplicates.push(Duplicate {
+                    qualifier,
+                    existing: existing.borrow().sig.ident.clone(),
+                });
+                continue;
+            }
+
+            self.dependencies.insert(qualifier, dependency.clone());
+        }
 
         visit_container_mut(self, container)
     }
 
     fn visit_dependency_mut(&mut self, dependency: &mut Dependency) {
-        let dependencies = dependency
+        let linked: Vec<_> = dependency
             .sig
             .inputs
             .iter()
@@ -46,30 +158,173 @@ impl VisitorMut for LinkDependencies {
                     return None;
                 };
 
-                let Some(child_dependency) =
-                    self.dependencies.get(&pat.ident).map(|d| ChildDependency {
-                        inner: d.clone(),
-                        ty: pat_type.ty.as_ref().clone(),
-                    })
-                else {
-                    let best_match = get_best_dependency_match(
-                        &self.dependencies.keys().collect::<Vec<_>>(),
-                        &pat.ident.to_string(),
-                    );
-
-                    self.errors.push(Error {
-                        requested: pat.ident.clone(),
-                        best_match,
-                    });
-
+                if pat_type
+                    .attrs
+                    .iter()
+                    .any(|attr| attr.path().is_ident("arg"))
+                {
                     return None;
+                }
+
+                let (handle, ty) = match get_handle(pat_type.ty.as_ref()) {
+                    Some(_) if !self.despatma => {
+                        self.standalone_handles.push(pat_type.ty.as_ref().clone());
+                        return None;
+                    }
+                    Some((handle, ty)) => (Some(handle), ty),
+                    None => (None, pat_type.ty.as_ref()),
+                };
+
+                let is_inject = pat_type
+                    .attrs
+                    .iter()
+                    .any(|attr| attr.path().is_ident("inject"));
+
+                let (requested, is_from) = match get_from_attribute(&pat_type.attrs) {
+                    Some(Ok(requested)) => (requested, true),
+                    Some(Err(error)) => {
+                        self.invalid_attributes.push(error);
+                        return None;
+                    }
+                    None => (pat.ident.clone(), false),
+                };
+
+                let by_name = if is_inject && !is_from {
+                    None
+                } else {
+                    self.dependencies.get(&requested).cloned()
                 };
 
-                Some(child_dependency)
+                let inner = match by_name {
+                    Some(inner) => inner,
+                    None if is_inject || (self.by_type && !is_from) => {
+                        let mut candidates =
+                            self.get_dependencies_of_type(ty, &dependency.sig.ident);
+
+                        if candidates.len() > 1 {
+                            self.ambiguities.push(Ambiguity {
+                                ty: ty.clone(),
+                                candidates: candidates
+                                    .into_iter()
+                                    .map(|(ident, _)| ident)
+                                    .collect(),
+                            });
+
+                            return None;
+                        }
+
+                        match candidates.pop() {
+                            Some((_, inner)) => inner,
+                            None if is_inject => {
+                                self.missing_types.push(MissingType {
+                                    requested,
+                                    ty: ty.clone(),
+                                });
+
+                                return None;
+                            }
+                            None => {
+                                self.push_missing_dependency(requested);
+                                return None;
+                            }
+                        }
+                    }
+                    None => {
+                        self.push_missing_dependency(requested);
+                        return None;
+                    }
+                };
+
+                let takes_arguments = match inner.try_borrow() {
+                    Ok(inner) => !inner.arguments.is_empty(),
+                    // Only the dependency being linked is borrowed already
+                    Err(_) => !dependency.arguments.is_empty(),
+                };
+
+                let handle = match handle {
+                    Some(_) if takes_arguments => {
+                        self.handled_factories.push(HandledFactory {
+                            argument: pat.ident.clone(),
+                            dependency: requested,
+                        });
+
+                        return None;
+                    }
+                    None if takes_arguments => Some(Handle::Factory),
+                    handle => handle,
+                };
+
+                let alternatives = match inner.try_borrow() {
+                    Ok(inner) => self.alternatives.get(&inner.sig.ident),
+                    Err(_) => self.alternatives.get(&dependency.sig.ident),
+                }
+                .cloned()
+                .unwrap_or_default();
+
+                Some((
+                    handle,
+                    ChildDependency {
+                        inner,
+                        alternatives,
+                        ident: pat.ident.clone(),
+                        ty: pat_type.ty.as_ref().clone(),
+                    },
+                ))
             })
             .collect();
 
-        dependency.dependencies = dependencies;
+        dependency.dependencies = Vec::new();
+        dependency.deferred = Vec::new();
+
+        for (handle, child) in linked {
+            match handle {
+                Some(handle) => dependency.deferred.push(DeferredDependency {
+                    inner: child.inner,
+                    ident: child.ident,
+                    handle,
+                }),
+                None => dependency.dependencies.push(child),
+            }
+        }
+    }
+}
+
+impl LinkDependencies {
+    /// Get all the dependencies, other than `requester`, returning the given type
+    fn get_dependencies_of_type(
+        &self,
+        ty: &Type,
+        requester: &Ident,
+    ) -> Vec<(Ident, Rc<RefCell<Dependency>>)> {
+        let ty = normalize_type(ty, &self.pointers);
+
+        let mut candidates: Vec<(Ident, Rc<RefCell<Dependency>>)> = Vec::new();
+
+        for (ident, dependency_ty, dependency) in self.types.iter() {
+            // Only the first of any `#[cfg]` alternatives is a candidate
+            if ident == requester
+                || *dependency_ty != ty
+                || candidates.iter().any(|(candidate, _)| candidate == ident)
+            {
+                continue;
+            }
+
+            candidates.push((ident.clone(), dependency.clone()));
+        }
+
+        candidates
+    }
+
+    fn push_missing_dependency(&mut self, requested: Ident) {
+        let best_match = get_best_match(
+            &self.dependencies.keys().collect::<Vec<_>>(),
+            &requested.to_string(),
+        );
+
+        self.errors.push(Error {
+            requested,
+            best_match,
+        });
     }
 }
 
@@ -77,12 +332,98 @@ impl ErrorVisitorMut for LinkDependencies {
     fn new() -> Self {
         Self {
             dependencies: Default::default(),
+            alternatives: Default::default(),
+            types: Default::default(),
+            pointers: Default::default(),
+            by_type: false,
+            despatma: false,
             errors: Default::default(),
+            missing_types: Default::default(),
+            ambiguities: Default::default(),
+            duplicates: Default::default(),
+            handled_factories: Default::default(),
+            standalone_handles: Default::default(),
+            invalid_attributes: Default::default(),
         }
     }
 
     fn emit_errors(self) {
-        let Self { errors, .. } = self;
+        let Self {
+            errors,
+            missing_types,
+            ambiguities,
+            duplicates,
+            handled_factories,
+            standalone_handles,
+            invalid_attributes,
+            pointers,
+            ..
+        } = self;
+
+        for Duplicate {
+            qualifier,
+            existing,
+        } in duplicates
+        {
+            emit_error!(
+                qualifier,
+                "The '{}' qualifier is already the name of another dependency",
+                qualifier;
+                hint = existing.span() => format!("`{}` is registered here", existing)
+            );
+        }
+
+        for Ambiguity { ty, candidates } in ambiguities {
+            let candidates = candidates
+                .iter()
+                .map(|ident| format!("`{ident}`"))
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            emit_error!(
+                ty,
+                "More than one dependency has the type `{}`: {}",
+                normalize_type(&ty, &pointers).to_token_stream(),
+                candidates;
+                hint = "Pick one of them with `#[from(name)]`"
+            );
+        }
+
+        for MissingType { requested, ty } in missing_types {
+            emit_error!(
+                ty,
+                "No dependency has the type `{}`",
+                normalize_type(&ty, &pointers).to_token_stream();
+                hint = "Try adding it with `fn {}(&self) -> {} ...`", requested, normalize_type(&ty, &pointers).to_token_stream()
+            );
+        }
+
+        for HandledFactory {
+            argument,
+            dependency,
+        } in handled_factories
+        {
+            emit_error!(
+                argument,
+                "The '{}' dependency takes runtime arguments, so it cannot be requested through a `Lazy` or `Provider`",
+                dependency;
+                hint = "Request it with any other type to get a function creating it from its arguments"
+            );
+        }
+
+        for ty in standalone_handles {
+            emit_error!(
+                ty, "`Lazy` and `Provider` handles are types of the `despatma` crate";
+                hint = "Generate the container with `#[despatma::dependency_container]` to use them"
+            );
+        }
+
+        for error in invalid_attributes {
+            emit_error!(
+                error.span(), "{}", error;
+                hint = "Try `#[from(name)]` with the name of the dependency to use"
+            );
+        }
 
         for Error {
             requested,
@@ -107,11 +448,66 @@ impl ErrorVisitorMut for LinkDependencies {
     }
 }
 
+/// Get the type a dependency is compared on when requested by type.
+/// Managed dependencies are requested by reference and smart pointers are only a wrapper for the container to hand
+/// them out. So the pointers are taken off the same way `ExtractBoxType` takes them off the return type.
+fn normalize_type(ty: &Type, pointers: &[Path]) -> Type {
+    match ty {
+        Type::Reference(reference) => normalize_type(&reference.elem, pointers),
+        Type::Paren(paren) => normalize_type(&paren.elem, pointers),
+        ty => match get_pointer(ty, pointers) {
+            Some((_, inner)) => normalize_type(&inner, pointers),
+            None => ty.clone(),
+        },
+    }
+}
+
+/// Get the handle and the type of the dependency for a `Lazy<T>` or `Provider<T>` argument
+fn get_handle(ty: &Type) -> Option<(Handle, &Type)> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+
+    let last_segment = path.path.segments.last()?;
+
+    let handle = if last_segment.ident == "Lazy" {
+        Handle::Lazy
+    } else if last_segment.ident == "Provider" {
+        Handle::Provider
+    } else {
+        return None;
+    };
+
+    let PathArguments::AngleBracketed(generics) = &last_segment.arguments else {
+        return None;
+    };
+
+    // Skip any lifetimes, like in `Provider<'_, Service>`
+    let mut types = generics.args.iter().filter_map(|arg| match arg {
+        GenericArgument::Type(ty) => Some(ty),
+        _ => None,
+    });
+
+    match (types.next(), types.next()) {
+        (Some(ty), None) => Some((handle, ty)),
+        _ => None,
+    }
+}
+
+/// Get the name of the dependency requested with `#[from(name)]`, if any
+fn get_from_attribute(attrs: &[Attribute]) -> Option<syn::Result<Ident>> {
+    attrs
+        .iter()
+        .find(|attr| attr.path().is_ident("from"))
+        .map(|attr| attr.parse_args())
+}
+
 /// The maximum distance between two strings for them to be considered a misspelling.
 const MISSPELLING_THRESHOLD: usize = 3;
 
-fn get_best_dependency_match(dependencies: &[&Ident], needle: &str) -> Option<Ident> {
-    dependencies
+/// Get the candidate closest to the misspelled needle, if any is close enough
+pub(super) fn get_best_match(candidates: &[&Ident], needle: &str) -> Option<Ident> {
+    candidates
         .iter()
         .map(|d| (d, levenshtein(needle, &d.to_string())))
         .filter(|(_, distance)| *distance <= MISSPELLING_THRESHOLD)
@@ -127,7 +523,10 @@ mod tests {
 
     use crate::{
         input,
-        processing::{self, visitor::VisitableMut},
+        processing::{
+            self,
+            visitor::{ExtractArguments, ExtractLifetime, ExtractQualifier, VisitableMut},
+        },
     };
 
     use super::*;
@@ -194,4 +593,363 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn link_qualified_dependencies() {
+        let mut container: processing::Container = input::Container::from_item_impl(parse_quote!(
+            impl Container {
+                fn db(&self) -> Db {
+                    Db::new("primary")
+                }
+
+                #[Named("replica")]
+                fn replica_db(&self) -> Db {
+                    Db::new("replica")
+                }
+
+                #[Named("db")]
+                fn other_db(&self) -> Db {
+                    Db::new("other")
+                }
+
+                fn report(
+                    &self,
+                    #[from(replica_db)] db: &Db,
+                    #[from(replica)] replica: &Db,
+                    #[from(replika)] misspelled: &Db,
+                    #[from] invalid: &Db,
+                ) -> Report {
+                    Report::new(db, replica)
+                }
+            }
+        ))
+        .into();
+
+        container.apply_mut(&mut ExtractQualifier::new());
+
+        let mut visitor = LinkDependencies::new();
+
+        container.apply_mut(&mut visitor);
+
+        let report = container.dependencies[3].borrow();
+        let children: Vec<_> = report
+            .dependencies
+            .iter()
+            .map(|child| (child.ident.clone(), child.inner.borrow().sig.ident.clone()))
+            .collect();
+
+        // The argument names should be kept while the requested dependency is used
+        assert_eq!(
+            children,
+            vec![
+                (parse_quote!(db), parse_quote!(replica_db)),
+                (parse_quote!(replica), parse_quote!(replica_db)),
+            ]
+        );
+
+        assert_eq!(
+            visitor.errors,
+            vec![Error {
+                requested: parse_quote!(replika),
+                best_match: Some(parse_quote!(replica)),
+            }]
+        );
+        assert_eq!(
+            visitor.duplicates,
+            vec![Duplicate {
+                qualifier: parse_quote!(db),
+                existing: parse_quote!(db),
+            }]
+        );
+        assert_eq!(visitor.invalid_attributes.len(), 1);
+    }
+
+    #[test]
+    fn link_dependencies_by_type() {
+        let mut container: processing::Container = input::Container::from_item_impl(parse_quote!(
+            impl Container {
+                fn config(&self) -> Config {
+                    Config
+                }
+
+                fn dal(&self) -> Box<dyn DAL> {
+                    Box::new(Sqlite)
+                }
+
+                fn primary(&self) -> Db {
+                    Db::new("primary")
+                }
+
+                fn replica(&self) -> Db {
+                    Db::new("replica")
+                }
+
+                fn service(&self, settings: &Config, data: &dyn DAL, db: &Db) -> Service {
+                    Service::new(settings, data, db)
+                }
+
+                fn logger(&self, #[inject] inner: Logger) -> Logger {
+                    Logger::wrap(inner)
+                }
+
+                fn report(&self, #[inject] config: Config) -> Report {
+                    Report::new(config)
+                }
+            }
+        ))
+        .into();
+
+        container.options.by_type = true;
+
+        let mut visitor = LinkDependencies::new();
+
+        container.apply_mut(&mut visitor);
+
+        let children = |index: usize| -> Vec<(Ident, Ident)> {
+            container.dependencies[index]
+                .borrow()
+                .dependencies
+                .iter()
+                .map(|child| (child.ident.clone(), child.inner.borrow().sig.ident.clone()))
+                .collect()
+        };
+
+        assert_eq!(
+            children(4),
+            vec![
+                (parse_quote!(settings), parse_quote!(config)),
+                (parse_quote!(data), parse_quote!(dal)),
+            ]
+        );
+        assert_eq!(children(5), vec![]);
+        assert_eq!(
+            children(6),
+            vec![(parse_quote!(config), parse_quote!(config))]
+        );
+
+        assert_eq!(
+            visitor.ambiguities,
+            vec![Ambiguity {
+                ty: parse_quote!(&Db),
+                candidates: vec![parse_quote!(primary), parse_quote!(replica)],
+            }]
+        );
+
+        // A dependency should never be injected into itself
+        assert_eq!(
+            visitor.missing_types,
+            vec![MissingType {
+                requested: parse_quote!(inner),
+                ty: parse_quote!(Logger),
+            }]
+        );
+        assert!(visitor.errors.is_empty());
+    }
+
+    #[test]
+    fn link_dependencies_by_pointer_type() {
+        let mut container: processing::Container = input::Container::from_item_impl(parse_quote!(
+            impl Container {
+                fn repository(&self) -> Arc<dyn Repository> {
+                    Arc::new(Postgres)
+                }
+
+                fn cache(&self) -> std::rc::Rc<dyn Cache> {
+                    Rc::new(Memory)
+                }
+
+                fn mailer(&self) -> Shared<dyn Mailer> {
+                    Shared::new(Smtp)
+                }
+
+                fn service(
+                    &self,
+                    store: Arc<dyn Repository>,
+                    memory: &Rc<dyn Cache>,
+                    smtp: &dyn Mailer,
+                ) -> Service {
+                    Service::new(store, memory, smtp)
+                }
+            }
+        ))
+        .into();
+
+        container.options.by_type = true;
+        container.options.pointers = vec![parse_quote!(Shared)];
+
+        let mut visitor = LinkDependencies::new();
+
+        container.apply_mut(&mut visitor);
+
+        let children: Vec<(Ident, Ident)> = container.dependencies[3]
+            .borrow()
+            .dependencies
+            .iter()
+            .map(|child| (child.ident.clone(), child.inner.borrow().sig.ident.clone()))
+            .collect();
+
+        assert_eq!(
+            children,
+            vec![
+                (parse_quote!(store), parse_quote!(repository)),
+                (parse_quote!(memory), parse_quote!(cache)),
+                (parse_quote!(smtp), parse_quote!(mailer)),
+            ]
+        );
+        assert!(visitor.missing_types.is_empty());
+        assert!(visitor.errors.is_empty());
+    }
+
+    #[test]
+    fn link_deferred_dependencies() {
+        let mut container: processing::Container = input::Container::from_item_impl(parse_quote!(
+            impl Container {
+                fn users(&self, notifications: Provider<Notifications>) -> Users {
+                    Users::new(notifications)
+                }
+
+                fn notifications(&self, users: Users, #[inject] mailer: Lazy<'_, Mailer>) -> Notifications {
+                    Notifications::new(users, mailer)
+                }
+
+                fn mailer(&self) -> Mailer {
+                    Mailer
+                }
+            }
+        ))
+        .into();
+
+        // The handles are types of `despatma`
+        container.options.despatma = true;
+
+        let mut visitor = LinkDependencies::new();
+
+        container.apply_mut(&mut visitor);
+
+        let deferred = |index: usize| -> Vec<(Ident, Ident, Handle)> {
+            container.dependencies[index]
+                .borrow()
+                .deferred
+                .iter()
+                .map(|deferred| {
+                    (
+                        deferred.ident.clone(),
+                        deferred.inner.borrow().sig.ident.clone(),
+                        deferred.handle,
+                    )
+                })
+                .collect()
+        };
+
+        // Deferred dependencies are not needed to create the dependency
+        assert!(container.dependencies[0].borrow().dependencies.is_empty());
+        assert_eq!(
+            deferred(0),
+            vec![(
+                parse_quote!(notifications),
+                parse_quote!(notifications),
+                Handle::Provider
+            )]
+        );
+
+        assert_eq!(
+            container.dependencies[1].borrow().dependencies[0].ident,
+            "users"
+        );
+        assert_eq!(
+            deferred(1),
+            vec![(parse_quote!(mailer), parse_quote!(mailer), Handle::Lazy)]
+        );
+
+        assert!(visitor.errors.is_empty());
+        assert!(visitor.missing_types.is_empty());
+    }
+
+    #[test]
+    fn link_factory_dependencies() {
+        let mut container: processing::Container = input::Container::from_item_impl(parse_quote!(
+            impl Container {
+                fn order_handler(&self, repo: Repo, #[arg] order_id: OrderId) -> OrderHandler {
+                    OrderHandler::new(repo, order_id)
+                }
+
+                fn repo(&self) -> Repo {
+                    Repo
+                }
+
+                fn checkout(&self, order_handler: impl Fn(OrderId) -> OrderHandler) -> Checkout {
+                    Checkout::new(order_handler)
+                }
+
+                fn shipping(&self, order_handler: Provider<OrderHandler>) -> Shipping {
+                    Shipping::new(order_handler)
+                }
+            }
+        ))
+        .into();
+
+        container.options.despatma = true;
+
+        // Test needs runtime arguments to be extracted
+        container.apply_mut(&mut ExtractLifetime::new());
+        container.apply_mut(&mut ExtractArguments::new());
+
+        let mut visitor = LinkDependencies::new();
+
+        container.apply_mut(&mut visitor);
+
+        // Runtime arguments are not dependencies
+        let order_handler = container.dependencies[0].borrow();
+        assert_eq!(order_handler.dependencies.len(), 1);
+        assert_eq!(order_handler.dependencies[0].ident, "repo");
+
+        let checkout = container.dependencies[2].borrow();
+        assert!(checkout.dependencies.is_empty());
+        assert_eq!(checkout.deferred.len(), 1);
+        assert_eq!(checkout.deferred[0].ident, "order_handler");
+        assert_eq!(checkout.deferred[0].handle, Handle::Factory);
+
+        assert_eq!(
+            visitor.handled_factories,
+            vec![HandledFactory {
+                argument: parse_quote!(order_handler),
+                dependency: parse_quote!(order_handler),
+            }]
+        );
+        assert!(visitor.errors.is_empty());
+    }
+
+    #[test]
+    fn link_standalone_handles() {
+        let mut container: processing::Container = input::Container::from_item_impl(parse_quote!(
+            impl Container {
+                fn users(&self, notifications: Provider<Notifications>, mailer: Mailer) -> Users {
+                    Users::new(notifications, mailer)
+                }
+
+                fn notifications(&self) -> Notifications {
+                    Notifications
+                }
+
+                fn mailer(&self) -> Mailer {
+                    Mailer
+                }
+            }
+        ))
+        .into();
+
+        let mut visitor = LinkDependencies::new();
+
+        container.apply_mut(&mut visitor);
+
+        let users = container.dependencies[0].borrow();
+        assert_eq!(users.dependencies.len(), 1);
+        assert_eq!(users.dependencies[0].ident, "mailer");
+        assert!(users.deferred.is_empty());
+
+        assert_eq!(
+            visitor.standalone_handles,
+            vec![parse_quote!(Provider<Notifications>)]
+        );
+    }
 }
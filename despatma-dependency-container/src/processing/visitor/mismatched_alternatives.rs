@@ -0,0 +1,193 @@
+use std::mem::discriminant;
+
+use proc_macro_error2::emit_error;
+use syn::Ident;
+
+use crate::processing::{Container, Dependency};
+
+use super::{ErrorVisitorMut, VisitorMut};
+
+/// Reports the `#[cfg(...)]` alternatives of a dependency which cannot stand in for each other. Other dependencies
+/// request whichever alternative is active in the same way, so every alternative needs the same lifetime, return type
+/// and asyncness.
+///
+/// Needs lifetimes, error types and asyncness to be extracted first.
+pub struct MismatchedAlternatives {
+    errors: Vec<Error>,
+}
+
+#[cfg_attr(test, derive(Eq, PartialEq, Debug))]
+struct Error {
+    dependency: Ident,
+    first: Ident,
+    difference: &'static str,
+}
+
+impl VisitorMut for MismatchedAlternatives {
+    fn visit_container_mut(&mut self, container: &mut Container) {
+        for (index, dependency) in container.dependencies.iter().enumerate() {
+            let dependency = dependency.borrow();
+
+            // The providers for a profile are checked by the dependency picking between them
+            if dependency.profile.is_some() {
+                continue;
+            }
+
+            let Some(first) = container.dependencies[..index].iter().find(|first| {
+                let first = first.borrow();
+                first.sig.ident == dependency.sig.ident && first.profile.is_none()
+            }) else {
+                continue;
+            };
+
+            if let Some(difference) = get_difference(&first.borrow(), &dependency) {
+                self.errors.push(Error {
+                    dependency: dependency.sig.ident.clone(),
+                    first: first.borrow().sig.ident.clone(),
+                    difference,
+                });
+            }
+        }
+    }
+}
+
+/// Get what keeps the two alternatives from standing in for each other, if anything
+fn get_difference(first: &Dependency, other: &Dependency) -> Option<&'static str> {
+    if discriminant(&first.lifetime) != discriminant(&other.lifetime) || first.scope != other.scope
+    {
+        Some("lifetime")
+    } else if first.ty != other.ty || first.error_ty != other.error_ty {
+        Some("return type")
+    } else if first.is_async != other.is_async {
+        Some("asyncness")
+    } else {
+        None
+    }
+}
+
+impl ErrorVisitorMut for MismatchedAlternatives {
+    fn new() -> Self {
+        Self {
+            errors: Default::default(),
+        }
+    }
+
+    fn emit_errors(self) {
+        for Error {
+            dependency,
+            first,
+            difference,
+        } in self.errors
+        {
+            emit_error!(
+                dependency,
+                "Every `#[cfg]` alternative of the '{}' dependency needs the same {}",
+                dependency,
+                difference;
+                hint = first.span() => format!("`{first}` is first registered here")
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use syn::parse_quote;
+
+    use crate::{
+        input,
+        processing::{
+            self,
+            visitor::{ExtractAsync, ExtractCfg, ExtractLifetime, LinkDependencies, VisitableMut},
+        },
+    };
+
+    use super::*;
+
+    #[test]
+    fn mismatched_alternatives() {
+        let mut container: processing::Container = input::Container::from_item_impl(parse_quote!(
+            impl Container {
+                #[cfg(feature = "redis")]
+                #[Singleton]
+                fn cache(&self) -> Box<dyn Cache> {
+                    Box::new(Redis)
+                }
+
+                #[cfg(not(feature = "redis"))]
+                #[Singleton]
+                fn cache(&self) -> Box<dyn Cache> {
+                    Box::new(Memory)
+                }
+
+                #[cfg(feature = "postgres")]
+                #[Singleton]
+                fn db(&self) -> Box<dyn Db> {
+                    Box::new(Postgres)
+                }
+
+                #[cfg(not(feature = "postgres"))]
+                fn db(&self) -> Box<dyn Db> {
+                    Box::new(Sqlite)
+                }
+
+                #[cfg(unix)]
+                fn path(&self) -> UnixPath {
+                    UnixPath
+                }
+
+                #[cfg(windows)]
+                fn path(&self) -> WindowsPath {
+                    WindowsPath
+                }
+
+                #[cfg(debug_assertions)]
+                fn client(&self) -> Client {
+                    Client
+                }
+
+                #[cfg(not(debug_assertions))]
+                fn client(&self, path: UnixPath, session: Session) -> Client {
+                    Client::new(path, session)
+                }
+
+                async fn session(&self) -> Session {
+                    Session
+                }
+            }
+        ))
+        .into();
+
+        // Test needs cfgs, lifetimes and asyncness to be extracted
+        container.apply_mut(&mut ExtractCfg::new());
+        container.apply_mut(&mut ExtractLifetime::new());
+        container.apply_mut(&mut LinkDependencies::new());
+        container.apply_mut(&mut ExtractAsync::new());
+
+        let mut visitor = MismatchedAlternatives::new();
+
+        container.apply_mut(&mut visitor);
+
+        assert_eq!(
+            visitor.errors,
+            vec![
+                Error {
+                    dependency: parse_quote!(db),
+                    first: parse_quote!(db),
+                    difference: "lifetime",
+                },
+                Error {
+                    dependency: parse_quote!(path),
+                    first: parse_quote!(path),
+                    difference: "return type",
+                },
+                Error {
+                    dependency: parse_quote!(client),
+                    first: parse_quote!(client),
+                    difference: "asyncness",
+                },
+            ]
+        );
+    }
+}
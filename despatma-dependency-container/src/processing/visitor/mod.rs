@@ -2,29 +2,61 @@ use despatma_visitor::visitor_mut;
 
 use super::{ChildDependency, Container, Dependency};
 
+pub use add_group_dependencies::AddGroupDependencies;
+pub use add_profile_dependencies::AddProfileDependencies;
 pub use add_wildcard_lifetime::AddWildcardLifetime;
+pub use captive_dependencies::CaptiveDependencies;
+pub use cyclic_dependencies::CyclicDependencies;
+pub use eager_error_types::EagerErrorTypes;
+pub use extract_arguments::ExtractArguments;
 pub use extract_async::ExtractAsync;
 pub use extract_box_type::ExtractBoxType;
+pub use extract_cfg::ExtractCfg;
+pub use extract_dispose::ExtractDispose;
 pub use extract_embedded_dependency::ExtractEmbeddedDependency;
+pub use extract_error_type::ExtractErrorType;
+pub use extract_group::ExtractGroup;
+pub use extract_imports::ExtractImports;
 pub use extract_lifetime::ExtractLifetime;
+pub use extract_qualifier::ExtractQualifier;
+pub use extract_result_type::ExtractResultType;
 pub use impl_trait_but_registered_concrete::ImplTraitButRegisteredConcrete;
 pub use impl_trait_fields::ImplTraitFields;
 pub use link_dependencies::LinkDependencies;
+pub use mismatched_alternatives::MismatchedAlternatives;
 pub use owning_managed_dependency::OwningManagedDependency;
 pub use replace_impl_generics_with_concrete::ReplaceImplGenericsWithConcrete;
+pub use reserved_names::ReservedNames;
+pub use unmanaged_dispose::UnmanagedDispose;
 pub use unsupported_registered_types::UnsupportedRegisteredTypes;
 pub use wrap_box_type::WrapBoxType;
 
+mod add_group_dependencies;
+mod add_profile_dependencies;
 mod add_wildcard_lifetime;
+mod captive_dependencies;
+mod cyclic_dependencies;
+mod eager_error_types;
+mod extract_arguments;
 mod extract_async;
 mod extract_box_type;
+mod extract_cfg;
+mod extract_dispose;
 mod extract_embedded_dependency;
+mod extract_error_type;
+mod extract_group;
+mod extract_imports;
 mod extract_lifetime;
+mod extract_qualifier;
+mod extract_result_type;
 mod impl_trait_but_registered_concrete;
 mod impl_trait_fields;
 mod link_dependencies;
+mod mismatched_alternatives;
 mod owning_managed_dependency;
 mod replace_impl_generics_with_concrete;
+mod reserved_names;
+mod unmanaged_dispose;
 mod unsupported_registered_types;
 mod wrap_box_type;
 
@@ -1,39 +1,67 @@
 use proc_macro_error2::emit_error;
 use quote::ToTokens;
-use syn::Type;
+use syn::{Path, Type};
 
-use crate::processing::Dependency;
+use crate::processing::{Container, Dependency};
 
-use super::{ErrorVisitorMut, VisitorMut};
+use super::{extract_box_type::get_pointer, visit_container_mut, ErrorVisitorMut, VisitorMut};
 
 /// Visitor to help users when they are trying to take ownership of a dependency which is managed by the container.
 /// The container needs to manage Singleton and Scoped dependencies to control their constructions. This means references
 /// of theses dependencies are given to anything that requires them. Another visitor already fixes this for `impl Trait`
 /// dependencies. This visitor fixes it for concrete (non-abstract) dependencies.
+/// Shared pointers like `Arc` and `Rc` can still be taken by value, since they are cloned out of the container. As can
+/// the `Arc` handles of shared dependencies.
 ///
 /// Needs to happen after child dependencies are linked.
 /// And after lifetimes are extracted.
 /// But before any types are changed.
 pub struct OwningManagedDependency {
+    pointers: Vec<Path>,
     types: Vec<Type>,
 }
 
 impl VisitorMut for OwningManagedDependency {
+    fn visit_container_mut(&mut self, container: &mut Container) {
+        self.pointers = container.options.pointers.clone();
+
+        visit_container_mut(self, container);
+    }
+
     fn visit_dependency_mut(&mut self, dependency: &mut Dependency) {
         let types = dependency
             .dependencies
             .iter()
             .filter(|child| matches!(child.ty, Type::Path(_)))
             .filter(|child| child.inner.borrow().lifetime.is_managed())
+            .filter(|child| {
+                !get_pointer(&child.inner.borrow().ty, &self.pointers)
+                    .is_some_and(|(pointer, _)| pointer.is_shared())
+            })
+            .filter(|child| !(child.inner.borrow().is_shared && is_arc(&child.ty)))
             .map(|child| child.ty.clone());
 
         self.types.extend(types);
     }
 }
 
+/// Whether the type is the `Arc` handle a shared dependency is handed out as
+fn is_arc(ty: &Type) -> bool {
+    let Type::Path(type_path) = ty else {
+        return false;
+    };
+
+    type_path
+        .path
+        .segments
+        .last()
+        .is_some_and(|segment| segment.ident == "Arc")
+}
+
 impl ErrorVisitorMut for OwningManagedDependency {
     fn new() -> Self {
         Self {
+            pointers: Default::default(),
             types: Default::default(),
         }
     }
@@ -72,6 +100,42 @@ mod tests {
                     Config
                 }
 
+                #[Singleton]
+                fn repo(&self) -> Arc<dyn Repo> {
+                    Arc::new(PostgresRepo)
+                }
+
+                fn service(&self, config: Config, repo: Arc<dyn Repo>) -> Service {
+                    Service::new(config, repo)
+                }
+            }
+        ))
+        .into();
+
+        // Test needs them to be linked nd lifetimes to be extracted
+        container.apply_mut(&mut LinkDependencies::new());
+        container.apply_mut(&mut ExtractLifetime::new());
+
+        let mut visitor = OwningManagedDependency::new();
+
+        container.apply_mut(&mut visitor);
+
+        assert_eq!(visitor.types, vec![parse_quote!(Config)]);
+    }
+
+    #[test]
+    fn owning_shared_dependency() {
+        let mut container: processing::Container = input::Container::from_item_impl(parse_quote!(
+            impl Container {
+                #[Singleton(shared)]
+                fn config(&self) -> Config {
+                    Config
+                }
+
+                fn server(&self, config: Arc<Config>) -> Server {
+                    Server::new(config)
+                }
+
                 fn service(&self, config: Config) -> Service {
                     Service::new(config)
                 }
@@ -79,7 +143,6 @@ mod tests {
         ))
         .into();
 
-        // Test needs them to be linked nd lifetimes to be extracted
         container.apply_mut(&mut LinkDependencies::new());
         container.apply_mut(&mut ExtractLifetime::new());
 
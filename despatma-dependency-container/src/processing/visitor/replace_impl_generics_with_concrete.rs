@@ -32,28 +32,30 @@ impl VisitorMut for ReplaceImplGenericsWithConcrete {
         // Fix `field_ty` first before it is registered for later replacements
         self.replacer.visit_type_mut(&mut dependency.field_ty);
 
-        self.replacer.to_replace.insert(
-            dependency.ty.clone(),
-            (
-                dependency.lifetime.is_managed(),
-                dependency.field_ty.clone(),
-            ),
-        );
+        let concrete_type = &dependency.field_ty;
+        let replacement = if dependency.is_shared {
+            parse_quote! { std::sync::Arc<#concrete_type> }
+        } else if dependency.lifetime.is_managed() {
+            parse_quote! { &'a #concrete_type }
+        } else {
+            concrete_type.clone()
+        };
+
+        self.replacer
+            .to_replace
+            .insert(dependency.ty.clone(), replacement);
     }
 }
 
 struct Replacer {
-    to_replace: HashMap<Type, (bool, Type)>,
+    /// The `impl Trait` types with the types of the concrete values given out for them
+    to_replace: HashMap<Type, Type>,
 }
 
 impl VisitMut for Replacer {
     fn visit_type_mut(&mut self, ty: &mut Type) {
-        if let Some((is_managed, concrete_type)) = self.to_replace.get(ty) {
-            *ty = if *is_managed {
-                parse_quote! { &'a #concrete_type }
-            } else {
-                parse_quote! { #concrete_type }
-            };
+        if let Some(replacement) = self.to_replace.get(ty) {
+            *ty = replacement.clone();
         } else {
             // Continue checking for any impl types on inner generics
             visit_type_mut(self, ty);
@@ -140,4 +142,31 @@ mod tests {
             parse_quote!(Service<&'a Sqlite, HtmlPresenter>)
         );
     }
+
+    #[test]
+    fn shared_impl_trait_registered_concrete() {
+        let mut container: processing::Container = input::Container::from_item_impl(parse_quote!(
+            impl Container {
+                #[Singleton(shared, Sqlite)]
+                fn db(&self) -> impl DB {
+                    Sqlite
+                }
+
+                #[Singleton]
+                fn service(&self, db: impl DB) -> Service<impl DB> {
+                    Service(db)
+                }
+            }
+        ))
+        .into();
+
+        container.apply_mut(&mut ExtractLifetime::new());
+        container.apply_mut(&mut LinkDependencies::new());
+        container.apply_mut(&mut ReplaceImplGenericsWithConcrete::new());
+
+        assert_eq!(
+            container.dependencies[1].borrow().field_ty,
+            parse_quote!(Service<std::sync::Arc<Sqlite>>)
+        );
+    }
 }
@@ -0,0 +1,313 @@
+use proc_macro_error2::emit_error;
+use syn::Ident;
+
+use crate::processing::{Container, Lifetime};
+
+use super::{ErrorVisitorMut, VisitorMut};
+
+/// The methods every container gets to inspect which dependencies have been created and how they are wired together
+const RESERVED_NAMES: &[&str] = &[
+    "initialized",
+    "dependency_graph_dot",
+    "dependency_graph_mermaid",
+];
+
+/// Reports on any dependencies named after a method which the container generates for itself. The method of the
+/// dependency would clash with the generated one.
+/// Some methods are only generated for some containers, like `builder` for containers with constructor arguments,
+/// `init` for containers with eager singletons, `shutdown` for containers which dispose their dependencies and
+/// `constructions` for containers with the `constructions` option. So their names are only reserved on those
+/// containers.
+///
+/// Needs dispose hooks, lifetimes and constructor arguments to be extracted first.
+pub struct ReservedNames {
+    errors: Vec<Ident>,
+}
+
+impl VisitorMut for ReservedNames {
+    fn visit_container_mut(&mut self, container: &mut Container) {
+        let mut reserved_names = RESERVED_NAMES.to_vec();
+
+        if container.options.constructions {
+            reserved_names.push("constructions");
+        }
+
+        if container
+            .dependencies
+            .iter()
+            .any(|dependency| dependency.borrow().lifetime.is_embedded())
+        {
+            reserved_names.push("builder");
+        }
+
+        if container
+            .dependencies
+            .iter()
+            .any(|dependency| dependency.borrow().is_eager)
+        {
+            reserved_names.push("init");
+        }
+
+        if container.dependencies.iter().any(|dependency| {
+            let dependency = dependency.borrow();
+
+            dependency.dispose.is_some()
+                && matches!(
+                    dependency.lifetime,
+                    Lifetime::Singleton(_) | Lifetime::Scoped(_)
+                )
+        }) {
+            reserved_names.push("shutdown");
+        }
+
+        for dependency in container.dependencies.iter() {
+            let ident = &dependency.borrow().sig.ident;
+
+            if reserved_names.iter().any(|name| ident == name) {
+                self.errors.push(ident.clone());
+            }
+        }
+    }
+}
+
+impl ErrorVisitorMut for ReservedNames {
+    fn new() -> Self {
+        Self {
+            errors: Default::default(),
+        }
+    }
+
+    fn emit_errors(self) {
+        for ident in self.errors {
+            emit_error!(
+                ident,
+                "The '{}' name is reserved for a method of the container",
+                ident;
+                hint = "Rename the dependency and add `#[Named(\"{}\")]` to keep requesting it as `{}`", ident, ident
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use syn::parse_quote;
+
+    use crate::{
+        input,
+        processing::{
+            self,
+            visitor::{ExtractDispose, ExtractEmbeddedDependency, ExtractLifetime, VisitableMut},
+        },
+    };
+
+    use super::*;
+
+    #[test]
+    fn reserved_names() {
+        let mut input: input::Container = input::Container::from_item_impl(parse_quote!(
+            impl Container {
+                fn initialized(&self) -> Initialized {
+                    Initialized
+                }
+
+                fn constructions(&self) -> Constructions {
+                    Constructions
+                }
+
+                fn dependency_graph_dot(&self) -> Graph {
+                    Graph
+                }
+
+                fn config(&self) -> Config {
+                    Config
+                }
+            }
+        ));
+        input.set_options(input::Options {
+            constructions: true,
+            ..Default::default()
+        });
+        let mut container: processing::Container = input.into();
+
+        let mut visitor = ReservedNames::new();
+
+        container.apply_mut(&mut visitor);
+
+        let expected: Vec<Ident> = vec![
+            parse_quote!(initialized),
+            parse_quote!(constructions),
+            parse_quote!(dependency_graph_dot),
+        ];
+
+        assert_eq!(visitor.errors, expected);
+
+        // Without the `constructions` option there is no `constructions` method to clash with
+        let mut container: processing::Container = input::Container::from_item_impl(parse_quote!(
+            impl Container {
+                fn constructions(&self) -> Constructions {
+                    Constructions
+                }
+            }
+        ))
+        .into();
+
+        let mut visitor = ReservedNames::new();
+
+        container.apply_mut(&mut visitor);
+
+        assert_eq!(visitor.errors, Vec::<Ident>::new());
+    }
+
+    #[test]
+    fn reserved_builder_name() {
+        let mut container: processing::Container = input::Container::from_item_impl(parse_quote!(
+            impl Container {
+                fn new(config: Config) {}
+
+                fn builder(&self) -> Builder {
+                    Builder
+                }
+            }
+        ))
+        .into();
+
+        container.apply_mut(&mut ExtractLifetime::new());
+        container.apply_mut(&mut ExtractEmbeddedDependency::new());
+
+        let mut visitor = ReservedNames::new();
+
+        container.apply_mut(&mut visitor);
+
+        let expected: Vec<Ident> = vec![parse_quote!(builder)];
+
+        assert_eq!(visitor.errors, expected);
+
+        // Without constructor arguments there is no `builder` method to clash with
+        let mut container: processing::Container = input::Container::from_item_impl(parse_quote!(
+            impl Container {
+                fn builder(&self) -> Builder {
+                    Builder
+                }
+            }
+        ))
+        .into();
+
+        container.apply_mut(&mut ExtractLifetime::new());
+        container.apply_mut(&mut ExtractEmbeddedDependency::new());
+
+        let mut visitor = ReservedNames::new();
+
+        container.apply_mut(&mut visitor);
+
+        assert_eq!(visitor.errors, Vec::<Ident>::new());
+    }
+
+    #[test]
+    fn reserved_init_name() {
+        let mut container: processing::Container = input::Container::from_item_impl(parse_quote!(
+            impl Container {
+                #[Singleton(eager)]
+                fn pool(&self) -> Pool {
+                    Pool
+                }
+
+                fn init(&self) -> Init {
+                    Init
+                }
+            }
+        ))
+        .into();
+
+        container.apply_mut(&mut ExtractLifetime::new());
+        container.apply_mut(&mut ExtractEmbeddedDependency::new());
+
+        let mut visitor = ReservedNames::new();
+
+        container.apply_mut(&mut visitor);
+
+        let expected: Vec<Ident> = vec![parse_quote!(init)];
+
+        assert_eq!(visitor.errors, expected);
+
+        // Without eager singletons there is no `init` method to clash with
+        let mut container: processing::Container = input::Container::from_item_impl(parse_quote!(
+            impl Container {
+                #[Singleton]
+                fn pool(&self) -> Pool {
+                    Pool
+                }
+
+                fn init(&self) -> Init {
+                    Init
+                }
+            }
+        ))
+        .into();
+
+        container.apply_mut(&mut ExtractLifetime::new());
+        container.apply_mut(&mut ExtractEmbeddedDependency::new());
+
+        let mut visitor = ReservedNames::new();
+
+        container.apply_mut(&mut visitor);
+
+        assert_eq!(visitor.errors, Vec::<Ident>::new());
+    }
+
+    #[test]
+    fn reserved_shutdown_name() {
+        let mut container: processing::Container = input::Container::from_item_impl(parse_quote!(
+            impl Container {
+                #[Singleton]
+                #[Dispose(close)]
+                fn pool(&self) -> Pool {
+                    Pool
+                }
+
+                fn shutdown(&self) -> Shutdown {
+                    Shutdown
+                }
+            }
+        ))
+        .into();
+
+        container.apply_mut(&mut ExtractDispose::new());
+        container.apply_mut(&mut ExtractLifetime::new());
+        container.apply_mut(&mut ExtractEmbeddedDependency::new());
+
+        let mut visitor = ReservedNames::new();
+
+        container.apply_mut(&mut visitor);
+
+        let expected: Vec<Ident> = vec![parse_quote!(shutdown)];
+
+        assert_eq!(visitor.errors, expected);
+
+        // Without managed dispose hooks there is no `shutdown` method to clash with
+        let mut container: processing::Container = input::Container::from_item_impl(parse_quote!(
+            impl Container {
+                #[Singleton]
+                fn pool(&self) -> Pool {
+                    Pool
+                }
+
+                fn shutdown(&self) -> Shutdown {
+                    Shutdown
+                }
+            }
+        ))
+        .into();
+
+        container.apply_mut(&mut ExtractDispose::new());
+        container.apply_mut(&mut ExtractLifetime::new());
+        container.apply_mut(&mut ExtractEmbeddedDependency::new());
+
+        let mut visitor = ReservedNames::new();
+
+        container.apply_mut(&mut visitor);
+
+        assert_eq!(visitor.errors, Vec::<Ident>::new());
+    }
+}
@@ -0,0 +1,116 @@
+use proc_macro_error2::emit_error;
+use syn::Ident;
+
+use crate::processing::{Dependency, Lifetime};
+
+use super::{ErrorVisitorMut, VisitorMut};
+
+/// Reports on any dependencies with a dispose hook which are not managed by the container.
+/// The container only holds on to singleton and scoped dependencies, so those are the only ones it can dispose.
+///
+/// Needs lifetimes to be extracted first.
+pub struct UnmanagedDispose {
+    errors: Vec<Error>,
+}
+
+#[cfg_attr(test, derive(Eq, PartialEq, Debug))]
+struct Error {
+    dependency: Ident,
+    method: Ident,
+}
+
+impl VisitorMut for UnmanagedDispose {
+    fn visit_dependency_mut(&mut self, dependency: &mut Dependency) {
+        let Some(dispose) = &dependency.dispose else {
+            return;
+        };
+
+        if matches!(
+            dependency.lifetime,
+            Lifetime::Singleton(_) | Lifetime::Scoped(_)
+        ) {
+            return;
+        }
+
+        self.errors.push(Error {
+            dependency: dependency.sig.ident.clone(),
+            method: dispose.method.clone(),
+        });
+    }
+}
+
+impl ErrorVisitorMut for UnmanagedDispose {
+    fn new() -> Self {
+        Self {
+            errors: Default::default(),
+        }
+    }
+
+    fn emit_errors(self) {
+        for Error { dependency, method } in self.errors {
+            emit_error!(
+                method,
+                "Only Singleton and Scoped dependencies can be disposed";
+                hint = "Mark `{}` with `#[Singleton]` or `#[Scoped]` so that the container can dispose it", dependency
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use proc_macro2::Span;
+    use syn::parse_quote;
+
+    use crate::{
+        input,
+        processing::{
+            self,
+            visitor::{ExtractDispose, ExtractLifetime, VisitableMut},
+        },
+    };
+
+    use super::*;
+
+    #[test]
+    fn unmanaged_dispose() {
+        let mut container: processing::Container = input::Container::from_item_impl(parse_quote!(
+            impl Container {
+                #[Singleton]
+                #[Dispose(close)]
+                fn pool(&self) -> Pool {
+                    Pool::new()
+                }
+
+                #[Scoped]
+                #[Dispose(stop)]
+                fn worker(&self) -> Worker {
+                    Worker::start()
+                }
+
+                #[Dispose(flush)]
+                fn writer(&self) -> Writer {
+                    Writer
+                }
+            }
+        ))
+        .into();
+
+        // Test needs the hooks and lifetimes to be extracted
+        container.apply_mut(&mut ExtractDispose::new());
+        container.apply_mut(&mut ExtractLifetime::new());
+
+        let mut visitor = UnmanagedDispose::new();
+
+        container.apply_mut(&mut visitor);
+
+        assert_eq!(
+            visitor.errors,
+            vec![Error {
+                dependency: Ident::new("writer", Span::call_site()),
+                method: Ident::new("flush", Span::call_site()),
+            }]
+        );
+    }
+}
@@ -4,18 +4,19 @@ use crate::processing::Dependency;
 
 use super::{ErrorVisitorMut, VisitorMut};
 
-/// Correctly restores the boxes that were removed from any types
+/// Correctly restores the smart pointers that were removed from any types
 ///
-/// Needs to happen after boxes and lifetimes are extracted
+/// Needs to happen after smart pointers and lifetimes are extracted
 pub struct WrapBoxType;
 
 impl VisitorMut for WrapBoxType {
     fn visit_dependency_mut(&mut self, dependency: &mut Dependency) {
-        if dependency.is_boxed {
+        if let Some(pointer) = &dependency.pointer {
+            let path = pointer.path();
             let ty = &dependency.ty;
 
-            dependency.field_ty = parse_quote!(std::boxed::Box<#ty>);
-            dependency.ty = parse_quote!(std::boxed::Box<#ty>);
+            dependency.field_ty = parse_quote!(#path<#ty>);
+            dependency.ty = parse_quote!(#path<#ty>);
         }
     }
 }
@@ -61,8 +62,8 @@ mod tests {
         ))
         .into();
 
-        container.apply_mut(&mut ExtractBoxType);
-        container.apply_mut(&mut ExtractLifetime);
+        container.apply_mut(&mut ExtractBoxType::new());
+        container.apply_mut(&mut ExtractLifetime::new());
 
         assert_eq!(container.dependencies[0].borrow().ty, parse_quote!(dyn DAL));
         assert_eq!(
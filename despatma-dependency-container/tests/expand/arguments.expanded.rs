@@ -0,0 +1,133 @@
+struct Repo;
+impl Repo {
+    fn status(&self, order_id: u32) -> &'static str {
+        if order_id == 1 { "shipped" } else { "pending" }
+    }
+}
+struct OrderHandler {
+    repo: Repo,
+    order_id: u32,
+    user: String,
+}
+impl OrderHandler {
+    fn describe(&self) -> String {
+        ::alloc::__export::must_use({
+            ::alloc::fmt::format(
+                format_args!(
+                    "{0} ordered #{1}: {2}", self.user, self.order_id, self.repo
+                    .status(self.order_id),
+                ),
+            )
+        })
+    }
+}
+struct DependencyContainer<'a> {
+    _phantom: std::marker::PhantomData<&'a ()>,
+}
+#[automatically_derived]
+impl<'a> ::core::clone::Clone for DependencyContainer<'a> {
+    #[inline]
+    fn clone(&self) -> DependencyContainer<'a> {
+        DependencyContainer {
+            _phantom: ::core::clone::Clone::clone(&self._phantom),
+        }
+    }
+}
+impl<'a> DependencyContainer<'a> {
+    pub fn new() -> Self {
+        Self {
+            _phantom: Default::default(),
+        }
+    }
+    pub fn new_scope(&self) -> Self {
+        Self {
+            _phantom: Default::default(),
+        }
+    }
+    /// The names of the singleton and scoped dependencies which have been created, without creating any others
+    pub fn initialized(&self) -> Vec<&'static str> {
+        #[allow(unused_mut)]
+        let mut initialized = Vec::new();
+        initialized
+    }
+    /// The dependencies of this container and how they are wired together in the DOT format
+    pub fn dependency_graph_dot() -> &'static str {
+        "digraph DependencyContainer {\n    node [shape=box];\n    \"repo\" [label=\"repo\\nTransient\"];\n    \"order_handler\" [label=\"order_handler\\nTransient\"];\n    \"report\" [label=\"report\\nTransient\"];\n    \"order_handler\" -> \"repo\";\n    \"report\" -> \"order_handler\" [style=dashed];\n}\n"
+    }
+    /// The dependencies of this container and how they are wired together as a Mermaid flowchart
+    pub fn dependency_graph_mermaid() -> &'static str {
+        "graph TD\n    repo[\"repo<br/>Transient\"]\n    order_handler[\"order_handler<br/>Transient\"]\n    report[\"report<br/>Transient\"]\n    order_handler --> repo\n    report -.-> order_handler\n"
+    }
+    pub fn repo(&'a self) -> Repo {
+        Repo
+    }
+    pub fn order_handler(&'a self, order_id: u32, user: &str) -> OrderHandler {
+        let repo = self.repo();
+        OrderHandler {
+            repo,
+            order_id,
+            user: user.to_string(),
+        }
+    }
+    pub fn report(&'a self) -> Vec<String> {
+        let order_handler = move |order_id: u32, user: &str| {
+            self.order_handler(order_id, user)
+        };
+        ::alloc::boxed::box_assume_init_into_vec_unsafe(
+            ::alloc::intrinsics::write_box_via_move(
+                ::alloc::boxed::Box::new_uninit(),
+                [
+                    order_handler(1, "alice").describe(),
+                    order_handler(2, "bob").describe(),
+                ],
+            ),
+        )
+    }
+}
+impl<'a> core::fmt::Debug for DependencyContainer<'a> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        #[allow(unused_mut)]
+        let mut debug = f.debug_struct("DependencyContainer");
+        debug.finish_non_exhaustive()
+    }
+}
+fn main() {
+    let container = DependencyContainer::new();
+    match (
+        &container.order_handler(1, "carol").describe(),
+        &"carol ordered #1: shipped",
+    ) {
+        (left_val, right_val) => {
+            if !(*left_val == *right_val) {
+                let kind = ::core::panicking::AssertKind::Eq;
+                ::core::panicking::assert_failed(
+                    kind,
+                    &*left_val,
+                    &*right_val,
+                    ::core::option::Option::None,
+                );
+            }
+        }
+    };
+    match (
+        &container.report(),
+        &::alloc::boxed::box_assume_init_into_vec_unsafe(
+            ::alloc::intrinsics::write_box_via_move(
+                ::alloc::boxed::Box::new_uninit(),
+                ["alice ordered #1: shipped", "bob ordered #2: pending"],
+            ),
+        ),
+    ) {
+        (left_val, right_val) => {
+            if !(*left_val == *right_val) {
+                let kind = ::core::panicking::AssertKind::Eq;
+                ::core::panicking::assert_failed(
+                    kind,
+                    &*left_val,
+                    &*right_val,
+                    ::core::option::Option::None,
+                );
+            }
+        }
+    };
+}
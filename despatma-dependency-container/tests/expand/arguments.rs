@@ -0,0 +1,63 @@
+struct Repo;
+
+impl Repo {
+    fn status(&self, order_id: u32) -> &'static str {
+        if order_id == 1 {
+            "shipped"
+        } else {
+            "pending"
+        }
+    }
+}
+
+struct OrderHandler {
+    repo: Repo,
+    order_id: u32,
+    user: String,
+}
+
+impl OrderHandler {
+    fn describe(&self) -> String {
+        format!(
+            "{} ordered #{}: {}",
+            self.user,
+            self.order_id,
+            self.repo.status(self.order_id)
+        )
+    }
+}
+
+#[despatma_dependency_container::dependency_container]
+impl DependencyContainer {
+    fn repo(&self) -> Repo {
+        Repo
+    }
+
+    fn order_handler(&self, repo: Repo, #[arg] order_id: u32, #[arg] user: &str) -> OrderHandler {
+        OrderHandler {
+            repo,
+            order_id,
+            user: user.to_string(),
+        }
+    }
+
+    fn report(&self, order_handler: impl Fn(u32, &str) -> OrderHandler) -> Vec<String> {
+        vec![
+            order_handler(1, "alice").describe(),
+            order_handler(2, "bob").describe(),
+        ]
+    }
+}
+
+fn main() {
+    let container = DependencyContainer::new();
+
+    assert_eq!(
+        container.order_handler(1, "carol").describe(),
+        "carol ordered #1: shipped"
+    );
+    assert_eq!(
+        container.report(),
+        vec!["alice ordered #1: shipped", "bob ordered #2: pending"]
+    );
+}
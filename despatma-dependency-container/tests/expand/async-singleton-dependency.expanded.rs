@@ -41,6 +41,23 @@ impl<'a> DependencyContainer<'a> {
             _phantom: Default::default(),
         }
     }
+    /// The names of the singleton and scoped dependencies which have been created, without creating any others
+    pub fn initialized(&self) -> Vec<&'static str> {
+        #[allow(unused_mut)]
+        let mut initialized = Vec::new();
+        if self.config.get().is_some() {
+            initialized.push("config");
+        }
+        initialized
+    }
+    /// The dependencies of this container and how they are wired together in the DOT format
+    pub fn dependency_graph_dot() -> &'static str {
+        "digraph DependencyContainer {\n    node [shape=box];\n    \"config\" [label=\"config\\nSingleton, async\"];\n    \"service\" [label=\"service\\nTransient, async\"];\n    \"service\" -> \"config\";\n}\n"
+    }
+    /// The dependencies of this container and how they are wired together as a Mermaid flowchart
+    pub fn dependency_graph_mermaid() -> &'static str {
+        "graph TD\n    config[\"config<br/>Singleton, async\"]\n    service[\"service<br/>Transient, async\"]\n    service --> config\n"
+    }
     pub async fn config(&'a self) -> &Config {
         self.config
             .get_or_init(async {
@@ -50,16 +67,25 @@ impl<'a> DependencyContainer<'a> {
             .await
     }
     pub async fn service(&'a self) -> Service {
-        let config = self
-            .config
-            .get_or_init(async {
-                sleep(Duration::from_millis(10)).await;
-                Config { port: 8080 }
-            })
-            .await;
+        let config = self.config().await;
         Service::new(config.port)
     }
 }
+impl<'a> core::fmt::Debug for DependencyContainer<'a> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        #[allow(unused_mut)]
+        let mut debug = f.debug_struct("DependencyContainer");
+        debug
+            .field(
+                "config",
+                &format_args!(
+                    "{0}", if self.config.get().is_some() { "initialized" } else {
+                    "uninitialized" }
+                ),
+            );
+        debug.finish_non_exhaustive()
+    }
+}
 fn main() {
     let body = async {
         let container = DependencyContainer::new();
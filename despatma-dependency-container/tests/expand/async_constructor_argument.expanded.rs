@@ -39,8 +39,22 @@ impl<'a> DependencyContainer<'a> {
             _phantom: Default::default(),
         }
     }
+    /// The names of the singleton and scoped dependencies which have been created, without creating any others
+    pub fn initialized(&self) -> Vec<&'static str> {
+        #[allow(unused_mut)]
+        let mut initialized = Vec::new();
+        initialized
+    }
+    /// The dependencies of this container and how they are wired together in the DOT format
+    pub fn dependency_graph_dot() -> &'static str {
+        "digraph DependencyContainer {\n    node [shape=box];\n    \"service\" [label=\"service\\nTransient, async\"];\n    \"config\" [label=\"config\\nEmbedded\"];\n    \"service\" -> \"config\";\n}\n"
+    }
+    /// The dependencies of this container and how they are wired together as a Mermaid flowchart
+    pub fn dependency_graph_mermaid() -> &'static str {
+        "graph TD\n    service[\"service<br/>Transient, async\"]\n    config[\"config<br/>Embedded\"]\n    service --> config\n"
+    }
     pub async fn service(&'a self) -> Service {
-        let config = self.config.as_ref();
+        let config = self.config();
         sleep(Duration::from_millis(10)).await;
         Service::new(config.port)
     }
@@ -48,6 +62,57 @@ impl<'a> DependencyContainer<'a> {
         self.config.as_ref()
     }
 }
+/// Builder to set the constructor arguments of [`DependencyContainer`] by name.
+#[must_use]
+struct DependencyContainerBuilder<'a, __Config> {
+    config: __Config,
+    _phantom: std::marker::PhantomData<&'a ()>,
+}
+impl<'a> DependencyContainer<'a> {
+    pub fn builder() -> DependencyContainerBuilder<'a, ()> {
+        DependencyContainerBuilder {
+            config: (),
+            _phantom: Default::default(),
+        }
+    }
+}
+impl<'a, __Config> DependencyContainerBuilder<'a, __Config> {
+    pub fn config(self, config: Config) -> DependencyContainerBuilder<'a, (Config,)> {
+        DependencyContainerBuilder {
+            config: (config,),
+            _phantom: Default::default(),
+        }
+    }
+    pub fn build(self) -> DependencyContainer<'a>
+    where
+        __Config: DependencyContainerBuilderHasConfig<'a>,
+    {
+        DependencyContainer::new(
+            DependencyContainerBuilderHasConfig::into_argument(self.config),
+        )
+    }
+}
+#[doc(hidden)]
+#[diagnostic::on_unimplemented(
+    message = "The `config` constructor argument of `DependencyContainer` has not been set",
+    label = "`config` is missing",
+    note = "Set it with `.config(...)` before calling `.build()`"
+)]
+trait DependencyContainerBuilderHasConfig<'a> {
+    fn into_argument(self) -> Config;
+}
+impl<'a> DependencyContainerBuilderHasConfig<'a> for (Config,) {
+    fn into_argument(self) -> Config {
+        self.0
+    }
+}
+impl<'a> core::fmt::Debug for DependencyContainer<'a> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        #[allow(unused_mut)]
+        let mut debug = f.debug_struct("DependencyContainer");
+        debug.finish_non_exhaustive()
+    }
+}
 fn main() {
     let body = async {
         let config = Config { port: 8080 };
@@ -35,18 +35,36 @@ impl<'a> DependencyContainer<'a> {
             _phantom: Default::default(),
         }
     }
+    /// The names of the singleton and scoped dependencies which have been created, without creating any others
+    pub fn initialized(&self) -> Vec<&'static str> {
+        #[allow(unused_mut)]
+        let mut initialized = Vec::new();
+        initialized
+    }
+    /// The dependencies of this container and how they are wired together in the DOT format
+    pub fn dependency_graph_dot() -> &'static str {
+        "digraph DependencyContainer {\n    node [shape=box];\n    \"config\" [label=\"config\\nTransient, async\"];\n    \"service\" [label=\"service\\nTransient, async\"];\n    \"service\" -> \"config\";\n}\n"
+    }
+    /// The dependencies of this container and how they are wired together as a Mermaid flowchart
+    pub fn dependency_graph_mermaid() -> &'static str {
+        "graph TD\n    config[\"config<br/>Transient, async\"]\n    service[\"service<br/>Transient, async\"]\n    service --> config\n"
+    }
     pub async fn config(&'a self) -> Config {
         sleep(Duration::from_millis(10)).await;
         Config { port: 8080 }
     }
     pub async fn service(&'a self) -> Service {
-        let config = {
-            sleep(Duration::from_millis(10)).await;
-            Config { port: 8080 }
-        };
+        let config = self.config().await;
         Service::new(config.port)
     }
 }
+impl<'a> core::fmt::Debug for DependencyContainer<'a> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        #[allow(unused_mut)]
+        let mut debug = f.debug_struct("DependencyContainer");
+        debug.finish_non_exhaustive()
+    }
+}
 fn main() {
     let body = async {
         let container = DependencyContainer::new();
@@ -0,0 +1,232 @@
+use std::sync::{Arc, Mutex};
+type Log = Arc<Mutex<Vec<&'static str>>>;
+struct Pool {
+    log: Log,
+}
+impl Pool {
+    async fn close(&self) {
+        self.log.lock().unwrap().push("pool");
+    }
+}
+struct Worker {
+    log: Log,
+}
+impl Worker {
+    fn stop(&self) {
+        self.log.lock().unwrap().push("worker");
+    }
+}
+struct DependencyContainer<'a> {
+    pool: std::sync::Arc<async_once_cell::OnceCell<Pool>>,
+    worker: std::sync::Arc<std::sync::OnceLock<Worker>>,
+    log: std::sync::Arc<Log>,
+    _is_scope: bool,
+    _phantom: std::marker::PhantomData<&'a ()>,
+}
+#[automatically_derived]
+impl<'a> ::core::clone::Clone for DependencyContainer<'a> {
+    #[inline]
+    fn clone(&self) -> DependencyContainer<'a> {
+        DependencyContainer {
+            pool: ::core::clone::Clone::clone(&self.pool),
+            worker: ::core::clone::Clone::clone(&self.worker),
+            log: ::core::clone::Clone::clone(&self.log),
+            _is_scope: ::core::clone::Clone::clone(&self._is_scope),
+            _phantom: ::core::clone::Clone::clone(&self._phantom),
+        }
+    }
+}
+impl<'a> DependencyContainer<'a> {
+    pub fn new(log: Log) -> Self {
+        Self {
+            pool: Default::default(),
+            worker: Default::default(),
+            log: std::sync::Arc::new(log),
+            _is_scope: false,
+            _phantom: Default::default(),
+        }
+    }
+    pub fn new_scope(&self) -> Self {
+        Self {
+            pool: self.pool.clone(),
+            worker: self.worker.clone(),
+            log: self.log.clone(),
+            _is_scope: true,
+            _phantom: Default::default(),
+        }
+    }
+    /// Call the dispose hooks of the dependencies which have been created, starting with those that depend on the others.
+    /// A scope only disposes its own scoped dependencies.
+    pub fn shutdown(self) -> impl std::future::Future<Output = ()> + Send + 'a {
+        async move {
+            if !self._is_scope {
+                if let Some(worker) = self.worker.get() {
+                    worker.stop();
+                }
+            }
+            if !self._is_scope {
+                if let Some(pool) = self.pool.get() {
+                    pool.close().await;
+                }
+            }
+        }
+    }
+    /// The names of the singleton and scoped dependencies which have been created, without creating any others
+    pub fn initialized(&self) -> Vec<&'static str> {
+        #[allow(unused_mut)]
+        let mut initialized = Vec::new();
+        if self.pool.get().is_some() {
+            initialized.push("pool");
+        }
+        if self.worker.get().is_some() {
+            initialized.push("worker");
+        }
+        initialized
+    }
+    /// The dependencies of this container and how they are wired together in the DOT format
+    pub fn dependency_graph_dot() -> &'static str {
+        "digraph DependencyContainer {\n    node [shape=box];\n    \"pool\" [label=\"pool\\nSingleton, async\"];\n    \"worker\" [label=\"worker\\nSingleton, async\"];\n    \"log\" [label=\"log\\nEmbedded\"];\n    \"pool\" -> \"log\";\n    \"worker\" -> \"log\";\n    \"worker\" -> \"pool\";\n}\n"
+    }
+    /// The dependencies of this container and how they are wired together as a Mermaid flowchart
+    pub fn dependency_graph_mermaid() -> &'static str {
+        "graph TD\n    pool[\"pool<br/>Singleton, async\"]\n    worker[\"worker<br/>Singleton, async\"]\n    log[\"log<br/>Embedded\"]\n    pool --> log\n    worker --> log\n    worker --> pool\n"
+    }
+    pub fn pool(&'a self) -> impl std::future::Future<Output = &Pool> + Send + 'a {
+        async move {
+            let log = self.log();
+            self.pool.get_or_init(async { Pool { log: log.clone() } }).await
+        }
+    }
+    pub fn worker(&'a self) -> impl std::future::Future<Output = &Worker> + Send + 'a {
+        async move {
+            let log = self.log();
+            let pool = self.pool().await;
+            self.worker
+                .get_or_init(|| {
+                    let _ = pool;
+                    Worker { log: log.clone() }
+                })
+        }
+    }
+    pub fn log(&'a self) -> &Log {
+        self.log.as_ref()
+    }
+}
+/// Builder to set the constructor arguments of [`DependencyContainer`] by name.
+#[must_use]
+struct DependencyContainerBuilder<'a, __Log> {
+    log: __Log,
+    _phantom: std::marker::PhantomData<&'a ()>,
+}
+impl<'a> DependencyContainer<'a> {
+    pub fn builder() -> DependencyContainerBuilder<'a, ()> {
+        DependencyContainerBuilder {
+            log: (),
+            _phantom: Default::default(),
+        }
+    }
+}
+impl<'a, __Log> DependencyContainerBuilder<'a, __Log> {
+    pub fn log(self, log: Log) -> DependencyContainerBuilder<'a, (Log,)> {
+        DependencyContainerBuilder {
+            log: (log,),
+            _phantom: Default::default(),
+        }
+    }
+    pub fn build(self) -> DependencyContainer<'a>
+    where
+        __Log: DependencyContainerBuilderHasLog<'a>,
+    {
+        DependencyContainer::new(
+            DependencyContainerBuilderHasLog::into_argument(self.log),
+        )
+    }
+}
+#[doc(hidden)]
+#[diagnostic::on_unimplemented(
+    message = "The `log` constructor argument of `DependencyContainer` has not been set",
+    label = "`log` is missing",
+    note = "Set it with `.log(...)` before calling `.build()`"
+)]
+trait DependencyContainerBuilderHasLog<'a> {
+    fn into_argument(self) -> Log;
+}
+impl<'a> DependencyContainerBuilderHasLog<'a> for (Log,) {
+    fn into_argument(self) -> Log {
+        self.0
+    }
+}
+impl<'a> core::fmt::Debug for DependencyContainer<'a> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        #[allow(unused_mut)]
+        let mut debug = f.debug_struct("DependencyContainer");
+        debug
+            .field(
+                "pool",
+                &format_args!(
+                    "{0}", if self.pool.get().is_some() { "initialized" } else {
+                    "uninitialized" }
+                ),
+            );
+        debug
+            .field(
+                "worker",
+                &format_args!(
+                    "{0}", if self.worker.get().is_some() { "initialized" } else {
+                    "uninitialized" }
+                ),
+            );
+        debug.finish_non_exhaustive()
+    }
+}
+const _: () = {
+    fn assert_send_sync<T: ?Sized + Send + Sync>() {}
+    #[allow(dead_code)]
+    fn assert_dependencies<'a>() {
+        assert_send_sync::<Pool>();
+        assert_send_sync::<Worker>();
+        assert_send_sync::<Log>();
+    }
+};
+fn main() {
+    let body = async {
+        let log = Log::default();
+        let container = DependencyContainer::new(log.clone());
+        let _worker = container.worker().await;
+        tokio::spawn(container.shutdown()).await.unwrap();
+        match (
+            &*log.lock().unwrap(),
+            &::alloc::boxed::box_assume_init_into_vec_unsafe(
+                ::alloc::intrinsics::write_box_via_move(
+                    ::alloc::boxed::Box::new_uninit(),
+                    ["worker", "pool"],
+                ),
+            ),
+        ) {
+            (left_val, right_val) => {
+                if !(*left_val == *right_val) {
+                    let kind = ::core::panicking::AssertKind::Eq;
+                    ::core::panicking::assert_failed(
+                        kind,
+                        &*left_val,
+                        &*right_val,
+                        ::core::option::Option::None,
+                    );
+                }
+            }
+        };
+    };
+    #[allow(
+        clippy::expect_used,
+        clippy::diverging_sub_expression,
+        clippy::needless_return,
+        clippy::unwrap_in_result
+    )]
+    {
+        return tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .build()
+            .expect("Failed building the Runtime")
+            .block_on(body);
+    }
+}
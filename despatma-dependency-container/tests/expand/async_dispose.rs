@@ -0,0 +1,54 @@
+use std::sync::{Arc, Mutex};
+
+type Log = Arc<Mutex<Vec<&'static str>>>;
+
+struct Pool {
+    log: Log,
+}
+
+impl Pool {
+    async fn close(&self) {
+        self.log.lock().unwrap().push("pool");
+    }
+}
+
+struct Worker {
+    log: Log,
+}
+
+impl Worker {
+    fn stop(&self) {
+        self.log.lock().unwrap().push("worker");
+    }
+}
+
+#[despatma_dependency_container::dependency_container(sync)]
+impl DependencyContainer {
+    fn new(log: Log) {}
+
+    #[Singleton]
+    #[Dispose(async close)]
+    async fn pool(&self, log: &Log) -> Pool {
+        Pool { log: log.clone() }
+    }
+
+    #[Singleton]
+    #[Dispose(stop)]
+    fn worker(&self, log: &Log, pool: &Pool) -> Worker {
+        let _ = pool;
+
+        Worker { log: log.clone() }
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    let log = Log::default();
+    let container = DependencyContainer::new(log.clone());
+    let _worker = container.worker().await;
+
+    // The shutdown should be able to move between threads
+    tokio::spawn(container.shutdown()).await.unwrap();
+
+    assert_eq!(*log.lock().unwrap(), vec!["worker", "pool"]);
+}
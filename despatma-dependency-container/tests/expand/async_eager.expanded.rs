@@ -0,0 +1,129 @@
+use std::time::Duration;
+use tokio::time::sleep;
+struct Pool;
+impl Pool {
+    async fn open() -> Self {
+        sleep(Duration::from_millis(10)).await;
+        Self
+    }
+}
+struct Service;
+struct DependencyContainer<'a> {
+    pool: std::sync::Arc<async_once_cell::OnceCell<Pool>>,
+    service: std::sync::Arc<std::sync::OnceLock<Service>>,
+    _phantom: std::marker::PhantomData<&'a ()>,
+}
+#[automatically_derived]
+impl<'a> ::core::clone::Clone for DependencyContainer<'a> {
+    #[inline]
+    fn clone(&self) -> DependencyContainer<'a> {
+        DependencyContainer {
+            pool: ::core::clone::Clone::clone(&self.pool),
+            service: ::core::clone::Clone::clone(&self.service),
+            _phantom: ::core::clone::Clone::clone(&self._phantom),
+        }
+    }
+}
+impl<'a> DependencyContainer<'a> {
+    pub fn new() -> Self {
+        Self {
+            pool: Default::default(),
+            service: Default::default(),
+            _phantom: Default::default(),
+        }
+    }
+    pub fn new_scope(&self) -> Self {
+        Self {
+            pool: self.pool.clone(),
+            service: self.service.clone(),
+            _phantom: Default::default(),
+        }
+    }
+    /// Create the eager singletons now rather than when they are first used. Their dependencies are created first.
+    pub fn init(&'a self) -> impl std::future::Future<Output = ()> + Send + 'a {
+        async move {
+            self.pool().await;
+            self.service();
+        }
+    }
+    /// The names of the singleton and scoped dependencies which have been created, without creating any others
+    pub fn initialized(&self) -> Vec<&'static str> {
+        #[allow(unused_mut)]
+        let mut initialized = Vec::new();
+        if self.pool.get().is_some() {
+            initialized.push("pool");
+        }
+        if self.service.get().is_some() {
+            initialized.push("service");
+        }
+        initialized
+    }
+    /// The dependencies of this container and how they are wired together in the DOT format
+    pub fn dependency_graph_dot() -> &'static str {
+        "digraph DependencyContainer {\n    node [shape=box];\n    \"pool\" [label=\"pool\\nSingleton, async\"];\n    \"service\" [label=\"service\\nSingleton\"];\n}\n"
+    }
+    /// The dependencies of this container and how they are wired together as a Mermaid flowchart
+    pub fn dependency_graph_mermaid() -> &'static str {
+        "graph TD\n    pool[\"pool<br/>Singleton, async\"]\n    service[\"service<br/>Singleton\"]\n"
+    }
+    pub fn pool(&'a self) -> impl std::future::Future<Output = &Pool> + Send + 'a {
+        async move { self.pool.get_or_init(async { Pool::open().await }).await }
+    }
+    pub fn service(&'a self) -> &Service {
+        self.service.get_or_init(|| { Service })
+    }
+}
+impl<'a> core::fmt::Debug for DependencyContainer<'a> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        #[allow(unused_mut)]
+        let mut debug = f.debug_struct("DependencyContainer");
+        debug
+            .field(
+                "pool",
+                &format_args!(
+                    "{0}", if self.pool.get().is_some() { "initialized" } else {
+                    "uninitialized" }
+                ),
+            );
+        debug
+            .field(
+                "service",
+                &format_args!(
+                    "{0}", if self.service.get().is_some() { "initialized" } else {
+                    "uninitialized" }
+                ),
+            );
+        debug.finish_non_exhaustive()
+    }
+}
+const _: () = {
+    fn assert_send_sync<T: ?Sized + Send + Sync>() {}
+    #[allow(dead_code)]
+    fn assert_dependencies<'a>() {
+        assert_send_sync::<Pool>();
+        assert_send_sync::<Service>();
+    }
+};
+fn main() {
+    let body = async {
+        let container = DependencyContainer::new();
+        tokio::spawn(async move {
+                container.init().await;
+            })
+            .await
+            .unwrap();
+    };
+    #[allow(
+        clippy::expect_used,
+        clippy::diverging_sub_expression,
+        clippy::needless_return,
+        clippy::unwrap_in_result
+    )]
+    {
+        return tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .build()
+            .expect("Failed building the Runtime")
+            .block_on(body);
+    }
+}
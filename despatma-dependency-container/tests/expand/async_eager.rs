@@ -0,0 +1,38 @@
+use std::time::Duration;
+use tokio::time::sleep;
+
+struct Pool;
+
+impl Pool {
+    async fn open() -> Self {
+        sleep(Duration::from_millis(10)).await;
+        Self
+    }
+}
+
+struct Service;
+
+#[despatma_dependency_container::dependency_container(sync)]
+impl DependencyContainer {
+    #[Singleton(eager)]
+    async fn pool(&self) -> Pool {
+        Pool::open().await
+    }
+
+    #[Singleton(eager)]
+    fn service(&self) -> Service {
+        Service
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    let container = DependencyContainer::new();
+
+    // The warm-up should be able to move between threads
+    tokio::spawn(async move {
+        container.init().await;
+    })
+    .await
+    .unwrap();
+}
@@ -49,6 +49,23 @@ impl<'a> DependencyContainer<'a> {
             _phantom: Default::default(),
         }
     }
+    /// The names of the singleton and scoped dependencies which have been created, without creating any others
+    pub fn initialized(&self) -> Vec<&'static str> {
+        #[allow(unused_mut)]
+        let mut initialized = Vec::new();
+        if self.dal.get().is_some() {
+            initialized.push("dal");
+        }
+        initialized
+    }
+    /// The dependencies of this container and how they are wired together in the DOT format
+    pub fn dependency_graph_dot() -> &'static str {
+        "digraph DependencyContainer {\n    node [shape=box];\n    \"dal\" [label=\"dal\\nSingleton, boxed\"];\n    \"service\" [label=\"service\\nTransient\"];\n    \"service\" -> \"dal\";\n}\n"
+    }
+    /// The dependencies of this container and how they are wired together as a Mermaid flowchart
+    pub fn dependency_graph_mermaid() -> &'static str {
+        "graph TD\n    dal[\"dal<br/>Singleton, boxed\"]\n    service[\"service<br/>Transient\"]\n    service --> dal\n"
+    }
     pub fn dal(&'a self) -> &std::boxed::Box<dyn DAL> {
         self.dal
             .get_or_init(|| {
@@ -56,14 +73,25 @@ impl<'a> DependencyContainer<'a> {
             })
     }
     pub fn service(&'a self) -> Service<&Box<dyn DAL>> {
-        let dal = self
-            .dal
-            .get_or_init(|| {
-                if true { Box::new(PostgresDAL) } else { Box::new(SQLiteDAL) }
-            });
+        let dal = self.dal();
         Service::new(dal)
     }
 }
+impl<'a> core::fmt::Debug for DependencyContainer<'a> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        #[allow(unused_mut)]
+        let mut debug = f.debug_struct("DependencyContainer");
+        debug
+            .field(
+                "dal",
+                &format_args!(
+                    "{0}", if self.dal.get().is_some() { "initialized" } else {
+                    "uninitialized" }
+                ),
+            );
+        debug.finish_non_exhaustive()
+    }
+}
 fn main() {
     let container = DependencyContainer::new();
     let _service = container.service();
@@ -0,0 +1,246 @@
+struct Config {
+    port: u32,
+}
+struct Clock {
+    offset: u64,
+}
+#[automatically_derived]
+impl ::core::default::Default for Clock {
+    #[inline]
+    fn default() -> Clock {
+        Clock {
+            offset: ::core::default::Default::default(),
+        }
+    }
+}
+struct Service {
+    port: u32,
+    offset: u64,
+    retries: u8,
+}
+struct DependencyContainer<'a> {
+    config: std::sync::Arc<Config>,
+    clock: std::sync::Arc<Clock>,
+    retries: std::sync::Arc<u8>,
+    _phantom: std::marker::PhantomData<&'a ()>,
+}
+#[automatically_derived]
+impl<'a> ::core::clone::Clone for DependencyContainer<'a> {
+    #[inline]
+    fn clone(&self) -> DependencyContainer<'a> {
+        DependencyContainer {
+            config: ::core::clone::Clone::clone(&self.config),
+            clock: ::core::clone::Clone::clone(&self.clock),
+            retries: ::core::clone::Clone::clone(&self.retries),
+            _phantom: ::core::clone::Clone::clone(&self._phantom),
+        }
+    }
+}
+impl<'a> DependencyContainer<'a> {
+    pub fn new(config: Config, clock: Clock, retries: u8) -> Self {
+        Self {
+            config: std::sync::Arc::new(config),
+            clock: std::sync::Arc::new(clock),
+            retries: std::sync::Arc::new(retries),
+            _phantom: Default::default(),
+        }
+    }
+    pub fn new_scope(&self) -> Self {
+        Self {
+            config: self.config.clone(),
+            clock: self.clock.clone(),
+            retries: self.retries.clone(),
+            _phantom: Default::default(),
+        }
+    }
+    /// The names of the singleton and scoped dependencies which have been created, without creating any others
+    pub fn initialized(&self) -> Vec<&'static str> {
+        #[allow(unused_mut)]
+        let mut initialized = Vec::new();
+        initialized
+    }
+    /// The dependencies of this container and how they are wired together in the DOT format
+    pub fn dependency_graph_dot() -> &'static str {
+        "digraph DependencyContainer {\n    node [shape=box];\n    \"service\" [label=\"service\\nTransient\"];\n    \"config\" [label=\"config\\nEmbedded\"];\n    \"clock\" [label=\"clock\\nEmbedded\"];\n    \"retries\" [label=\"retries\\nEmbedded\"];\n    \"service\" -> \"config\";\n    \"service\" -> \"clock\";\n    \"service\" -> \"retries\";\n}\n"
+    }
+    /// The dependencies of this container and how they are wired together as a Mermaid flowchart
+    pub fn dependency_graph_mermaid() -> &'static str {
+        "graph TD\n    service[\"service<br/>Transient\"]\n    config[\"config<br/>Embedded\"]\n    clock[\"clock<br/>Embedded\"]\n    retries[\"retries<br/>Embedded\"]\n    service --> config\n    service --> clock\n    service --> retries\n"
+    }
+    pub fn service(&'a self) -> Service {
+        let config = self.config();
+        let clock = self.clock();
+        let retries = self.retries();
+        Service {
+            port: config.port,
+            offset: clock.offset,
+            retries: *retries,
+        }
+    }
+    pub fn config(&'a self) -> &Config {
+        self.config.as_ref()
+    }
+    pub fn clock(&'a self) -> &Clock {
+        self.clock.as_ref()
+    }
+    pub fn retries(&'a self) -> &u8 {
+        self.retries.as_ref()
+    }
+}
+/// Builder to set the constructor arguments of [`DependencyContainer`] by name.
+#[must_use]
+struct DependencyContainerBuilder<'a, __Config> {
+    config: __Config,
+    clock: Option<Clock>,
+    retries: Option<u8>,
+    _phantom: std::marker::PhantomData<&'a ()>,
+}
+impl<'a> DependencyContainer<'a> {
+    pub fn builder() -> DependencyContainerBuilder<'a, ()> {
+        DependencyContainerBuilder {
+            config: (),
+            clock: None,
+            retries: None,
+            _phantom: Default::default(),
+        }
+    }
+}
+impl<'a, __Config> DependencyContainerBuilder<'a, __Config> {
+    pub fn config(self, config: Config) -> DependencyContainerBuilder<'a, (Config,)> {
+        DependencyContainerBuilder {
+            config: (config,),
+            clock: self.clock,
+            retries: self.retries,
+            _phantom: Default::default(),
+        }
+    }
+    pub fn clock(mut self, clock: Clock) -> Self {
+        self.clock = Some(clock);
+        self
+    }
+    pub fn retries(mut self, retries: u8) -> Self {
+        self.retries = Some(retries);
+        self
+    }
+    pub fn build(self) -> DependencyContainer<'a>
+    where
+        __Config: DependencyContainerBuilderHasConfig<'a>,
+    {
+        DependencyContainer::new(
+            DependencyContainerBuilderHasConfig::into_argument(self.config),
+            self.clock.unwrap_or_else(|| Default::default()),
+            self.retries.unwrap_or_else(|| 3),
+        )
+    }
+}
+#[doc(hidden)]
+#[diagnostic::on_unimplemented(
+    message = "The `config` constructor argument of `DependencyContainer` has not been set",
+    label = "`config` is missing",
+    note = "Set it with `.config(...)` before calling `.build()`"
+)]
+trait DependencyContainerBuilderHasConfig<'a> {
+    fn into_argument(self) -> Config;
+}
+impl<'a> DependencyContainerBuilderHasConfig<'a> for (Config,) {
+    fn into_argument(self) -> Config {
+        self.0
+    }
+}
+impl<'a> core::fmt::Debug for DependencyContainer<'a> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        #[allow(unused_mut)]
+        let mut debug = f.debug_struct("DependencyContainer");
+        debug.finish_non_exhaustive()
+    }
+}
+fn main() {
+    let container = DependencyContainer::builder()
+        .retries(5)
+        .config(Config { port: 8080 })
+        .build();
+    let service = container.service();
+    match (&service.port, &8080) {
+        (left_val, right_val) => {
+            if !(*left_val == *right_val) {
+                let kind = ::core::panicking::AssertKind::Eq;
+                ::core::panicking::assert_failed(
+                    kind,
+                    &*left_val,
+                    &*right_val,
+                    ::core::option::Option::None,
+                );
+            }
+        }
+    };
+    match (&service.offset, &0) {
+        (left_val, right_val) => {
+            if !(*left_val == *right_val) {
+                let kind = ::core::panicking::AssertKind::Eq;
+                ::core::panicking::assert_failed(
+                    kind,
+                    &*left_val,
+                    &*right_val,
+                    ::core::option::Option::None,
+                );
+            }
+        }
+    };
+    match (&service.retries, &5) {
+        (left_val, right_val) => {
+            if !(*left_val == *right_val) {
+                let kind = ::core::panicking::AssertKind::Eq;
+                ::core::panicking::assert_failed(
+                    kind,
+                    &*left_val,
+                    &*right_val,
+                    ::core::option::Option::None,
+                );
+            }
+        }
+    };
+    let container = DependencyContainer::builder()
+        .clock(Clock { offset: 10 })
+        .config(Config { port: 3000 })
+        .build();
+    let service = container.service();
+    match (&service.port, &3000) {
+        (left_val, right_val) => {
+            if !(*left_val == *right_val) {
+                let kind = ::core::panicking::AssertKind::Eq;
+                ::core::panicking::assert_failed(
+                    kind,
+                    &*left_val,
+                    &*right_val,
+                    ::core::option::Option::None,
+                );
+            }
+        }
+    };
+    match (&service.offset, &10) {
+        (left_val, right_val) => {
+            if !(*left_val == *right_val) {
+                let kind = ::core::panicking::AssertKind::Eq;
+                ::core::panicking::assert_failed(
+                    kind,
+                    &*left_val,
+                    &*right_val,
+                    ::core::option::Option::None,
+                );
+            }
+        }
+    };
+    match (&service.retries, &3) {
+        (left_val, right_val) => {
+            if !(*left_val == *right_val) {
+                let kind = ::core::panicking::AssertKind::Eq;
+                ::core::panicking::assert_failed(
+                    kind,
+                    &*left_val,
+                    &*right_val,
+                    ::core::option::Option::None,
+                );
+            }
+        }
+    };
+}
@@ -0,0 +1,49 @@
+struct Config {
+    port: u32,
+}
+
+#[derive(Default)]
+struct Clock {
+    offset: u64,
+}
+
+struct Service {
+    port: u32,
+    offset: u64,
+    retries: u8,
+}
+
+#[despatma_dependency_container::dependency_container]
+impl DependencyContainer {
+    fn new(config: Config, #[default] clock: Clock, #[default(3)] retries: u8) {}
+
+    fn service(&self, config: &Config, clock: &Clock, retries: &u8) -> Service {
+        Service {
+            port: config.port,
+            offset: clock.offset,
+            retries: *retries,
+        }
+    }
+}
+
+fn main() {
+    let container = DependencyContainer::builder()
+        .retries(5)
+        .config(Config { port: 8080 })
+        .build();
+    let service = container.service();
+
+    assert_eq!(service.port, 8080);
+    assert_eq!(service.offset, 0);
+    assert_eq!(service.retries, 5);
+
+    let container = DependencyContainer::builder()
+        .clock(Clock { offset: 10 })
+        .config(Config { port: 3000 })
+        .build();
+    let service = container.service();
+
+    assert_eq!(service.port, 3000);
+    assert_eq!(service.offset, 10);
+    assert_eq!(service.retries, 3);
+}
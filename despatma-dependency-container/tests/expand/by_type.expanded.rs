@@ -0,0 +1,135 @@
+struct Config {
+    port: u32,
+}
+trait DAL {
+    fn name(&self) -> &'static str;
+}
+struct PostgresDAL;
+impl DAL for PostgresDAL {
+    fn name(&self) -> &'static str {
+        "postgres"
+    }
+}
+struct Service {
+    port: u32,
+    dal: &'static str,
+}
+struct DependencyContainer<'a> {
+    config: std::rc::Rc<std::cell::OnceCell<Config>>,
+    dal: std::rc::Rc<std::cell::OnceCell<std::boxed::Box<dyn DAL>>>,
+    _phantom: std::marker::PhantomData<&'a ()>,
+}
+#[automatically_derived]
+impl<'a> ::core::clone::Clone for DependencyContainer<'a> {
+    #[inline]
+    fn clone(&self) -> DependencyContainer<'a> {
+        DependencyContainer {
+            config: ::core::clone::Clone::clone(&self.config),
+            dal: ::core::clone::Clone::clone(&self.dal),
+            _phantom: ::core::clone::Clone::clone(&self._phantom),
+        }
+    }
+}
+impl<'a> DependencyContainer<'a> {
+    pub fn new() -> Self {
+        Self {
+            config: Default::default(),
+            dal: Default::default(),
+            _phantom: Default::default(),
+        }
+    }
+    pub fn new_scope(&self) -> Self {
+        Self {
+            config: self.config.clone(),
+            dal: self.dal.clone(),
+            _phantom: Default::default(),
+        }
+    }
+    /// The names of the singleton and scoped dependencies which have been created, without creating any others
+    pub fn initialized(&self) -> Vec<&'static str> {
+        #[allow(unused_mut)]
+        let mut initialized = Vec::new();
+        if self.config.get().is_some() {
+            initialized.push("config");
+        }
+        if self.dal.get().is_some() {
+            initialized.push("dal");
+        }
+        initialized
+    }
+    /// The dependencies of this container and how they are wired together in the DOT format
+    pub fn dependency_graph_dot() -> &'static str {
+        "digraph DependencyContainer {\n    node [shape=box];\n    \"config\" [label=\"config\\nSingleton\"];\n    \"dal\" [label=\"dal\\nSingleton, boxed\"];\n    \"service\" [label=\"service\\nTransient\"];\n    \"service\" -> \"config\" [label=\"settings\"];\n    \"service\" -> \"dal\" [label=\"data_layer\"];\n}\n"
+    }
+    /// The dependencies of this container and how they are wired together as a Mermaid flowchart
+    pub fn dependency_graph_mermaid() -> &'static str {
+        "graph TD\n    config[\"config<br/>Singleton\"]\n    dal[\"dal<br/>Singleton, boxed\"]\n    service[\"service<br/>Transient\"]\n    service -- settings --> config\n    service -- data_layer --> dal\n"
+    }
+    pub fn config(&'a self) -> &Config {
+        self.config.get_or_init(|| { Config { port: 8080 } })
+    }
+    pub fn dal(&'a self) -> &std::boxed::Box<dyn DAL> {
+        self.dal.get_or_init(|| { Box::new(PostgresDAL) })
+    }
+    pub fn service(&'a self) -> Service {
+        let settings = self.config();
+        let data_layer = self.dal();
+        Service {
+            port: settings.port,
+            dal: data_layer.name(),
+        }
+    }
+}
+impl<'a> core::fmt::Debug for DependencyContainer<'a> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        #[allow(unused_mut)]
+        let mut debug = f.debug_struct("DependencyContainer");
+        debug
+            .field(
+                "config",
+                &format_args!(
+                    "{0}", if self.config.get().is_some() { "initialized" } else {
+                    "uninitialized" }
+                ),
+            );
+        debug
+            .field(
+                "dal",
+                &format_args!(
+                    "{0}", if self.dal.get().is_some() { "initialized" } else {
+                    "uninitialized" }
+                ),
+            );
+        debug.finish_non_exhaustive()
+    }
+}
+fn main() {
+    let container = DependencyContainer::new();
+    let service = container.service();
+    match (&service.port, &8080) {
+        (left_val, right_val) => {
+            if !(*left_val == *right_val) {
+                let kind = ::core::panicking::AssertKind::Eq;
+                ::core::panicking::assert_failed(
+                    kind,
+                    &*left_val,
+                    &*right_val,
+                    ::core::option::Option::None,
+                );
+            }
+        }
+    };
+    match (&service.dal, &"postgres") {
+        (left_val, right_val) => {
+            if !(*left_val == *right_val) {
+                let kind = ::core::panicking::AssertKind::Eq;
+                ::core::panicking::assert_failed(
+                    kind,
+                    &*left_val,
+                    &*right_val,
+                    ::core::option::Option::None,
+                );
+            }
+        }
+    };
+}
@@ -0,0 +1,48 @@
+struct Config {
+    port: u32,
+}
+
+trait DAL {
+    fn name(&self) -> &'static str;
+}
+
+struct PostgresDAL;
+
+impl DAL for PostgresDAL {
+    fn name(&self) -> &'static str {
+        "postgres"
+    }
+}
+
+struct Service {
+    port: u32,
+    dal: &'static str,
+}
+
+#[despatma_dependency_container::dependency_container(by_type)]
+impl DependencyContainer {
+    #[Singleton]
+    fn config(&self) -> Config {
+        Config { port: 8080 }
+    }
+
+    #[Singleton]
+    fn dal(&self) -> Box<dyn DAL> {
+        Box::new(PostgresDAL)
+    }
+
+    fn service(&self, settings: &Config, data_layer: &dyn DAL) -> Service {
+        Service {
+            port: settings.port,
+            dal: data_layer.name(),
+        }
+    }
+}
+
+fn main() {
+    let container = DependencyContainer::new();
+    let service = container.service();
+
+    assert_eq!(service.port, 8080);
+    assert_eq!(service.dal, "postgres");
+}
@@ -0,0 +1,126 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+static STARTED: AtomicUsize = AtomicUsize::new(0);
+struct Clock {
+    started: usize,
+}
+struct Audit {
+    started: usize,
+}
+struct DependencyContainer<'a> {
+    clock: std::rc::Rc<std::cell::OnceCell<Clock>>,
+    audit: std::rc::Rc<std::cell::OnceCell<Audit>>,
+    _phantom: std::marker::PhantomData<&'a ()>,
+}
+#[automatically_derived]
+impl<'a> ::core::clone::Clone for DependencyContainer<'a> {
+    #[inline]
+    fn clone(&self) -> DependencyContainer<'a> {
+        DependencyContainer {
+            clock: ::core::clone::Clone::clone(&self.clock),
+            audit: ::core::clone::Clone::clone(&self.audit),
+            _phantom: ::core::clone::Clone::clone(&self._phantom),
+        }
+    }
+}
+impl<'a> DependencyContainer<'a> {
+    pub fn new() -> Self {
+        Self {
+            clock: Default::default(),
+            audit: Default::default(),
+            _phantom: Default::default(),
+        }
+    }
+    pub fn new_scope(&self) -> Self {
+        Self {
+            clock: Default::default(),
+            audit: self.audit.clone(),
+            _phantom: Default::default(),
+        }
+    }
+    /// The names of the singleton and scoped dependencies which have been created, without creating any others
+    pub fn initialized(&self) -> Vec<&'static str> {
+        #[allow(unused_mut)]
+        let mut initialized = Vec::new();
+        if self.clock.get().is_some() {
+            initialized.push("clock");
+        }
+        if self.audit.get().is_some() {
+            initialized.push("audit");
+        }
+        initialized
+    }
+    /// The dependencies of this container and how they are wired together in the DOT format
+    pub fn dependency_graph_dot() -> &'static str {
+        "digraph DependencyContainer {\n    node [shape=box];\n    \"clock\" [label=\"clock\\nScoped\"];\n    \"audit\" [label=\"audit\\nSingleton\"];\n    \"audit\" -> \"clock\";\n}\n"
+    }
+    /// The dependencies of this container and how they are wired together as a Mermaid flowchart
+    pub fn dependency_graph_mermaid() -> &'static str {
+        "graph TD\n    clock[\"clock<br/>Scoped\"]\n    audit[\"audit<br/>Singleton\"]\n    audit --> clock\n"
+    }
+    pub fn clock(&'a self) -> &Clock {
+        self.clock
+            .get_or_init(|| {
+                Clock {
+                    started: STARTED.fetch_add(1, Ordering::SeqCst),
+                }
+            })
+    }
+    pub fn audit(&'a self) -> &Audit {
+        let clock = self.clock();
+        self.audit.get_or_init(|| { Audit { started: clock.started } })
+    }
+}
+impl<'a> core::fmt::Debug for DependencyContainer<'a> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        #[allow(unused_mut)]
+        let mut debug = f.debug_struct("DependencyContainer");
+        debug
+            .field(
+                "clock",
+                &format_args!(
+                    "{0}", if self.clock.get().is_some() { "initialized" } else {
+                    "uninitialized" }
+                ),
+            );
+        debug
+            .field(
+                "audit",
+                &format_args!(
+                    "{0}", if self.audit.get().is_some() { "initialized" } else {
+                    "uninitialized" }
+                ),
+            );
+        debug.finish_non_exhaustive()
+    }
+}
+fn main() {
+    let container = DependencyContainer::new();
+    let first = container.new_scope();
+    let second = container.new_scope();
+    match (&first.audit().started, &second.audit().started) {
+        (left_val, right_val) => {
+            if !(*left_val == *right_val) {
+                let kind = ::core::panicking::AssertKind::Eq;
+                ::core::panicking::assert_failed(
+                    kind,
+                    &*left_val,
+                    &*right_val,
+                    ::core::option::Option::None,
+                );
+            }
+        }
+    };
+    match (&first.clock().started, &second.clock().started) {
+        (left_val, right_val) => {
+            if *left_val == *right_val {
+                let kind = ::core::panicking::AssertKind::Ne;
+                ::core::panicking::assert_failed(
+                    kind,
+                    &*left_val,
+                    &*right_val,
+                    ::core::option::Option::None,
+                );
+            }
+        }
+    };
+}
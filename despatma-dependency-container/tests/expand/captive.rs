@@ -0,0 +1,38 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static STARTED: AtomicUsize = AtomicUsize::new(0);
+
+struct Clock {
+    started: usize,
+}
+
+struct Audit {
+    started: usize,
+}
+
+#[despatma_dependency_container::dependency_container]
+impl DependencyContainer {
+    #[Scoped]
+    fn clock(&self) -> Clock {
+        Clock {
+            started: STARTED.fetch_add(1, Ordering::SeqCst),
+        }
+    }
+
+    // Deliberately keeps the clock of the first scope
+    #[Singleton]
+    fn audit(&self, #[captive] clock: &Clock) -> Audit {
+        Audit {
+            started: clock.started,
+        }
+    }
+}
+
+fn main() {
+    let container = DependencyContainer::new();
+    let first = container.new_scope();
+    let second = container.new_scope();
+
+    assert_eq!(first.audit().started, second.audit().started);
+    assert_ne!(first.clock().started, second.clock().started);
+}
@@ -33,10 +33,31 @@ impl<'a> ConfigContainer<'a> {
             _phantom: Default::default(),
         }
     }
+    /// The names of the singleton and scoped dependencies which have been created, without creating any others
+    pub fn initialized(&self) -> Vec<&'static str> {
+        #[allow(unused_mut)]
+        let mut initialized = Vec::new();
+        initialized
+    }
+    /// The dependencies of this container and how they are wired together in the DOT format
+    pub fn dependency_graph_dot() -> &'static str {
+        "digraph ConfigContainer {\n    node [shape=box];\n    \"config\" [label=\"config\\nTransient\"];\n}\n"
+    }
+    /// The dependencies of this container and how they are wired together as a Mermaid flowchart
+    pub fn dependency_graph_mermaid() -> &'static str {
+        "graph TD\n    config[\"config<br/>Transient\"]\n"
+    }
     pub fn config(&'a self) -> Config {
         Config { port: 8080 }
     }
 }
+impl<'a> core::fmt::Debug for ConfigContainer<'a> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        #[allow(unused_mut)]
+        let mut debug = f.debug_struct("ConfigContainer");
+        debug.finish_non_exhaustive()
+    }
+}
 struct ServiceContainer<'a> {
     config_container: std::sync::Arc<ConfigContainer<'a>>,
     _phantom: std::marker::PhantomData<&'a ()>,
@@ -64,14 +85,84 @@ impl<'a> ServiceContainer<'a> {
             _phantom: Default::default(),
         }
     }
+    /// The names of the singleton and scoped dependencies which have been created, without creating any others
+    pub fn initialized(&self) -> Vec<&'static str> {
+        #[allow(unused_mut)]
+        let mut initialized = Vec::new();
+        initialized
+    }
+    /// The dependencies of this container and how they are wired together in the DOT format
+    pub fn dependency_graph_dot() -> &'static str {
+        "digraph ServiceContainer {\n    node [shape=box];\n    \"service\" [label=\"service\\nTransient\"];\n    \"config_container\" [label=\"config_container\\nEmbedded\"];\n    \"service\" -> \"config_container\";\n}\n"
+    }
+    /// The dependencies of this container and how they are wired together as a Mermaid flowchart
+    pub fn dependency_graph_mermaid() -> &'static str {
+        "graph TD\n    service[\"service<br/>Transient\"]\n    config_container[\"config_container<br/>Embedded\"]\n    service --> config_container\n"
+    }
     pub fn service(&'a self) -> Service {
-        let config_container = self.config_container.as_ref();
+        let config_container = self.config_container();
         Service::new(config_container.config().port)
     }
     pub fn config_container(&'a self) -> &ConfigContainer<'a> {
         self.config_container.as_ref()
     }
 }
+/// Builder to set the constructor arguments of [`ServiceContainer`] by name.
+#[must_use]
+struct ServiceContainerBuilder<'a, __ConfigContainer> {
+    config_container: __ConfigContainer,
+    _phantom: std::marker::PhantomData<&'a ()>,
+}
+impl<'a> ServiceContainer<'a> {
+    pub fn builder() -> ServiceContainerBuilder<'a, ()> {
+        ServiceContainerBuilder {
+            config_container: (),
+            _phantom: Default::default(),
+        }
+    }
+}
+impl<'a, __ConfigContainer> ServiceContainerBuilder<'a, __ConfigContainer> {
+    pub fn config_container(
+        self,
+        config_container: ConfigContainer<'a>,
+    ) -> ServiceContainerBuilder<'a, (ConfigContainer<'a>,)> {
+        ServiceContainerBuilder {
+            config_container: (config_container,),
+            _phantom: Default::default(),
+        }
+    }
+    pub fn build(self) -> ServiceContainer<'a>
+    where
+        __ConfigContainer: ServiceContainerBuilderHasConfigContainer<'a>,
+    {
+        ServiceContainer::new(
+            ServiceContainerBuilderHasConfigContainer::into_argument(
+                self.config_container,
+            ),
+        )
+    }
+}
+#[doc(hidden)]
+#[diagnostic::on_unimplemented(
+    message = "The `config_container` constructor argument of `ServiceContainer` has not been set",
+    label = "`config_container` is missing",
+    note = "Set it with `.config_container(...)` before calling `.build()`"
+)]
+trait ServiceContainerBuilderHasConfigContainer<'a> {
+    fn into_argument(self) -> ConfigContainer<'a>;
+}
+impl<'a> ServiceContainerBuilderHasConfigContainer<'a> for (ConfigContainer<'a>,) {
+    fn into_argument(self) -> ConfigContainer<'a> {
+        self.0
+    }
+}
+impl<'a> core::fmt::Debug for ServiceContainer<'a> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        #[allow(unused_mut)]
+        let mut debug = f.debug_struct("ServiceContainer");
+        debug.finish_non_exhaustive()
+    }
+}
 fn main() {
     let config_container = ConfigContainer::new();
     let container = ServiceContainer::new(config_container);
@@ -0,0 +1,303 @@
+use std::{cell::RefCell, rc::Rc};
+type Log = Rc<RefCell<Vec<&'static str>>>;
+struct Pool {
+    log: Log,
+}
+impl Pool {
+    fn close(&self) {
+        self.log.borrow_mut().push("pool");
+    }
+}
+struct Repository;
+struct Cache {
+    log: Log,
+    _repository: Repository,
+}
+impl Cache {
+    fn flush(&self) {
+        self.log.borrow_mut().push("cache");
+    }
+}
+struct Session {
+    log: Log,
+}
+impl Session {
+    fn end(&self) {
+        self.log.borrow_mut().push("session");
+    }
+}
+struct Unused;
+impl Unused {
+    fn close(&self) {
+        {
+            ::core::panicking::panic_fmt(
+                format_args!("should only dispose dependencies which were created"),
+            );
+        };
+    }
+}
+struct DependencyContainer<'a> {
+    cache: std::rc::Rc<std::cell::OnceCell<Cache>>,
+    pool: std::rc::Rc<std::cell::OnceCell<Pool>>,
+    session: std::rc::Rc<std::cell::OnceCell<Session>>,
+    unused: std::rc::Rc<std::cell::OnceCell<Unused>>,
+    log: std::sync::Arc<Log>,
+    _is_scope: bool,
+    _phantom: std::marker::PhantomData<&'a ()>,
+}
+#[automatically_derived]
+impl<'a> ::core::clone::Clone for DependencyContainer<'a> {
+    #[inline]
+    fn clone(&self) -> DependencyContainer<'a> {
+        DependencyContainer {
+            cache: ::core::clone::Clone::clone(&self.cache),
+            pool: ::core::clone::Clone::clone(&self.pool),
+            session: ::core::clone::Clone::clone(&self.session),
+            unused: ::core::clone::Clone::clone(&self.unused),
+            log: ::core::clone::Clone::clone(&self.log),
+            _is_scope: ::core::clone::Clone::clone(&self._is_scope),
+            _phantom: ::core::clone::Clone::clone(&self._phantom),
+        }
+    }
+}
+impl<'a> DependencyContainer<'a> {
+    pub fn new(log: Log) -> Self {
+        Self {
+            cache: Default::default(),
+            pool: Default::default(),
+            session: Default::default(),
+            unused: Default::default(),
+            log: std::sync::Arc::new(log),
+            _is_scope: false,
+            _phantom: Default::default(),
+        }
+    }
+    pub fn new_scope(&self) -> Self {
+        Self {
+            cache: self.cache.clone(),
+            pool: self.pool.clone(),
+            session: Default::default(),
+            unused: self.unused.clone(),
+            log: self.log.clone(),
+            _is_scope: true,
+            _phantom: Default::default(),
+        }
+    }
+    /// Call the dispose hooks of the dependencies which have been created, starting with those that depend on the others.
+    /// A scope only disposes its own scoped dependencies.
+    pub fn shutdown(self) {
+        if !self._is_scope {
+            if let Some(unused) = self.unused.get() {
+                unused.close();
+            }
+        }
+        if let Some(session) = self.session.get() {
+            session.end();
+        }
+        if !self._is_scope {
+            if let Some(cache) = self.cache.get() {
+                cache.flush();
+            }
+        }
+        if !self._is_scope {
+            if let Some(pool) = self.pool.get() {
+                pool.close();
+            }
+        }
+    }
+    /// The names of the singleton and scoped dependencies which have been created, without creating any others
+    pub fn initialized(&self) -> Vec<&'static str> {
+        #[allow(unused_mut)]
+        let mut initialized = Vec::new();
+        if self.cache.get().is_some() {
+            initialized.push("cache");
+        }
+        if self.pool.get().is_some() {
+            initialized.push("pool");
+        }
+        if self.session.get().is_some() {
+            initialized.push("session");
+        }
+        if self.unused.get().is_some() {
+            initialized.push("unused");
+        }
+        initialized
+    }
+    /// The dependencies of this container and how they are wired together in the DOT format
+    pub fn dependency_graph_dot() -> &'static str {
+        "digraph DependencyContainer {\n    node [shape=box];\n    \"cache\" [label=\"cache\\nSingleton\"];\n    \"repository\" [label=\"repository\\nTransient\"];\n    \"pool\" [label=\"pool\\nSingleton\"];\n    \"session\" [label=\"session\\nScoped\"];\n    \"unused\" [label=\"unused\\nSingleton\"];\n    \"log\" [label=\"log\\nEmbedded\"];\n    \"cache\" -> \"log\";\n    \"cache\" -> \"repository\";\n    \"repository\" -> \"pool\";\n    \"pool\" -> \"log\";\n    \"session\" -> \"log\";\n    \"session\" -> \"cache\";\n}\n"
+    }
+    /// The dependencies of this container and how they are wired together as a Mermaid flowchart
+    pub fn dependency_graph_mermaid() -> &'static str {
+        "graph TD\n    cache[\"cache<br/>Singleton\"]\n    repository[\"repository<br/>Transient\"]\n    pool[\"pool<br/>Singleton\"]\n    session[\"session<br/>Scoped\"]\n    unused[\"unused<br/>Singleton\"]\n    log[\"log<br/>Embedded\"]\n    cache --> log\n    cache --> repository\n    repository --> pool\n    pool --> log\n    session --> log\n    session --> cache\n"
+    }
+    pub fn cache(&'a self) -> &Cache {
+        let log = self.log();
+        let repository = self.repository();
+        self.cache
+            .get_or_init(|| {
+                Cache {
+                    log: log.clone(),
+                    _repository: repository,
+                }
+            })
+    }
+    pub fn repository(&'a self) -> Repository {
+        let pool = self.pool();
+        let _ = pool;
+        Repository
+    }
+    pub fn pool(&'a self) -> &Pool {
+        let log = self.log();
+        self.pool.get_or_init(|| { Pool { log: log.clone() } })
+    }
+    pub fn session(&'a self) -> &Session {
+        let log = self.log();
+        let cache = self.cache();
+        self.session
+            .get_or_init(|| {
+                let _ = cache;
+                Session { log: log.clone() }
+            })
+    }
+    pub fn unused(&'a self) -> &Unused {
+        self.unused.get_or_init(|| { Unused })
+    }
+    pub fn log(&'a self) -> &Log {
+        self.log.as_ref()
+    }
+}
+/// Builder to set the constructor arguments of [`DependencyContainer`] by name.
+#[must_use]
+struct DependencyContainerBuilder<'a, __Log> {
+    log: __Log,
+    _phantom: std::marker::PhantomData<&'a ()>,
+}
+impl<'a> DependencyContainer<'a> {
+    pub fn builder() -> DependencyContainerBuilder<'a, ()> {
+        DependencyContainerBuilder {
+            log: (),
+            _phantom: Default::default(),
+        }
+    }
+}
+impl<'a, __Log> DependencyContainerBuilder<'a, __Log> {
+    pub fn log(self, log: Log) -> DependencyContainerBuilder<'a, (Log,)> {
+        DependencyContainerBuilder {
+            log: (log,),
+            _phantom: Default::default(),
+        }
+    }
+    pub fn build(self) -> DependencyContainer<'a>
+    where
+        __Log: DependencyContainerBuilderHasLog<'a>,
+    {
+        DependencyContainer::new(
+            DependencyContainerBuilderHasLog::into_argument(self.log),
+        )
+    }
+}
+#[doc(hidden)]
+#[diagnostic::on_unimplemented(
+    message = "The `log` constructor argument of `DependencyContainer` has not been set",
+    label = "`log` is missing",
+    note = "Set it with `.log(...)` before calling `.build()`"
+)]
+trait DependencyContainerBuilderHasLog<'a> {
+    fn into_argument(self) -> Log;
+}
+impl<'a> DependencyContainerBuilderHasLog<'a> for (Log,) {
+    fn into_argument(self) -> Log {
+        self.0
+    }
+}
+impl<'a> core::fmt::Debug for DependencyContainer<'a> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        #[allow(unused_mut)]
+        let mut debug = f.debug_struct("DependencyContainer");
+        debug
+            .field(
+                "cache",
+                &format_args!(
+                    "{0}", if self.cache.get().is_some() { "initialized" } else {
+                    "uninitialized" }
+                ),
+            );
+        debug
+            .field(
+                "pool",
+                &format_args!(
+                    "{0}", if self.pool.get().is_some() { "initialized" } else {
+                    "uninitialized" }
+                ),
+            );
+        debug
+            .field(
+                "session",
+                &format_args!(
+                    "{0}", if self.session.get().is_some() { "initialized" } else {
+                    "uninitialized" }
+                ),
+            );
+        debug
+            .field(
+                "unused",
+                &format_args!(
+                    "{0}", if self.unused.get().is_some() { "initialized" } else {
+                    "uninitialized" }
+                ),
+            );
+        debug.finish_non_exhaustive()
+    }
+}
+fn main() {
+    let log = Log::default();
+    let container = DependencyContainer::new(log.clone());
+    let scope = container.new_scope();
+    let _session = scope.session();
+    scope.shutdown();
+    match (
+        &*log.borrow(),
+        &::alloc::boxed::box_assume_init_into_vec_unsafe(
+            ::alloc::intrinsics::write_box_via_move(
+                ::alloc::boxed::Box::new_uninit(),
+                ["session"],
+            ),
+        ),
+    ) {
+        (left_val, right_val) => {
+            if !(*left_val == *right_val) {
+                let kind = ::core::panicking::AssertKind::Eq;
+                ::core::panicking::assert_failed(
+                    kind,
+                    &*left_val,
+                    &*right_val,
+                    ::core::option::Option::None,
+                );
+            }
+        }
+    };
+    let _session = container.session();
+    container.shutdown();
+    match (
+        &*log.borrow(),
+        &::alloc::boxed::box_assume_init_into_vec_unsafe(
+            ::alloc::intrinsics::write_box_via_move(
+                ::alloc::boxed::Box::new_uninit(),
+                ["session", "session", "cache", "pool"],
+            ),
+        ),
+    ) {
+        (left_val, right_val) => {
+            if !(*left_val == *right_val) {
+                let kind = ::core::panicking::AssertKind::Eq;
+                ::core::panicking::assert_failed(
+                    kind,
+                    &*left_val,
+                    &*right_val,
+                    ::core::option::Option::None,
+                );
+            }
+        }
+    };
+}
@@ -0,0 +1,101 @@
+use std::{cell::RefCell, rc::Rc};
+
+type Log = Rc<RefCell<Vec<&'static str>>>;
+
+struct Pool {
+    log: Log,
+}
+
+impl Pool {
+    fn close(&self) {
+        self.log.borrow_mut().push("pool");
+    }
+}
+
+struct Repository;
+
+struct Cache {
+    log: Log,
+    _repository: Repository,
+}
+
+impl Cache {
+    fn flush(&self) {
+        self.log.borrow_mut().push("cache");
+    }
+}
+
+struct Session {
+    log: Log,
+}
+
+impl Session {
+    fn end(&self) {
+        self.log.borrow_mut().push("session");
+    }
+}
+
+struct Unused;
+
+impl Unused {
+    fn close(&self) {
+        panic!("should only dispose dependencies which were created");
+    }
+}
+
+#[despatma_dependency_container::dependency_container]
+impl DependencyContainer {
+    fn new(log: Log) {}
+
+    // Declared before the dependencies it is created from to show the order does not matter
+    #[Singleton]
+    #[Dispose(flush)]
+    fn cache(&self, log: &Log, repository: Repository) -> Cache {
+        Cache {
+            log: log.clone(),
+            _repository: repository,
+        }
+    }
+
+    fn repository(&self, pool: &Pool) -> Repository {
+        let _ = pool;
+        Repository
+    }
+
+    #[Singleton]
+    #[Dispose(close)]
+    fn pool(&self, log: &Log) -> Pool {
+        Pool { log: log.clone() }
+    }
+
+    #[Scoped]
+    #[Dispose(end)]
+    fn session(&self, log: &Log, cache: &Cache) -> Session {
+        let _ = cache;
+
+        Session { log: log.clone() }
+    }
+
+    #[Singleton]
+    #[Dispose(close)]
+    fn unused(&self) -> Unused {
+        Unused
+    }
+}
+
+fn main() {
+    let log = Log::default();
+    let container = DependencyContainer::new(log.clone());
+
+    let scope = container.new_scope();
+    let _session = scope.session();
+    scope.shutdown();
+
+    // A scope does not dispose the singletons it shares with its parent
+    assert_eq!(*log.borrow(), vec!["session"]);
+
+    let _session = container.session();
+    container.shutdown();
+
+    assert_eq!(*log.borrow(), vec!["session", "session", "cache", "pool"]);
+}
@@ -0,0 +1,236 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+static CREATED: AtomicUsize = AtomicUsize::new(0);
+struct DbError;
+#[automatically_derived]
+impl ::core::fmt::Debug for DbError {
+    #[inline]
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        ::core::fmt::Formatter::write_str(f, "DbError")
+    }
+}
+struct Db;
+impl Db {
+    fn connect(url: &str) -> Result<Self, DbError> {
+        if url.is_empty() {
+            return Err(DbError);
+        }
+        CREATED.fetch_add(1, Ordering::SeqCst);
+        Ok(Self)
+    }
+}
+struct Cache;
+impl Cache {
+    fn load(_db: &Db) -> Self {
+        CREATED.fetch_add(1, Ordering::SeqCst);
+        Self
+    }
+}
+struct Report;
+struct DependencyContainer<'a> {
+    cache: std::rc::Rc<std::cell::OnceCell<Cache>>,
+    db: std::rc::Rc<std::cell::OnceCell<Db>>,
+    report: std::rc::Rc<std::cell::OnceCell<Report>>,
+    url: std::sync::Arc<&'static str>,
+    _phantom: std::marker::PhantomData<&'a ()>,
+}
+#[automatically_derived]
+impl<'a> ::core::clone::Clone for DependencyContainer<'a> {
+    #[inline]
+    fn clone(&self) -> DependencyContainer<'a> {
+        DependencyContainer {
+            cache: ::core::clone::Clone::clone(&self.cache),
+            db: ::core::clone::Clone::clone(&self.db),
+            report: ::core::clone::Clone::clone(&self.report),
+            url: ::core::clone::Clone::clone(&self.url),
+            _phantom: ::core::clone::Clone::clone(&self._phantom),
+        }
+    }
+}
+impl<'a> DependencyContainer<'a> {
+    pub fn new(url: &'static str) -> Self {
+        Self {
+            cache: Default::default(),
+            db: Default::default(),
+            report: Default::default(),
+            url: std::sync::Arc::new(url),
+            _phantom: Default::default(),
+        }
+    }
+    pub fn new_scope(&self) -> Self {
+        Self {
+            cache: self.cache.clone(),
+            db: self.db.clone(),
+            report: self.report.clone(),
+            url: self.url.clone(),
+            _phantom: Default::default(),
+        }
+    }
+    /// Create the eager singletons now rather than when they are first used. Their dependencies are created first.
+    pub fn init(&'a self) -> std::result::Result<(), DbError> {
+        self.db()?;
+        self.cache()?;
+        Ok(())
+    }
+    /// The names of the singleton and scoped dependencies which have been created, without creating any others
+    pub fn initialized(&self) -> Vec<&'static str> {
+        #[allow(unused_mut)]
+        let mut initialized = Vec::new();
+        if self.cache.get().is_some() {
+            initialized.push("cache");
+        }
+        if self.db.get().is_some() {
+            initialized.push("db");
+        }
+        if self.report.get().is_some() {
+            initialized.push("report");
+        }
+        initialized
+    }
+    /// The dependencies of this container and how they are wired together in the DOT format
+    pub fn dependency_graph_dot() -> &'static str {
+        "digraph DependencyContainer {\n    node [shape=box];\n    \"cache\" [label=\"cache\\nSingleton\"];\n    \"db\" [label=\"db\\nSingleton\"];\n    \"report\" [label=\"report\\nSingleton\"];\n    \"url\" [label=\"url\\nEmbedded\"];\n    \"cache\" -> \"db\";\n    \"db\" -> \"url\";\n}\n"
+    }
+    /// The dependencies of this container and how they are wired together as a Mermaid flowchart
+    pub fn dependency_graph_mermaid() -> &'static str {
+        "graph TD\n    cache[\"cache<br/>Singleton\"]\n    db[\"db<br/>Singleton\"]\n    report[\"report<br/>Singleton\"]\n    url[\"url<br/>Embedded\"]\n    cache --> db\n    db --> url\n"
+    }
+    pub fn cache(&'a self) -> std::result::Result<&Cache, DbError> {
+        let db = self.db()?;
+        Ok({ self.cache.get_or_init(|| { Cache::load(db) }) })
+    }
+    pub fn db(&'a self) -> std::result::Result<&Db, DbError> {
+        let url = self.url();
+        match self.db.get() {
+            Some(db) => Ok(db),
+            None => {
+                let db = (|| -> std::result::Result<Db, DbError> {
+                    Db::connect(url)
+                })()?;
+                Ok(self.db.get_or_init(|| db))
+            }
+        }
+    }
+    pub fn report(&'a self) -> &Report {
+        self.report
+            .get_or_init(|| {
+                CREATED.fetch_add(1, Ordering::SeqCst);
+                Report
+            })
+    }
+    pub fn url(&'a self) -> &&'static str {
+        self.url.as_ref()
+    }
+}
+/// Builder to set the constructor arguments of [`DependencyContainer`] by name.
+#[must_use]
+struct DependencyContainerBuilder<'a, __Url> {
+    url: __Url,
+    _phantom: std::marker::PhantomData<&'a ()>,
+}
+impl<'a> DependencyContainer<'a> {
+    pub fn builder() -> DependencyContainerBuilder<'a, ()> {
+        DependencyContainerBuilder {
+            url: (),
+            _phantom: Default::default(),
+        }
+    }
+}
+impl<'a, __Url> DependencyContainerBuilder<'a, __Url> {
+    pub fn url(
+        self,
+        url: &'static str,
+    ) -> DependencyContainerBuilder<'a, (&'static str,)> {
+        DependencyContainerBuilder {
+            url: (url,),
+            _phantom: Default::default(),
+        }
+    }
+    pub fn build(self) -> DependencyContainer<'a>
+    where
+        __Url: DependencyContainerBuilderHasUrl<'a>,
+    {
+        DependencyContainer::new(
+            DependencyContainerBuilderHasUrl::into_argument(self.url),
+        )
+    }
+}
+#[doc(hidden)]
+#[diagnostic::on_unimplemented(
+    message = "The `url` constructor argument of `DependencyContainer` has not been set",
+    label = "`url` is missing",
+    note = "Set it with `.url(...)` before calling `.build()`"
+)]
+trait DependencyContainerBuilderHasUrl<'a> {
+    fn into_argument(self) -> &'static str;
+}
+impl<'a> DependencyContainerBuilderHasUrl<'a> for (&'static str,) {
+    fn into_argument(self) -> &'static str {
+        self.0
+    }
+}
+impl<'a> core::fmt::Debug for DependencyContainer<'a> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        #[allow(unused_mut)]
+        let mut debug = f.debug_struct("DependencyContainer");
+        debug
+            .field(
+                "cache",
+                &format_args!(
+                    "{0}", if self.cache.get().is_some() { "initialized" } else {
+                    "uninitialized" }
+                ),
+            );
+        debug
+            .field(
+                "db",
+                &format_args!(
+                    "{0}", if self.db.get().is_some() { "initialized" } else {
+                    "uninitialized" }
+                ),
+            );
+        debug
+            .field(
+                "report",
+                &format_args!(
+                    "{0}", if self.report.get().is_some() { "initialized" } else {
+                    "uninitialized" }
+                ),
+            );
+        debug.finish_non_exhaustive()
+    }
+}
+fn main() {
+    let container = DependencyContainer::new("sqlite://memory");
+    container.init().unwrap();
+    match (&CREATED.load(Ordering::SeqCst), &2) {
+        (left_val, right_val) => {
+            if !(*left_val == *right_val) {
+                let kind = ::core::panicking::AssertKind::Eq;
+                ::core::panicking::assert_failed(
+                    kind,
+                    &*left_val,
+                    &*right_val,
+                    ::core::option::Option::None,
+                );
+            }
+        }
+    };
+    let _cache = container.cache().unwrap();
+    match (&CREATED.load(Ordering::SeqCst), &2) {
+        (left_val, right_val) => {
+            if !(*left_val == *right_val) {
+                let kind = ::core::panicking::AssertKind::Eq;
+                ::core::panicking::assert_failed(
+                    kind,
+                    &*left_val,
+                    &*right_val,
+                    ::core::option::Option::None,
+                );
+            }
+        }
+    };
+    let container = DependencyContainer::new("");
+    if !container.init().is_err() {
+        ::core::panicking::panic("assertion failed: container.init().is_err()")
+    }
+}
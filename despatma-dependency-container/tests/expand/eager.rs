@@ -0,0 +1,65 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static CREATED: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Debug)]
+struct DbError;
+
+struct Db;
+
+impl Db {
+    fn connect(url: &str) -> Result<Self, DbError> {
+        if url.is_empty() {
+            return Err(DbError);
+        }
+
+        CREATED.fetch_add(1, Ordering::SeqCst);
+        Ok(Self)
+    }
+}
+
+struct Cache;
+
+impl Cache {
+    fn load(_db: &Db) -> Self {
+        CREATED.fetch_add(1, Ordering::SeqCst);
+        Self
+    }
+}
+
+struct Report;
+
+#[despatma_dependency_container::dependency_container]
+impl DependencyContainer {
+    fn new(url: &'static str) {}
+
+    // Declared before the dependency it is created from to show the order does not matter
+    #[Singleton(eager)]
+    fn cache(&self, db: &Db) -> Cache {
+        Cache::load(db)
+    }
+
+    #[Singleton(eager)]
+    fn db(&self, url: &&'static str) -> Result<Db, DbError> {
+        Db::connect(url)
+    }
+
+    #[Singleton]
+    fn report(&self) -> Report {
+        CREATED.fetch_add(1, Ordering::SeqCst);
+        Report
+    }
+}
+
+fn main() {
+    let container = DependencyContainer::new("sqlite://memory");
+    container.init().unwrap();
+
+    // Only the eager singletons are created and only once
+    assert_eq!(CREATED.load(Ordering::SeqCst), 2);
+    let _cache = container.cache().unwrap();
+    assert_eq!(CREATED.load(Ordering::SeqCst), 2);
+
+    let container = DependencyContainer::new("");
+    assert!(container.init().is_err());
+}
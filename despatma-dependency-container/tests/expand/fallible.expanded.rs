@@ -0,0 +1,217 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+static CONNECTION_ATTEMPTS: AtomicU32 = AtomicU32::new(0);
+struct ConfigError;
+#[automatically_derived]
+impl ::core::fmt::Debug for ConfigError {
+    #[inline]
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        ::core::fmt::Formatter::write_str(f, "ConfigError")
+    }
+}
+struct DatabaseError;
+#[automatically_derived]
+impl ::core::fmt::Debug for DatabaseError {
+    #[inline]
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        ::core::fmt::Formatter::write_str(f, "DatabaseError")
+    }
+}
+enum ServiceError {
+    Config(ConfigError),
+    Database(DatabaseError),
+}
+#[automatically_derived]
+impl ::core::fmt::Debug for ServiceError {
+    #[inline]
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        match self {
+            ServiceError::Config(__self_0) => {
+                ::core::fmt::Formatter::debug_tuple_field1_finish(f, "Config", &__self_0)
+            }
+            ServiceError::Database(__self_0) => {
+                ::core::fmt::Formatter::debug_tuple_field1_finish(
+                    f,
+                    "Database",
+                    &__self_0,
+                )
+            }
+        }
+    }
+}
+impl From<ConfigError> for ServiceError {
+    fn from(error: ConfigError) -> Self {
+        Self::Config(error)
+    }
+}
+impl From<DatabaseError> for ServiceError {
+    fn from(error: DatabaseError) -> Self {
+        Self::Database(error)
+    }
+}
+struct Config {
+    port: u32,
+}
+struct Database;
+impl Database {
+    async fn connect() -> Result<Self, DatabaseError> {
+        if CONNECTION_ATTEMPTS.fetch_add(1, Ordering::SeqCst) == 0 {
+            Err(DatabaseError)
+        } else {
+            Ok(Self)
+        }
+    }
+}
+struct Repository;
+impl Repository {
+    fn new(_database: &Database) -> Result<Self, DatabaseError> {
+        Ok(Self)
+    }
+}
+struct Handler;
+impl Handler {
+    fn new(_repository: Repository) -> Self {
+        Self
+    }
+}
+struct Service;
+impl Service {
+    fn new(port: u32, _handler: Handler) -> Self {
+        {
+            ::std::io::_print(
+                format_args!("Fallible service started on port {0}\n", port),
+            );
+        };
+        Self
+    }
+}
+struct DependencyContainer<'a> {
+    config: std::rc::Rc<std::cell::OnceCell<Config>>,
+    database: std::sync::Arc<async_once_cell::OnceCell<Database>>,
+    _phantom: std::marker::PhantomData<&'a ()>,
+}
+#[automatically_derived]
+impl<'a> ::core::clone::Clone for DependencyContainer<'a> {
+    #[inline]
+    fn clone(&self) -> DependencyContainer<'a> {
+        DependencyContainer {
+            config: ::core::clone::Clone::clone(&self.config),
+            database: ::core::clone::Clone::clone(&self.database),
+            _phantom: ::core::clone::Clone::clone(&self._phantom),
+        }
+    }
+}
+impl<'a> DependencyContainer<'a> {
+    pub fn new() -> Self {
+        Self {
+            config: Default::default(),
+            database: Default::default(),
+            _phantom: Default::default(),
+        }
+    }
+    pub fn new_scope(&self) -> Self {
+        Self {
+            config: self.config.clone(),
+            database: self.database.clone(),
+            _phantom: Default::default(),
+        }
+    }
+    /// The names of the singleton and scoped dependencies which have been created, without creating any others
+    pub fn initialized(&self) -> Vec<&'static str> {
+        #[allow(unused_mut)]
+        let mut initialized = Vec::new();
+        if self.config.get().is_some() {
+            initialized.push("config");
+        }
+        if self.database.get().is_some() {
+            initialized.push("database");
+        }
+        initialized
+    }
+    /// The dependencies of this container and how they are wired together in the DOT format
+    pub fn dependency_graph_dot() -> &'static str {
+        "digraph DependencyContainer {\n    node [shape=box];\n    \"config\" [label=\"config\\nSingleton\"];\n    \"database\" [label=\"database\\nSingleton, async\"];\n    \"repository\" [label=\"repository\\nTransient, async\"];\n    \"handler\" [label=\"handler\\nTransient, async\"];\n    \"service\" [label=\"service\\nTransient, async\"];\n    \"repository\" -> \"database\";\n    \"handler\" -> \"repository\";\n    \"service\" -> \"config\";\n    \"service\" -> \"handler\";\n}\n"
+    }
+    /// The dependencies of this container and how they are wired together as a Mermaid flowchart
+    pub fn dependency_graph_mermaid() -> &'static str {
+        "graph TD\n    config[\"config<br/>Singleton\"]\n    database[\"database<br/>Singleton, async\"]\n    repository[\"repository<br/>Transient, async\"]\n    handler[\"handler<br/>Transient, async\"]\n    service[\"service<br/>Transient, async\"]\n    repository --> database\n    handler --> repository\n    service --> config\n    service --> handler\n"
+    }
+    pub fn config(&'a self) -> std::result::Result<&Config, ConfigError> {
+        match self.config.get() {
+            Some(config) => Ok(config),
+            None => {
+                let config = (|| -> std::result::Result<Config, ConfigError> {
+                    let port = "8080".parse().map_err(|_| ConfigError)?;
+                    Ok(Config { port })
+                })()?;
+                Ok(self.config.get_or_init(|| config))
+            }
+        }
+    }
+    pub async fn database(&'a self) -> std::result::Result<&Database, DatabaseError> {
+        self.database.get_or_try_init(async { Database::connect().await }).await
+    }
+    pub async fn repository(&'a self) -> std::result::Result<Repository, DatabaseError> {
+        let database = self.database().await?;
+        Repository::new(database)
+    }
+    pub async fn handler(&'a self) -> std::result::Result<Handler, DatabaseError> {
+        let repository = self.repository().await?;
+        Ok({ Handler::new(repository) })
+    }
+    pub async fn service(&'a self) -> std::result::Result<Service, ServiceError> {
+        let config = self.config()?;
+        let handler = self.handler().await?;
+        Ok(Service::new(config.port, handler))
+    }
+}
+impl<'a> core::fmt::Debug for DependencyContainer<'a> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        #[allow(unused_mut)]
+        let mut debug = f.debug_struct("DependencyContainer");
+        debug
+            .field(
+                "config",
+                &format_args!(
+                    "{0}", if self.config.get().is_some() { "initialized" } else {
+                    "uninitialized" }
+                ),
+            );
+        debug
+            .field(
+                "database",
+                &format_args!(
+                    "{0}", if self.database.get().is_some() { "initialized" } else {
+                    "uninitialized" }
+                ),
+            );
+        debug.finish_non_exhaustive()
+    }
+}
+fn main() {
+    let body = async {
+        let container = DependencyContainer::new();
+        if !container.service().await.is_err() {
+            ::core::panicking::panic(
+                "assertion failed: container.service().await.is_err()",
+            )
+        }
+        if !container.service().await.is_ok() {
+            ::core::panicking::panic(
+                "assertion failed: container.service().await.is_ok()",
+            )
+        }
+    };
+    #[allow(
+        clippy::expect_used,
+        clippy::diverging_sub_expression,
+        clippy::needless_return,
+        clippy::unwrap_in_result
+    )]
+    {
+        return tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .build()
+            .expect("Failed building the Runtime")
+            .block_on(body);
+    }
+}
@@ -0,0 +1,104 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+
+static CONNECTION_ATTEMPTS: AtomicU32 = AtomicU32::new(0);
+
+#[derive(Debug)]
+struct ConfigError;
+
+#[derive(Debug)]
+struct DatabaseError;
+
+#[derive(Debug)]
+enum ServiceError {
+    Config(ConfigError),
+    Database(DatabaseError),
+}
+
+impl From<ConfigError> for ServiceError {
+    fn from(error: ConfigError) -> Self {
+        Self::Config(error)
+    }
+}
+
+impl From<DatabaseError> for ServiceError {
+    fn from(error: DatabaseError) -> Self {
+        Self::Database(error)
+    }
+}
+
+struct Config {
+    port: u32,
+}
+
+struct Database;
+
+impl Database {
+    async fn connect() -> Result<Self, DatabaseError> {
+        // Fail the first connection attempt
+        if CONNECTION_ATTEMPTS.fetch_add(1, Ordering::SeqCst) == 0 {
+            Err(DatabaseError)
+        } else {
+            Ok(Self)
+        }
+    }
+}
+
+struct Repository;
+
+impl Repository {
+    fn new(_database: &Database) -> Result<Self, DatabaseError> {
+        Ok(Self)
+    }
+}
+
+struct Handler;
+
+impl Handler {
+    fn new(_repository: Repository) -> Self {
+        Self
+    }
+}
+
+struct Service;
+
+impl Service {
+    fn new(port: u32, _handler: Handler) -> Self {
+        println!("Fallible service started on port {}", port);
+        Self
+    }
+}
+
+#[despatma_dependency_container::dependency_container]
+impl DependencyContainer {
+    #[Singleton]
+    fn config(&self) -> Result<Config, ConfigError> {
+        let port = "8080".parse().map_err(|_| ConfigError)?;
+
+        Ok(Config { port })
+    }
+
+    #[Singleton]
+    async fn database(&self) -> Result<Database, DatabaseError> {
+        Database::connect().await
+    }
+
+    fn repository(&self, database: &Database) -> Result<Repository, DatabaseError> {
+        Repository::new(database)
+    }
+
+    fn handler(&self, repository: Repository) -> Handler {
+        Handler::new(repository)
+    }
+
+    fn service(&self, config: &Config, handler: Handler) -> Result<Service, ServiceError> {
+        Ok(Service::new(config.port, handler))
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    let container = DependencyContainer::new();
+
+    assert!(container.service().await.is_err());
+    assert!(container.service().await.is_ok());
+}
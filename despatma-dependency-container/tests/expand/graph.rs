@@ -0,0 +1,59 @@
+struct Config {
+    url: &'static str,
+}
+
+struct Db;
+
+impl Db {
+    async fn connect(_url: &str) -> Self {
+        Self
+    }
+}
+
+struct Report;
+
+impl Report {
+    fn new(_source: &Db) -> Self {
+        Self
+    }
+}
+
+#[despatma_dependency_container::dependency_container]
+impl DependencyContainer {
+    fn new(config: Config) {}
+
+    #[Singleton]
+    async fn db(&self, config: &Config) -> Db {
+        Db::connect(config.url).await
+    }
+
+    fn report(&self, #[from(db)] source: &Db) -> Report {
+        Report::new(source)
+    }
+}
+
+fn main() {
+    assert_eq!(
+        DependencyContainer::dependency_graph_dot(),
+        r#"digraph DependencyContainer {
+    node [shape=box];
+    "db" [label="db\nSingleton, async"];
+    "report" [label="report\nTransient, async"];
+    "config" [label="config\nEmbedded"];
+    "db" -> "config";
+    "report" -> "db" [label="source"];
+}
+"#
+    );
+
+    assert_eq!(
+        DependencyContainer::dependency_graph_mermaid(),
+        r#"graph TD
+    db["db<br/>Singleton, async"]
+    report["report<br/>Transient, async"]
+    config["config<br/>Embedded"]
+    db --> config
+    report -- source --> db
+"#
+    );
+}
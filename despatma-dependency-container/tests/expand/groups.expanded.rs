@@ -0,0 +1,195 @@
+trait Handler {
+    fn handle(&self, request: &str) -> Option<String>;
+}
+struct Ping;
+impl Handler for Ping {
+    fn handle(&self, request: &str) -> Option<String> {
+        (request == "ping").then(|| "pong".to_string())
+    }
+}
+struct Echo {
+    prefix: String,
+}
+impl Handler for Echo {
+    fn handle(&self, request: &str) -> Option<String> {
+        Some(
+            ::alloc::__export::must_use({
+                ::alloc::fmt::format(format_args!("{0}{1}", self.prefix, request))
+            }),
+        )
+    }
+}
+struct Config {
+    prefix: String,
+}
+struct Router {
+    handlers: Vec<Box<dyn Handler>>,
+}
+impl Router {
+    fn new(handlers: Vec<Box<dyn Handler>>) -> Self {
+        Self { handlers }
+    }
+    fn route(&self, request: &str) -> Option<String> {
+        self.handlers.iter().find_map(|handler| handler.handle(request))
+    }
+}
+struct DependencyContainer<'a> {
+    shared_ping: std::rc::Rc<std::cell::OnceCell<std::boxed::Box<dyn Handler>>>,
+    _phantom: std::marker::PhantomData<&'a ()>,
+}
+#[automatically_derived]
+impl<'a> ::core::clone::Clone for DependencyContainer<'a> {
+    #[inline]
+    fn clone(&self) -> DependencyContainer<'a> {
+        DependencyContainer {
+            shared_ping: ::core::clone::Clone::clone(&self.shared_ping),
+            _phantom: ::core::clone::Clone::clone(&self._phantom),
+        }
+    }
+}
+impl<'a> DependencyContainer<'a> {
+    pub fn new() -> Self {
+        Self {
+            shared_ping: Default::default(),
+            _phantom: Default::default(),
+        }
+    }
+    pub fn new_scope(&self) -> Self {
+        Self {
+            shared_ping: self.shared_ping.clone(),
+            _phantom: Default::default(),
+        }
+    }
+    /// The names of the singleton and scoped dependencies which have been created, without creating any others
+    pub fn initialized(&self) -> Vec<&'static str> {
+        #[allow(unused_mut)]
+        let mut initialized = Vec::new();
+        if self.shared_ping.get().is_some() {
+            initialized.push("shared_ping");
+        }
+        initialized
+    }
+    /// The dependencies of this container and how they are wired together in the DOT format
+    pub fn dependency_graph_dot() -> &'static str {
+        "digraph DependencyContainer {\n    node [shape=box];\n    \"config\" [label=\"config\\nTransient\"];\n    \"ping\" [label=\"ping\\nTransient, boxed\"];\n    \"echo\" [label=\"echo\\nTransient, boxed\"];\n    \"shared_ping\" [label=\"shared_ping\\nSingleton, boxed\"];\n    \"router\" [label=\"router\\nTransient\"];\n    \"health\" [label=\"health\\nTransient\"];\n    \"handlers\" [label=\"handlers\\nTransient\"];\n    \"fallbacks\" [label=\"fallbacks\\nTransient\"];\n    \"echo\" -> \"config\";\n    \"router\" -> \"handlers\";\n    \"health\" -> \"fallbacks\";\n    \"handlers\" -> \"ping\";\n    \"handlers\" -> \"echo\";\n    \"fallbacks\" -> \"shared_ping\";\n}\n"
+    }
+    /// The dependencies of this container and how they are wired together as a Mermaid flowchart
+    pub fn dependency_graph_mermaid() -> &'static str {
+        "graph TD\n    config[\"config<br/>Transient\"]\n    ping[\"ping<br/>Transient, boxed\"]\n    echo[\"echo<br/>Transient, boxed\"]\n    shared_ping[\"shared_ping<br/>Singleton, boxed\"]\n    router[\"router<br/>Transient\"]\n    health[\"health<br/>Transient\"]\n    handlers[\"handlers<br/>Transient\"]\n    fallbacks[\"fallbacks<br/>Transient\"]\n    echo --> config\n    router --> handlers\n    health --> fallbacks\n    handlers --> ping\n    handlers --> echo\n    fallbacks --> shared_ping\n"
+    }
+    pub fn config(&'a self) -> Config {
+        Config {
+            prefix: "echo: ".to_string(),
+        }
+    }
+    pub fn ping(&'a self) -> std::boxed::Box<dyn Handler> {
+        Box::new(Ping)
+    }
+    pub fn echo(&'a self) -> std::boxed::Box<dyn Handler> {
+        let config = self.config();
+        Box::new(Echo { prefix: config.prefix })
+    }
+    pub fn shared_ping(&'a self) -> &std::boxed::Box<dyn Handler> {
+        self.shared_ping.get_or_init(|| { Box::new(Ping) })
+    }
+    pub fn router(&'a self) -> Router {
+        let handlers = self.handlers();
+        Router::new(handlers)
+    }
+    pub fn health(&'a self) -> String {
+        let fallbacks = self.fallbacks();
+        fallbacks.iter().filter_map(|handler| handler.handle("ping")).collect()
+    }
+    /// All the dependencies in the `handlers` group
+    pub fn handlers(&'a self) -> Vec<Box<dyn Handler>> {
+        let ping = self.ping();
+        let echo = self.echo();
+        ::alloc::boxed::box_assume_init_into_vec_unsafe(
+            ::alloc::intrinsics::write_box_via_move(
+                ::alloc::boxed::Box::new_uninit(),
+                [ping, echo],
+            ),
+        )
+    }
+    /// All the dependencies in the `fallbacks` group
+    pub fn fallbacks(&'a self) -> Vec<&dyn Handler> {
+        let shared_ping = self.shared_ping();
+        ::alloc::boxed::box_assume_init_into_vec_unsafe(
+            ::alloc::intrinsics::write_box_via_move(
+                ::alloc::boxed::Box::new_uninit(),
+                [&**shared_ping],
+            ),
+        )
+    }
+}
+impl<'a> core::fmt::Debug for DependencyContainer<'a> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        #[allow(unused_mut)]
+        let mut debug = f.debug_struct("DependencyContainer");
+        debug
+            .field(
+                "shared_ping",
+                &format_args!(
+                    "{0}", if self.shared_ping.get().is_some() { "initialized" } else {
+                    "uninitialized" }
+                ),
+            );
+        debug.finish_non_exhaustive()
+    }
+}
+fn main() {
+    let container = DependencyContainer::new();
+    let router = container.router();
+    match (&router.route("ping"), &Some("pong".to_string())) {
+        (left_val, right_val) => {
+            if !(*left_val == *right_val) {
+                let kind = ::core::panicking::AssertKind::Eq;
+                ::core::panicking::assert_failed(
+                    kind,
+                    &*left_val,
+                    &*right_val,
+                    ::core::option::Option::None,
+                );
+            }
+        }
+    };
+    match (&router.route("hello"), &Some("echo: hello".to_string())) {
+        (left_val, right_val) => {
+            if !(*left_val == *right_val) {
+                let kind = ::core::panicking::AssertKind::Eq;
+                ::core::panicking::assert_failed(
+                    kind,
+                    &*left_val,
+                    &*right_val,
+                    ::core::option::Option::None,
+                );
+            }
+        }
+    };
+    match (&container.health(), &"pong") {
+        (left_val, right_val) => {
+            if !(*left_val == *right_val) {
+                let kind = ::core::panicking::AssertKind::Eq;
+                ::core::panicking::assert_failed(
+                    kind,
+                    &*left_val,
+                    &*right_val,
+                    ::core::option::Option::None,
+                );
+            }
+        }
+    };
+    match (&container.handlers().len(), &2) {
+        (left_val, right_val) => {
+            if !(*left_val == *right_val) {
+                let kind = ::core::panicking::AssertKind::Eq;
+                ::core::panicking::assert_failed(
+                    kind,
+                    &*left_val,
+                    &*right_val,
+                    ::core::option::Option::None,
+                );
+            }
+        }
+    };
+}
@@ -0,0 +1,89 @@
+trait Handler {
+    fn handle(&self, request: &str) -> Option<String>;
+}
+
+struct Ping;
+
+impl Handler for Ping {
+    fn handle(&self, request: &str) -> Option<String> {
+        (request == "ping").then(|| "pong".to_string())
+    }
+}
+
+struct Echo {
+    prefix: String,
+}
+
+impl Handler for Echo {
+    fn handle(&self, request: &str) -> Option<String> {
+        Some(format!("{}{request}", self.prefix))
+    }
+}
+
+struct Config {
+    prefix: String,
+}
+
+struct Router {
+    handlers: Vec<Box<dyn Handler>>,
+}
+
+impl Router {
+    fn new(handlers: Vec<Box<dyn Handler>>) -> Self {
+        Self { handlers }
+    }
+
+    fn route(&self, request: &str) -> Option<String> {
+        self.handlers
+            .iter()
+            .find_map(|handler| handler.handle(request))
+    }
+}
+
+#[despatma_dependency_container::dependency_container]
+impl DependencyContainer {
+    fn config(&self) -> Config {
+        Config {
+            prefix: "echo: ".to_string(),
+        }
+    }
+
+    #[Group(handlers)]
+    fn ping(&self) -> Box<dyn Handler> {
+        Box::new(Ping)
+    }
+
+    #[Group(handlers)]
+    fn echo(&self, config: Config) -> Box<dyn Handler> {
+        Box::new(Echo {
+            prefix: config.prefix,
+        })
+    }
+
+    #[Singleton]
+    #[Group(fallbacks)]
+    fn shared_ping(&self) -> Box<dyn Handler> {
+        Box::new(Ping)
+    }
+
+    fn router(&self, handlers: Vec<Box<dyn Handler>>) -> Router {
+        Router::new(handlers)
+    }
+
+    fn health(&self, fallbacks: Vec<&dyn Handler>) -> String {
+        fallbacks
+            .iter()
+            .filter_map(|handler| handler.handle("ping"))
+            .collect()
+    }
+}
+
+fn main() {
+    let container = DependencyContainer::new();
+    let router = container.router();
+
+    assert_eq!(router.route("ping"), Some("pong".to_string()));
+    assert_eq!(router.route("hello"), Some("echo: hello".to_string()));
+    assert_eq!(container.health(), "pong");
+    assert_eq!(container.handlers().len(), 2);
+}
@@ -43,6 +43,20 @@ impl<'a> DependencyContainer<'a> {
             _phantom: Default::default(),
         }
     }
+    /// The names of the singleton and scoped dependencies which have been created, without creating any others
+    pub fn initialized(&self) -> Vec<&'static str> {
+        #[allow(unused_mut)]
+        let mut initialized = Vec::new();
+        initialized
+    }
+    /// The dependencies of this container and how they are wired together in the DOT format
+    pub fn dependency_graph_dot() -> &'static str {
+        "digraph DependencyContainer {\n    node [shape=box];\n    \"config\" [label=\"config\\nTransient\"];\n    \"dal\" [label=\"dal\\nTransient\"];\n    \"service\" [label=\"service\\nTransient\"];\n    \"service\" -> \"config\";\n    \"service\" -> \"dal\";\n}\n"
+    }
+    /// The dependencies of this container and how they are wired together as a Mermaid flowchart
+    pub fn dependency_graph_mermaid() -> &'static str {
+        "graph TD\n    config[\"config<br/>Transient\"]\n    dal[\"dal<br/>Transient\"]\n    service[\"service<br/>Transient\"]\n    service --> config\n    service --> dal\n"
+    }
     pub fn config(&'a self) -> Config {
         Config { port: 8080 }
     }
@@ -50,11 +64,18 @@ impl<'a> DependencyContainer<'a> {
         PostgresDAL
     }
     pub fn service(&'a self) -> Service<impl DAL> {
-        let config = Config { port: 8080 };
-        let dal = PostgresDAL;
+        let config = self.config();
+        let dal = self.dal();
         Service::new(config.port, dal)
     }
 }
+impl<'a> core::fmt::Debug for DependencyContainer<'a> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        #[allow(unused_mut)]
+        let mut debug = f.debug_struct("DependencyContainer");
+        debug.finish_non_exhaustive()
+    }
+}
 fn main() {
     let container = DependencyContainer::new();
     let _service = container.service();
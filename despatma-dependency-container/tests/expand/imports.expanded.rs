@@ -0,0 +1,289 @@
+struct Config {
+    port: u32,
+}
+struct Db;
+struct Session {
+    user: String,
+}
+struct Service {
+    port: u32,
+}
+impl Service {
+    fn new(config: Config, _db: &Db, session: Session) -> Self {
+        {
+            ::std::io::_print(
+                format_args!(
+                    "Service started on port {0} for {1}\n", config.port, session.user,
+                ),
+            );
+        };
+        Self { port: config.port }
+    }
+}
+struct ConfigContainer<'a> {
+    db: std::rc::Rc<std::cell::OnceCell<Db>>,
+    session: std::sync::Arc<async_once_cell::OnceCell<Session>>,
+    _phantom: std::marker::PhantomData<&'a ()>,
+}
+#[automatically_derived]
+impl<'a> ::core::clone::Clone for ConfigContainer<'a> {
+    #[inline]
+    fn clone(&self) -> ConfigContainer<'a> {
+        ConfigContainer {
+            db: ::core::clone::Clone::clone(&self.db),
+            session: ::core::clone::Clone::clone(&self.session),
+            _phantom: ::core::clone::Clone::clone(&self._phantom),
+        }
+    }
+}
+impl<'a> ConfigContainer<'a> {
+    pub fn new() -> Self {
+        Self {
+            db: Default::default(),
+            session: Default::default(),
+            _phantom: Default::default(),
+        }
+    }
+    pub fn new_scope(&self) -> Self {
+        Self {
+            db: self.db.clone(),
+            session: Default::default(),
+            _phantom: Default::default(),
+        }
+    }
+    /// The names of the singleton and scoped dependencies which have been created, without creating any others
+    pub fn initialized(&self) -> Vec<&'static str> {
+        #[allow(unused_mut)]
+        let mut initialized = Vec::new();
+        if self.db.get().is_some() {
+            initialized.push("db");
+        }
+        if self.session.get().is_some() {
+            initialized.push("session");
+        }
+        initialized
+    }
+    /// The dependencies of this container and how they are wired together in the DOT format
+    pub fn dependency_graph_dot() -> &'static str {
+        "digraph ConfigContainer {\n    node [shape=box];\n    \"config\" [label=\"config\\nTransient\"];\n    \"db\" [label=\"db\\nSingleton\"];\n    \"session\" [label=\"session\\nScoped, async\"];\n}\n"
+    }
+    /// The dependencies of this container and how they are wired together as a Mermaid flowchart
+    pub fn dependency_graph_mermaid() -> &'static str {
+        "graph TD\n    config[\"config<br/>Transient\"]\n    db[\"db<br/>Singleton\"]\n    session[\"session<br/>Scoped, async\"]\n"
+    }
+    pub fn config(&'a self) -> Config {
+        Config { port: 8080 }
+    }
+    pub fn db(&'a self) -> &Db {
+        self.db.get_or_init(|| { Db })
+    }
+    pub async fn session(&'a self) -> &Session {
+        self.session
+            .get_or_init(async {
+                Session {
+                    user: "admin".to_string(),
+                }
+            })
+            .await
+    }
+}
+impl<'a> core::fmt::Debug for ConfigContainer<'a> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        #[allow(unused_mut)]
+        let mut debug = f.debug_struct("ConfigContainer");
+        debug
+            .field(
+                "db",
+                &format_args!(
+                    "{0}", if self.db.get().is_some() { "initialized" } else {
+                    "uninitialized" }
+                ),
+            );
+        debug
+            .field(
+                "session",
+                &format_args!(
+                    "{0}", if self.session.get().is_some() { "initialized" } else {
+                    "uninitialized" }
+                ),
+            );
+        debug.finish_non_exhaustive()
+    }
+}
+struct ServiceContainer<'a> {
+    config_container: std::sync::Arc<ConfigContainer<'a>>,
+    service_constructions: std::rc::Rc<std::cell::Cell<usize>>,
+    _phantom: std::marker::PhantomData<&'a ()>,
+}
+#[automatically_derived]
+impl<'a> ::core::clone::Clone for ServiceContainer<'a> {
+    #[inline]
+    fn clone(&self) -> ServiceContainer<'a> {
+        ServiceContainer {
+            config_container: ::core::clone::Clone::clone(&self.config_container),
+            service_constructions: ::core::clone::Clone::clone(
+                &self.service_constructions,
+            ),
+            _phantom: ::core::clone::Clone::clone(&self._phantom),
+        }
+    }
+}
+impl<'a> ServiceContainer<'a> {
+    pub fn new(config_container: ConfigContainer<'a>) -> Self {
+        Self {
+            config_container: std::sync::Arc::new(config_container),
+            service_constructions: Default::default(),
+            _phantom: Default::default(),
+        }
+    }
+    pub fn new_scope(&self) -> Self {
+        Self {
+            config_container: std::sync::Arc::new(self.config_container.new_scope()),
+            service_constructions: self.service_constructions.clone(),
+            _phantom: Default::default(),
+        }
+    }
+    /// The names of the singleton and scoped dependencies which have been created, without creating any others
+    pub fn initialized(&self) -> Vec<&'static str> {
+        #[allow(unused_mut)]
+        let mut initialized = Vec::new();
+        initialized
+    }
+    /// How many times the dependency with the given name has been created by this container and its scopes.
+    /// Or `None` if the container does not have such a dependency.
+    pub fn constructions(&self, dependency: &str) -> Option<usize> {
+        match dependency {
+            "service" => Some(self.service_constructions.get()),
+            _ => None,
+        }
+    }
+    /// The dependencies of this container and how they are wired together in the DOT format
+    pub fn dependency_graph_dot() -> &'static str {
+        "digraph ServiceContainer {\n    node [shape=box];\n    \"service\" [label=\"service\\nTransient, async\"];\n    \"config\" [label=\"config\\nTransient\"];\n    \"db\" [label=\"db\\nTransient\"];\n    \"session\" [label=\"session\\nTransient, async\"];\n    \"config_container\" [label=\"config_container\\nEmbedded\"];\n    \"service\" -> \"config\";\n    \"service\" -> \"db\";\n    \"service\" -> \"session\";\n    \"config\" -> \"config_container\";\n    \"db\" -> \"config_container\";\n    \"session\" -> \"config_container\";\n}\n"
+    }
+    /// The dependencies of this container and how they are wired together as a Mermaid flowchart
+    pub fn dependency_graph_mermaid() -> &'static str {
+        "graph TD\n    service[\"service<br/>Transient, async\"]\n    config[\"config<br/>Transient\"]\n    db[\"db<br/>Transient\"]\n    session[\"session<br/>Transient, async\"]\n    config_container[\"config_container<br/>Embedded\"]\n    service --> config\n    service --> db\n    service --> session\n    config --> config_container\n    db --> config_container\n    session --> config_container\n"
+    }
+    pub async fn service(&'a self) -> Service {
+        let config = self.config();
+        let db = self.db();
+        let session = self.session().await;
+        self.service_constructions.set(self.service_constructions.get() + 1);
+        Service::new(
+            config,
+            db,
+            Session {
+                user: session.user.clone(),
+            },
+        )
+    }
+    /// Imported from the `config_container` container
+    pub fn config(&'a self) -> Config {
+        let config_container = self.config_container();
+        config_container.config()
+    }
+    /// Imported from the `config_container` container
+    pub fn db(&'a self) -> &'a Db {
+        let config_container = self.config_container();
+        config_container.db()
+    }
+    /// Imported from the `config_container` container
+    pub async fn session(&'a self) -> &'a Session {
+        let config_container = self.config_container();
+        config_container.session().await
+    }
+    pub fn config_container(&'a self) -> &ConfigContainer<'a> {
+        self.config_container.as_ref()
+    }
+}
+/// Builder to set the constructor arguments of [`ServiceContainer`] by name.
+#[must_use]
+struct ServiceContainerBuilder<'a, __ConfigContainer> {
+    config_container: __ConfigContainer,
+    _phantom: std::marker::PhantomData<&'a ()>,
+}
+impl<'a> ServiceContainer<'a> {
+    pub fn builder() -> ServiceContainerBuilder<'a, ()> {
+        ServiceContainerBuilder {
+            config_container: (),
+            _phantom: Default::default(),
+        }
+    }
+}
+impl<'a, __ConfigContainer> ServiceContainerBuilder<'a, __ConfigContainer> {
+    pub fn config_container(
+        self,
+        config_container: ConfigContainer<'a>,
+    ) -> ServiceContainerBuilder<'a, (ConfigContainer<'a>,)> {
+        ServiceContainerBuilder {
+            config_container: (config_container,),
+            _phantom: Default::default(),
+        }
+    }
+    pub fn build(self) -> ServiceContainer<'a>
+    where
+        __ConfigContainer: ServiceContainerBuilderHasConfigContainer<'a>,
+    {
+        ServiceContainer::new(
+            ServiceContainerBuilderHasConfigContainer::into_argument(
+                self.config_container,
+            ),
+        )
+    }
+}
+#[doc(hidden)]
+#[diagnostic::on_unimplemented(
+    message = "The `config_container` constructor argument of `ServiceContainer` has not been set",
+    label = "`config_container` is missing",
+    note = "Set it with `.config_container(...)` before calling `.build()`"
+)]
+trait ServiceContainerBuilderHasConfigContainer<'a> {
+    fn into_argument(self) -> ConfigContainer<'a>;
+}
+impl<'a> ServiceContainerBuilderHasConfigContainer<'a> for (ConfigContainer<'a>,) {
+    fn into_argument(self) -> ConfigContainer<'a> {
+        self.0
+    }
+}
+impl<'a> core::fmt::Debug for ServiceContainer<'a> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        #[allow(unused_mut)]
+        let mut debug = f.debug_struct("ServiceContainer");
+        debug.finish_non_exhaustive()
+    }
+}
+fn main() {
+    let body = async {
+        let container = ServiceContainer::new(ConfigContainer::new());
+        let service = container.service().await;
+        match (&service.port, &8080) {
+            (left_val, right_val) => {
+                if !(*left_val == *right_val) {
+                    let kind = ::core::panicking::AssertKind::Eq;
+                    ::core::panicking::assert_failed(
+                        kind,
+                        &*left_val,
+                        &*right_val,
+                        ::core::option::Option::None,
+                    );
+                }
+            }
+        };
+        let scope = container.new_scope();
+        let _service = scope.service().await;
+    };
+    #[allow(
+        clippy::expect_used,
+        clippy::diverging_sub_expression,
+        clippy::needless_return,
+        clippy::unwrap_in_result
+    )]
+    {
+        return tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .build()
+            .expect("Failed building the Runtime")
+            .block_on(body);
+    }
+}
@@ -0,0 +1,68 @@
+struct Config {
+    port: u32,
+}
+
+struct Db;
+
+struct Session {
+    user: String,
+}
+
+struct Service {
+    port: u32,
+}
+
+impl Service {
+    fn new(config: Config, _db: &Db, session: Session) -> Self {
+        println!("Service started on port {} for {}", config.port, session.user);
+        Self { port: config.port }
+    }
+}
+
+#[despatma_dependency_container::dependency_container]
+impl ConfigContainer {
+    fn config(&self) -> Config {
+        Config { port: 8080 }
+    }
+
+    #[Singleton]
+    fn db(&self) -> Db {
+        Db
+    }
+
+    #[Scoped]
+    async fn session(&self) -> Session {
+        Session {
+            user: "admin".to_string(),
+        }
+    }
+}
+
+#[despatma_dependency_container::dependency_container(constructions)]
+impl ServiceContainer {
+    fn new<'a>(
+        #[import(config: Config, db: &'a Db, async session: &'a Session)] config_container: ConfigContainer<'a>,
+    ) {
+    }
+
+    fn service(&self, config: Config, db: &Db, session: &Session) -> Service {
+        Service::new(
+            config,
+            db,
+            Session {
+                user: session.user.clone(),
+            },
+        )
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    let container = ServiceContainer::new(ConfigContainer::new());
+    let service = container.service().await;
+
+    assert_eq!(service.port, 8080);
+
+    let scope = container.new_scope();
+    let _service = scope.service().await;
+}
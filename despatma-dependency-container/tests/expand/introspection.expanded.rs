@@ -0,0 +1,196 @@
+struct Config {
+    port: u32,
+}
+struct Session;
+struct Service;
+impl Service {
+    fn new(port: u32, _session: &Session) -> Self {
+        {
+            ::std::io::_print(format_args!("Service started on port {0}\n", port));
+        };
+        Self
+    }
+}
+struct DependencyContainer<'a> {
+    config: std::rc::Rc<std::cell::OnceCell<Config>>,
+    session: std::rc::Rc<std::cell::OnceCell<Session>>,
+    config_constructions: std::rc::Rc<std::cell::Cell<usize>>,
+    session_constructions: std::rc::Rc<std::cell::Cell<usize>>,
+    service_constructions: std::rc::Rc<std::cell::Cell<usize>>,
+    _phantom: std::marker::PhantomData<&'a ()>,
+}
+#[automatically_derived]
+impl<'a> ::core::clone::Clone for DependencyContainer<'a> {
+    #[inline]
+    fn clone(&self) -> DependencyContainer<'a> {
+        DependencyContainer {
+            config: ::core::clone::Clone::clone(&self.config),
+            session: ::core::clone::Clone::clone(&self.session),
+            config_constructions: ::core::clone::Clone::clone(
+                &self.config_constructions,
+            ),
+            session_constructions: ::core::clone::Clone::clone(
+                &self.session_constructions,
+            ),
+            service_constructions: ::core::clone::Clone::clone(
+                &self.service_constructions,
+            ),
+            _phantom: ::core::clone::Clone::clone(&self._phantom),
+        }
+    }
+}
+impl<'a> DependencyContainer<'a> {
+    pub fn new() -> Self {
+        Self {
+            config: Default::default(),
+            session: Default::default(),
+            config_constructions: Default::default(),
+            session_constructions: Default::default(),
+            service_constructions: Default::default(),
+            _phantom: Default::default(),
+        }
+    }
+    pub fn new_scope(&self) -> Self {
+        Self {
+            config: self.config.clone(),
+            session: Default::default(),
+            config_constructions: self.config_constructions.clone(),
+            session_constructions: self.session_constructions.clone(),
+            service_constructions: self.service_constructions.clone(),
+            _phantom: Default::default(),
+        }
+    }
+    /// The names of the singleton and scoped dependencies which have been created, without creating any others
+    pub fn initialized(&self) -> Vec<&'static str> {
+        #[allow(unused_mut)]
+        let mut initialized = Vec::new();
+        if self.config.get().is_some() {
+            initialized.push("config");
+        }
+        if self.session.get().is_some() {
+            initialized.push("session");
+        }
+        initialized
+    }
+    /// How many times the dependency with the given name has been created by this container and its scopes.
+    /// Or `None` if the container does not have such a dependency.
+    pub fn constructions(&self, dependency: &str) -> Option<usize> {
+        match dependency {
+            "config" => Some(self.config_constructions.get()),
+            "session" => Some(self.session_constructions.get()),
+            "service" => Some(self.service_constructions.get()),
+            _ => None,
+        }
+    }
+    /// The dependencies of this container and how they are wired together in the DOT format
+    pub fn dependency_graph_dot() -> &'static str {
+        "digraph DependencyContainer {\n    node [shape=box];\n    \"config\" [label=\"config\\nSingleton\"];\n    \"session\" [label=\"session\\nScoped\"];\n    \"service\" [label=\"service\\nTransient\"];\n    \"service\" -> \"config\";\n    \"service\" -> \"session\";\n}\n"
+    }
+    /// The dependencies of this container and how they are wired together as a Mermaid flowchart
+    pub fn dependency_graph_mermaid() -> &'static str {
+        "graph TD\n    config[\"config<br/>Singleton\"]\n    session[\"session<br/>Scoped\"]\n    service[\"service<br/>Transient\"]\n    service --> config\n    service --> session\n"
+    }
+    pub fn config(&'a self) -> &Config {
+        self.config
+            .get_or_init(|| {
+                self.config_constructions.set(self.config_constructions.get() + 1);
+                Config { port: 8080 }
+            })
+    }
+    pub fn session(&'a self) -> &Session {
+        self.session
+            .get_or_init(|| {
+                self.session_constructions.set(self.session_constructions.get() + 1);
+                Session
+            })
+    }
+    pub fn service(&'a self) -> Service {
+        let config = self.config();
+        let session = self.session();
+        self.service_constructions.set(self.service_constructions.get() + 1);
+        Service::new(config.port, session)
+    }
+}
+impl<'a> core::fmt::Debug for DependencyContainer<'a> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        #[allow(unused_mut)]
+        let mut debug = f.debug_struct("DependencyContainer");
+        debug
+            .field(
+                "config",
+                &format_args!(
+                    "{0}", if self.config.get().is_some() { "initialized" } else {
+                    "uninitialized" }
+                ),
+            );
+        debug
+            .field(
+                "session",
+                &format_args!(
+                    "{0}", if self.session.get().is_some() { "initialized" } else {
+                    "uninitialized" }
+                ),
+            );
+        debug.finish_non_exhaustive()
+    }
+}
+fn main() {
+    let container = DependencyContainer::new();
+    let scope = container.new_scope();
+    let _service = scope.service();
+    match (&container.initialized(), &["config"]) {
+        (left_val, right_val) => {
+            if !(*left_val == *right_val) {
+                let kind = ::core::panicking::AssertKind::Eq;
+                ::core::panicking::assert_failed(
+                    kind,
+                    &*left_val,
+                    &*right_val,
+                    ::core::option::Option::None,
+                );
+            }
+        }
+    };
+    match (&scope.initialized(), &["config", "session"]) {
+        (left_val, right_val) => {
+            if !(*left_val == *right_val) {
+                let kind = ::core::panicking::AssertKind::Eq;
+                ::core::panicking::assert_failed(
+                    kind,
+                    &*left_val,
+                    &*right_val,
+                    ::core::option::Option::None,
+                );
+            }
+        }
+    };
+    match (&container.constructions("config"), &Some(1)) {
+        (left_val, right_val) => {
+            if !(*left_val == *right_val) {
+                let kind = ::core::panicking::AssertKind::Eq;
+                ::core::panicking::assert_failed(
+                    kind,
+                    &*left_val,
+                    &*right_val,
+                    ::core::option::Option::None,
+                );
+            }
+        }
+    };
+    match (&container.constructions("missing"), &None) {
+        (left_val, right_val) => {
+            if !(*left_val == *right_val) {
+                let kind = ::core::panicking::AssertKind::Eq;
+                ::core::panicking::assert_failed(
+                    kind,
+                    &*left_val,
+                    &*right_val,
+                    ::core::option::Option::None,
+                );
+            }
+        }
+    };
+    {
+        ::std::io::_print(format_args!("{0:?}\n", container));
+    };
+}
@@ -0,0 +1,43 @@
+struct Config {
+    port: u32,
+}
+
+struct Session;
+
+struct Service;
+
+impl Service {
+    fn new(port: u32, _session: &Session) -> Self {
+        println!("Service started on port {}", port);
+        Self
+    }
+}
+
+#[despatma_dependency_container::dependency_container(constructions)]
+impl DependencyContainer {
+    #[Singleton]
+    fn config(&self) -> Config {
+        Config { port: 8080 }
+    }
+
+    #[Scoped]
+    fn session(&self) -> Session {
+        Session
+    }
+
+    fn service(&self, config: &Config, session: &Session) -> Service {
+        Service::new(config.port, session)
+    }
+}
+
+fn main() {
+    let container = DependencyContainer::new();
+    let scope = container.new_scope();
+    let _service = scope.service();
+
+    assert_eq!(container.initialized(), ["config"]);
+    assert_eq!(scope.initialized(), ["config", "session"]);
+    assert_eq!(container.constructions("config"), Some(1));
+    assert_eq!(container.constructions("missing"), None);
+    println!("{container:?}");
+}
@@ -0,0 +1,218 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+static TENANTS: AtomicU32 = AtomicU32::new(0);
+static REQUESTS: AtomicU32 = AtomicU32::new(0);
+struct Config {
+    region: &'static str,
+}
+struct Tenant {
+    id: u32,
+}
+struct Request {
+    id: u32,
+    tenant: u32,
+}
+impl Request {
+    fn finish(&self) {
+        {
+            ::std::io::_print(format_args!("Request {0} finished\n", self.id));
+        };
+    }
+}
+struct DependencyContainer<'a> {
+    config: std::rc::Rc<std::cell::OnceCell<Config>>,
+    tenant: std::rc::Rc<std::cell::OnceCell<Tenant>>,
+    request: std::rc::Rc<std::cell::OnceCell<Request>>,
+    _scope_depth: usize,
+    _phantom: std::marker::PhantomData<&'a ()>,
+}
+#[automatically_derived]
+impl<'a> ::core::clone::Clone for DependencyContainer<'a> {
+    #[inline]
+    fn clone(&self) -> DependencyContainer<'a> {
+        DependencyContainer {
+            config: ::core::clone::Clone::clone(&self.config),
+            tenant: ::core::clone::Clone::clone(&self.tenant),
+            request: ::core::clone::Clone::clone(&self.request),
+            _scope_depth: ::core::clone::Clone::clone(&self._scope_depth),
+            _phantom: ::core::clone::Clone::clone(&self._phantom),
+        }
+    }
+}
+impl<'a> DependencyContainer<'a> {
+    pub fn new() -> Self {
+        Self {
+            config: Default::default(),
+            tenant: Default::default(),
+            request: Default::default(),
+            _scope_depth: 0,
+            _phantom: Default::default(),
+        }
+    }
+    pub fn new_scope(&self) -> Self {
+        Self {
+            config: self.config.clone(),
+            tenant: Default::default(),
+            request: Default::default(),
+            _scope_depth: 1,
+            _phantom: Default::default(),
+        }
+    }
+    /// Create a new `tenant` scope. The scoped dependencies of this scope and the scopes inside it are created again, while the others are shared with this container.
+    pub fn new_tenant_scope(&self) -> Self {
+        Self {
+            config: self.config.clone(),
+            tenant: Default::default(),
+            request: Default::default(),
+            _scope_depth: 1,
+            _phantom: Default::default(),
+        }
+    }
+    /// Create a new `request` scope. The scoped dependencies of this scope and the scopes inside it are created again, while the others are shared with this container.
+    pub fn new_request_scope(&self) -> Self {
+        Self {
+            config: self.config.clone(),
+            tenant: self.tenant.clone(),
+            request: Default::default(),
+            _scope_depth: 2,
+            _phantom: Default::default(),
+        }
+    }
+    /// Call the dispose hooks of the dependencies which have been created, starting with those that depend on the others.
+    /// A scope only disposes its own scoped dependencies.
+    pub fn shutdown(self) {
+        if self._scope_depth <= 2 {
+            if let Some(request) = self.request.get() {
+                request.finish();
+            }
+        }
+    }
+    /// The names of the singleton and scoped dependencies which have been created, without creating any others
+    pub fn initialized(&self) -> Vec<&'static str> {
+        #[allow(unused_mut)]
+        let mut initialized = Vec::new();
+        if self.config.get().is_some() {
+            initialized.push("config");
+        }
+        if self.tenant.get().is_some() {
+            initialized.push("tenant");
+        }
+        if self.request.get().is_some() {
+            initialized.push("request");
+        }
+        initialized
+    }
+    /// The dependencies of this container and how they are wired together in the DOT format
+    pub fn dependency_graph_dot() -> &'static str {
+        "digraph DependencyContainer {\n    node [shape=box];\n    \"config\" [label=\"config\\nSingleton\"];\n    \"tenant\" [label=\"tenant\\nScoped\"];\n    \"request\" [label=\"request\\nScoped\"];\n    \"tenant\" -> \"config\";\n    \"request\" -> \"tenant\";\n}\n"
+    }
+    /// The dependencies of this container and how they are wired together as a Mermaid flowchart
+    pub fn dependency_graph_mermaid() -> &'static str {
+        "graph TD\n    config[\"config<br/>Singleton\"]\n    tenant[\"tenant<br/>Scoped\"]\n    request[\"request<br/>Scoped\"]\n    tenant --> config\n    request --> tenant\n"
+    }
+    pub fn config(&'a self) -> &Config {
+        self.config.get_or_init(|| { Config { region: "eu" } })
+    }
+    pub fn tenant(&'a self) -> &Tenant {
+        let config = self.config();
+        self.tenant
+            .get_or_init(|| {
+                {
+                    ::std::io::_print(
+                        format_args!("Creating tenant in {0}\n", config.region),
+                    );
+                };
+                Tenant {
+                    id: TENANTS.fetch_add(1, Ordering::Relaxed),
+                }
+            })
+    }
+    pub fn request(&'a self) -> &Request {
+        let tenant = self.tenant();
+        self.request
+            .get_or_init(|| {
+                Request {
+                    id: REQUESTS.fetch_add(1, Ordering::Relaxed),
+                    tenant: tenant.id,
+                }
+            })
+    }
+}
+impl<'a> core::fmt::Debug for DependencyContainer<'a> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        #[allow(unused_mut)]
+        let mut debug = f.debug_struct("DependencyContainer");
+        debug
+            .field(
+                "config",
+                &format_args!(
+                    "{0}", if self.config.get().is_some() { "initialized" } else {
+                    "uninitialized" }
+                ),
+            );
+        debug
+            .field(
+                "tenant",
+                &format_args!(
+                    "{0}", if self.tenant.get().is_some() { "initialized" } else {
+                    "uninitialized" }
+                ),
+            );
+        debug
+            .field(
+                "request",
+                &format_args!(
+                    "{0}", if self.request.get().is_some() { "initialized" } else {
+                    "uninitialized" }
+                ),
+            );
+        debug.finish_non_exhaustive()
+    }
+}
+fn main() {
+    let container = DependencyContainer::new();
+    let tenant_scope = container.new_tenant_scope();
+    let first = tenant_scope.new_request_scope();
+    let second = tenant_scope.new_request_scope();
+    match (&first.request().id, &second.request().id) {
+        (left_val, right_val) => {
+            if *left_val == *right_val {
+                let kind = ::core::panicking::AssertKind::Ne;
+                ::core::panicking::assert_failed(
+                    kind,
+                    &*left_val,
+                    &*right_val,
+                    ::core::option::Option::None,
+                );
+            }
+        }
+    };
+    match (&first.request().tenant, &second.request().tenant) {
+        (left_val, right_val) => {
+            if !(*left_val == *right_val) {
+                let kind = ::core::panicking::AssertKind::Eq;
+                ::core::panicking::assert_failed(
+                    kind,
+                    &*left_val,
+                    &*right_val,
+                    ::core::option::Option::None,
+                );
+            }
+        }
+    };
+    let other_tenant = container.new_tenant_scope().new_request_scope();
+    match (&other_tenant.request().tenant, &first.request().tenant) {
+        (left_val, right_val) => {
+            if *left_val == *right_val {
+                let kind = ::core::panicking::AssertKind::Ne;
+                ::core::panicking::assert_failed(
+                    kind,
+                    &*left_val,
+                    &*right_val,
+                    ::core::option::Option::None,
+                );
+            }
+        }
+    };
+    first.shutdown();
+    second.shutdown();
+}
@@ -0,0 +1,68 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+
+static TENANTS: AtomicU32 = AtomicU32::new(0);
+static REQUESTS: AtomicU32 = AtomicU32::new(0);
+
+struct Config {
+    region: &'static str,
+}
+
+struct Tenant {
+    id: u32,
+}
+
+struct Request {
+    id: u32,
+    tenant: u32,
+}
+
+impl Request {
+    fn finish(&self) {
+        println!("Request {} finished", self.id);
+    }
+}
+
+#[despatma_dependency_container::dependency_container(scopes(tenant, request))]
+impl DependencyContainer {
+    #[Singleton]
+    fn config(&self) -> Config {
+        Config { region: "eu" }
+    }
+
+    #[Scoped(tenant)]
+    fn tenant(&self, config: &Config) -> Tenant {
+        println!("Creating tenant in {}", config.region);
+
+        Tenant {
+            id: TENANTS.fetch_add(1, Ordering::Relaxed),
+        }
+    }
+
+    #[Scoped(request)]
+    #[Dispose(finish)]
+    fn request(&self, tenant: &Tenant) -> Request {
+        Request {
+            id: REQUESTS.fetch_add(1, Ordering::Relaxed),
+            tenant: tenant.id,
+        }
+    }
+}
+
+fn main() {
+    let container = DependencyContainer::new();
+
+    let tenant_scope = container.new_tenant_scope();
+    let first = tenant_scope.new_request_scope();
+    let second = tenant_scope.new_request_scope();
+
+    // Every request gets its own request, but they share the tenant
+    assert_ne!(first.request().id, second.request().id);
+    assert_eq!(first.request().tenant, second.request().tenant);
+
+    // A new tenant scope creates the tenant again
+    let other_tenant = container.new_tenant_scope().new_request_scope();
+    assert_ne!(other_tenant.request().tenant, first.request().tenant);
+
+    first.shutdown();
+    second.shutdown();
+}
@@ -0,0 +1,24 @@
+use std::fmt;
+
+struct Config;
+
+#[despatma_dependency_container::dependency_container(no_debug)]
+impl DependencyContainer {
+    #[Singleton]
+    fn config(&self) -> Config {
+        Config
+    }
+}
+
+impl fmt::Debug for DependencyContainer<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self.initialized())
+    }
+}
+
+fn main() {
+    let container = DependencyContainer::new();
+    let _config = container.config();
+
+    assert_eq!(format!("{container:?}"), r#"["config"]"#);
+}
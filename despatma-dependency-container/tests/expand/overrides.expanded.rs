@@ -0,0 +1,215 @@
+use auto_impl::auto_impl;
+struct Config {
+    url: &'static str,
+}
+trait DAL {
+    fn name(&self) -> String;
+}
+const _: () = {
+    impl<'a, T: 'a + DAL + ?::core::marker::Sized> DAL for &'a T {
+        fn name(&self) -> String {
+            T::name(self)
+        }
+    }
+};
+const _: () = {
+    extern crate alloc;
+    impl<T: DAL + ?::core::marker::Sized> DAL for alloc::boxed::Box<T> {
+        fn name(&self) -> String {
+            T::name(self)
+        }
+    }
+};
+struct PostgresDAL {
+    url: &'static str,
+}
+impl DAL for PostgresDAL {
+    fn name(&self) -> String {
+        ::alloc::__export::must_use({
+            ::alloc::fmt::format(format_args!("postgres at {0}", self.url))
+        })
+    }
+}
+struct FakeDAL;
+impl DAL for FakeDAL {
+    fn name(&self) -> String {
+        "fake".to_string()
+    }
+}
+struct Cache;
+struct Service<D: DAL> {
+    dal: D,
+}
+struct DependencyContainer<'a> {
+    config: std::rc::Rc<std::cell::OnceCell<Config>>,
+    dal: std::rc::Rc<std::cell::OnceCell<std::boxed::Box<dyn DAL>>>,
+    config_override: Option<std::rc::Rc<dyn Fn() -> Config>>,
+    dal_override: Option<std::rc::Rc<dyn Fn() -> std::boxed::Box<dyn DAL>>>,
+    cache_override: Option<std::rc::Rc<dyn Fn() -> Cache>>,
+    _phantom: std::marker::PhantomData<&'a ()>,
+}
+#[automatically_derived]
+impl<'a> ::core::clone::Clone for DependencyContainer<'a> {
+    #[inline]
+    fn clone(&self) -> DependencyContainer<'a> {
+        DependencyContainer {
+            config: ::core::clone::Clone::clone(&self.config),
+            dal: ::core::clone::Clone::clone(&self.dal),
+            config_override: ::core::clone::Clone::clone(&self.config_override),
+            dal_override: ::core::clone::Clone::clone(&self.dal_override),
+            cache_override: ::core::clone::Clone::clone(&self.cache_override),
+            _phantom: ::core::clone::Clone::clone(&self._phantom),
+        }
+    }
+}
+impl<'a> DependencyContainer<'a> {
+    pub fn new() -> Self {
+        Self {
+            config: Default::default(),
+            dal: Default::default(),
+            config_override: None,
+            dal_override: None,
+            cache_override: None,
+            _phantom: Default::default(),
+        }
+    }
+    pub fn new_scope(&self) -> Self {
+        Self {
+            config: self.config.clone(),
+            dal: Default::default(),
+            config_override: self.config_override.clone(),
+            dal_override: self.dal_override.clone(),
+            cache_override: self.cache_override.clone(),
+            _phantom: Default::default(),
+        }
+    }
+    /// The names of the singleton and scoped dependencies which have been created, without creating any others
+    pub fn initialized(&self) -> Vec<&'static str> {
+        #[allow(unused_mut)]
+        let mut initialized = Vec::new();
+        if self.config.get().is_some() {
+            initialized.push("config");
+        }
+        if self.dal.get().is_some() {
+            initialized.push("dal");
+        }
+        initialized
+    }
+    /// The dependencies of this container and how they are wired together in the DOT format
+    pub fn dependency_graph_dot() -> &'static str {
+        "digraph DependencyContainer {\n    node [shape=box];\n    \"config\" [label=\"config\\nSingleton\"];\n    \"dal\" [label=\"dal\\nScoped, boxed\"];\n    \"cache\" [label=\"cache\\nTransient\"];\n    \"service\" [label=\"service\\nTransient\"];\n    \"dal\" -> \"config\";\n    \"service\" -> \"dal\";\n}\n"
+    }
+    /// The dependencies of this container and how they are wired together as a Mermaid flowchart
+    pub fn dependency_graph_mermaid() -> &'static str {
+        "graph TD\n    config[\"config<br/>Singleton\"]\n    dal[\"dal<br/>Scoped, boxed\"]\n    cache[\"cache<br/>Transient\"]\n    service[\"service<br/>Transient\"]\n    dal --> config\n    service --> dal\n"
+    }
+    pub fn config(&'a self) -> &Config {
+        if let Some(create) = &self.config_override {
+            self.config.get_or_init(|| { create() })
+        } else {
+            self.config
+                .get_or_init(|| {
+                    {
+                        ::core::panicking::panic_fmt(
+                            format_args!(
+                                "the config should not be read when the dal is overridden",
+                            ),
+                        );
+                    }
+                })
+        }
+    }
+    /// Replace how the `config` dependency is created. Useful to swap in a fake during tests.
+    pub fn override_config(mut self, create: impl Fn() -> Config + 'static) -> Self {
+        self.config_override = Some(std::rc::Rc::new(create));
+        self
+    }
+    pub fn dal(&'a self) -> &std::boxed::Box<dyn DAL> {
+        if let Some(create) = &self.dal_override {
+            self.dal.get_or_init(|| { create() })
+        } else {
+            let config = self.config();
+            self.dal.get_or_init(|| { Box::new(PostgresDAL { url: config.url }) })
+        }
+    }
+    /// Replace how the `dal` dependency is created. Useful to swap in a fake during tests.
+    pub fn override_dal(
+        mut self,
+        create: impl Fn() -> std::boxed::Box<dyn DAL> + 'static,
+    ) -> Self {
+        self.dal_override = Some(std::rc::Rc::new(create));
+        self
+    }
+    pub fn cache(&'a self) -> impl Sized {
+        self.create_cache()
+    }
+    fn create_cache(&'a self) -> Cache {
+        if let Some(create) = &self.cache_override { create() } else { Cache }
+    }
+    /// Replace how the `cache` dependency is created. Useful to swap in a fake during tests.
+    pub fn override_cache(mut self, create: impl Fn() -> Cache + 'static) -> Self {
+        self.cache_override = Some(std::rc::Rc::new(create));
+        self
+    }
+    pub fn service(&'a self) -> Service<impl DAL + use<'a>> {
+        let dal = self.dal();
+        Service { dal }
+    }
+}
+impl<'a> core::fmt::Debug for DependencyContainer<'a> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        #[allow(unused_mut)]
+        let mut debug = f.debug_struct("DependencyContainer");
+        debug
+            .field(
+                "config",
+                &format_args!(
+                    "{0}", if self.config.get().is_some() { "initialized" } else {
+                    "uninitialized" }
+                ),
+            );
+        debug
+            .field(
+                "dal",
+                &format_args!(
+                    "{0}", if self.dal.get().is_some() { "initialized" } else {
+                    "uninitialized" }
+                ),
+            );
+        debug.finish_non_exhaustive()
+    }
+}
+fn main() {
+    let container = DependencyContainer::new()
+        .override_dal(|| Box::new(FakeDAL))
+        .override_cache(|| Cache);
+    match (&container.service().dal.name(), &"fake") {
+        (left_val, right_val) => {
+            if !(*left_val == *right_val) {
+                let kind = ::core::panicking::AssertKind::Eq;
+                ::core::panicking::assert_failed(
+                    kind,
+                    &*left_val,
+                    &*right_val,
+                    ::core::option::Option::None,
+                );
+            }
+        }
+    };
+    let _cache = container.cache();
+    let container = DependencyContainer::new()
+        .override_config(|| Config { url: "localhost:5432" });
+    match (&container.service().dal.name(), &"postgres at localhost:5432") {
+        (left_val, right_val) => {
+            if !(*left_val == *right_val) {
+                let kind = ::core::panicking::AssertKind::Eq;
+                ::core::panicking::assert_failed(
+                    kind,
+                    &*left_val,
+                    &*right_val,
+                    ::core::option::Option::None,
+                );
+            }
+        }
+    };
+}
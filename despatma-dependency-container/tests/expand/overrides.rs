@@ -0,0 +1,74 @@
+use auto_impl::auto_impl;
+
+struct Config {
+    url: &'static str,
+}
+
+#[auto_impl(&, Box)]
+trait DAL {
+    fn name(&self) -> String;
+}
+
+struct PostgresDAL {
+    url: &'static str,
+}
+
+impl DAL for PostgresDAL {
+    fn name(&self) -> String {
+        format!("postgres at {}", self.url)
+    }
+}
+
+struct FakeDAL;
+
+impl DAL for FakeDAL {
+    fn name(&self) -> String {
+        "fake".to_string()
+    }
+}
+
+struct Cache;
+
+struct Service<D: DAL> {
+    dal: D,
+}
+
+#[despatma_dependency_container::dependency_container(overrides)]
+impl DependencyContainer {
+    #[Singleton]
+    fn config(&self) -> Config {
+        panic!("the config should not be read when the dal is overridden")
+    }
+
+    #[Scoped]
+    fn dal(&self, config: &Config) -> Box<dyn DAL> {
+        Box::new(PostgresDAL { url: config.url })
+    }
+
+    #[Transient(Cache)]
+    fn cache(&self) -> impl Sized {
+        Cache
+    }
+
+    fn service(&self, dal: impl DAL) -> Service<impl DAL> {
+        Service { dal }
+    }
+}
+
+fn main() {
+    let container = DependencyContainer::new()
+        .override_dal(|| Box::new(FakeDAL))
+        .override_cache(|| Cache);
+
+    assert_eq!(container.service().dal.name(), "fake");
+    let _cache = container.cache();
+
+    let container = DependencyContainer::new().override_config(|| Config {
+        url: "localhost:5432",
+    });
+
+    assert_eq!(
+        container.service().dal.name(),
+        "postgres at localhost:5432"
+    );
+}
@@ -0,0 +1,126 @@
+struct Db {
+    url: &'static str,
+}
+struct Report {
+    source: &'static str,
+    fallback: &'static str,
+}
+struct DependencyContainer<'a> {
+    db: std::rc::Rc<std::cell::OnceCell<Db>>,
+    replica_db: std::rc::Rc<std::cell::OnceCell<Db>>,
+    _phantom: std::marker::PhantomData<&'a ()>,
+}
+#[automatically_derived]
+impl<'a> ::core::clone::Clone for DependencyContainer<'a> {
+    #[inline]
+    fn clone(&self) -> DependencyContainer<'a> {
+        DependencyContainer {
+            db: ::core::clone::Clone::clone(&self.db),
+            replica_db: ::core::clone::Clone::clone(&self.replica_db),
+            _phantom: ::core::clone::Clone::clone(&self._phantom),
+        }
+    }
+}
+impl<'a> DependencyContainer<'a> {
+    pub fn new() -> Self {
+        Self {
+            db: Default::default(),
+            replica_db: Default::default(),
+            _phantom: Default::default(),
+        }
+    }
+    pub fn new_scope(&self) -> Self {
+        Self {
+            db: self.db.clone(),
+            replica_db: self.replica_db.clone(),
+            _phantom: Default::default(),
+        }
+    }
+    /// The names of the singleton and scoped dependencies which have been created, without creating any others
+    pub fn initialized(&self) -> Vec<&'static str> {
+        #[allow(unused_mut)]
+        let mut initialized = Vec::new();
+        if self.db.get().is_some() {
+            initialized.push("db");
+        }
+        if self.replica_db.get().is_some() {
+            initialized.push("replica_db");
+        }
+        initialized
+    }
+    /// The dependencies of this container and how they are wired together in the DOT format
+    pub fn dependency_graph_dot() -> &'static str {
+        "digraph DependencyContainer {\n    node [shape=box];\n    \"db\" [label=\"db\\nSingleton\"];\n    \"replica_db\" [label=\"replica_db\\nSingleton\"];\n    \"report\" [label=\"report\\nTransient\"];\n    \"report\" -> \"replica_db\" [label=\"db\"];\n    \"report\" -> \"db\" [label=\"fallback\"];\n}\n"
+    }
+    /// The dependencies of this container and how they are wired together as a Mermaid flowchart
+    pub fn dependency_graph_mermaid() -> &'static str {
+        "graph TD\n    db[\"db<br/>Singleton\"]\n    replica_db[\"replica_db<br/>Singleton\"]\n    report[\"report<br/>Transient\"]\n    report -- db --> replica_db\n    report -- fallback --> db\n"
+    }
+    pub fn db(&'a self) -> &Db {
+        self.db.get_or_init(|| { Db { url: "postgres://primary" } })
+    }
+    pub fn replica_db(&'a self) -> &Db {
+        self.replica_db.get_or_init(|| { Db { url: "postgres://replica" } })
+    }
+    pub fn report(&'a self) -> Report {
+        let db = self.replica_db();
+        let fallback = self.db();
+        Report {
+            source: db.url,
+            fallback: fallback.url,
+        }
+    }
+}
+impl<'a> core::fmt::Debug for DependencyContainer<'a> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        #[allow(unused_mut)]
+        let mut debug = f.debug_struct("DependencyContainer");
+        debug
+            .field(
+                "db",
+                &format_args!(
+                    "{0}", if self.db.get().is_some() { "initialized" } else {
+                    "uninitialized" }
+                ),
+            );
+        debug
+            .field(
+                "replica_db",
+                &format_args!(
+                    "{0}", if self.replica_db.get().is_some() { "initialized" } else {
+                    "uninitialized" }
+                ),
+            );
+        debug.finish_non_exhaustive()
+    }
+}
+fn main() {
+    let container = DependencyContainer::new();
+    let report = container.report();
+    match (&report.source, &"postgres://replica") {
+        (left_val, right_val) => {
+            if !(*left_val == *right_val) {
+                let kind = ::core::panicking::AssertKind::Eq;
+                ::core::panicking::assert_failed(
+                    kind,
+                    &*left_val,
+                    &*right_val,
+                    ::core::option::Option::None,
+                );
+            }
+        }
+    };
+    match (&report.fallback, &"postgres://primary") {
+        (left_val, right_val) => {
+            if !(*left_val == *right_val) {
+                let kind = ::core::panicking::AssertKind::Eq;
+                ::core::panicking::assert_failed(
+                    kind,
+                    &*left_val,
+                    &*right_val,
+                    ::core::option::Option::None,
+                );
+            }
+        }
+    };
+}
@@ -0,0 +1,41 @@
+struct Db {
+    url: &'static str,
+}
+
+struct Report {
+    source: &'static str,
+    fallback: &'static str,
+}
+
+#[despatma_dependency_container::dependency_container]
+impl DependencyContainer {
+    #[Singleton]
+    fn db(&self) -> Db {
+        Db {
+            url: "postgres://primary",
+        }
+    }
+
+    #[Singleton]
+    #[Named("replica")]
+    fn replica_db(&self) -> Db {
+        Db {
+            url: "postgres://replica",
+        }
+    }
+
+    fn report(&self, #[from(replica)] db: &Db, #[from(db)] fallback: &Db) -> Report {
+        Report {
+            source: db.url,
+            fallback: fallback.url,
+        }
+    }
+}
+
+fn main() {
+    let container = DependencyContainer::new();
+    let report = container.report();
+
+    assert_eq!(report.source, "postgres://replica");
+    assert_eq!(report.fallback, "postgres://primary");
+}
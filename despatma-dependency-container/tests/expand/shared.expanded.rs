@@ -0,0 +1,191 @@
+use std::{sync::Arc, thread};
+use auto_impl::auto_impl;
+struct Config {
+    port: u32,
+}
+trait Repo {
+    fn name(&self) -> &str;
+}
+const _: () = {
+    impl<'a, T: 'a + Repo + ?::core::marker::Sized> Repo for &'a T {
+        fn name(&self) -> &str {
+            T::name(self)
+        }
+    }
+};
+const _: () = {
+    extern crate alloc;
+    impl<T: Repo + ?::core::marker::Sized> Repo for alloc::sync::Arc<T> {
+        fn name(&self) -> &str {
+            T::name(self)
+        }
+    }
+};
+struct PostgresRepo;
+impl Repo for PostgresRepo {
+    fn name(&self) -> &str {
+        "postgres"
+    }
+}
+struct Service<R: Repo> {
+    repo: R,
+}
+struct Server {
+    config: Arc<Config>,
+}
+struct Report<'a> {
+    config: &'a Config,
+}
+struct DependencyContainer<'a> {
+    config: std::rc::Rc<std::cell::OnceCell<std::sync::Arc<Config>>>,
+    repo: std::rc::Rc<std::cell::OnceCell<std::sync::Arc<PostgresRepo>>>,
+    _phantom: std::marker::PhantomData<&'a ()>,
+}
+#[automatically_derived]
+impl<'a> ::core::clone::Clone for DependencyContainer<'a> {
+    #[inline]
+    fn clone(&self) -> DependencyContainer<'a> {
+        DependencyContainer {
+            config: ::core::clone::Clone::clone(&self.config),
+            repo: ::core::clone::Clone::clone(&self.repo),
+            _phantom: ::core::clone::Clone::clone(&self._phantom),
+        }
+    }
+}
+impl<'a> DependencyContainer<'a> {
+    pub fn new() -> Self {
+        Self {
+            config: Default::default(),
+            repo: Default::default(),
+            _phantom: Default::default(),
+        }
+    }
+    pub fn new_scope(&self) -> Self {
+        Self {
+            config: self.config.clone(),
+            repo: self.repo.clone(),
+            _phantom: Default::default(),
+        }
+    }
+    /// The names of the singleton and scoped dependencies which have been created, without creating any others
+    pub fn initialized(&self) -> Vec<&'static str> {
+        #[allow(unused_mut)]
+        let mut initialized = Vec::new();
+        if self.config.get().is_some() {
+            initialized.push("config");
+        }
+        if self.repo.get().is_some() {
+            initialized.push("repo");
+        }
+        initialized
+    }
+    /// The dependencies of this container and how they are wired together in the DOT format
+    pub fn dependency_graph_dot() -> &'static str {
+        "digraph DependencyContainer {\n    node [shape=box];\n    \"config\" [label=\"config\\nSingleton\"];\n    \"repo\" [label=\"repo\\nSingleton\"];\n    \"service\" [label=\"service\\nTransient\"];\n    \"server\" [label=\"server\\nTransient\"];\n    \"report\" [label=\"report\\nTransient\"];\n    \"service\" -> \"repo\";\n    \"server\" -> \"config\";\n    \"report\" -> \"config\";\n}\n"
+    }
+    /// The dependencies of this container and how they are wired together as a Mermaid flowchart
+    pub fn dependency_graph_mermaid() -> &'static str {
+        "graph TD\n    config[\"config<br/>Singleton\"]\n    repo[\"repo<br/>Singleton\"]\n    service[\"service<br/>Transient\"]\n    server[\"server<br/>Transient\"]\n    report[\"report<br/>Transient\"]\n    service --> repo\n    server --> config\n    report --> config\n"
+    }
+    pub fn config(&'a self) -> std::sync::Arc<Config> {
+        self.config
+            .get_or_init(|| std::sync::Arc::new({ Config { port: 8080 } }))
+            .clone()
+    }
+    pub fn repo(&'a self) -> std::sync::Arc<impl Repo> {
+        self.create_repo()
+    }
+    fn create_repo(&'a self) -> std::sync::Arc<PostgresRepo> {
+        self.repo.get_or_init(|| std::sync::Arc::new({ PostgresRepo })).clone()
+    }
+    pub fn service(&'a self) -> Service<impl Repo> {
+        self.create_service()
+    }
+    fn create_service(&'a self) -> Service<std::sync::Arc<PostgresRepo>> {
+        let repo = self.create_repo();
+        Service { repo }
+    }
+    pub fn server(&'a self) -> Server {
+        let config = self.config();
+        Server { config }
+    }
+    pub fn report(&'a self) -> Report<'a> {
+        let config: &Config = {
+            self.config();
+            self.config.get().expect("the dependency to have been created")
+        };
+        Report { config }
+    }
+}
+impl<'a> core::fmt::Debug for DependencyContainer<'a> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        #[allow(unused_mut)]
+        let mut debug = f.debug_struct("DependencyContainer");
+        debug
+            .field(
+                "config",
+                &format_args!(
+                    "{0}", if self.config.get().is_some() { "initialized" } else {
+                    "uninitialized" }
+                ),
+            );
+        debug
+            .field(
+                "repo",
+                &format_args!(
+                    "{0}", if self.repo.get().is_some() { "initialized" } else {
+                    "uninitialized" }
+                ),
+            );
+        debug.finish_non_exhaustive()
+    }
+}
+fn main() {
+    let container = DependencyContainer::new();
+    let config = container.config();
+    let port = thread::spawn(move || config.port).join().unwrap();
+    match (&port, &8080) {
+        (left_val, right_val) => {
+            if !(*left_val == *right_val) {
+                let kind = ::core::panicking::AssertKind::Eq;
+                ::core::panicking::assert_failed(
+                    kind,
+                    &*left_val,
+                    &*right_val,
+                    ::core::option::Option::None,
+                );
+            }
+        }
+    };
+    if !Arc::ptr_eq(&container.server().config, &container.config()) {
+        ::core::panicking::panic(
+            "assertion failed: Arc::ptr_eq(&container.server().config, &container.config())",
+        )
+    }
+    match (&container.service().repo.name(), &"postgres") {
+        (left_val, right_val) => {
+            if !(*left_val == *right_val) {
+                let kind = ::core::panicking::AssertKind::Eq;
+                ::core::panicking::assert_failed(
+                    kind,
+                    &*left_val,
+                    &*right_val,
+                    ::core::option::Option::None,
+                );
+            }
+        }
+    };
+    match (&container.report().config.port, &8080) {
+        (left_val, right_val) => {
+            if !(*left_val == *right_val) {
+                let kind = ::core::panicking::AssertKind::Eq;
+                ::core::panicking::assert_failed(
+                    kind,
+                    &*left_val,
+                    &*right_val,
+                    ::core::option::Option::None,
+                );
+            }
+        }
+    };
+}
@@ -0,0 +1,70 @@
+use std::{sync::Arc, thread};
+
+use auto_impl::auto_impl;
+
+struct Config {
+    port: u32,
+}
+
+#[auto_impl(&, Arc)]
+trait Repo {
+    fn name(&self) -> &str;
+}
+
+struct PostgresRepo;
+
+impl Repo for PostgresRepo {
+    fn name(&self) -> &str {
+        "postgres"
+    }
+}
+
+struct Service<R: Repo> {
+    repo: R,
+}
+
+struct Server {
+    config: Arc<Config>,
+}
+
+struct Report<'a> {
+    config: &'a Config,
+}
+
+#[despatma_dependency_container::dependency_container]
+impl DependencyContainer {
+    #[Singleton(shared)]
+    fn config(&self) -> Config {
+        Config { port: 8080 }
+    }
+
+    #[Singleton(shared, PostgresRepo)]
+    fn repo(&self) -> impl Repo {
+        PostgresRepo
+    }
+
+    fn service(&self, repo: impl Repo) -> Service<impl Repo> {
+        Service { repo }
+    }
+
+    fn server(&self, config: Arc<Config>) -> Server {
+        Server { config }
+    }
+
+    fn report(&self, config: &Config) -> Report<'a> {
+        Report { config }
+    }
+}
+
+fn main() {
+    let container = DependencyContainer::new();
+    let config = container.config();
+
+    // The handle is owned. So it can be moved to another thread
+    let port = thread::spawn(move || config.port).join().unwrap();
+
+    assert_eq!(port, 8080);
+    assert!(Arc::ptr_eq(&container.server().config, &container.config()));
+    assert_eq!(container.service().repo.name(), "postgres");
+    assert_eq!(container.report().config.port, 8080);
+}
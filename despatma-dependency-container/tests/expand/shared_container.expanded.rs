@@ -0,0 +1,178 @@
+use std::sync::Arc;
+struct Config {
+    port: u32,
+}
+impl Config {
+    async fn load() -> Self {
+        Self { port: 8080 }
+    }
+}
+struct Database {
+    port: u32,
+}
+struct Handler {
+    db: Arc<Database>,
+}
+struct DependencyContainer {
+    config: std::sync::Arc<async_once_cell::OnceCell<std::sync::Arc<Config>>>,
+    database: std::sync::Arc<std::sync::OnceLock<std::sync::Arc<Database>>>,
+}
+#[automatically_derived]
+impl ::core::clone::Clone for DependencyContainer {
+    #[inline]
+    fn clone(&self) -> DependencyContainer {
+        DependencyContainer {
+            config: ::core::clone::Clone::clone(&self.config),
+            database: ::core::clone::Clone::clone(&self.database),
+        }
+    }
+}
+impl<'a> DependencyContainer {
+    pub fn new() -> Self {
+        Self {
+            config: Default::default(),
+            database: Default::default(),
+        }
+    }
+    pub fn new_scope(&self) -> Self {
+        Self {
+            config: self.config.clone(),
+            database: Default::default(),
+        }
+    }
+    /// The names of the singleton and scoped dependencies which have been created, without creating any others
+    pub fn initialized(&self) -> Vec<&'static str> {
+        #[allow(unused_mut)]
+        let mut initialized = Vec::new();
+        if self.config.get().is_some() {
+            initialized.push("config");
+        }
+        if self.database.get().is_some() {
+            initialized.push("database");
+        }
+        initialized
+    }
+    /// The dependencies of this container and how they are wired together in the DOT format
+    pub fn dependency_graph_dot() -> &'static str {
+        "digraph DependencyContainer {\n    node [shape=box];\n    \"config\" [label=\"config\\nSingleton, async\"];\n    \"database\" [label=\"database\\nScoped, async\"];\n    \"handler\" [label=\"handler\\nTransient, async\"];\n    \"database\" -> \"config\";\n    \"handler\" -> \"database\";\n}\n"
+    }
+    /// The dependencies of this container and how they are wired together as a Mermaid flowchart
+    pub fn dependency_graph_mermaid() -> &'static str {
+        "graph TD\n    config[\"config<br/>Singleton, async\"]\n    database[\"database<br/>Scoped, async\"]\n    handler[\"handler<br/>Transient, async\"]\n    database --> config\n    handler --> database\n"
+    }
+    pub fn config(
+        &'a self,
+    ) -> impl std::future::Future<Output = std::sync::Arc<Config>> + Send + 'a {
+        async move {
+            self.config
+                .get_or_init(async { std::sync::Arc::new({ Config::load().await }) })
+                .await
+                .clone()
+        }
+    }
+    pub fn database(
+        &'a self,
+    ) -> impl std::future::Future<Output = std::sync::Arc<Database>> + Send + 'a {
+        async move {
+            let config: &Config = {
+                self.config().await;
+                self.config.get().expect("the dependency to have been created")
+            };
+            self.database
+                .get_or_init(|| std::sync::Arc::new({ Database { port: config.port } }))
+                .clone()
+        }
+    }
+    pub fn handler(&'a self) -> impl std::future::Future<Output = Handler> + Send + 'a {
+        async move {
+            let database = self.database().await;
+            Handler { db: database }
+        }
+    }
+}
+impl core::fmt::Debug for DependencyContainer {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        #[allow(unused_mut)]
+        let mut debug = f.debug_struct("DependencyContainer");
+        debug
+            .field(
+                "config",
+                &format_args!(
+                    "{0}", if self.config.get().is_some() { "initialized" } else {
+                    "uninitialized" }
+                ),
+            );
+        debug
+            .field(
+                "database",
+                &format_args!(
+                    "{0}", if self.database.get().is_some() { "initialized" } else {
+                    "uninitialized" }
+                ),
+            );
+        debug.finish_non_exhaustive()
+    }
+}
+const _: () = {
+    fn assert_send_sync<T: ?Sized + Send + Sync>() {}
+    #[allow(dead_code)]
+    fn assert_dependencies<'a>() {
+        assert_send_sync::<Config>();
+        assert_send_sync::<Database>();
+    }
+};
+fn main() {
+    let body = async {
+        let container: DependencyContainer = DependencyContainer::new();
+        let scope = container.new_scope();
+        let port = tokio::spawn(async move { scope.handler().await.db.port })
+            .await
+            .unwrap();
+        match (&port, &8080) {
+            (left_val, right_val) => {
+                if !(*left_val == *right_val) {
+                    let kind = ::core::panicking::AssertKind::Eq;
+                    ::core::panicking::assert_failed(
+                        kind,
+                        &*left_val,
+                        &*right_val,
+                        ::core::option::Option::None,
+                    );
+                }
+            }
+        };
+        match (&container.config().await.port, &8080) {
+            (left_val, right_val) => {
+                if !(*left_val == *right_val) {
+                    let kind = ::core::panicking::AssertKind::Eq;
+                    ::core::panicking::assert_failed(
+                        kind,
+                        &*left_val,
+                        &*right_val,
+                        ::core::option::Option::None,
+                    );
+                }
+            }
+        };
+    };
+    let body = {
+        if false {
+            let _: &dyn ::core::future::Future<Output = ()> = &body;
+        }
+        body
+    };
+    #[allow(
+        clippy::expect_used,
+        clippy::diverging_sub_expression,
+        clippy::needless_return,
+        clippy::unwrap_in_result
+    )]
+    {
+        use tokio::runtime::Builder;
+        return Builder::new_multi_thread()
+            .enable_all()
+            .build()
+            .expect("Failed building the Runtime")
+            .block_on(body);
+    }
+}
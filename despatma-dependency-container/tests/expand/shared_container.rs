@@ -0,0 +1,50 @@
+use std::sync::Arc;
+
+struct Config {
+    port: u32,
+}
+
+impl Config {
+    async fn load() -> Self {
+        Self { port: 8080 }
+    }
+}
+
+struct Database {
+    port: u32,
+}
+
+struct Handler {
+    db: Arc<Database>,
+}
+
+#[despatma_dependency_container::dependency_container(sync, shared)]
+impl DependencyContainer {
+    #[Singleton]
+    async fn config(&self) -> Config {
+        Config::load().await
+    }
+
+    #[Scoped]
+    fn database(&self, config: &Config) -> Database {
+        Database { port: config.port }
+    }
+
+    fn handler(&self, database: Arc<Database>) -> Handler {
+        Handler { db: database }
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    // The container does not borrow anything. So it can be moved into spawned tasks
+    let container: DependencyContainer = DependencyContainer::new();
+    let scope = container.new_scope();
+
+    let port = tokio::spawn(async move { scope.handler().await.db.port })
+        .await
+        .unwrap();
+
+    assert_eq!(port, 8080);
+    assert_eq!(container.config().await.port, 8080);
+}
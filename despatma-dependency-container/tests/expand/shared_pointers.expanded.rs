@@ -0,0 +1,178 @@
+use std::{rc::Rc, sync::Arc};
+use auto_impl::auto_impl;
+trait Repo {
+    fn name(&self) -> &str;
+}
+const _: () = {
+    impl<'a, T: 'a + Repo + ?::core::marker::Sized> Repo for &'a T {
+        fn name(&self) -> &str {
+            T::name(self)
+        }
+    }
+};
+const _: () = {
+    extern crate alloc;
+    impl<T: Repo + ?::core::marker::Sized> Repo for alloc::sync::Arc<T> {
+        fn name(&self) -> &str {
+            T::name(self)
+        }
+    }
+};
+struct PostgresRepo;
+impl Repo for PostgresRepo {
+    fn name(&self) -> &str {
+        "postgres"
+    }
+}
+trait Cache {}
+struct MemoryCache;
+impl Cache for MemoryCache {}
+trait Clock {}
+struct SystemClock;
+impl Clock for SystemClock {}
+/// A smart pointer of the application which is handled like `Arc` and `Rc`
+struct Shared<T: ?Sized>(Rc<T>);
+impl<T: ?Sized> Clone for Shared<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+struct Service<R: Repo> {
+    repo: R,
+}
+impl<R: Repo> Service<R> {
+    fn new(repo: R) -> Self {
+        {
+            ::std::io::_print(format_args!("Service started with {0}\n", repo.name()));
+        };
+        Self { repo }
+    }
+}
+struct Worker {
+    repo: Arc<dyn Repo>,
+    _cache: Rc<dyn Cache>,
+    _clock: Shared<dyn Clock>,
+}
+struct DependencyContainer<'a> {
+    repo: std::rc::Rc<std::cell::OnceCell<std::sync::Arc<dyn Repo>>>,
+    cache: std::rc::Rc<std::cell::OnceCell<std::rc::Rc<dyn Cache>>>,
+    clock: std::rc::Rc<std::cell::OnceCell<Shared<dyn Clock>>>,
+    _phantom: std::marker::PhantomData<&'a ()>,
+}
+#[automatically_derived]
+impl<'a> ::core::clone::Clone for DependencyContainer<'a> {
+    #[inline]
+    fn clone(&self) -> DependencyContainer<'a> {
+        DependencyContainer {
+            repo: ::core::clone::Clone::clone(&self.repo),
+            cache: ::core::clone::Clone::clone(&self.cache),
+            clock: ::core::clone::Clone::clone(&self.clock),
+            _phantom: ::core::clone::Clone::clone(&self._phantom),
+        }
+    }
+}
+impl<'a> DependencyContainer<'a> {
+    pub fn new() -> Self {
+        Self {
+            repo: Default::default(),
+            cache: Default::default(),
+            clock: Default::default(),
+            _phantom: Default::default(),
+        }
+    }
+    pub fn new_scope(&self) -> Self {
+        Self {
+            repo: self.repo.clone(),
+            cache: Default::default(),
+            clock: self.clock.clone(),
+            _phantom: Default::default(),
+        }
+    }
+    /// The names of the singleton and scoped dependencies which have been created, without creating any others
+    pub fn initialized(&self) -> Vec<&'static str> {
+        #[allow(unused_mut)]
+        let mut initialized = Vec::new();
+        if self.repo.get().is_some() {
+            initialized.push("repo");
+        }
+        if self.cache.get().is_some() {
+            initialized.push("cache");
+        }
+        if self.clock.get().is_some() {
+            initialized.push("clock");
+        }
+        initialized
+    }
+    /// The dependencies of this container and how they are wired together in the DOT format
+    pub fn dependency_graph_dot() -> &'static str {
+        "digraph DependencyContainer {\n    node [shape=box];\n    \"repo\" [label=\"repo\\nSingleton, boxed\"];\n    \"cache\" [label=\"cache\\nScoped, boxed\"];\n    \"clock\" [label=\"clock\\nSingleton, boxed\"];\n    \"service\" [label=\"service\\nTransient\"];\n    \"worker\" [label=\"worker\\nTransient\"];\n    \"service\" -> \"repo\";\n    \"worker\" -> \"repo\";\n    \"worker\" -> \"cache\";\n    \"worker\" -> \"clock\";\n}\n"
+    }
+    /// The dependencies of this container and how they are wired together as a Mermaid flowchart
+    pub fn dependency_graph_mermaid() -> &'static str {
+        "graph TD\n    repo[\"repo<br/>Singleton, boxed\"]\n    cache[\"cache<br/>Scoped, boxed\"]\n    clock[\"clock<br/>Singleton, boxed\"]\n    service[\"service<br/>Transient\"]\n    worker[\"worker<br/>Transient\"]\n    service --> repo\n    worker --> repo\n    worker --> cache\n    worker --> clock\n"
+    }
+    pub fn repo(&'a self) -> &std::sync::Arc<dyn Repo> {
+        self.repo.get_or_init(|| { Arc::new(PostgresRepo) })
+    }
+    pub fn cache(&'a self) -> &std::rc::Rc<dyn Cache> {
+        self.cache.get_or_init(|| { Rc::new(MemoryCache) })
+    }
+    pub fn clock(&'a self) -> &Shared<dyn Clock> {
+        self.clock.get_or_init(|| { Shared(Rc::new(SystemClock)) })
+    }
+    pub fn service(&'a self) -> Service<impl Repo + use<'a>> {
+        let repo = self.repo();
+        Service::new(repo)
+    }
+    pub fn worker(&'a self) -> Worker {
+        let repo = self.repo().clone();
+        let cache = self.cache().clone();
+        let clock = self.clock().clone();
+        Worker {
+            repo,
+            _cache: cache,
+            _clock: clock,
+        }
+    }
+}
+impl<'a> core::fmt::Debug for DependencyContainer<'a> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        #[allow(unused_mut)]
+        let mut debug = f.debug_struct("DependencyContainer");
+        debug
+            .field(
+                "repo",
+                &format_args!(
+                    "{0}", if self.repo.get().is_some() { "initialized" } else {
+                    "uninitialized" }
+                ),
+            );
+        debug
+            .field(
+                "cache",
+                &format_args!(
+                    "{0}", if self.cache.get().is_some() { "initialized" } else {
+                    "uninitialized" }
+                ),
+            );
+        debug
+            .field(
+                "clock",
+                &format_args!(
+                    "{0}", if self.clock.get().is_some() { "initialized" } else {
+                    "uninitialized" }
+                ),
+            );
+        debug.finish_non_exhaustive()
+    }
+}
+fn main() {
+    let container = DependencyContainer::new();
+    let _service = container.service();
+    let worker = container.worker();
+    if !Arc::ptr_eq(&worker.repo, container.repo()) {
+        ::core::panicking::panic(
+            "assertion failed: Arc::ptr_eq(&worker.repo, container.repo())",
+        )
+    }
+}
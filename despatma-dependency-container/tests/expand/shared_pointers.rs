@@ -0,0 +1,92 @@
+use std::{rc::Rc, sync::Arc};
+
+use auto_impl::auto_impl;
+
+#[auto_impl(&, Arc)]
+trait Repo {
+    fn name(&self) -> &str;
+}
+
+struct PostgresRepo;
+
+impl Repo for PostgresRepo {
+    fn name(&self) -> &str {
+        "postgres"
+    }
+}
+
+trait Cache {}
+
+struct MemoryCache;
+
+impl Cache for MemoryCache {}
+
+trait Clock {}
+
+struct SystemClock;
+
+impl Clock for SystemClock {}
+
+/// A smart pointer of the application which is handled like `Arc` and `Rc`
+struct Shared<T: ?Sized>(Rc<T>);
+
+impl<T: ?Sized> Clone for Shared<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+struct Service<R: Repo> {
+    repo: R,
+}
+
+impl<R: Repo> Service<R> {
+    fn new(repo: R) -> Self {
+        println!("Service started with {}", repo.name());
+        Self { repo }
+    }
+}
+
+struct Worker {
+    repo: Arc<dyn Repo>,
+    _cache: Rc<dyn Cache>,
+    _clock: Shared<dyn Clock>,
+}
+
+#[despatma_dependency_container::dependency_container(pointers(Shared))]
+impl DependencyContainer {
+    #[Singleton]
+    fn repo(&self) -> Arc<dyn Repo> {
+        Arc::new(PostgresRepo)
+    }
+
+    #[Scoped]
+    fn cache(&self) -> Rc<dyn Cache> {
+        Rc::new(MemoryCache)
+    }
+
+    #[Singleton]
+    fn clock(&self) -> Shared<dyn Clock> {
+        Shared(Rc::new(SystemClock))
+    }
+
+    fn service(&self, repo: impl Repo) -> Service<impl Repo> {
+        Service::new(repo)
+    }
+
+    fn worker(&self, repo: Arc<dyn Repo>, cache: Rc<dyn Cache>, clock: Shared<dyn Clock>) -> Worker {
+        Worker {
+            repo,
+            _cache: cache,
+            _clock: clock,
+        }
+    }
+}
+
+fn main() {
+    let container = DependencyContainer::new();
+    let _service = container.service();
+    let worker = container.worker();
+
+    assert!(Arc::ptr_eq(&worker.repo, container.repo()));
+}
@@ -39,6 +39,23 @@ impl<'a> DependencyContainer<'a> {
             _phantom: Default::default(),
         }
     }
+    /// The names of the singleton and scoped dependencies which have been created, without creating any others
+    pub fn initialized(&self) -> Vec<&'static str> {
+        #[allow(unused_mut)]
+        let mut initialized = Vec::new();
+        if self._tracing.get().is_some() {
+            initialized.push("_tracing");
+        }
+        initialized
+    }
+    /// The dependencies of this container and how they are wired together in the DOT format
+    pub fn dependency_graph_dot() -> &'static str {
+        "digraph DependencyContainer {\n    node [shape=box];\n    \"config\" [label=\"config\\nTransient\"];\n    \"_tracing\" [label=\"_tracing\\nSingleton\"];\n    \"service\" [label=\"service\\nTransient\"];\n    \"service\" -> \"_tracing\";\n    \"service\" -> \"config\";\n}\n"
+    }
+    /// The dependencies of this container and how they are wired together as a Mermaid flowchart
+    pub fn dependency_graph_mermaid() -> &'static str {
+        "graph TD\n    config[\"config<br/>Transient\"]\n    _tracing[\"_tracing<br/>Singleton\"]\n    service[\"service<br/>Transient\"]\n    service --> _tracing\n    service --> config\n"
+    }
     pub fn config(&'a self) -> Config {
         Config { port: 8080 }
     }
@@ -51,17 +68,26 @@ impl<'a> DependencyContainer<'a> {
             })
     }
     pub fn service(&'a self) -> Service {
-        let _tracing = self
-            ._tracing
-            .get_or_init(|| {
-                {
-                    ::std::io::_print(format_args!("Tracing enabled\n"));
-                };
-            });
-        let config = Config { port: 8080 };
+        let _tracing = self._tracing();
+        let config = self.config();
         Service::new(config.port)
     }
 }
+impl<'a> core::fmt::Debug for DependencyContainer<'a> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        #[allow(unused_mut)]
+        let mut debug = f.debug_struct("DependencyContainer");
+        debug
+            .field(
+                "_tracing",
+                &format_args!(
+                    "{0}", if self._tracing.get().is_some() { "initialized" } else {
+                    "uninitialized" }
+                ),
+            );
+        debug.finish_non_exhaustive()
+    }
+}
 fn main() {
     let container = DependencyContainer::new();
     let _service = container.service();
@@ -33,14 +33,35 @@ impl<'a> Dependencies<'a> {
             _phantom: Default::default(),
         }
     }
+    /// The names of the singleton and scoped dependencies which have been created, without creating any others
+    pub fn initialized(&self) -> Vec<&'static str> {
+        #[allow(unused_mut)]
+        let mut initialized = Vec::new();
+        initialized
+    }
+    /// The dependencies of this container and how they are wired together in the DOT format
+    pub fn dependency_graph_dot() -> &'static str {
+        "digraph Dependencies {\n    node [shape=box];\n    \"configuration\" [label=\"configuration\\nTransient\"];\n    \"task\" [label=\"task\\nTransient\"];\n    \"task\" -> \"configuration\";\n}\n"
+    }
+    /// The dependencies of this container and how they are wired together as a Mermaid flowchart
+    pub fn dependency_graph_mermaid() -> &'static str {
+        "graph TD\n    configuration[\"configuration<br/>Transient\"]\n    task[\"task<br/>Transient\"]\n    task --> configuration\n"
+    }
     pub fn configuration(&'a self) -> Configuration {
         Configuration { port: 8080 }
     }
     pub fn task(&'a self) -> Task {
-        let configuration = Configuration { port: 8080 };
+        let configuration = self.configuration();
         Task::new(configuration.port)
     }
 }
+impl<'a> core::fmt::Debug for Dependencies<'a> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        #[allow(unused_mut)]
+        let mut debug = f.debug_struct("Dependencies");
+        debug.finish_non_exhaustive()
+    }
+}
 fn main() {
     let deps = Dependencies::new();
     let _task = deps.task();
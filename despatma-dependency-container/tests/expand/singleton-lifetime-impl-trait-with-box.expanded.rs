@@ -57,25 +57,59 @@ impl<'a> DependencyContainer<'a> {
             _phantom: Default::default(),
         }
     }
+    /// The names of the singleton and scoped dependencies which have been created, without creating any others
+    pub fn initialized(&self) -> Vec<&'static str> {
+        #[allow(unused_mut)]
+        let mut initialized = Vec::new();
+        if self.dal.get().is_some() {
+            initialized.push("dal");
+        }
+        initialized
+    }
+    /// The dependencies of this container and how they are wired together in the DOT format
+    pub fn dependency_graph_dot() -> &'static str {
+        "digraph DependencyContainer {\n    node [shape=box];\n    \"config\" [label=\"config\\nTransient\"];\n    \"dal\" [label=\"dal\\nSingleton\"];\n    \"service\" [label=\"service\\nTransient\"];\n    \"service\" -> \"config\";\n    \"service\" -> \"dal\";\n}\n"
+    }
+    /// The dependencies of this container and how they are wired together as a Mermaid flowchart
+    pub fn dependency_graph_mermaid() -> &'static str {
+        "graph TD\n    config[\"config<br/>Transient\"]\n    dal[\"dal<br/>Singleton\"]\n    service[\"service<br/>Transient\"]\n    service --> config\n    service --> dal\n"
+    }
     pub fn config(&'a self) -> Config {
         Config { port: 8080 }
     }
     pub fn dal(&'a self) -> &impl DAL {
+        self.create_dal()
+    }
+    fn create_dal(&'a self) -> &Box<dyn DAL> {
         self.dal
             .get_or_init(|| {
                 if true { Box::new(PostgresDAL) } else { Box::new(SQLiteDAL) }
             })
     }
     pub fn service(&'a self) -> Service<impl DAL + use<'a>> {
-        let config = Config { port: 8080 };
-        let dal = self
-            .dal
-            .get_or_init(|| {
-                if true { Box::new(PostgresDAL) } else { Box::new(SQLiteDAL) }
-            });
+        self.create_service()
+    }
+    fn create_service(&'a self) -> Service<&'a Box<dyn DAL>> {
+        let config = self.config();
+        let dal = self.create_dal();
         Service::new(config.port, dal)
     }
 }
+impl<'a> core::fmt::Debug for DependencyContainer<'a> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        #[allow(unused_mut)]
+        let mut debug = f.debug_struct("DependencyContainer");
+        debug
+            .field(
+                "dal",
+                &format_args!(
+                    "{0}", if self.dal.get().is_some() { "initialized" } else {
+                    "uninitialized" }
+                ),
+            );
+        debug.finish_non_exhaustive()
+    }
+}
 fn main() {
     let container = DependencyContainer::new();
     let _service = container.service();
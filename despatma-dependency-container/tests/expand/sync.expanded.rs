@@ -0,0 +1,152 @@
+use std::time::Duration;
+use tokio::time::sleep;
+struct Config {
+    port: u32,
+}
+struct Database;
+impl Database {
+    async fn connect(port: u32) -> Self {
+        sleep(Duration::from_millis(10)).await;
+        {
+            ::std::io::_print(format_args!("Connected to database on port {0}\n", port));
+        };
+        Self
+    }
+}
+struct Service;
+impl Service {
+    fn new(_database: &Database) -> Self {
+        {
+            ::std::io::_print(format_args!("Sync service started\n"));
+        };
+        Self
+    }
+}
+struct DependencyContainer<'a> {
+    config: std::sync::Arc<std::sync::OnceLock<Config>>,
+    database: std::sync::Arc<async_once_cell::OnceCell<Database>>,
+    _phantom: std::marker::PhantomData<&'a ()>,
+}
+#[automatically_derived]
+impl<'a> ::core::clone::Clone for DependencyContainer<'a> {
+    #[inline]
+    fn clone(&self) -> DependencyContainer<'a> {
+        DependencyContainer {
+            config: ::core::clone::Clone::clone(&self.config),
+            database: ::core::clone::Clone::clone(&self.database),
+            _phantom: ::core::clone::Clone::clone(&self._phantom),
+        }
+    }
+}
+impl<'a> DependencyContainer<'a> {
+    pub fn new() -> Self {
+        Self {
+            config: Default::default(),
+            database: Default::default(),
+            _phantom: Default::default(),
+        }
+    }
+    pub fn new_scope(&self) -> Self {
+        Self {
+            config: self.config.clone(),
+            database: self.database.clone(),
+            _phantom: Default::default(),
+        }
+    }
+    /// The names of the singleton and scoped dependencies which have been created, without creating any others
+    pub fn initialized(&self) -> Vec<&'static str> {
+        #[allow(unused_mut)]
+        let mut initialized = Vec::new();
+        if self.config.get().is_some() {
+            initialized.push("config");
+        }
+        if self.database.get().is_some() {
+            initialized.push("database");
+        }
+        initialized
+    }
+    /// The dependencies of this container and how they are wired together in the DOT format
+    pub fn dependency_graph_dot() -> &'static str {
+        "digraph DependencyContainer {\n    node [shape=box];\n    \"config\" [label=\"config\\nSingleton\"];\n    \"database\" [label=\"database\\nSingleton, async\"];\n    \"service\" [label=\"service\\nTransient, async\"];\n    \"database\" -> \"config\";\n    \"service\" -> \"database\";\n}\n"
+    }
+    /// The dependencies of this container and how they are wired together as a Mermaid flowchart
+    pub fn dependency_graph_mermaid() -> &'static str {
+        "graph TD\n    config[\"config<br/>Singleton\"]\n    database[\"database<br/>Singleton, async\"]\n    service[\"service<br/>Transient, async\"]\n    database --> config\n    service --> database\n"
+    }
+    pub fn config(&'a self) -> &Config {
+        self.config.get_or_init(|| { Config { port: 8080 } })
+    }
+    pub fn database(
+        &'a self,
+    ) -> impl std::future::Future<Output = &Database> + Send + 'a {
+        async move {
+            let config = self.config();
+            self.database
+                .get_or_init(async { Database::connect(config.port).await })
+                .await
+        }
+    }
+    pub fn service(&'a self) -> impl std::future::Future<Output = Service> + Send + 'a {
+        async move {
+            let database = self.database().await;
+            Service::new(database)
+        }
+    }
+}
+impl<'a> core::fmt::Debug for DependencyContainer<'a> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        #[allow(unused_mut)]
+        let mut debug = f.debug_struct("DependencyContainer");
+        debug
+            .field(
+                "config",
+                &format_args!(
+                    "{0}", if self.config.get().is_some() { "initialized" } else {
+                    "uninitialized" }
+                ),
+            );
+        debug
+            .field(
+                "database",
+                &format_args!(
+                    "{0}", if self.database.get().is_some() { "initialized" } else {
+                    "uninitialized" }
+                ),
+            );
+        debug.finish_non_exhaustive()
+    }
+}
+const _: () = {
+    fn assert_send_sync<T: ?Sized + Send + Sync>() {}
+    #[allow(dead_code)]
+    fn assert_dependencies<'a>() {
+        assert_send_sync::<Config>();
+        assert_send_sync::<Database>();
+    }
+};
+fn main() {
+    let body = async {
+        let container: &'static DependencyContainer = Box::leak(
+            Box::new(DependencyContainer::new()),
+        );
+        let handles: Vec<_> = (0..2)
+            .map(|_| tokio::spawn(async move { container.service().await }))
+            .collect();
+        for handle in handles {
+            let _service = handle.await.unwrap();
+        }
+    };
+    #[allow(
+        clippy::expect_used,
+        clippy::diverging_sub_expression,
+        clippy::needless_return,
+        clippy::unwrap_in_result
+    )]
+    {
+        return tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .build()
+            .expect("Failed building the Runtime")
+            .block_on(body);
+    }
+}
@@ -0,0 +1,55 @@
+use std::time::Duration;
+use tokio::time::sleep;
+
+struct Config {
+    port: u32,
+}
+
+struct Database;
+
+impl Database {
+    async fn connect(port: u32) -> Self {
+        sleep(Duration::from_millis(10)).await;
+        println!("Connected to database on port {}", port);
+        Self
+    }
+}
+
+struct Service;
+
+impl Service {
+    fn new(_database: &Database) -> Self {
+        println!("Sync service started");
+        Self
+    }
+}
+
+#[despatma_dependency_container::dependency_container(sync)]
+impl DependencyContainer {
+    #[Singleton]
+    fn config(&self) -> Config {
+        Config { port: 8080 }
+    }
+
+    #[Singleton]
+    async fn database(&self, config: &Config) -> Database {
+        Database::connect(config.port).await
+    }
+
+    fn service(&self, database: &Database) -> Service {
+        Service::new(database)
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    let container: &'static DependencyContainer = Box::leak(Box::new(DependencyContainer::new()));
+
+    let handles: Vec<_> = (0..2)
+        .map(|_| tokio::spawn(async move { container.service().await }))
+        .collect();
+
+    for handle in handles {
+        let _service = handle.await.unwrap();
+    }
+}
@@ -37,6 +37,20 @@ impl<'a> DependencyContainer<'a> {
             _phantom: Default::default(),
         }
     }
+    /// The names of the singleton and scoped dependencies which have been created, without creating any others
+    pub fn initialized(&self) -> Vec<&'static str> {
+        #[allow(unused_mut)]
+        let mut initialized = Vec::new();
+        initialized
+    }
+    /// The dependencies of this container and how they are wired together in the DOT format
+    pub fn dependency_graph_dot() -> &'static str {
+        "digraph DependencyContainer {\n    node [shape=box];\n    \"configuration\" [label=\"configuration\\nTransient\"];\n    \"my_data_layer_over_socket\" [label=\"my_data_layer_over_socket\\nTransient\"];\n    \"service\" [label=\"service\\nTransient\"];\n    \"service\" -> \"configuration\";\n    \"service\" -> \"my_data_layer_over_socket\";\n}\n"
+    }
+    /// The dependencies of this container and how they are wired together as a Mermaid flowchart
+    pub fn dependency_graph_mermaid() -> &'static str {
+        "graph TD\n    configuration[\"configuration<br/>Transient\"]\n    my_data_layer_over_socket[\"my_data_layer_over_socket<br/>Transient\"]\n    service[\"service<br/>Transient\"]\n    service --> configuration\n    service --> my_data_layer_over_socket\n"
+    }
     pub fn configuration(&'a self) -> Configuration {
         Configuration { port: 8080 }
     }
@@ -44,11 +58,18 @@ impl<'a> DependencyContainer<'a> {
         MyDataLayerOverSocket
     }
     pub fn service(&'a self) -> Service {
-        let configuration = Configuration { port: 8080 };
-        let my_data_layer_over_socket = MyDataLayerOverSocket;
+        let configuration = self.configuration();
+        let my_data_layer_over_socket = self.my_data_layer_over_socket();
         Service::new(configuration.port, my_data_layer_over_socket)
     }
 }
+impl<'a> core::fmt::Debug for DependencyContainer<'a> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        #[allow(unused_mut)]
+        let mut debug = f.debug_struct("DependencyContainer");
+        debug.finish_non_exhaustive()
+    }
+}
 fn main() {
     let container = DependencyContainer::new();
     let _service = container.service();
@@ -22,6 +22,27 @@ impl<'a> PrivateDependencyContainer<'a> {
             _phantom: Default::default(),
         }
     }
+    /// The names of the singleton and scoped dependencies which have been created, without creating any others
+    pub fn initialized(&self) -> Vec<&'static str> {
+        #[allow(unused_mut)]
+        let mut initialized = Vec::new();
+        initialized
+    }
+    /// The dependencies of this container and how they are wired together in the DOT format
+    pub fn dependency_graph_dot() -> &'static str {
+        "digraph PrivateDependencyContainer {\n    node [shape=box];\n}\n"
+    }
+    /// The dependencies of this container and how they are wired together as a Mermaid flowchart
+    pub fn dependency_graph_mermaid() -> &'static str {
+        "graph TD\n"
+    }
+}
+impl<'a> core::fmt::Debug for PrivateDependencyContainer<'a> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        #[allow(unused_mut)]
+        let mut debug = f.debug_struct("PrivateDependencyContainer");
+        debug.finish_non_exhaustive()
+    }
 }
 pub struct PublicDependencyContainer<'a> {
     _phantom: std::marker::PhantomData<&'a ()>,
@@ -46,6 +67,27 @@ impl<'a> PublicDependencyContainer<'a> {
             _phantom: Default::default(),
         }
     }
+    /// The names of the singleton and scoped dependencies which have been created, without creating any others
+    pub fn initialized(&self) -> Vec<&'static str> {
+        #[allow(unused_mut)]
+        let mut initialized = Vec::new();
+        initialized
+    }
+    /// The dependencies of this container and how they are wired together in the DOT format
+    pub fn dependency_graph_dot() -> &'static str {
+        "digraph PublicDependencyContainer {\n    node [shape=box];\n}\n"
+    }
+    /// The dependencies of this container and how they are wired together as a Mermaid flowchart
+    pub fn dependency_graph_mermaid() -> &'static str {
+        "graph TD\n"
+    }
+}
+impl<'a> core::fmt::Debug for PublicDependencyContainer<'a> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        #[allow(unused_mut)]
+        let mut debug = f.debug_struct("PublicDependencyContainer");
+        debug.finish_non_exhaustive()
+    }
 }
 pub(crate) struct PublicCrateDependencyContainer<'a> {
     _phantom: std::marker::PhantomData<&'a ()>,
@@ -70,6 +112,27 @@ impl<'a> PublicCrateDependencyContainer<'a> {
             _phantom: Default::default(),
         }
     }
+    /// The names of the singleton and scoped dependencies which have been created, without creating any others
+    pub fn initialized(&self) -> Vec<&'static str> {
+        #[allow(unused_mut)]
+        let mut initialized = Vec::new();
+        initialized
+    }
+    /// The dependencies of this container and how they are wired together in the DOT format
+    pub fn dependency_graph_dot() -> &'static str {
+        "digraph PublicCrateDependencyContainer {\n    node [shape=box];\n}\n"
+    }
+    /// The dependencies of this container and how they are wired together as a Mermaid flowchart
+    pub fn dependency_graph_mermaid() -> &'static str {
+        "graph TD\n"
+    }
+}
+impl<'a> core::fmt::Debug for PublicCrateDependencyContainer<'a> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        #[allow(unused_mut)]
+        let mut debug = f.debug_struct("PublicCrateDependencyContainer");
+        debug.finish_non_exhaustive()
+    }
 }
 pub(self) struct PublicSelfDependencyContainer<'a> {
     _phantom: std::marker::PhantomData<&'a ()>,
@@ -94,6 +157,27 @@ impl<'a> PublicSelfDependencyContainer<'a> {
             _phantom: Default::default(),
         }
     }
+    /// The names of the singleton and scoped dependencies which have been created, without creating any others
+    pub fn initialized(&self) -> Vec<&'static str> {
+        #[allow(unused_mut)]
+        let mut initialized = Vec::new();
+        initialized
+    }
+    /// The dependencies of this container and how they are wired together in the DOT format
+    pub fn dependency_graph_dot() -> &'static str {
+        "digraph PublicSelfDependencyContainer {\n    node [shape=box];\n}\n"
+    }
+    /// The dependencies of this container and how they are wired together as a Mermaid flowchart
+    pub fn dependency_graph_mermaid() -> &'static str {
+        "graph TD\n"
+    }
+}
+impl<'a> core::fmt::Debug for PublicSelfDependencyContainer<'a> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        #[allow(unused_mut)]
+        let mut debug = f.debug_struct("PublicSelfDependencyContainer");
+        debug.finish_non_exhaustive()
+    }
 }
 mod outer {
     pub(super) struct PublicSuperDependencyContainer<'a> {
@@ -119,6 +203,27 @@ mod outer {
                 _phantom: Default::default(),
             }
         }
+        /// The names of the singleton and scoped dependencies which have been created, without creating any others
+        pub fn initialized(&self) -> Vec<&'static str> {
+            #[allow(unused_mut)]
+            let mut initialized = Vec::new();
+            initialized
+        }
+        /// The dependencies of this container and how they are wired together in the DOT format
+        pub fn dependency_graph_dot() -> &'static str {
+            "digraph PublicSuperDependencyContainer {\n    node [shape=box];\n}\n"
+        }
+        /// The dependencies of this container and how they are wired together as a Mermaid flowchart
+        pub fn dependency_graph_mermaid() -> &'static str {
+            "graph TD\n"
+        }
+    }
+    impl<'a> core::fmt::Debug for PublicSuperDependencyContainer<'a> {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            #[allow(unused_mut)]
+            let mut debug = f.debug_struct("PublicSuperDependencyContainer");
+            debug.finish_non_exhaustive()
+        }
     }
     mod inner {
         pub(in crate::outer) struct PublicModInOuterDependencyContainer<'a> {
@@ -144,6 +249,27 @@ mod outer {
                     _phantom: Default::default(),
                 }
             }
+            /// The names of the singleton and scoped dependencies which have been created, without creating any others
+            pub fn initialized(&self) -> Vec<&'static str> {
+                #[allow(unused_mut)]
+                let mut initialized = Vec::new();
+                initialized
+            }
+            /// The dependencies of this container and how they are wired together in the DOT format
+            pub fn dependency_graph_dot() -> &'static str {
+                "digraph PublicModInOuterDependencyContainer {\n    node [shape=box];\n}\n"
+            }
+            /// The dependencies of this container and how they are wired together as a Mermaid flowchart
+            pub fn dependency_graph_mermaid() -> &'static str {
+                "graph TD\n"
+            }
+        }
+        impl<'a> core::fmt::Debug for PublicModInOuterDependencyContainer<'a> {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                #[allow(unused_mut)]
+                let mut debug = f.debug_struct("PublicModInOuterDependencyContainer");
+                debug.finish_non_exhaustive()
+            }
         }
     }
 }
@@ -0,0 +1,31 @@
+struct Db;
+
+struct Report;
+
+impl Report {
+    fn new(_db: &Db) -> Self {
+        Self
+    }
+}
+
+#[despatma_dependency_container::dependency_container]
+impl DependencyContainer {
+    #[Singleton]
+    fn primary_db(&self) -> Db {
+        Db
+    }
+
+    #[Singleton]
+    fn replica_db(&self) -> Db {
+        Db
+    }
+
+    fn report(&self, #[inject] db: &Db) -> Report {
+        Report::new(db)
+    }
+}
+
+fn main() {
+    let container = DependencyContainer::new();
+    let _report = container.report();
+}
@@ -0,0 +1,27 @@
+struct Config {
+    port: u32,
+}
+
+struct Clock;
+
+struct Service;
+
+impl Service {
+    fn new(_port: u32, _clock: &Clock) -> Self {
+        Self
+    }
+}
+
+#[despatma_dependency_container::dependency_container]
+impl DependencyContainer {
+    fn new(config: Config, clock: Clock) {}
+
+    fn service(&self, config: &Config, clock: &Clock) -> Service {
+        Service::new(config.port, clock)
+    }
+}
+
+fn main() {
+    let container = DependencyContainer::builder().clock(Clock).build();
+    let _service = container.service();
+}
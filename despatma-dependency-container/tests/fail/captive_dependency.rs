@@ -0,0 +1,39 @@
+struct Transaction;
+
+struct Repository;
+
+impl Repository {
+    fn new(_transaction: &Transaction) -> Self {
+        Self
+    }
+}
+
+struct Service;
+
+impl Service {
+    fn new(_repository: Repository) -> Self {
+        Self
+    }
+}
+
+#[despatma_dependency_container::dependency_container]
+impl DependencyContainer {
+    #[Scoped]
+    fn transaction(&self) -> Transaction {
+        Transaction
+    }
+
+    fn repository(&self, transaction: &Transaction) -> Repository {
+        Repository::new(transaction)
+    }
+
+    #[Singleton]
+    fn service(&self, repository: Repository) -> Service {
+        Service::new(repository)
+    }
+}
+
+fn main() {
+    let container = DependencyContainer::new();
+    let _service = container.service();
+}
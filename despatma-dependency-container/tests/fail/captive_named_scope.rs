@@ -0,0 +1,20 @@
+struct Tenant;
+
+struct Request;
+
+struct Billing;
+
+#[despatma_dependency_container::dependency_container(scopes(tenant, request))]
+impl DependencyContainer {
+    #[Scoped(request)]
+    fn request(&self) -> Request {
+        Request
+    }
+
+    #[Scoped(tenant)]
+    fn billing(&self, request: &Request) -> Billing {
+        Billing
+    }
+}
+
+fn main() {}
@@ -0,0 +1,34 @@
+struct Config;
+
+struct Redis;
+
+struct Memory;
+
+#[despatma_dependency_container::dependency_container]
+impl DependencyContainer {
+    fn config(&self) -> Config {
+        Config
+    }
+
+    #[cfg(test)]
+    fn config(&self) -> Config {
+        Config
+    }
+
+    #[cfg(feature = "redis")]
+    #[Singleton]
+    fn cache(&self) -> Redis {
+        Redis
+    }
+
+    #[cfg(not(feature = "redis"))]
+    #[Singleton]
+    fn cache(&self) -> Memory {
+        Memory
+    }
+}
+
+fn main() {
+    let container = DependencyContainer::new();
+    let _config = container.config();
+}
@@ -0,0 +1,25 @@
+struct A;
+
+struct B;
+
+#[despatma_dependency_container::dependency_container]
+impl DependencyContainer {
+    #[cfg(not(debug_assertions))]
+    fn a(&self) -> A {
+        A
+    }
+
+    #[cfg(debug_assertions)]
+    fn a(&self, b: B) -> A {
+        A
+    }
+
+    fn b(&self, a: A) -> B {
+        B
+    }
+}
+
+fn main() {
+    let container = DependencyContainer::new();
+    let _b = container.b();
+}
@@ -0,0 +1,39 @@
+struct Config;
+
+struct Repository;
+
+impl Repository {
+    fn new(_service: &Service) -> Self {
+        Self
+    }
+}
+
+struct Service;
+
+impl Service {
+    fn new(_repository: Repository, _config: &Config) -> Self {
+        Self
+    }
+}
+
+#[despatma_dependency_container::dependency_container]
+impl DependencyContainer {
+    #[Singleton]
+    fn config(&self) -> Config {
+        Config
+    }
+
+    fn repository(&self, service: &Service) -> Repository {
+        Repository::new(service)
+    }
+
+    #[Singleton]
+    fn service(&self, repository: Repository, config: &Config) -> Service {
+        Service::new(repository, config)
+    }
+}
+
+fn main() {
+    let container = DependencyContainer::new();
+    let _service = container.service();
+}
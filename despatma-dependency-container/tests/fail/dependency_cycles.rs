@@ -0,0 +1,26 @@
+struct Cache;
+
+struct Repository;
+
+struct Service;
+
+#[despatma_dependency_container::dependency_container]
+impl DependencyContainer {
+    fn cache(&self, service: &Service) -> Cache {
+        Cache
+    }
+
+    fn repository(&self, service: &Service) -> Repository {
+        Repository
+    }
+
+    #[Singleton]
+    fn service(&self, repository: Repository, cache: Cache) -> Service {
+        Service
+    }
+}
+
+fn main() {
+    let container = DependencyContainer::new();
+    let _service = container.service();
+}
@@ -0,0 +1,18 @@
+struct Writer;
+
+impl Writer {
+    fn flush(&self) {}
+}
+
+#[despatma_dependency_container::dependency_container]
+impl DependencyContainer {
+    #[Dispose(flush)]
+    fn writer(&self) -> Writer {
+        Writer
+    }
+}
+
+fn main() {
+    let container = DependencyContainer::new();
+    let _writer = container.writer();
+}
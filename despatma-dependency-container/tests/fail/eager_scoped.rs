@@ -0,0 +1,14 @@
+struct Session;
+
+#[despatma_dependency_container::dependency_container]
+impl DependencyContainer {
+    #[Scoped(eager)]
+    fn session(&self) -> Session {
+        Session
+    }
+}
+
+fn main() {
+    let container = DependencyContainer::new();
+    container.init();
+}
@@ -0,0 +1,30 @@
+struct ConfigError;
+
+struct DatabaseError;
+
+struct Config;
+
+struct Database;
+
+struct Service;
+
+#[despatma_dependency_container::dependency_container]
+impl DependencyContainer {
+    fn config(&self) -> Result<Config, ConfigError> {
+        Ok(Config)
+    }
+
+    fn database(&self) -> Result<Database, DatabaseError> {
+        Ok(Database)
+    }
+
+    // Does not know whether to fail with `ConfigError` or `DatabaseError`
+    fn service(&self, config: Config, database: Database) -> Service {
+        Service
+    }
+}
+
+fn main() {
+    let container = DependencyContainer::new();
+    let _service = container.service();
+}
@@ -0,0 +1,28 @@
+trait Handler {}
+
+struct Ping;
+
+impl Handler for Ping {}
+
+struct Echo;
+
+impl Handler for Echo {}
+
+#[despatma_dependency_container::dependency_container]
+impl DependencyContainer {
+    #[Group(handlers)]
+    fn ping(&self) -> Box<dyn Handler> {
+        Box::new(Ping)
+    }
+
+    #[Singleton]
+    #[Group(handlers)]
+    fn echo(&self) -> Box<dyn Handler> {
+        Box::new(Echo)
+    }
+}
+
+fn main() {
+    let container = DependencyContainer::new();
+    let _ping = container.ping();
+}
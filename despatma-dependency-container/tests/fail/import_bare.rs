@@ -0,0 +1,15 @@
+struct Config;
+
+#[despatma_dependency_container::dependency_container]
+impl ConfigContainer {
+    fn config(&self) -> Config {
+        Config
+    }
+}
+
+#[despatma_dependency_container::dependency_container]
+impl ServiceContainer {
+    fn new<'a>(#[import] config_container: ConfigContainer<'a>) {}
+}
+
+fn main() {}
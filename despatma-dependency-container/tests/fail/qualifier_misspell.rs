@@ -0,0 +1,27 @@
+struct Db;
+
+struct Report;
+
+impl Report {
+    fn new(_db: &Db) -> Self {
+        Self
+    }
+}
+
+#[despatma_dependency_container::dependency_container]
+impl DependencyContainer {
+    #[Singleton]
+    #[Named("replica")]
+    fn replica_db(&self) -> Db {
+        Db
+    }
+
+    fn report(&self, #[from(replika)] db: &Db) -> Report {
+        Report::new(db)
+    }
+}
+
+fn main() {
+    let container = DependencyContainer::new();
+    let _report = container.report();
+}
@@ -0,0 +1,49 @@
+struct Health;
+
+struct Builder;
+
+struct Pool;
+
+struct Init;
+
+impl Pool {
+    fn close(&self) {}
+}
+
+#[despatma_dependency_container::dependency_container(constructions)]
+impl DependencyContainer {
+    fn new(port: u32) {}
+
+    #[Singleton]
+    fn initialized(&self) -> Health {
+        Health
+    }
+
+    fn builder(&self, port: &u32) -> Builder {
+        Builder
+    }
+
+    #[Singleton(eager)]
+    #[Dispose(close)]
+    fn pool(&self) -> Pool {
+        Pool
+    }
+
+    fn init(&self) -> Init {
+        Init
+    }
+
+    fn shutdown(&self) -> Init {
+        Init
+    }
+
+    fn dependency_graph_mermaid(&self) -> Init {
+        Init
+    }
+
+    fn constructions(&self) -> Init {
+        Init
+    }
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+struct Cache;
+
+impl Cache {
+    fn new(_size: usize) -> Self {
+        Self
+    }
+}
+
+#[despatma_dependency_container::dependency_container]
+impl DependencyContainer {
+    #[Singleton]
+    fn cache(&self, #[arg] size: usize) -> Cache {
+        Cache::new(size)
+    }
+}
+
+fn main() {}
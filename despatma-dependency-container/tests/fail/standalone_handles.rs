@@ -0,0 +1,44 @@
+struct Report;
+
+struct Mailer;
+
+trait Repository {}
+
+struct InMemory;
+
+impl Repository for InMemory {}
+
+struct Postgres;
+
+impl Repository for Postgres {}
+
+#[derive(Clone, Copy)]
+enum DependencyContainerProfile {
+    Dev,
+    Prod,
+}
+
+#[despatma_dependency_container::dependency_container]
+impl DependencyContainer {
+    fn new(profile: DependencyContainerProfile) {}
+
+    fn mailer(&self) -> Mailer {
+        Mailer
+    }
+
+    fn report(&self, mailer: Provider<Mailer>) -> Report {
+        Report
+    }
+
+    #[Profile(dev)]
+    fn repository(&self) -> Box<dyn Repository> {
+        Box::new(InMemory)
+    }
+
+    #[Profile(prod)]
+    fn repository(&self) -> Box<dyn Repository> {
+        Box::new(Postgres)
+    }
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+struct Config;
+
+#[despatma_dependency_container::dependency_container(provides)]
+impl DependencyContainer {
+    fn config(&self) -> Config {
+        Config
+    }
+}
+
+fn main() {}
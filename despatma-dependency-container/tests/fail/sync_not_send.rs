@@ -0,0 +1,18 @@
+use std::rc::Rc;
+
+struct Config {
+    port: u32,
+}
+
+#[despatma_dependency_container::dependency_container(sync)]
+impl DependencyContainer {
+    #[Singleton]
+    fn config(&self) -> Rc<Config> {
+        Rc::new(Config { port: 8080 })
+    }
+}
+
+fn main() {
+    let container = DependencyContainer::new();
+    let _port = container.config().port;
+}
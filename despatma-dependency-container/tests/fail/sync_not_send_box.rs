@@ -0,0 +1,24 @@
+trait DataLayer {
+    fn get(&self) -> u32;
+}
+
+struct Postgres;
+
+impl DataLayer for Postgres {
+    fn get(&self) -> u32 {
+        5
+    }
+}
+
+#[despatma_dependency_container::dependency_container(sync)]
+impl DependencyContainer {
+    #[Singleton]
+    fn data_layer(&self) -> Box<dyn DataLayer> {
+        Box::new(Postgres)
+    }
+}
+
+fn main() {
+    let container = DependencyContainer::new();
+    let _value = container.data_layer().get();
+}
@@ -0,0 +1,18 @@
+struct Tenant;
+
+struct Request;
+
+#[despatma_dependency_container::dependency_container(scopes(tenant, request))]
+impl DependencyContainer {
+    #[Scoped(tenat)]
+    fn tenant(&self) -> Tenant {
+        Tenant
+    }
+
+    #[Scoped(session)]
+    fn request(&self) -> Request {
+        Request
+    }
+}
+
+fn main() {}
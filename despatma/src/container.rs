@@ -0,0 +1,144 @@
+//! Types used by the code generated by the [dependency_container](crate::dependency_container) macro.
+
+use std::{future::Future, marker::PhantomData, pin::Pin, sync::OnceLock};
+
+/// Describes a dependency registered on a container. Every container lists its dependencies in its `METADATA`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct DependencyInfo {
+    /// The name of the dependency
+    pub name: &'static str,
+    /// The return type as it was declared
+    pub ty: &'static str,
+    /// The type the container stores for singleton, scoped and embedded dependencies
+    pub field_ty: &'static str,
+    pub lifetime: Lifetime,
+    pub is_async: bool,
+    /// Whether the return type is in a smart pointer like `Box`, `Arc` or `Rc`
+    pub is_boxed: bool,
+    /// The names of the dependencies this dependency is created from
+    pub dependencies: &'static [&'static str],
+}
+
+/// How long a dependency lives for
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum Lifetime {
+    /// Created each time it is requested
+    Transient,
+    /// Created once per scope
+    Scoped,
+    /// Created once and shared with every scope
+    Singleton,
+    /// Passed in as a constructor argument
+    Embedded,
+}
+
+/// A handle to create a dependency when it is needed rather than when the dependency requesting it is created.
+/// Every call to [get](Provider::get) resolves the dependency again from the container.
+///
+/// Requested with `Provider<T>` as the type of an argument. Containers using the `sync` option give a
+/// `Provider<'a, T, dyn Fn() -> T + Send + Sync + 'a>` so that it can be shared between threads.
+pub struct Provider<'a, T, R: ?Sized = dyn Fn() -> T + 'a> {
+    resolve: Box<R>,
+    _marker: PhantomData<fn() -> &'a T>,
+}
+
+impl<'a, T, R: ?Sized + Fn() -> T + 'a> Provider<'a, T, R> {
+    pub fn new(resolve: Box<R>) -> Self {
+        Self {
+            resolve,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Resolve the dependency from the container
+    pub fn get(&self) -> T {
+        (self.resolve)()
+    }
+}
+
+/// A handle to create a dependency the first time it is needed. The dependency is kept for any later calls to
+/// [get](Lazy::get).
+///
+/// Requested with `Lazy<T>` as the type of an argument. Containers using the `sync` option give a
+/// `Lazy<'a, T, dyn Fn() -> T + Send + Sync + 'a>` so that it can be shared between threads.
+pub struct Lazy<'a, T, R: ?Sized = dyn Fn() -> T + 'a> {
+    provider: Provider<'a, T, R>,
+    value: OnceLock<T>,
+}
+
+impl<'a, T, R: ?Sized + Fn() -> T + 'a> Lazy<'a, T, R> {
+    pub fn new(resolve: Box<R>) -> Self {
+        Self {
+            provider: Provider::new(resolve),
+            value: OnceLock::new(),
+        }
+    }
+
+    /// Resolve the dependency from the container the first time it is needed
+    pub fn get(&self) -> &T {
+        self.value.get_or_init(|| self.provider.get())
+    }
+}
+
+/// The future returned by the resolver of an [AsyncProvider] and an [AsyncLazy]
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + 'a>>;
+
+/// A [Provider] for an async dependency. Containers using the `sync` option give an
+/// `AsyncProvider<'a, T, dyn Fn() -> Pin<Box<dyn Future<Output = T> + Send + 'a>> + Send + Sync + 'a>`.
+pub struct AsyncProvider<'a, T, R: ?Sized = dyn Fn() -> BoxFuture<'a, T> + 'a> {
+    resolve: Box<R>,
+    _marker: PhantomData<fn() -> &'a T>,
+}
+
+impl<'a, T, F: Future<Output = T>, R: ?Sized + Fn() -> F + 'a> AsyncProvider<'a, T, R> {
+    pub fn new(resolve: Box<R>) -> Self {
+        Self {
+            resolve,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Resolve the dependency from the container
+    pub async fn get(&self) -> T {
+        (self.resolve)().await
+    }
+}
+
+/// A [Lazy] for an async dependency. Containers using the `sync` option give an
+/// `AsyncLazy<'a, T, dyn Fn() -> Pin<Box<dyn Future<Output = T> + Send + 'a>> + Send + Sync + 'a>`.
+pub struct AsyncLazy<'a, T, R: ?Sized = dyn Fn() -> BoxFuture<'a, T> + 'a> {
+    provider: AsyncProvider<'a, T, R>,
+    value: async_once_cell::OnceCell<T>,
+}
+
+impl<'a, T, F: Future<Output = T>, R: ?Sized + Fn() -> F + 'a> AsyncLazy<'a, T, R> {
+    pub fn new(resolve: Box<R>) -> Self {
+        Self {
+            provider: AsyncProvider::new(resolve),
+            value: async_once_cell::OnceCell::new(),
+        }
+    }
+
+    /// Resolve the dependency from the container the first time it is needed
+    pub async fn get(&self) -> &T {
+        self.value.get_or_init(self.provider.get()).await
+    }
+}
+
+/// Implemented by a container for the type of every dependency it provides, so that code can be generic over any
+/// container which provides a `T`. Ie `fn handle<'a>(container: &'a impl Provides<'a, UserService>)`.
+///
+/// A type is only implemented when exactly one dependency returns it, and the dependency does not take runtime
+/// arguments or return an `impl Trait`. Async dependencies implement [ProvidesAsync] instead.
+pub trait Provides<'a, T> {
+    /// Resolve the dependency from the container
+    fn provide(&'a self) -> T;
+}
+
+/// [Provides] for an async dependency
+pub trait ProvidesAsync<'a, T> {
+    /// Resolve the dependency from the container
+    fn provide(&'a self) -> impl Future<Output = T>;
+}
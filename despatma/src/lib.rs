@@ -848,6 +848,228 @@ pub use despatma_visitor::visitor_mut;
 /// 1. Annotate the `DataLayer` trait with `#[auto_impl(Box)]`. This implements the `DataLayer` trait for `Box<dyn DataLayer>`.
 /// 2. Use `impl DataLayer` as the return type, but create a `Box<dyn DataLayer>` internally to handle different concrete types.
 ///
+/// #### Shared trait objects
+///
+/// Singleton and scoped dependencies can also return `Arc<dyn Trait>` or `Rc<dyn Trait>`. Dependencies requesting them
+/// by value get a clone of the pointer kept by the container, while `impl Trait` and references work as for `Box`.
+/// Declare any other smart pointers of your own with the `pointers` option, like `#[dependency_container(pointers(Shared))]`,
+/// to have them handled the same way.
+///
+/// ```
+/// use std::sync::Arc;
+///
+/// use despatma::dependency_container;
+///
+/// trait Repo {
+///     fn name(&self) -> &str;
+/// }
+///
+/// struct Postgres;
+///
+/// impl Repo for Postgres {
+///     fn name(&self) -> &str {
+///         "postgres"
+///     }
+/// }
+///
+/// struct Worker {
+///     repo: Arc<dyn Repo>,
+/// }
+///
+/// #[dependency_container]
+/// impl DependencyContainer {
+///     #[Singleton]
+///     fn repo(&self) -> Arc<dyn Repo> {
+///         Arc::new(Postgres)
+///     }
+///
+///     fn worker(&self, repo: Arc<dyn Repo>) -> Worker {
+///         Worker { repo }
+///     }
+/// }
+///
+/// let container = DependencyContainer::new();
+/// let worker = container.worker();
+///
+/// assert_eq!(worker.repo.name(), "postgres");
+/// assert!(Arc::ptr_eq(&worker.repo, container.repo()));
+/// ```
+///
+/// #### Groups
+///
+/// Many dependencies returning the same `Box<dyn Trait>` can be collected with `#[Group(name)]`. The container then
+/// has a `name` dependency with every dependency in the group, in the order they are declared. It is a
+/// `Vec<Box<dyn Trait>>` when the dependencies in the group are transient, and a `Vec<&dyn Trait>` when they are all
+/// singleton or scoped.
+///
+/// ```
+/// use despatma::dependency_container;
+///
+/// trait Handler {
+///     fn handle(&self, request: &str) -> Option<String>;
+/// }
+///
+/// // Implementation details...
+/// # struct Ping;
+/// #
+/// # impl Handler for Ping {
+/// #     fn handle(&self, request: &str) -> Option<String> {
+/// #         (request == "ping").then(|| "pong".to_string())
+/// #     }
+/// # }
+/// #
+/// # struct Echo;
+/// #
+/// # impl Handler for Echo {
+/// #     fn handle(&self, request: &str) -> Option<String> {
+/// #         Some(request.to_string())
+/// #     }
+/// # }
+/// #
+/// # struct Router {
+/// #     handlers: Vec<Box<dyn Handler>>,
+/// # }
+/// #
+/// # impl Router {
+/// #     fn route(&self, request: &str) -> Option<String> {
+/// #         self.handlers.iter().find_map(|handler| handler.handle(request))
+/// #     }
+/// # }
+///
+/// #[dependency_container]
+/// impl DependencyContainer {
+///     #[Group(handlers)]
+///     fn ping(&self) -> Box<dyn Handler> {
+///         Box::new(Ping)
+///     }
+///
+///     #[Group(handlers)]
+///     fn echo(&self) -> Box<dyn Handler> {
+///         Box::new(Echo)
+///     }
+///
+///     fn router(&self, handlers: Vec<Box<dyn Handler>>) -> Router {
+///         Router { handlers }
+///     }
+/// }
+///
+/// let container = DependencyContainer::new();
+///
+/// assert_eq!(container.router().route("ping"), Some("pong".to_string()));
+/// ```
+///
+/// #### Conditional compilation
+///
+/// To pick a dependency at compile time instead, put a `#[cfg(...)]` attribute on it. Everything the container
+/// generates for the dependency is compiled in or out with it. A dependency can be registered more than once when
+/// every registration has a mutually exclusive `#[cfg(...)]`. Other dependencies then get whichever one is active.
+///
+/// ```
+/// use despatma::dependency_container;
+///
+/// struct Path(&'static str);
+///
+/// #[dependency_container]
+/// impl DependencyContainer {
+///     #[cfg(unix)]
+///     #[Singleton]
+///     fn config_path(&self) -> Path {
+///         Path("/etc/app.toml")
+///     }
+///
+///     #[cfg(not(unix))]
+///     #[Singleton]
+///     fn config_path(&self) -> Path {
+///         Path("C:\\app.toml")
+///     }
+///
+///     fn config(&self, config_path: &Path) -> String {
+///         format!("Reading {}", config_path.0)
+///     }
+/// }
+///
+/// let container = DependencyContainer::new();
+///
+/// assert!(container.config().starts_with("Reading"));
+/// ```
+///
+/// **Important**: The alternatives of a dependency need the same lifetime, return type and asyncness. The dependency
+/// graph shows the wiring of all of them.
+///
+/// #### Runtime profiles
+///
+/// To pick a dependency when the container is created, give it a provider for every profile with `#[Profile(name)]`.
+/// The container then gets a `DependencyContainerProfile` enum with a variant for every profile, and a `profile`
+/// dependency returning it says which profile is active. This can be a constructor argument, or a dependency reading
+/// it from a config. Only the provider of the active profile, and the dependencies it needs, are created.
+///
+/// ```
+/// use despatma::dependency_container;
+///
+/// trait Repository {
+///     fn find(&self, id: u32) -> String;
+/// }
+///
+/// // Implementation details...
+/// # struct InMemory;
+/// #
+/// # impl Repository for InMemory {
+/// #     fn find(&self, id: u32) -> String {
+/// #         format!("Memory {}", id)
+/// #     }
+/// # }
+/// #
+/// # struct Pool;
+/// #
+/// # struct Postgres;
+/// #
+/// # impl Postgres {
+/// #     fn connect(_pool: &Pool) -> Self {
+/// #         Postgres
+/// #     }
+/// # }
+/// #
+/// # impl Repository for Postgres {
+/// #     fn find(&self, id: u32) -> String {
+/// #         format!("Postgres {}", id)
+/// #     }
+/// # }
+///
+/// #[dependency_container]
+/// impl DependencyContainer {
+///     fn new(profile: DependencyContainerProfile) {}
+///
+///     #[Singleton]
+///     fn pool(&self) -> Pool {
+///         Pool
+///     }
+///
+///     #[Profile(dev)]
+///     #[Singleton]
+///     fn repository(&self) -> Box<dyn Repository> {
+///         Box::new(InMemory)
+///     }
+///
+///     #[Profile(prod)]
+///     #[Singleton]
+///     fn repository(&self, pool: &Pool) -> Box<dyn Repository> {
+///         Box::new(Postgres::connect(pool))
+///     }
+///
+///     fn user_name(&self, repository: &Box<dyn Repository>) -> String {
+///         repository.find(1)
+///     }
+/// }
+///
+/// let container = DependencyContainer::new(DependencyContainerProfile::Prod);
+///
+/// assert_eq!(container.user_name(), "Postgres 1");
+/// ```
+///
+/// Every profile used in the container needs a provider for each dependency with profiles, else the container does not
+/// compile. The providers need the same return type and attributes, like their lifetime, which move to the dependency
+/// picking between them. The providers themselves are available as `repository_dev` and `repository_prod`.
+///
 /// ### Async Dependencies
 ///
 /// The macro supports async dependencies by automatically making parent dependencies async:
@@ -891,6 +1113,76 @@ pub use despatma_visitor::visitor_mut;
 ///
 /// Note that the `service` method will be automatically made `async` by the macro to accommodate the async `config` dependency.
 ///
+/// ### Fallible Dependencies
+///
+/// Dependencies can fail to be created by returning a `Result<T, E>`:
+///
+/// ```
+/// use despatma::dependency_container;
+///
+/// // Implementation details...
+/// # #[derive(Debug)]
+/// # struct ConfigError;
+/// #
+/// # #[derive(Debug)]
+/// # struct ServiceError;
+/// #
+/// # impl From<ConfigError> for ServiceError {
+/// #     fn from(_: ConfigError) -> Self {
+/// #         ServiceError
+/// #     }
+/// # }
+/// #
+/// # struct Config {
+/// #     port: u32,
+/// # }
+/// #
+/// # impl Config {
+/// #     fn read() -> Result<Self, ConfigError> {
+/// #         Ok(Config { port: 8080 })
+/// #     }
+/// # }
+/// #
+/// # struct Service;
+/// #
+/// # impl Service {
+/// #     pub fn new(port: u32) -> Self {
+/// #         Service
+/// #     }
+/// # }
+///
+/// #[dependency_container]
+/// impl MyContainer {
+///     #[Singleton]
+///     fn config(&self) -> Result<Config, ConfigError> {
+///         Config::read()
+///     }
+///
+///     fn port(&self, config: &Config) -> u32 {
+///         config.port
+///     }
+///
+///     fn service(&self, port: u32) -> Result<Service, ServiceError> {
+///         Ok(Service::new(port))
+///     }
+/// }
+///
+/// let container = MyContainer::new();
+/// let port: Result<u32, ConfigError> = container.port();
+/// let service: Result<Service, ServiceError> = container.service();
+/// ```
+///
+/// Dependents still request the successful type (`&Config` here) and will automatically return a `Result` too:
+/// - `port` does not return a `Result` itself, so it will fail with the `ConfigError` of `config`.
+/// - `service` returns its own `Result`, so any errors of its dependencies are converted into a `ServiceError` using
+///   `From`. This is also needed when a dependency depends on dependencies failing with different error types.
+///
+/// Singleton and scoped dependencies only store the successful value. So a failed dependency is created again the
+/// next time it is requested.
+///
+/// **Important**: The `Result<T, E>` needs to be spelled out in full for the macro to know the error type. Aliases like
+/// `std::io::Result<T>` are treated as a normal type.
+///
 /// ### Singleton / Scoped Dependencies
 /// This macro also allows for the management of singleton or scoped dependencies:
 ///
@@ -1005,6 +1297,136 @@ pub use despatma_visitor::visitor_mut;
 ///    too. We need this since we are still only giving a reference to `service` when it requests the `DataLayer`
 ///    dependency. However, `service` no longer needs to know it is getting a reference like the previous example.
 ///
+/// #### Eager singletons
+/// Singletons are created the first time they are requested. Mark a singleton with `#[Singleton(eager)]`, or
+/// `#[Singleton(eager, Sqlite)]` when it needs a type hint, to have it created by the generated `init` method instead.
+/// `init` creates the eager singletons after the dependencies they are created from. It returns the first error when
+/// any of them are fallible and is async when any of them are async. This makes it possible to fail at startup rather
+/// than on the first request. A container with eager singletons cannot have a dependency called `init`.
+///
+/// ```
+/// use despatma::dependency_container;
+///
+/// struct Pool;
+///
+/// impl Pool {
+///     fn open(url: &str) -> Result<Self, String> {
+///         println!("Pool opened to {url}");
+///         Ok(Self)
+///     }
+/// }
+///
+/// #[dependency_container]
+/// impl DependencyContainer {
+///     #[Singleton(eager)]
+///     fn pool(&self) -> Result<Pool, String> {
+///         Pool::open("sqlite://memory")
+///     }
+/// }
+///
+/// let container = DependencyContainer::new();
+/// container.init().expect("pool to open");
+/// ```
+///
+/// #### Captive dependencies
+/// A singleton is shared with every scope. So a singleton depending on a scoped dependency, directly or through
+/// transient dependencies, would keep using the scoped dependency of the first scope it was created in. This is
+/// reported as a compile error. Mark the argument with `#[captive]` when this is deliberate:
+///
+/// ```
+/// use despatma::dependency_container;
+///
+/// struct Clock;
+///
+/// struct Audit;
+///
+/// #[dependency_container]
+/// impl DependencyContainer {
+///     #[Scoped]
+///     fn clock(&self) -> Clock {
+///         Clock
+///     }
+///
+///     // Only ever uses the clock of the first scope
+///     #[Singleton]
+///     fn audit(&self, #[captive] clock: &Clock) -> Audit {
+///         Audit
+///     }
+/// }
+///
+/// let container = DependencyContainer::new();
+/// let _audit = container.new_scope().audit();
+/// ```
+///
+/// A transient dependency held by a singleton or scoped dependency is kept just as long. This is a problem when the
+/// transient has state meant for a single use, like a transaction. Mark such a dependency with
+/// `#[Transient(stateful)]` to have any singleton or scoped dependency holding on to it reported in the same way:
+///
+/// ```
+/// use despatma::dependency_container;
+///
+/// struct Transaction;
+///
+/// struct Repository;
+///
+/// #[dependency_container]
+/// impl DependencyContainer {
+///     #[Transient(stateful)]
+///     fn transaction(&self) -> Transaction {
+///         Transaction
+///     }
+///
+///     // Gets a new transaction every time, but would be reported as a singleton
+///     fn repository(&self, transaction: Transaction) -> Repository {
+///         Repository
+///     }
+/// }
+///
+/// let container = DependencyContainer::new();
+/// let _repository = container.repository();
+/// ```
+///
+/// #### Named scopes
+/// Applications often have scopes inside other scopes, like a request for a tenant. Declare the scopes from the
+/// outermost to the innermost with the `scopes` option and mark scoped dependencies with the scope they belong to. Every
+/// scope gets a `new_<name>_scope` method which creates the dependencies of that scope and the scopes inside it again,
+/// while sharing the dependencies of the scopes around it.
+///
+/// ```
+/// use despatma::dependency_container;
+///
+/// struct Tenant;
+///
+/// struct Request;
+///
+/// #[dependency_container(scopes(tenant, request))]
+/// impl DependencyContainer {
+///     #[Scoped(tenant)]
+///     fn tenant(&self) -> Tenant {
+///         Tenant
+///     }
+///
+///     #[Scoped(request)]
+///     fn request(&self, tenant: &Tenant) -> Request {
+///         Request
+///     }
+/// }
+///
+/// let container = DependencyContainer::new();
+/// let tenant_scope = container.new_tenant_scope();
+///
+/// // Both requests use the same tenant
+/// let _first = tenant_scope.new_request_scope().request();
+/// let _second = tenant_scope.new_request_scope().request();
+/// ```
+///
+/// A dependency of an outer scope depending on a dependency of an inner scope is reported just like a captive
+/// dependency. Scoped dependencies without a named scope are created again by every scope, and `new_scope` still
+/// creates every scoped dependency again.
+///
+/// Once scopes are declared, a lowercase name in the attribute which is not one of them, like `#[Scoped(tenat)]`, is
+/// reported as an unknown scope instead of being used as a type hint.
+///
 /// ### Constructor arguments
 ///
 /// In some cases, you may need to initialize dependencies outside the container. In such cases, you can define a static `new` method with arguments listing dependencies of this type.
@@ -1041,16 +1463,671 @@ pub use despatma_visitor::visitor_mut;
 /// let _service = container.service();
 /// ```
 ///
-/// ## Considerations
+/// ### Builder
 ///
-/// - The macro determines wiring based on method names matching argument names.
-/// - When using runtime abstractions, ensure you're following the pattern shown in the `Box<dyn Trait>` example.
-/// - Async dependencies will cause parent dependencies to become async as well.
-/// - Consider the performance implications of excessive boxing or async calls in your dependency tree.
+/// Containers with constructor arguments also get a `builder()` to set the arguments by name. Arguments marked with
+/// `#[default]` fall back to `Default::default()` and arguments marked with `#[default(expr)]` fall back to the given
+/// expression. Calling `build()` before every other argument has been set is a compile error naming the missing
+/// argument. So no dependency of these containers can be called `builder`.
 ///
-/// More [advanced example are also in the repository](https://github.com/chesedo/despatma/tree/main/despatma/examples).
-/// For more information on dependency injection in Rust, see this article on [Manual Dependency Injection in Rust](https://chesedo.me/blog/manual-dependency-injection-rust/).
-pub use despatma_dependency_container::dependency_container;
-
-// Re-export this since it is used by the dependency_container macro
-pub use async_once_cell;
+/// ```
+/// use despatma::dependency_container;
+///
+/// struct Config {
+///     port: u32,
+/// }
+///
+/// struct Service;
+///
+/// impl Service {
+///     fn new(port: u32, retries: u8) -> Self {
+///         println!("Service started on port {} with {} retries", port, retries);
+///         Self
+///     }
+/// }
+///
+/// #[dependency_container]
+/// impl DependencyContainer {
+///     fn new(config: Config, #[default(3)] retries: u8) {}
+///
+///     fn service(&self, config: &Config, retries: &u8) -> Service {
+///         Service::new(config.port, *retries)
+///     }
+/// }
+///
+/// let container = DependencyContainer::builder()
+///     .config(Config { port: 8080 })
+///     .build();
+/// let _service = container.service();
+/// ```
+///
+/// ### Importing other containers
+///
+/// Containers can be split up and composed again by passing one container to the `new` of another. Mark such a
+/// constructor argument with `#[import(...)]` to request the dependencies of that container like any other dependency.
+/// The macro only sees the container it is placed on, so the import lists the name and type of every dependency to
+/// use, with `async` in front of async dependencies. A bare `#[import]` is reported for the same reason, and so are
+/// imports in the `dependency_container` arguments, like `imports(ConfigContainer)`.
+///
+/// ```
+/// use despatma::dependency_container;
+///
+/// struct Config {
+///     port: u32,
+/// }
+///
+/// struct Db;
+///
+/// struct Service;
+///
+/// impl Service {
+///     fn new(port: u32, db: &Db) -> Self {
+///         println!("Service started on port {}", port);
+///         Self
+///     }
+/// }
+///
+/// #[dependency_container]
+/// impl ConfigContainer {
+///     fn config(&self) -> Config {
+///         Config { port: 8080 }
+///     }
+///
+///     #[Singleton]
+///     fn db(&self) -> Db {
+///         Db
+///     }
+/// }
+///
+/// #[dependency_container]
+/// impl ServiceContainer {
+///     fn new<'a>(#[import(config: Config, db: &'a Db)] config_container: ConfigContainer<'a>) {}
+///
+///     fn service(&self, config: Config, db: &Db) -> Service {
+///         Service::new(config.port, db)
+///     }
+/// }
+///
+/// let container = ServiceContainer::new(ConfigContainer::new());
+/// let _service = container.service();
+/// ```
+///
+/// The imported container still decides the lifetime of its dependencies. So a singleton of the imported container is
+/// requested by reference. Calling `new_scope` on this container also creates a new scope of every imported container.
+///
+/// ### Naming dependencies
+///
+/// An argument can request a dependency with a different name by using the `#[from(name)]` attribute. A dependency can
+/// also be given an extra name with the `#[Named("name")]` attribute, which can then be requested like any other
+/// dependency:
+///
+/// ```
+/// use despatma::dependency_container;
+///
+/// struct Db {
+///     url: &'static str,
+/// }
+///
+/// struct Report;
+///
+/// impl Report {
+///     fn new(source: &Db, fallback: &Db) -> Self {
+///         Self
+///     }
+/// }
+///
+/// #[dependency_container]
+/// impl DependencyContainer {
+///     #[Singleton]
+///     fn db(&self) -> Db {
+///         Db { url: "postgres://primary" }
+///     }
+///
+///     #[Singleton]
+///     #[Named("replica")]
+///     fn replica_db(&self) -> Db {
+///         Db { url: "postgres://replica" }
+///     }
+///
+///     fn report(&self, #[from(replica)] db: &Db, #[from(db)] fallback: &Db) -> Report {
+///         Report::new(db, fallback)
+///     }
+/// }
+///
+/// let container = DependencyContainer::new();
+/// let _report = container.report();
+/// ```
+///
+/// This keeps the argument names idiomatic, while still being explicit about which dependency is used.
+///
+/// ### Resolving dependencies by type
+///
+/// An argument marked with `#[inject]` is wired to the only dependency returning the requested type, regardless of
+/// its name. Add the `by_type` option to do the same for every argument which does not match the name of a dependency:
+///
+/// ```
+/// use despatma::dependency_container;
+///
+/// struct Config {
+///     port: u32,
+/// }
+///
+/// struct Service;
+///
+/// impl Service {
+///     fn new(port: u32) -> Self {
+///         Self
+///     }
+/// }
+///
+/// #[dependency_container(by_type)]
+/// impl DependencyContainer {
+///     #[Singleton]
+///     fn config(&self) -> Config {
+///         Config { port: 8080 }
+///     }
+///
+///     fn service(&self, settings: &Config) -> Service {
+///         Service::new(settings.port)
+///     }
+/// }
+///
+/// let container = DependencyContainer::new();
+/// let _service = container.service();
+/// ```
+///
+/// References and smart pointers are ignored when comparing types. So `&Config` matches a dependency returning `Config`
+/// and `&dyn DataLayer` matches a dependency returning `Box<dyn DataLayer>`, `Arc<dyn DataLayer>` or any of the
+/// pointers declared with the `pointers` option. A compile error lists all the candidates when more than one dependency
+/// returns the requested type, in which case `#[from(name)]` can pick one of them.
+///
+/// Only the declared return type is matched, not the type hint of a dependency. So `#[Singleton(Sqlite)] fn db(&self)
+/// -> impl Db` cannot be requested as `Sqlite` by type, just like it cannot be requested as `Sqlite` by name. The
+/// `impl Db` keeps the concrete type hidden from the other dependencies too.
+///
+/// ### Lazy and provider handles
+///
+/// A dependency which is only needed on some code paths can be requested as `Lazy<T>` or `Provider<T>`. Neither
+/// creates the dependency up front. A [Lazy](crate::container::Lazy) creates it the first time `get()` is called and
+/// keeps it, while a [Provider](crate::container::Provider) resolves it again on every call to `get()`. Async
+/// dependencies give an [AsyncLazy](crate::container::AsyncLazy) or [AsyncProvider](crate::container::AsyncProvider)
+/// instead, which have to be awaited.
+///
+/// ```
+/// use despatma::dependency_container;
+///
+/// struct Report {
+///     rows: usize,
+/// }
+///
+/// struct Mailer;
+///
+/// impl Mailer {
+///     fn send(&self, to: &str) -> bool {
+///         true
+///     }
+/// }
+///
+/// #[dependency_container]
+/// impl DependencyContainer {
+///     #[Singleton]
+///     fn report(&self) -> Report {
+///         Report { rows: 10 }
+///     }
+///
+///     fn mailer(&self) -> Mailer {
+///         Mailer
+///     }
+///
+///     fn summary(&self, report: Lazy<Report>, mailer: Provider<Mailer>) -> bool {
+///         report.get().rows > 5 && mailer.get().send("admin")
+///     }
+/// }
+///
+/// let container = DependencyContainer::new();
+///
+/// assert!(container.summary());
+/// ```
+///
+/// The requested dependency is still checked to exist, but it is not needed to create the dependency requesting it.
+/// So two dependencies can depend on each other as long as one of them uses a handle.
+///
+/// ### Runtime arguments
+///
+/// Some values are only known when a dependency is created, like the id of the order being handled. Mark such
+/// arguments with `#[arg]` to have the caller pass them in. Other dependencies request such a dependency as a function
+/// which takes the same arguments.
+///
+/// ```
+/// use despatma::dependency_container;
+///
+/// struct Repo;
+///
+/// struct OrderHandler {
+///     repo: Repo,
+///     order_id: u32,
+/// }
+///
+/// #[dependency_container]
+/// impl DependencyContainer {
+///     fn repo(&self) -> Repo {
+///         Repo
+///     }
+///
+///     fn order_handler(&self, repo: Repo, #[arg] order_id: u32) -> OrderHandler {
+///         OrderHandler { repo, order_id }
+///     }
+///
+///     fn order_ids(&self, order_handler: impl Fn(u32) -> OrderHandler) -> Vec<u32> {
+///         (1..=3).map(|id| order_handler(id).order_id).collect()
+///     }
+/// }
+///
+/// let container = DependencyContainer::new();
+///
+/// assert_eq!(container.order_handler(7).order_id, 7);
+/// assert_eq!(container.order_ids(), vec![1, 2, 3]);
+/// ```
+///
+/// Only transient dependencies can take runtime arguments, since singleton and scoped dependencies are shared by every
+/// caller. Overrides of such a dependency take the same arguments.
+///
+/// ### Overriding dependencies
+///
+/// The `overrides` option gives every dependency an `override_<name>` method to replace how it is created. This makes it
+/// possible to swap in a fake during tests without writing a second container:
+///
+/// ```
+/// use despatma::dependency_container;
+///
+/// trait DataLayer {
+///     fn get_user_name(&self, id: u32) -> String;
+/// }
+///
+/// struct Postgres;
+///
+/// impl DataLayer for Postgres {
+///     fn get_user_name(&self, id: u32) -> String {
+///         format!("Postgres User {}", id)
+///     }
+/// }
+///
+/// struct FakeDataLayer;
+///
+/// impl DataLayer for FakeDataLayer {
+///     fn get_user_name(&self, id: u32) -> String {
+///         format!("Fake User {}", id)
+///     }
+/// }
+///
+/// #[dependency_container(overrides)]
+/// impl DependencyContainer {
+///     #[Singleton]
+///     fn data_layer(&self) -> Box<dyn DataLayer> {
+///         Box::new(Postgres)
+///     }
+///
+///     fn user_name(&self, data_layer: &Box<dyn DataLayer>) -> String {
+///         data_layer.get_user_name(1)
+///     }
+/// }
+///
+/// let container = DependencyContainer::new().override_data_layer(|| Box::new(FakeDataLayer));
+///
+/// assert_eq!(container.user_name(), "Fake User 1");
+/// ```
+///
+/// The override is used instead of the original function, so the dependencies of the original function are not created
+/// either. Singleton and scoped dependencies still only call the override once. And a dependency returning a `Result`
+/// should be overridden with a function returning a `Result` too.
+///
+/// The option is off by default, since every overridable dependency then needs a field for its override and a check
+/// for it each time it is created.
+///
+/// **Important**: Overrides are `'static` functions. So a dependency whose type contains a reference, a lifetime or an
+/// `impl Trait` without a type hint does not get an `override_<name>` method. This includes types like
+/// `Service<&Config>`, `Repo<'a>` and `Box<dyn Trait + 'a>`. Proc macros cannot emit warnings on stable Rust, so the
+/// method is left out silently and calling it fails with "no method named `override_<name>`". Give such a dependency
+/// an owned type, like `Arc<Config>` instead of `&Config`, to be able to override it.
+///
+/// ### Disposing dependencies
+///
+/// Singleton and scoped dependencies which need to be cleaned up can name a method to call with
+/// `#[Dispose(close)]`, or `#[Dispose(async close)]` for an async method. The container then gets a `shutdown` method
+/// which calls these hooks for the dependencies that were created. A dependency is disposed before the dependencies it
+/// was created from. `shutdown` is async when any of the hooks are async. A container with dispose hooks cannot have a
+/// dependency called `shutdown`.
+///
+/// ```
+/// use despatma::dependency_container;
+///
+/// struct Pool;
+///
+/// impl Pool {
+///     fn close(&self) {
+///         println!("Pool closed");
+///     }
+/// }
+///
+/// struct Worker;
+///
+/// impl Worker {
+///     fn stop(&self) {
+///         println!("Worker stopped");
+///     }
+/// }
+///
+/// #[dependency_container]
+/// impl DependencyContainer {
+///     #[Singleton]
+///     #[Dispose(close)]
+///     fn pool(&self) -> Pool {
+///         Pool
+///     }
+///
+///     #[Scoped]
+///     #[Dispose(stop)]
+///     fn worker(&self, pool: &Pool) -> Worker {
+///         Worker
+///     }
+/// }
+///
+/// let container = DependencyContainer::new();
+///
+/// let scope = container.new_scope();
+/// let _worker = scope.worker();
+///
+/// // Only stops the worker, since the pool is shared with the parent container
+/// scope.shutdown();
+///
+/// // Closes the pool
+/// container.shutdown();
+/// ```
+///
+/// ### Sharing between threads
+///
+/// By default the container stores synchronous singleton and scoped dependencies in a [std::cell::OnceCell], which
+/// means the container cannot be shared between threads. Add the `sync` option to generate a container which can be
+/// shared between threads instead:
+///
+/// ```
+/// use despatma::dependency_container;
+///
+/// struct Config {
+///     port: u32,
+/// }
+///
+/// struct Service;
+///
+/// impl Service {
+///     fn new(port: u32) -> Self {
+///         Self
+///     }
+/// }
+///
+/// #[dependency_container(pub, sync)]
+/// impl DependencyContainer {
+///     #[Singleton]
+///     fn config(&self) -> Config {
+///         Config { port: 8080 }
+///     }
+///
+///     fn service(&self, config: &Config) -> Service {
+///         Service::new(config.port)
+///     }
+/// }
+///
+/// let container = DependencyContainer::new();
+///
+/// std::thread::scope(|scope| {
+///     scope.spawn(|| container.service());
+///     scope.spawn(|| container.service());
+/// });
+/// ```
+///
+/// In this mode:
+/// - Synchronous singleton and scoped dependencies are stored in a [std::sync::OnceLock].
+/// - The futures returned by async dependencies are guaranteed to be `Send` so that they can be spawned on a
+///   multi-threaded runtime.
+/// - Every singleton, scoped and constructor dependency needs to be `Send + Sync`. A compile error will point to the
+///   dependency if it is not.
+///
+/// #### Shared handles
+/// Singleton and scoped dependencies are handed out as references which borrow the container. So they cannot be moved
+/// into a spawned task or thread. Mark a dependency with `#[Singleton(shared)]` or `#[Scoped(shared)]`, which combine
+/// with `eager`, a named scope and a type hint, to have it handed out as a [std::sync::Arc] instead. Other
+/// dependencies can take the `Arc`, a reference to it, or an `impl Trait` for it.
+///
+/// The `shared` option does this for every singleton and scoped dependency. The container then has no lifetime
+/// parameter, unless one of its dependencies still borrows it. So the container itself can be `'static` and moved into
+/// spawned tasks:
+///
+/// ```
+/// use std::sync::Arc;
+///
+/// use despatma::dependency_container;
+///
+/// struct Config {
+///     port: u32,
+/// }
+///
+/// struct Server {
+///     config: Arc<Config>,
+/// }
+///
+/// #[dependency_container(sync, shared)]
+/// impl DependencyContainer {
+///     #[Singleton]
+///     fn config(&self) -> Config {
+///         Config { port: 8080 }
+///     }
+///
+///     fn server(&self, config: Arc<Config>) -> Server {
+///         Server { config }
+///     }
+/// }
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let container: DependencyContainer = DependencyContainer::new();
+/// let config = container.config();
+///
+/// let server = tokio::spawn(async move { container.server() }).await.unwrap();
+///
+/// assert!(Arc::ptr_eq(&server.config, &config));
+/// # }
+/// ```
+///
+/// ### Dependency graph
+///
+/// Every container gets a `dependency_graph_dot()` and a `dependency_graph_mermaid()` function. They describe each
+/// dependency with its lifetime, whether it is async or boxed, and which dependencies it requests. Arguments which
+/// request a dependency under another name are shown on the edge. So no dependency can be called
+/// `dependency_graph_dot` or `dependency_graph_mermaid`.
+///
+/// ```
+/// use despatma::dependency_container;
+///
+/// struct Db;
+///
+/// struct Service;
+///
+/// #[dependency_container]
+/// impl DependencyContainer {
+///     #[Singleton]
+///     fn db(&self) -> Db {
+///         Db
+///     }
+///
+///     fn service(&self, db: &Db) -> Service {
+///         Service
+///     }
+/// }
+///
+/// assert_eq!(
+///     DependencyContainer::dependency_graph_mermaid(),
+///     r#"graph TD
+///     db["db<br/>Singleton"]
+///     service["service<br/>Transient"]
+///     service --> db
+/// "#
+/// );
+/// ```
+///
+/// ### Metadata
+///
+/// Every container also gets a `METADATA` constant listing a [container::DependencyInfo] for each dependency. This can
+/// be used in tests to check layering rules. Enable the `serde` feature to serialize it for other tools.
+///
+/// ```
+/// use despatma::dependency_container;
+///
+/// struct Db;
+///
+/// struct Handler;
+///
+/// #[dependency_container]
+/// impl DependencyContainer {
+///     #[Singleton]
+///     fn db(&self) -> Db {
+///         Db
+///     }
+///
+///     fn handler(&self, db: &Db) -> Handler {
+///         Handler
+///     }
+/// }
+///
+/// let handler = DependencyContainer::METADATA
+///     .iter()
+///     .find(|dependency| dependency.name == "handler")
+///     .unwrap();
+///
+/// assert_eq!(handler.dependencies, &["db"]);
+/// ```
+///
+/// ### Introspection
+///
+/// Containers can report which of their singleton and scoped dependencies have been created without creating any
+/// others. `initialized` lists their names and the `Debug` output of a container shows the same without needing the
+/// dependencies to implement `Debug`. The `constructions` option adds a `constructions` method too, which counts how
+/// many times a dependency has been created by the container and its scopes. It is off by default since every
+/// dependency then needs a counter which is updated each time it is created. Imported dependencies are counted by the
+/// container they are imported from instead.
+///
+/// ```
+/// use despatma::dependency_container;
+///
+/// struct Config;
+///
+/// struct Cache;
+///
+/// struct Service;
+///
+/// #[dependency_container(constructions)]
+/// impl DependencyContainer {
+///     #[Singleton]
+///     fn config(&self) -> Config {
+///         Config
+///     }
+///
+///     #[Singleton]
+///     fn cache(&self) -> Cache {
+///         Cache
+///     }
+///
+///     fn service(&self, config: &Config) -> Service {
+///         Service
+///     }
+/// }
+///
+/// let container = DependencyContainer::new();
+/// let scope = container.new_scope();
+///
+/// scope.service();
+/// scope.service();
+///
+/// assert_eq!(container.initialized(), ["config"]);
+/// assert_eq!(container.constructions("service"), Some(2));
+/// assert_eq!(container.constructions("cache"), Some(0));
+/// assert_eq!(
+///     format!("{container:?}"),
+///     "DependencyContainer { config: initialized, cache: uninitialized, .. }"
+/// );
+/// ```
+///
+/// Dependencies cannot be called `initialized`, or `constructions` with the option, since those names are taken by the
+/// container. Add the `no_debug` option, as in `#[dependency_container(no_debug)]`, to implement `Debug` for the
+/// container yourself.
+///
+/// ### Tracing
+///
+/// Enable the `tracing` feature to wrap every resolver in a `resolve` debug span. The span records the `dependency`
+/// name, its `lifetime`, whether it was `cached` (served from the cell of a singleton or scoped dependency) and the
+/// `duration` it took to create when it was not cached. This makes it easy to find which dependency is slowing down
+/// the startup of a service.
+///
+/// ### Generic code
+///
+/// Containers with the `provides` option implement [container::Provides] for the type of every dependency, and
+/// [container::ProvidesAsync] for async dependencies. Code can then ask for anything which provides the types it needs
+/// instead of naming a container, which makes it easy to test with a different container.
+///
+/// ```
+/// use despatma::{container::Provides, dependency_container};
+///
+/// struct UserService {
+///     name: String,
+/// }
+///
+/// #[dependency_container(provides)]
+/// impl DependencyContainer {
+///     fn user_service(&self) -> UserService {
+///         UserService { name: "Alice".to_string() }
+///     }
+/// }
+///
+/// #[dependency_container(provides)]
+/// impl TestContainer {
+///     fn user_service(&self) -> UserService {
+///         UserService { name: "Test".to_string() }
+///     }
+/// }
+///
+/// fn greet<'a>(container: &'a impl Provides<'a, UserService>) -> String {
+///     format!("Hello {}", container.provide().name)
+/// }
+///
+/// assert_eq!(greet(&DependencyContainer::new()), "Hello Alice");
+/// assert_eq!(greet(&TestContainer::new()), "Hello Test");
+/// ```
+///
+/// Singleton and scoped dependencies are provided as references, like `Provides<'a, &'a Config>`. A type returned by
+/// more than one dependency, or by a dependency returning an `impl Trait` or taking runtime arguments, is not provided.
+/// Types are compared as they are written, with smart pointers normalised. So a type alias and the type it stands for,
+/// like `Url` and `String`, conflict when both are returned. Spell them the same way to leave the type out.
+///
+/// ## Considerations
+///
+/// - The macro determines wiring based on method names matching argument names, unless `#[from(name)]`, `#[inject]`
+///   or the `by_type` option is used.
+/// - Dependencies cannot (indirectly) depend on themselves, unless through a `Lazy` or `Provider` handle. Any such
+///   cycle is reported as a compile error.
+/// - When using runtime abstractions, ensure you're following the pattern shown in the `Box<dyn Trait>` example.
+/// - Async dependencies will cause parent dependencies to become async as well.
+/// - Dependencies are created by calling the methods of the dependencies they need. So overriding a dependency also
+///   changes every dependency created from it.
+/// - Consider the performance implications of excessive boxing or async calls in your dependency tree.
+///
+/// More [advanced example are also in the repository](https://github.com/chesedo/despatma/tree/main/despatma/examples).
+/// For more information on dependency injection in Rust, see this article on [Manual Dependency Injection in Rust](https://chesedo.me/blog/manual-dependency-injection-rust/).
+pub use despatma_dependency_container::despatma_dependency_container as dependency_container;
+
+pub mod container;
+
+// Re-export this since it is used by the dependency_container macro
+pub use async_once_cell;
+
+// Re-export this since it is used by the dependency_container macro when tracing is enabled
+#[cfg(feature = "tracing")]
+pub use tracing;
@@ -0,0 +1,395 @@
+use std::{cell::RefCell, rc::Rc};
+use despatma::container::Provides;
+type Log = Rc<RefCell<Vec<&'static str>>>;
+trait Mailer {
+    fn send(&self, to: &str) -> String;
+}
+struct SmtpMailer {
+    log: Log,
+}
+impl SmtpMailer {
+    fn close(&self) {
+        self.log.borrow_mut().push("smtp");
+    }
+}
+impl Mailer for SmtpMailer {
+    fn send(&self, to: &str) -> String {
+        ::alloc::__export::must_use({
+            ::alloc::fmt::format(format_args!("smtp: {0}", to))
+        })
+    }
+}
+struct FakeMailer;
+impl Mailer for FakeMailer {
+    fn send(&self, to: &str) -> String {
+        ::alloc::__export::must_use({
+            ::alloc::fmt::format(format_args!("fake: {0}", to))
+        })
+    }
+}
+struct Clock {
+    now: u64,
+}
+struct Recorder;
+struct DependencyContainer<'a> {
+    smtp: std::rc::Rc<std::cell::OnceCell<SmtpMailer>>,
+    mailer: std::rc::Rc<std::cell::OnceCell<std::boxed::Box<dyn Mailer>>>,
+    log: std::sync::Arc<Log>,
+    smtp_override: Option<std::rc::Rc<dyn Fn() -> SmtpMailer>>,
+    mailer_override: Option<std::rc::Rc<dyn Fn() -> std::boxed::Box<dyn Mailer>>>,
+    clock_override: Option<std::rc::Rc<dyn Fn() -> Clock>>,
+    greeting_override: Option<std::rc::Rc<dyn Fn() -> String>>,
+    _is_scope: bool,
+    _phantom: std::marker::PhantomData<&'a ()>,
+}
+#[automatically_derived]
+impl<'a> ::core::clone::Clone for DependencyContainer<'a> {
+    #[inline]
+    fn clone(&self) -> DependencyContainer<'a> {
+        DependencyContainer {
+            smtp: ::core::clone::Clone::clone(&self.smtp),
+            mailer: ::core::clone::Clone::clone(&self.mailer),
+            log: ::core::clone::Clone::clone(&self.log),
+            smtp_override: ::core::clone::Clone::clone(&self.smtp_override),
+            mailer_override: ::core::clone::Clone::clone(&self.mailer_override),
+            clock_override: ::core::clone::Clone::clone(&self.clock_override),
+            greeting_override: ::core::clone::Clone::clone(&self.greeting_override),
+            _is_scope: ::core::clone::Clone::clone(&self._is_scope),
+            _phantom: ::core::clone::Clone::clone(&self._phantom),
+        }
+    }
+}
+impl<'a> DependencyContainer<'a> {
+    pub fn new(log: Log) -> Self {
+        Self {
+            smtp: Default::default(),
+            mailer: Default::default(),
+            log: std::sync::Arc::new(log),
+            smtp_override: None,
+            mailer_override: None,
+            clock_override: None,
+            greeting_override: None,
+            _is_scope: false,
+            _phantom: Default::default(),
+        }
+    }
+    pub fn new_scope(&self) -> Self {
+        Self {
+            smtp: self.smtp.clone(),
+            mailer: self.mailer.clone(),
+            log: self.log.clone(),
+            smtp_override: self.smtp_override.clone(),
+            mailer_override: self.mailer_override.clone(),
+            clock_override: self.clock_override.clone(),
+            greeting_override: self.greeting_override.clone(),
+            _is_scope: true,
+            _phantom: Default::default(),
+        }
+    }
+    /// Call the dispose hooks of the dependencies which have been created, starting with those that depend on the others.
+    /// A scope only disposes its own scoped dependencies.
+    pub fn shutdown(self) {
+        if !self._is_scope {
+            if let Some(smtp) = self.smtp.get() {
+                smtp.close();
+            }
+        }
+    }
+    /// The names of the singleton and scoped dependencies which have been created, without creating any others
+    pub fn initialized(&self) -> Vec<&'static str> {
+        #[allow(unused_mut)]
+        let mut initialized = Vec::new();
+        if self.smtp.get().is_some() {
+            initialized.push("smtp");
+        }
+        if self.mailer.get().is_some() {
+            initialized.push("mailer");
+        }
+        initialized
+    }
+    /// The dependencies of this container and how they are wired together in the DOT format
+    pub fn dependency_graph_dot() -> &'static str {
+        "digraph DependencyContainer {\n    node [shape=box];\n    \"smtp\" [label=\"smtp\\nSingleton\"];\n    \"mailer\" [label=\"mailer\\nSingleton, boxed\"];\n    \"clock\" [label=\"clock\\nTransient\"];\n    \"recorder\" [label=\"recorder\\nSingleton\"];\n    \"greeting\" [label=\"greeting\\nTransient\"];\n    \"log\" [label=\"log\\nEmbedded\"];\n    \"smtp\" -> \"log\";\n    \"mailer\" -> \"smtp\";\n    \"greeting\" -> \"mailer\";\n    \"greeting\" -> \"clock\";\n}\n"
+    }
+    /// The dependencies of this container and how they are wired together as a Mermaid flowchart
+    pub fn dependency_graph_mermaid() -> &'static str {
+        "graph TD\n    smtp[\"smtp<br/>Singleton\"]\n    mailer[\"mailer<br/>Singleton, boxed\"]\n    clock[\"clock<br/>Transient\"]\n    recorder[\"recorder<br/>Singleton\"]\n    greeting[\"greeting<br/>Transient\"]\n    log[\"log<br/>Embedded\"]\n    smtp --> log\n    mailer --> smtp\n    greeting --> mailer\n    greeting --> clock\n"
+    }
+    /// Describes every dependency of this container so that tooling can check how it is wired together
+    pub const METADATA: &'static [despatma::container::DependencyInfo] = &[
+        despatma::container::DependencyInfo {
+            name: "smtp",
+            ty: "SmtpMailer",
+            field_ty: "SmtpMailer",
+            lifetime: despatma::container::Lifetime::Singleton,
+            is_async: false,
+            is_boxed: false,
+            dependencies: &["log"],
+        },
+        despatma::container::DependencyInfo {
+            name: "mailer",
+            ty: "Box<dyn Mailer>",
+            field_ty: "std::boxed::Box<dyn Mailer>",
+            lifetime: despatma::container::Lifetime::Singleton,
+            is_async: false,
+            is_boxed: true,
+            dependencies: &["smtp"],
+        },
+        despatma::container::DependencyInfo {
+            name: "clock",
+            ty: "Clock",
+            field_ty: "Clock",
+            lifetime: despatma::container::Lifetime::Transient,
+            is_async: false,
+            is_boxed: false,
+            dependencies: &[],
+        },
+        despatma::container::DependencyInfo {
+            name: "greeting",
+            ty: "String",
+            field_ty: "String",
+            lifetime: despatma::container::Lifetime::Transient,
+            is_async: false,
+            is_boxed: false,
+            dependencies: &["mailer", "clock"],
+        },
+        despatma::container::DependencyInfo {
+            name: "log",
+            ty: "Log",
+            field_ty: "Log",
+            lifetime: despatma::container::Lifetime::Embedded,
+            is_async: false,
+            is_boxed: false,
+            dependencies: &[],
+        },
+    ];
+    pub fn smtp(&'a self) -> &SmtpMailer {
+        if let Some(create) = &self.smtp_override {
+            self.smtp.get_or_init(|| { create() })
+        } else {
+            let log = self.log();
+            self.smtp.get_or_init(|| { SmtpMailer { log: log.clone() } })
+        }
+    }
+    /// Replace how the `smtp` dependency is created. Useful to swap in a fake during tests.
+    pub fn override_smtp(mut self, create: impl Fn() -> SmtpMailer + 'static) -> Self {
+        self.smtp_override = Some(std::rc::Rc::new(create));
+        self
+    }
+    pub fn mailer(&'a self) -> &std::boxed::Box<dyn Mailer> {
+        if let Some(create) = &self.mailer_override {
+            self.mailer.get_or_init(|| { create() })
+        } else {
+            let smtp = self.smtp();
+            self.mailer
+                .get_or_init(|| {
+                    Box::new(SmtpMailer {
+                        log: smtp.log.clone(),
+                    })
+                })
+        }
+    }
+    /// Replace how the `mailer` dependency is created. Useful to swap in a fake during tests.
+    pub fn override_mailer(
+        mut self,
+        create: impl Fn() -> std::boxed::Box<dyn Mailer> + 'static,
+    ) -> Self {
+        self.mailer_override = Some(std::rc::Rc::new(create));
+        self
+    }
+    pub fn clock(&'a self) -> Clock {
+        if let Some(create) = &self.clock_override {
+            create()
+        } else {
+            Clock { now: 42 }
+        }
+    }
+    /// Replace how the `clock` dependency is created. Useful to swap in a fake during tests.
+    pub fn override_clock(mut self, create: impl Fn() -> Clock + 'static) -> Self {
+        self.clock_override = Some(std::rc::Rc::new(create));
+        self
+    }
+    pub fn greeting(&'a self) -> String {
+        if let Some(create) = &self.greeting_override {
+            create()
+        } else {
+            let mailer = self.mailer();
+            let clock = self.clock();
+            ::alloc::__export::must_use({
+                ::alloc::fmt::format(
+                    format_args!("{0} at {1}", mailer.send("admin"), clock.now),
+                )
+            })
+        }
+    }
+    /// Replace how the `greeting` dependency is created. Useful to swap in a fake during tests.
+    pub fn override_greeting(mut self, create: impl Fn() -> String + 'static) -> Self {
+        self.greeting_override = Some(std::rc::Rc::new(create));
+        self
+    }
+    pub fn log(&'a self) -> &Log {
+        self.log.as_ref()
+    }
+}
+/// Builder to set the constructor arguments of [`DependencyContainer`] by name.
+#[must_use]
+struct DependencyContainerBuilder<'a, __Log> {
+    log: __Log,
+    _phantom: std::marker::PhantomData<&'a ()>,
+}
+impl<'a> DependencyContainer<'a> {
+    pub fn builder() -> DependencyContainerBuilder<'a, ()> {
+        DependencyContainerBuilder {
+            log: (),
+            _phantom: Default::default(),
+        }
+    }
+}
+impl<'a, __Log> DependencyContainerBuilder<'a, __Log> {
+    pub fn log(self, log: Log) -> DependencyContainerBuilder<'a, (Log,)> {
+        DependencyContainerBuilder {
+            log: (log,),
+            _phantom: Default::default(),
+        }
+    }
+    pub fn build(self) -> DependencyContainer<'a>
+    where
+        __Log: DependencyContainerBuilderHasLog<'a>,
+    {
+        DependencyContainer::new(
+            DependencyContainerBuilderHasLog::into_argument(self.log),
+        )
+    }
+}
+#[doc(hidden)]
+#[diagnostic::on_unimplemented(
+    message = "The `log` constructor argument of `DependencyContainer` has not been set",
+    label = "`log` is missing",
+    note = "Set it with `.log(...)` before calling `.build()`"
+)]
+trait DependencyContainerBuilderHasLog<'a> {
+    fn into_argument(self) -> Log;
+}
+impl<'a> DependencyContainerBuilderHasLog<'a> for (Log,) {
+    fn into_argument(self) -> Log {
+        self.0
+    }
+}
+impl<'a> core::fmt::Debug for DependencyContainer<'a> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        #[allow(unused_mut)]
+        let mut debug = f.debug_struct("DependencyContainer");
+        debug
+            .field(
+                "smtp",
+                &format_args!(
+                    "{0}", if self.smtp.get().is_some() { "initialized" } else {
+                    "uninitialized" }
+                ),
+            );
+        debug
+            .field(
+                "mailer",
+                &format_args!(
+                    "{0}", if self.mailer.get().is_some() { "initialized" } else {
+                    "uninitialized" }
+                ),
+            );
+        debug.finish_non_exhaustive()
+    }
+}
+impl<'a> despatma::container::Provides<'a, &'a SmtpMailer> for DependencyContainer<'a> {
+    fn provide(&'a self) -> &'a SmtpMailer {
+        self.smtp()
+    }
+}
+impl<'a> despatma::container::Provides<'a, &'a std::boxed::Box<dyn Mailer>>
+for DependencyContainer<'a> {
+    fn provide(&'a self) -> &'a std::boxed::Box<dyn Mailer> {
+        self.mailer()
+    }
+}
+impl<'a> despatma::container::Provides<'a, Clock> for DependencyContainer<'a> {
+    fn provide(&'a self) -> Clock {
+        self.clock()
+    }
+}
+impl<'a> despatma::container::Provides<'a, String> for DependencyContainer<'a> {
+    fn provide(&'a self) -> String {
+        self.greeting()
+    }
+}
+impl<'a> despatma::container::Provides<'a, &'a Log> for DependencyContainer<'a> {
+    fn provide(&'a self) -> &'a Log {
+        self.log()
+    }
+}
+fn main() {
+    let log = Log::default();
+    let container = DependencyContainer::new(log.clone());
+    match (&container.greeting(), &"smtp: admin at 42") {
+        (left_val, right_val) => {
+            if !(*left_val == *right_val) {
+                let kind = ::core::panicking::AssertKind::Eq;
+                ::core::panicking::assert_failed(
+                    kind,
+                    &*left_val,
+                    &*right_val,
+                    ::core::option::Option::None,
+                );
+            }
+        }
+    };
+    let clock: Clock = container.provide();
+    match (&clock.now, &42) {
+        (left_val, right_val) => {
+            if !(*left_val == *right_val) {
+                let kind = ::core::panicking::AssertKind::Eq;
+                ::core::panicking::assert_failed(
+                    kind,
+                    &*left_val,
+                    &*right_val,
+                    ::core::option::Option::None,
+                );
+            }
+        }
+    };
+    let container = DependencyContainer::new(log.clone())
+        .override_clock(|| Clock { now: 7 });
+    match (&container.greeting(), &"smtp: admin at 7") {
+        (left_val, right_val) => {
+            if !(*left_val == *right_val) {
+                let kind = ::core::panicking::AssertKind::Eq;
+                ::core::panicking::assert_failed(
+                    kind,
+                    &*left_val,
+                    &*right_val,
+                    ::core::option::Option::None,
+                );
+            }
+        }
+    };
+    let _smtp = container.smtp();
+    container.shutdown();
+    match (
+        &*log.borrow(),
+        &::alloc::boxed::box_assume_init_into_vec_unsafe(
+            ::alloc::intrinsics::write_box_via_move(
+                ::alloc::boxed::Box::new_uninit(),
+                ["smtp"],
+            ),
+        ),
+    ) {
+        (left_val, right_val) => {
+            if !(*left_val == *right_val) {
+                let kind = ::core::panicking::AssertKind::Eq;
+                ::core::panicking::assert_failed(
+                    kind,
+                    &*left_val,
+                    &*right_val,
+                    ::core::option::Option::None,
+                );
+            }
+        }
+    };
+}
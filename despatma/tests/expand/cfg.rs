@@ -0,0 +1,105 @@
+use std::{cell::RefCell, rc::Rc};
+
+use despatma::container::Provides;
+
+type Log = Rc<RefCell<Vec<&'static str>>>;
+
+trait Mailer {
+    fn send(&self, to: &str) -> String;
+}
+
+struct SmtpMailer {
+    log: Log,
+}
+
+impl SmtpMailer {
+    fn close(&self) {
+        self.log.borrow_mut().push("smtp");
+    }
+}
+
+impl Mailer for SmtpMailer {
+    fn send(&self, to: &str) -> String {
+        format!("smtp: {to}")
+    }
+}
+
+struct FakeMailer;
+
+impl Mailer for FakeMailer {
+    fn send(&self, to: &str) -> String {
+        format!("fake: {to}")
+    }
+}
+
+struct Clock {
+    now: u64,
+}
+
+struct Recorder;
+
+#[despatma::dependency_container(provides, overrides)]
+impl DependencyContainer {
+    fn new(log: Log) {}
+
+    // Only one of the alternatives is compiled in. Dependents request whichever one is active
+    #[cfg(not(test))]
+    #[Singleton]
+    #[Dispose(close)]
+    fn smtp(&self, log: &Log) -> SmtpMailer {
+        SmtpMailer { log: log.clone() }
+    }
+
+    #[cfg(not(test))]
+    #[Singleton]
+    fn mailer(&self, smtp: &SmtpMailer) -> Box<dyn Mailer> {
+        Box::new(SmtpMailer {
+            log: smtp.log.clone(),
+        })
+    }
+
+    #[cfg(test)]
+    #[Singleton]
+    fn mailer(&self) -> Box<dyn Mailer> {
+        Box::new(FakeMailer)
+    }
+
+    #[cfg(not(test))]
+    fn clock(&self) -> Clock {
+        Clock { now: 42 }
+    }
+
+    #[cfg(test)]
+    fn clock(&self) -> Clock {
+        Clock { now: 0 }
+    }
+
+    // Only compiled in for tests, like anything depending on it
+    #[cfg(test)]
+    #[Singleton]
+    fn recorder(&self) -> Recorder {
+        Recorder
+    }
+
+    fn greeting(&self, mailer: &Box<dyn Mailer>, clock: Clock) -> String {
+        format!("{} at {}", mailer.send("admin"), clock.now)
+    }
+}
+
+fn main() {
+    let log = Log::default();
+    let container = DependencyContainer::new(log.clone());
+
+    assert_eq!(container.greeting(), "smtp: admin at 42");
+
+    let clock: Clock = container.provide();
+    assert_eq!(clock.now, 42);
+
+    let container = DependencyContainer::new(log.clone()).override_clock(|| Clock { now: 7 });
+    assert_eq!(container.greeting(), "smtp: admin at 7");
+
+    let _smtp = container.smtp();
+    container.shutdown();
+
+    assert_eq!(*log.borrow(), vec!["smtp"]);
+}
@@ -0,0 +1,226 @@
+use despatma::container::{AsyncProvider, Provider};
+struct Report {
+    rows: usize,
+}
+struct Mailer;
+impl Mailer {
+    fn send(&self, _to: &str) -> bool {
+        true
+    }
+}
+struct Users<'a> {
+    notifications: Provider<'a, Notifications<'a>>,
+}
+struct Notifications<'a> {
+    users: Users<'a>,
+}
+struct Session {
+    id: u32,
+}
+struct DependencyContainer<'a> {
+    report: std::rc::Rc<std::cell::OnceCell<Report>>,
+    _phantom: std::marker::PhantomData<&'a ()>,
+}
+#[automatically_derived]
+impl<'a> ::core::clone::Clone for DependencyContainer<'a> {
+    #[inline]
+    fn clone(&self) -> DependencyContainer<'a> {
+        DependencyContainer {
+            report: ::core::clone::Clone::clone(&self.report),
+            _phantom: ::core::clone::Clone::clone(&self._phantom),
+        }
+    }
+}
+impl<'a> DependencyContainer<'a> {
+    pub fn new() -> Self {
+        Self {
+            report: Default::default(),
+            _phantom: Default::default(),
+        }
+    }
+    pub fn new_scope(&self) -> Self {
+        Self {
+            report: self.report.clone(),
+            _phantom: Default::default(),
+        }
+    }
+    /// The names of the singleton and scoped dependencies which have been created, without creating any others
+    pub fn initialized(&self) -> Vec<&'static str> {
+        #[allow(unused_mut)]
+        let mut initialized = Vec::new();
+        if self.report.get().is_some() {
+            initialized.push("report");
+        }
+        initialized
+    }
+    /// The dependencies of this container and how they are wired together in the DOT format
+    pub fn dependency_graph_dot() -> &'static str {
+        "digraph DependencyContainer {\n    node [shape=box];\n    \"report\" [label=\"report\\nSingleton\"];\n    \"mailer\" [label=\"mailer\\nTransient\"];\n    \"session\" [label=\"session\\nTransient, async\"];\n    \"summary\" [label=\"summary\\nTransient\"];\n    \"users\" [label=\"users\\nTransient\"];\n    \"notifications\" [label=\"notifications\\nTransient\"];\n    \"sessions\" [label=\"sessions\\nTransient\"];\n    \"summary\" -> \"report\" [style=dashed];\n    \"summary\" -> \"mailer\" [style=dashed];\n    \"users\" -> \"notifications\" [style=dashed];\n    \"notifications\" -> \"users\";\n    \"sessions\" -> \"session\" [style=dashed];\n}\n"
+    }
+    /// The dependencies of this container and how they are wired together as a Mermaid flowchart
+    pub fn dependency_graph_mermaid() -> &'static str {
+        "graph TD\n    report[\"report<br/>Singleton\"]\n    mailer[\"mailer<br/>Transient\"]\n    session[\"session<br/>Transient, async\"]\n    summary[\"summary<br/>Transient\"]\n    users[\"users<br/>Transient\"]\n    notifications[\"notifications<br/>Transient\"]\n    sessions[\"sessions<br/>Transient\"]\n    summary -.-> report\n    summary -.-> mailer\n    users -.-> notifications\n    notifications --> users\n    sessions -.-> session\n"
+    }
+    /// Describes every dependency of this container so that tooling can check how it is wired together
+    pub const METADATA: &'static [despatma::container::DependencyInfo] = &[
+        despatma::container::DependencyInfo {
+            name: "report",
+            ty: "Report",
+            field_ty: "Report",
+            lifetime: despatma::container::Lifetime::Singleton,
+            is_async: false,
+            is_boxed: false,
+            dependencies: &[],
+        },
+        despatma::container::DependencyInfo {
+            name: "mailer",
+            ty: "Mailer",
+            field_ty: "Mailer",
+            lifetime: despatma::container::Lifetime::Transient,
+            is_async: false,
+            is_boxed: false,
+            dependencies: &[],
+        },
+        despatma::container::DependencyInfo {
+            name: "session",
+            ty: "Session",
+            field_ty: "Session",
+            lifetime: despatma::container::Lifetime::Transient,
+            is_async: true,
+            is_boxed: false,
+            dependencies: &[],
+        },
+        despatma::container::DependencyInfo {
+            name: "summary",
+            ty: "bool",
+            field_ty: "bool",
+            lifetime: despatma::container::Lifetime::Transient,
+            is_async: false,
+            is_boxed: false,
+            dependencies: &[],
+        },
+        despatma::container::DependencyInfo {
+            name: "users",
+            ty: "Users<'a>",
+            field_ty: "Users<'a>",
+            lifetime: despatma::container::Lifetime::Transient,
+            is_async: false,
+            is_boxed: false,
+            dependencies: &[],
+        },
+        despatma::container::DependencyInfo {
+            name: "notifications",
+            ty: "Notifications<'a>",
+            field_ty: "Notifications<'a>",
+            lifetime: despatma::container::Lifetime::Transient,
+            is_async: false,
+            is_boxed: false,
+            dependencies: &["users"],
+        },
+        despatma::container::DependencyInfo {
+            name: "sessions",
+            ty: "AsyncProvider<'a, Session>",
+            field_ty: "AsyncProvider<'a, Session>",
+            lifetime: despatma::container::Lifetime::Transient,
+            is_async: false,
+            is_boxed: false,
+            dependencies: &[],
+        },
+    ];
+    pub fn report(&'a self) -> &Report {
+        self.report.get_or_init(|| { Report { rows: 10 } })
+    }
+    pub fn mailer(&'a self) -> Mailer {
+        Mailer
+    }
+    pub async fn session(&'a self) -> Session {
+        Session { id: 7 }
+    }
+    pub fn summary(&'a self) -> bool {
+        let report = despatma::container::Lazy::<
+            _,
+            dyn Fn() -> _ + 'a,
+        >::new(Box::new(move || self.report()));
+        let mailer = despatma::container::Provider::<
+            _,
+            dyn Fn() -> _ + 'a,
+        >::new(Box::new(move || self.mailer()));
+        report.get().rows > 5 && mailer.get().send("admin")
+    }
+    pub fn users(&'a self) -> Users<'a> {
+        let notifications = despatma::container::Provider::<
+            _,
+            dyn Fn() -> _ + 'a,
+        >::new(Box::new(move || self.notifications()));
+        Users { notifications }
+    }
+    pub fn notifications(&'a self) -> Notifications<'a> {
+        let users = self.users();
+        Notifications { users }
+    }
+    pub fn sessions(&'a self) -> AsyncProvider<'a, Session> {
+        let session = despatma::container::AsyncProvider::<
+            _,
+            dyn Fn() -> despatma::container::BoxFuture<'a, _> + 'a,
+        >::new(Box::new(move || Box::pin(self.session())));
+        session
+    }
+}
+impl<'a> core::fmt::Debug for DependencyContainer<'a> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        #[allow(unused_mut)]
+        let mut debug = f.debug_struct("DependencyContainer");
+        debug
+            .field(
+                "report",
+                &format_args!(
+                    "{0}", if self.report.get().is_some() { "initialized" } else {
+                    "uninitialized" }
+                ),
+            );
+        debug.finish_non_exhaustive()
+    }
+}
+fn main() {
+    let body = async {
+        let container = DependencyContainer::new();
+        if !container.summary() {
+            ::core::panicking::panic("assertion failed: container.summary()")
+        }
+        let notifications = container.notifications();
+        let _users = notifications.users.notifications.get();
+        match (&container.sessions().get().await.id, &7) {
+            (left_val, right_val) => {
+                if !(*left_val == *right_val) {
+                    let kind = ::core::panicking::AssertKind::Eq;
+                    ::core::panicking::assert_failed(
+                        kind,
+                        &*left_val,
+                        &*right_val,
+                        ::core::option::Option::None,
+                    );
+                }
+            }
+        };
+    };
+    let body = {
+        if false {
+            let _: &dyn ::core::future::Future<Output = ()> = &body;
+        }
+        body
+    };
+    #[allow(
+        clippy::expect_used,
+        clippy::diverging_sub_expression,
+        clippy::needless_return,
+        clippy::unwrap_in_result
+    )]
+    {
+        use tokio::runtime::Builder;
+        return Builder::new_multi_thread()
+            .enable_all()
+            .build()
+            .expect("Failed building the Runtime")
+            .block_on(body);
+    }
+}
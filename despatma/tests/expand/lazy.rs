@@ -0,0 +1,70 @@
+use despatma::container::{AsyncProvider, Provider};
+
+struct Report {
+    rows: usize,
+}
+
+struct Mailer;
+
+impl Mailer {
+    fn send(&self, _to: &str) -> bool {
+        true
+    }
+}
+
+struct Users<'a> {
+    notifications: Provider<'a, Notifications<'a>>,
+}
+
+struct Notifications<'a> {
+    users: Users<'a>,
+}
+
+struct Session {
+    id: u32,
+}
+
+#[despatma::dependency_container]
+impl DependencyContainer {
+    #[Singleton]
+    fn report(&self) -> Report {
+        Report { rows: 10 }
+    }
+
+    fn mailer(&self) -> Mailer {
+        Mailer
+    }
+
+    async fn session(&self) -> Session {
+        Session { id: 7 }
+    }
+
+    fn summary(&self, report: Lazy<Report>, mailer: Provider<Mailer>) -> bool {
+        report.get().rows > 5 && mailer.get().send("admin")
+    }
+
+    // The users and notifications need each other, but only once they are created
+    fn users(&self, notifications: Provider<Notifications>) -> Users<'a> {
+        Users { notifications }
+    }
+
+    fn notifications(&self, users: Users) -> Notifications<'a> {
+        Notifications { users }
+    }
+
+    fn sessions(&self, session: Provider<Session>) -> AsyncProvider<'a, Session> {
+        session
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    let container = DependencyContainer::new();
+
+    assert!(container.summary());
+
+    let notifications = container.notifications();
+    let _users = notifications.users.notifications.get();
+
+    assert_eq!(container.sessions().get().await.id, 7);
+}
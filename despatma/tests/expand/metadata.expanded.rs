@@ -0,0 +1,215 @@
+use despatma::container::{DependencyInfo, Lifetime};
+struct Config;
+struct Db;
+trait DataLayer {}
+struct Sqlite;
+impl DataLayer for Sqlite {}
+struct DependencyContainer<'a> {
+    db: std::rc::Rc<std::cell::OnceCell<Db>>,
+    config: std::sync::Arc<Config>,
+    _phantom: std::marker::PhantomData<&'a ()>,
+}
+#[automatically_derived]
+impl<'a> ::core::clone::Clone for DependencyContainer<'a> {
+    #[inline]
+    fn clone(&self) -> DependencyContainer<'a> {
+        DependencyContainer {
+            db: ::core::clone::Clone::clone(&self.db),
+            config: ::core::clone::Clone::clone(&self.config),
+            _phantom: ::core::clone::Clone::clone(&self._phantom),
+        }
+    }
+}
+impl<'a> DependencyContainer<'a> {
+    pub fn new(config: Config) -> Self {
+        Self {
+            db: Default::default(),
+            config: std::sync::Arc::new(config),
+            _phantom: Default::default(),
+        }
+    }
+    pub fn new_scope(&self) -> Self {
+        Self {
+            db: self.db.clone(),
+            config: self.config.clone(),
+            _phantom: Default::default(),
+        }
+    }
+    /// The names of the singleton and scoped dependencies which have been created, without creating any others
+    pub fn initialized(&self) -> Vec<&'static str> {
+        #[allow(unused_mut)]
+        let mut initialized = Vec::new();
+        if self.db.get().is_some() {
+            initialized.push("db");
+        }
+        initialized
+    }
+    /// The dependencies of this container and how they are wired together in the DOT format
+    pub fn dependency_graph_dot() -> &'static str {
+        "digraph DependencyContainer {\n    node [shape=box];\n    \"db\" [label=\"db\\nSingleton\"];\n    \"data_layer\" [label=\"data_layer\\nTransient, boxed\"];\n    \"config\" [label=\"config\\nEmbedded\"];\n    \"db\" -> \"config\";\n    \"data_layer\" -> \"db\";\n}\n"
+    }
+    /// The dependencies of this container and how they are wired together as a Mermaid flowchart
+    pub fn dependency_graph_mermaid() -> &'static str {
+        "graph TD\n    db[\"db<br/>Singleton\"]\n    data_layer[\"data_layer<br/>Transient, boxed\"]\n    config[\"config<br/>Embedded\"]\n    db --> config\n    data_layer --> db\n"
+    }
+    /// Describes every dependency of this container so that tooling can check how it is wired together
+    pub const METADATA: &'static [despatma::container::DependencyInfo] = &[
+        despatma::container::DependencyInfo {
+            name: "db",
+            ty: "Db",
+            field_ty: "Db",
+            lifetime: despatma::container::Lifetime::Singleton,
+            is_async: false,
+            is_boxed: false,
+            dependencies: &["config"],
+        },
+        despatma::container::DependencyInfo {
+            name: "data_layer",
+            ty: "Box<dyn DataLayer>",
+            field_ty: "std::boxed::Box<dyn DataLayer>",
+            lifetime: despatma::container::Lifetime::Transient,
+            is_async: false,
+            is_boxed: true,
+            dependencies: &["db"],
+        },
+        despatma::container::DependencyInfo {
+            name: "config",
+            ty: "Config",
+            field_ty: "Config",
+            lifetime: despatma::container::Lifetime::Embedded,
+            is_async: false,
+            is_boxed: false,
+            dependencies: &[],
+        },
+    ];
+    pub fn db(&'a self) -> &Db {
+        let config = self.config();
+        self.db
+            .get_or_init(|| {
+                let _ = config;
+                Db
+            })
+    }
+    pub fn data_layer(&'a self) -> std::boxed::Box<dyn DataLayer> {
+        let db = self.db();
+        let _ = db;
+        Box::new(Sqlite)
+    }
+    pub fn config(&'a self) -> &Config {
+        self.config.as_ref()
+    }
+}
+/// Builder to set the constructor arguments of [`DependencyContainer`] by name.
+#[must_use]
+struct DependencyContainerBuilder<'a, __Config> {
+    config: __Config,
+    _phantom: std::marker::PhantomData<&'a ()>,
+}
+impl<'a> DependencyContainer<'a> {
+    pub fn builder() -> DependencyContainerBuilder<'a, ()> {
+        DependencyContainerBuilder {
+            config: (),
+            _phantom: Default::default(),
+        }
+    }
+}
+impl<'a, __Config> DependencyContainerBuilder<'a, __Config> {
+    pub fn config(self, config: Config) -> DependencyContainerBuilder<'a, (Config,)> {
+        DependencyContainerBuilder {
+            config: (config,),
+            _phantom: Default::default(),
+        }
+    }
+    pub fn build(self) -> DependencyContainer<'a>
+    where
+        __Config: DependencyContainerBuilderHasConfig<'a>,
+    {
+        DependencyContainer::new(
+            DependencyContainerBuilderHasConfig::into_argument(self.config),
+        )
+    }
+}
+#[doc(hidden)]
+#[diagnostic::on_unimplemented(
+    message = "The `config` constructor argument of `DependencyContainer` has not been set",
+    label = "`config` is missing",
+    note = "Set it with `.config(...)` before calling `.build()`"
+)]
+trait DependencyContainerBuilderHasConfig<'a> {
+    fn into_argument(self) -> Config;
+}
+impl<'a> DependencyContainerBuilderHasConfig<'a> for (Config,) {
+    fn into_argument(self) -> Config {
+        self.0
+    }
+}
+impl<'a> core::fmt::Debug for DependencyContainer<'a> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        #[allow(unused_mut)]
+        let mut debug = f.debug_struct("DependencyContainer");
+        debug
+            .field(
+                "db",
+                &format_args!(
+                    "{0}", if self.db.get().is_some() { "initialized" } else {
+                    "uninitialized" }
+                ),
+            );
+        debug.finish_non_exhaustive()
+    }
+}
+fn main() {
+    match (
+        &DependencyContainer::METADATA,
+        &&[
+            DependencyInfo {
+                name: "db",
+                ty: "Db",
+                field_ty: "Db",
+                lifetime: Lifetime::Singleton,
+                is_async: false,
+                is_boxed: false,
+                dependencies: &["config"],
+            },
+            DependencyInfo {
+                name: "data_layer",
+                ty: "Box<dyn DataLayer>",
+                field_ty: "std::boxed::Box<dyn DataLayer>",
+                lifetime: Lifetime::Transient,
+                is_async: false,
+                is_boxed: true,
+                dependencies: &["db"],
+            },
+            DependencyInfo {
+                name: "config",
+                ty: "Config",
+                field_ty: "Config",
+                lifetime: Lifetime::Embedded,
+                is_async: false,
+                is_boxed: false,
+                dependencies: &[],
+            },
+        ],
+    ) {
+        (left_val, right_val) => {
+            if !(*left_val == *right_val) {
+                let kind = ::core::panicking::AssertKind::Eq;
+                ::core::panicking::assert_failed(
+                    kind,
+                    &*left_val,
+                    &*right_val,
+                    ::core::option::Option::None,
+                );
+            }
+        }
+    };
+    let data_layer = DependencyContainer::METADATA
+        .iter()
+        .find(|dependency| dependency.name == "data_layer")
+        .unwrap();
+    if !!data_layer.dependencies.contains(&"config") {
+        ::core::panicking::panic(
+            "assertion failed: !data_layer.dependencies.contains(&\"config\")",
+        )
+    }
+}
@@ -0,0 +1,70 @@
+use despatma::container::{DependencyInfo, Lifetime};
+
+struct Config;
+
+struct Db;
+
+trait DataLayer {}
+
+struct Sqlite;
+
+impl DataLayer for Sqlite {}
+
+#[despatma::dependency_container]
+impl DependencyContainer {
+    fn new(config: Config) {}
+
+    #[Singleton]
+    fn db(&self, config: &Config) -> Db {
+        let _ = config;
+        Db
+    }
+
+    fn data_layer(&self, db: &Db) -> Box<dyn DataLayer> {
+        let _ = db;
+        Box::new(Sqlite)
+    }
+}
+
+fn main() {
+    assert_eq!(
+        DependencyContainer::METADATA,
+        &[
+            DependencyInfo {
+                name: "db",
+                ty: "Db",
+                field_ty: "Db",
+                lifetime: Lifetime::Singleton,
+                is_async: false,
+                is_boxed: false,
+                dependencies: &["config"],
+            },
+            DependencyInfo {
+                name: "data_layer",
+                ty: "Box<dyn DataLayer>",
+                field_ty: "std::boxed::Box<dyn DataLayer>",
+                lifetime: Lifetime::Transient,
+                is_async: false,
+                is_boxed: true,
+                dependencies: &["db"],
+            },
+            DependencyInfo {
+                name: "config",
+                ty: "Config",
+                field_ty: "Config",
+                lifetime: Lifetime::Embedded,
+                is_async: false,
+                is_boxed: false,
+                dependencies: &[],
+            },
+        ]
+    );
+
+    // Check a layering rule like tooling would
+    let data_layer = DependencyContainer::METADATA
+        .iter()
+        .find(|dependency| dependency.name == "data_layer")
+        .unwrap();
+
+    assert!(!data_layer.dependencies.contains(&"config"));
+}
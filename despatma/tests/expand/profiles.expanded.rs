@@ -0,0 +1,481 @@
+use std::cell::Cell;
+trait Repository {
+    fn find(&self, id: u32) -> String;
+}
+struct InMemory;
+impl Repository for InMemory {
+    fn find(&self, id: u32) -> String {
+        ::alloc::__export::must_use({
+            ::alloc::fmt::format(format_args!("memory {0}", id))
+        })
+    }
+}
+struct Pool;
+struct Postgres;
+impl Postgres {
+    fn connect(_pool: &Pool) -> Self {
+        Postgres
+    }
+}
+impl Repository for Postgres {
+    fn find(&self, id: u32) -> String {
+        ::alloc::__export::must_use({
+            ::alloc::fmt::format(format_args!("postgres {0}", id))
+        })
+    }
+}
+trait Mailer {
+    fn send(&self) -> &'static str;
+}
+struct FakeMailer;
+impl Mailer for FakeMailer {
+    fn send(&self) -> &'static str {
+        "fake"
+    }
+}
+struct SmtpMailer;
+impl Mailer for SmtpMailer {
+    fn send(&self) -> &'static str {
+        "smtp"
+    }
+}
+struct DependencyContainer<'a> {
+    pool: std::rc::Rc<std::cell::OnceCell<Pool>>,
+    repository: std::rc::Rc<std::cell::OnceCell<std::boxed::Box<dyn Repository>>>,
+    profile: std::sync::Arc<DependencyContainerProfile>,
+    pools: std::sync::Arc<Cell<u32>>,
+    _phantom: std::marker::PhantomData<&'a ()>,
+}
+#[automatically_derived]
+impl<'a> ::core::clone::Clone for DependencyContainer<'a> {
+    #[inline]
+    fn clone(&self) -> DependencyContainer<'a> {
+        DependencyContainer {
+            pool: ::core::clone::Clone::clone(&self.pool),
+            repository: ::core::clone::Clone::clone(&self.repository),
+            profile: ::core::clone::Clone::clone(&self.profile),
+            pools: ::core::clone::Clone::clone(&self.pools),
+            _phantom: ::core::clone::Clone::clone(&self._phantom),
+        }
+    }
+}
+impl<'a> DependencyContainer<'a> {
+    pub fn new(profile: DependencyContainerProfile, pools: Cell<u32>) -> Self {
+        Self {
+            pool: Default::default(),
+            repository: Default::default(),
+            profile: std::sync::Arc::new(profile),
+            pools: std::sync::Arc::new(pools),
+            _phantom: Default::default(),
+        }
+    }
+    pub fn new_scope(&self) -> Self {
+        Self {
+            pool: self.pool.clone(),
+            repository: self.repository.clone(),
+            profile: self.profile.clone(),
+            pools: self.pools.clone(),
+            _phantom: Default::default(),
+        }
+    }
+    /// The names of the singleton and scoped dependencies which have been created, without creating any others
+    pub fn initialized(&self) -> Vec<&'static str> {
+        #[allow(unused_mut)]
+        let mut initialized = Vec::new();
+        if self.pool.get().is_some() {
+            initialized.push("pool");
+        }
+        if self.repository.get().is_some() {
+            initialized.push("repository");
+        }
+        initialized
+    }
+    /// The dependencies of this container and how they are wired together in the DOT format
+    pub fn dependency_graph_dot() -> &'static str {
+        "digraph DependencyContainer {\n    node [shape=box];\n    \"pool\" [label=\"pool\\nSingleton\"];\n    \"repository_dev\" [label=\"repository_dev\\nTransient, boxed\"];\n    \"repository_prod\" [label=\"repository_prod\\nTransient, boxed\"];\n    \"mailer_dev\" [label=\"mailer_dev\\nTransient, boxed\"];\n    \"mailer_prod\" [label=\"mailer_prod\\nTransient, boxed\"];\n    \"report\" [label=\"report\\nTransient\"];\n    \"repository\" [label=\"repository\\nSingleton, boxed\"];\n    \"mailer\" [label=\"mailer\\nTransient, boxed\"];\n    \"profile\" [label=\"profile\\nEmbedded\"];\n    \"pools\" [label=\"pools\\nEmbedded\"];\n    \"pool\" -> \"pools\";\n    \"repository_prod\" -> \"pool\";\n    \"report\" -> \"repository\";\n    \"report\" -> \"mailer\";\n    \"repository\" -> \"profile\";\n    \"repository\" -> \"repository_dev\" [style=dashed];\n    \"repository\" -> \"repository_prod\" [style=dashed];\n    \"mailer\" -> \"profile\";\n    \"mailer\" -> \"mailer_dev\" [style=dashed];\n    \"mailer\" -> \"mailer_prod\" [style=dashed];\n}\n"
+    }
+    /// The dependencies of this container and how they are wired together as a Mermaid flowchart
+    pub fn dependency_graph_mermaid() -> &'static str {
+        "graph TD\n    pool[\"pool<br/>Singleton\"]\n    repository_dev[\"repository_dev<br/>Transient, boxed\"]\n    repository_prod[\"repository_prod<br/>Transient, boxed\"]\n    mailer_dev[\"mailer_dev<br/>Transient, boxed\"]\n    mailer_prod[\"mailer_prod<br/>Transient, boxed\"]\n    report[\"report<br/>Transient\"]\n    repository[\"repository<br/>Singleton, boxed\"]\n    mailer[\"mailer<br/>Transient, boxed\"]\n    profile[\"profile<br/>Embedded\"]\n    pools[\"pools<br/>Embedded\"]\n    pool --> pools\n    repository_prod --> pool\n    report --> repository\n    report --> mailer\n    repository --> profile\n    repository -.-> repository_dev\n    repository -.-> repository_prod\n    mailer --> profile\n    mailer -.-> mailer_dev\n    mailer -.-> mailer_prod\n"
+    }
+    /// Describes every dependency of this container so that tooling can check how it is wired together
+    pub const METADATA: &'static [despatma::container::DependencyInfo] = &[
+        despatma::container::DependencyInfo {
+            name: "pool",
+            ty: "Pool",
+            field_ty: "Pool",
+            lifetime: despatma::container::Lifetime::Singleton,
+            is_async: false,
+            is_boxed: false,
+            dependencies: &["pools"],
+        },
+        despatma::container::DependencyInfo {
+            name: "repository_dev",
+            ty: "Box<dyn Repository>",
+            field_ty: "std::boxed::Box<dyn Repository>",
+            lifetime: despatma::container::Lifetime::Transient,
+            is_async: false,
+            is_boxed: true,
+            dependencies: &[],
+        },
+        despatma::container::DependencyInfo {
+            name: "repository_prod",
+            ty: "Box<dyn Repository>",
+            field_ty: "std::boxed::Box<dyn Repository>",
+            lifetime: despatma::container::Lifetime::Transient,
+            is_async: false,
+            is_boxed: true,
+            dependencies: &["pool"],
+        },
+        despatma::container::DependencyInfo {
+            name: "mailer_dev",
+            ty: "Box<dyn Mailer>",
+            field_ty: "std::boxed::Box<dyn Mailer>",
+            lifetime: despatma::container::Lifetime::Transient,
+            is_async: false,
+            is_boxed: true,
+            dependencies: &[],
+        },
+        despatma::container::DependencyInfo {
+            name: "mailer_prod",
+            ty: "Box<dyn Mailer>",
+            field_ty: "std::boxed::Box<dyn Mailer>",
+            lifetime: despatma::container::Lifetime::Transient,
+            is_async: false,
+            is_boxed: true,
+            dependencies: &[],
+        },
+        despatma::container::DependencyInfo {
+            name: "report",
+            ty: "String",
+            field_ty: "String",
+            lifetime: despatma::container::Lifetime::Transient,
+            is_async: false,
+            is_boxed: false,
+            dependencies: &["repository", "mailer"],
+        },
+        despatma::container::DependencyInfo {
+            name: "repository",
+            ty: "Box<dyn Repository>",
+            field_ty: "std::boxed::Box<dyn Repository>",
+            lifetime: despatma::container::Lifetime::Singleton,
+            is_async: false,
+            is_boxed: true,
+            dependencies: &["profile"],
+        },
+        despatma::container::DependencyInfo {
+            name: "mailer",
+            ty: "Box<dyn Mailer>",
+            field_ty: "std::boxed::Box<dyn Mailer>",
+            lifetime: despatma::container::Lifetime::Transient,
+            is_async: false,
+            is_boxed: true,
+            dependencies: &["profile"],
+        },
+        despatma::container::DependencyInfo {
+            name: "profile",
+            ty: "DependencyContainerProfile",
+            field_ty: "DependencyContainerProfile",
+            lifetime: despatma::container::Lifetime::Embedded,
+            is_async: false,
+            is_boxed: false,
+            dependencies: &[],
+        },
+        despatma::container::DependencyInfo {
+            name: "pools",
+            ty: "Cell<u32>",
+            field_ty: "Cell<u32>",
+            lifetime: despatma::container::Lifetime::Embedded,
+            is_async: false,
+            is_boxed: false,
+            dependencies: &[],
+        },
+    ];
+    pub fn pool(&'a self) -> &Pool {
+        let pools = self.pools();
+        self.pool
+            .get_or_init(|| {
+                pools.set(pools.get() + 1);
+                Pool
+            })
+    }
+    pub fn repository_dev(&'a self) -> std::boxed::Box<dyn Repository> {
+        Box::new(InMemory)
+    }
+    pub fn repository_prod(&'a self) -> std::boxed::Box<dyn Repository> {
+        let pool = self.pool();
+        Box::new(Postgres::connect(pool))
+    }
+    pub fn mailer_dev(&'a self) -> std::boxed::Box<dyn Mailer> {
+        Box::new(FakeMailer)
+    }
+    pub fn mailer_prod(&'a self) -> std::boxed::Box<dyn Mailer> {
+        Box::new(SmtpMailer)
+    }
+    pub fn report(&'a self) -> String {
+        let repository = self.repository();
+        let mailer = self.mailer();
+        ::alloc::__export::must_use({
+            ::alloc::fmt::format(
+                format_args!("{0} by {1}", repository.find(1), mailer.send()),
+            )
+        })
+    }
+    /// Creates the `repository` dependency with the provider of the active profile
+    pub fn repository(&'a self) -> &std::boxed::Box<dyn Repository> {
+        let profile = self.profile();
+        let repository_dev = despatma::container::Provider::<
+            _,
+            dyn Fn() -> _ + 'a,
+        >::new(Box::new(move || self.repository_dev()));
+        let repository_prod = despatma::container::Provider::<
+            _,
+            dyn Fn() -> _ + 'a,
+        >::new(Box::new(move || self.repository_prod()));
+        self.repository
+            .get_or_init(|| {
+                match profile {
+                    DependencyContainerProfile::Dev => repository_dev.get(),
+                    DependencyContainerProfile::Prod => repository_prod.get(),
+                }
+            })
+    }
+    /// Creates the `mailer` dependency with the provider of the active profile
+    pub fn mailer(&'a self) -> std::boxed::Box<dyn Mailer> {
+        let profile = self.profile();
+        let mailer_dev = despatma::container::Provider::<
+            _,
+            dyn Fn() -> _ + 'a,
+        >::new(Box::new(move || self.mailer_dev()));
+        let mailer_prod = despatma::container::Provider::<
+            _,
+            dyn Fn() -> _ + 'a,
+        >::new(Box::new(move || self.mailer_prod()));
+        match profile {
+            DependencyContainerProfile::Dev => mailer_dev.get(),
+            DependencyContainerProfile::Prod => mailer_prod.get(),
+        }
+    }
+    pub fn profile(&'a self) -> &DependencyContainerProfile {
+        self.profile.as_ref()
+    }
+    pub fn pools(&'a self) -> &Cell<u32> {
+        self.pools.as_ref()
+    }
+}
+/// Builder to set the constructor arguments of [`DependencyContainer`] by name.
+#[must_use]
+struct DependencyContainerBuilder<'a, __Profile, __Pools> {
+    profile: __Profile,
+    pools: __Pools,
+    _phantom: std::marker::PhantomData<&'a ()>,
+}
+impl<'a> DependencyContainer<'a> {
+    pub fn builder() -> DependencyContainerBuilder<'a, (), ()> {
+        DependencyContainerBuilder {
+            profile: (),
+            pools: (),
+            _phantom: Default::default(),
+        }
+    }
+}
+impl<'a, __Profile, __Pools> DependencyContainerBuilder<'a, __Profile, __Pools> {
+    pub fn profile(
+        self,
+        profile: DependencyContainerProfile,
+    ) -> DependencyContainerBuilder<'a, (DependencyContainerProfile,), __Pools> {
+        DependencyContainerBuilder {
+            profile: (profile,),
+            pools: self.pools,
+            _phantom: Default::default(),
+        }
+    }
+    pub fn pools(
+        self,
+        pools: Cell<u32>,
+    ) -> DependencyContainerBuilder<'a, __Profile, (Cell<u32>,)> {
+        DependencyContainerBuilder {
+            profile: self.profile,
+            pools: (pools,),
+            _phantom: Default::default(),
+        }
+    }
+    pub fn build(self) -> DependencyContainer<'a>
+    where
+        __Profile: DependencyContainerBuilderHasProfile<'a>,
+        __Pools: DependencyContainerBuilderHasPools<'a>,
+    {
+        DependencyContainer::new(
+            DependencyContainerBuilderHasProfile::into_argument(self.profile),
+            DependencyContainerBuilderHasPools::into_argument(self.pools),
+        )
+    }
+}
+#[doc(hidden)]
+#[diagnostic::on_unimplemented(
+    message = "The `profile` constructor argument of `DependencyContainer` has not been set",
+    label = "`profile` is missing",
+    note = "Set it with `.profile(...)` before calling `.build()`"
+)]
+trait DependencyContainerBuilderHasProfile<'a> {
+    fn into_argument(self) -> DependencyContainerProfile;
+}
+impl<'a> DependencyContainerBuilderHasProfile<'a> for (DependencyContainerProfile,) {
+    fn into_argument(self) -> DependencyContainerProfile {
+        self.0
+    }
+}
+#[doc(hidden)]
+#[diagnostic::on_unimplemented(
+    message = "The `pools` constructor argument of `DependencyContainer` has not been set",
+    label = "`pools` is missing",
+    note = "Set it with `.pools(...)` before calling `.build()`"
+)]
+trait DependencyContainerBuilderHasPools<'a> {
+    fn into_argument(self) -> Cell<u32>;
+}
+impl<'a> DependencyContainerBuilderHasPools<'a> for (Cell<u32>,) {
+    fn into_argument(self) -> Cell<u32> {
+        self.0
+    }
+}
+/// The runtime profiles of [`DependencyContainer`]. The active profile picks the providers of its dependencies.
+enum DependencyContainerProfile {
+    Dev,
+    Prod,
+}
+#[automatically_derived]
+#[doc(hidden)]
+unsafe impl ::core::clone::TrivialClone for DependencyContainerProfile {}
+#[automatically_derived]
+impl ::core::clone::Clone for DependencyContainerProfile {
+    #[inline]
+    fn clone(&self) -> DependencyContainerProfile {
+        *self
+    }
+}
+#[automatically_derived]
+impl ::core::marker::Copy for DependencyContainerProfile {}
+#[automatically_derived]
+impl ::core::fmt::Debug for DependencyContainerProfile {
+    #[inline]
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        ::core::fmt::Formatter::write_str(
+            f,
+            match self {
+                DependencyContainerProfile::Dev => "Dev",
+                DependencyContainerProfile::Prod => "Prod",
+            },
+        )
+    }
+}
+#[automatically_derived]
+impl ::core::marker::StructuralPartialEq for DependencyContainerProfile {}
+#[automatically_derived]
+impl ::core::cmp::PartialEq for DependencyContainerProfile {
+    #[inline]
+    fn eq(&self, other: &DependencyContainerProfile) -> bool {
+        let __self_discr = ::core::intrinsics::discriminant_value(self);
+        let __arg1_discr = ::core::intrinsics::discriminant_value(other);
+        __self_discr == __arg1_discr
+    }
+}
+#[automatically_derived]
+impl ::core::cmp::Eq for DependencyContainerProfile {
+    #[inline]
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn assert_fields_are_eq(&self) {}
+}
+impl<'a> core::fmt::Debug for DependencyContainer<'a> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        #[allow(unused_mut)]
+        let mut debug = f.debug_struct("DependencyContainer");
+        debug
+            .field(
+                "pool",
+                &format_args!(
+                    "{0}", if self.pool.get().is_some() { "initialized" } else {
+                    "uninitialized" }
+                ),
+            );
+        debug
+            .field(
+                "repository",
+                &format_args!(
+                    "{0}", if self.repository.get().is_some() { "initialized" } else {
+                    "uninitialized" }
+                ),
+            );
+        debug.finish_non_exhaustive()
+    }
+}
+fn main() {
+    let dev = DependencyContainer::new(DependencyContainerProfile::Dev, Cell::new(0));
+    match (&dev.report(), &"memory 1 by fake") {
+        (left_val, right_val) => {
+            if !(*left_val == *right_val) {
+                let kind = ::core::panicking::AssertKind::Eq;
+                ::core::panicking::assert_failed(
+                    kind,
+                    &*left_val,
+                    &*right_val,
+                    ::core::option::Option::None,
+                );
+            }
+        }
+    };
+    match (&dev.pools().get(), &0) {
+        (left_val, right_val) => {
+            if !(*left_val == *right_val) {
+                let kind = ::core::panicking::AssertKind::Eq;
+                ::core::panicking::assert_failed(
+                    kind,
+                    &*left_val,
+                    &*right_val,
+                    ::core::option::Option::None,
+                );
+            }
+        }
+    };
+    let prod = DependencyContainer::new(DependencyContainerProfile::Prod, Cell::new(0));
+    match (&prod.report(), &"postgres 1 by smtp") {
+        (left_val, right_val) => {
+            if !(*left_val == *right_val) {
+                let kind = ::core::panicking::AssertKind::Eq;
+                ::core::panicking::assert_failed(
+                    kind,
+                    &*left_val,
+                    &*right_val,
+                    ::core::option::Option::None,
+                );
+            }
+        }
+    };
+    match (&prod.report(), &"postgres 1 by smtp") {
+        (left_val, right_val) => {
+            if !(*left_val == *right_val) {
+                let kind = ::core::panicking::AssertKind::Eq;
+                ::core::panicking::assert_failed(
+                    kind,
+                    &*left_val,
+                    &*right_val,
+                    ::core::option::Option::None,
+                );
+            }
+        }
+    };
+    match (&prod.pools().get(), &1) {
+        (left_val, right_val) => {
+            if !(*left_val == *right_val) {
+                let kind = ::core::panicking::AssertKind::Eq;
+                ::core::panicking::assert_failed(
+                    kind,
+                    &*left_val,
+                    &*right_val,
+                    ::core::option::Option::None,
+                );
+            }
+        }
+    };
+}
@@ -0,0 +1,99 @@
+use std::cell::Cell;
+
+trait Repository {
+    fn find(&self, id: u32) -> String;
+}
+
+struct InMemory;
+
+impl Repository for InMemory {
+    fn find(&self, id: u32) -> String {
+        format!("memory {id}")
+    }
+}
+
+struct Pool;
+
+struct Postgres;
+
+impl Postgres {
+    fn connect(_pool: &Pool) -> Self {
+        Postgres
+    }
+}
+
+impl Repository for Postgres {
+    fn find(&self, id: u32) -> String {
+        format!("postgres {id}")
+    }
+}
+
+trait Mailer {
+    fn send(&self) -> &'static str;
+}
+
+struct FakeMailer;
+
+impl Mailer for FakeMailer {
+    fn send(&self) -> &'static str {
+        "fake"
+    }
+}
+
+struct SmtpMailer;
+
+impl Mailer for SmtpMailer {
+    fn send(&self) -> &'static str {
+        "smtp"
+    }
+}
+
+#[despatma::dependency_container]
+impl DependencyContainer {
+    fn new(profile: DependencyContainerProfile, pools: Cell<u32>) {}
+
+    #[Singleton]
+    fn pool(&self, pools: &Cell<u32>) -> Pool {
+        pools.set(pools.get() + 1);
+        Pool
+    }
+
+    #[Profile(dev)]
+    #[Singleton]
+    fn repository(&self) -> Box<dyn Repository> {
+        Box::new(InMemory)
+    }
+
+    #[Profile(prod)]
+    #[Singleton]
+    fn repository(&self, pool: &Pool) -> Box<dyn Repository> {
+        Box::new(Postgres::connect(pool))
+    }
+
+    #[Profile(dev)]
+    fn mailer(&self) -> Box<dyn Mailer> {
+        Box::new(FakeMailer)
+    }
+
+    #[Profile(prod)]
+    fn mailer(&self) -> Box<dyn Mailer> {
+        Box::new(SmtpMailer)
+    }
+
+    fn report(&self, repository: &Box<dyn Repository>, mailer: Box<dyn Mailer>) -> String {
+        format!("{} by {}", repository.find(1), mailer.send())
+    }
+}
+
+fn main() {
+    let dev = DependencyContainer::new(DependencyContainerProfile::Dev, Cell::new(0));
+
+    assert_eq!(dev.report(), "memory 1 by fake");
+    assert_eq!(dev.pools().get(), 0);
+
+    let prod = DependencyContainer::new(DependencyContainerProfile::Prod, Cell::new(0));
+
+    assert_eq!(prod.report(), "postgres 1 by smtp");
+    assert_eq!(prod.report(), "postgres 1 by smtp");
+    assert_eq!(prod.pools().get(), 1);
+}
@@ -0,0 +1,298 @@
+use despatma::container::{Provides, ProvidesAsync};
+struct Config {
+    greeting: &'static str,
+}
+struct UserService {
+    name: String,
+}
+struct DependencyContainer<'a> {
+    config: std::rc::Rc<std::cell::OnceCell<Config>>,
+    _phantom: std::marker::PhantomData<&'a ()>,
+}
+#[automatically_derived]
+impl<'a> ::core::clone::Clone for DependencyContainer<'a> {
+    #[inline]
+    fn clone(&self) -> DependencyContainer<'a> {
+        DependencyContainer {
+            config: ::core::clone::Clone::clone(&self.config),
+            _phantom: ::core::clone::Clone::clone(&self._phantom),
+        }
+    }
+}
+impl<'a> DependencyContainer<'a> {
+    pub fn new() -> Self {
+        Self {
+            config: Default::default(),
+            _phantom: Default::default(),
+        }
+    }
+    pub fn new_scope(&self) -> Self {
+        Self {
+            config: self.config.clone(),
+            _phantom: Default::default(),
+        }
+    }
+    /// The names of the singleton and scoped dependencies which have been created, without creating any others
+    pub fn initialized(&self) -> Vec<&'static str> {
+        #[allow(unused_mut)]
+        let mut initialized = Vec::new();
+        if self.config.get().is_some() {
+            initialized.push("config");
+        }
+        initialized
+    }
+    /// The dependencies of this container and how they are wired together in the DOT format
+    pub fn dependency_graph_dot() -> &'static str {
+        "digraph DependencyContainer {\n    node [shape=box];\n    \"config\" [label=\"config\\nSingleton\"];\n    \"user_service\" [label=\"user_service\\nTransient\"];\n    \"session_id\" [label=\"session_id\\nTransient, async\"];\n}\n"
+    }
+    /// The dependencies of this container and how they are wired together as a Mermaid flowchart
+    pub fn dependency_graph_mermaid() -> &'static str {
+        "graph TD\n    config[\"config<br/>Singleton\"]\n    user_service[\"user_service<br/>Transient\"]\n    session_id[\"session_id<br/>Transient, async\"]\n"
+    }
+    /// Describes every dependency of this container so that tooling can check how it is wired together
+    pub const METADATA: &'static [despatma::container::DependencyInfo] = &[
+        despatma::container::DependencyInfo {
+            name: "config",
+            ty: "Config",
+            field_ty: "Config",
+            lifetime: despatma::container::Lifetime::Singleton,
+            is_async: false,
+            is_boxed: false,
+            dependencies: &[],
+        },
+        despatma::container::DependencyInfo {
+            name: "user_service",
+            ty: "UserService",
+            field_ty: "UserService",
+            lifetime: despatma::container::Lifetime::Transient,
+            is_async: false,
+            is_boxed: false,
+            dependencies: &[],
+        },
+        despatma::container::DependencyInfo {
+            name: "session_id",
+            ty: "u32",
+            field_ty: "u32",
+            lifetime: despatma::container::Lifetime::Transient,
+            is_async: true,
+            is_boxed: false,
+            dependencies: &[],
+        },
+    ];
+    pub fn config(&'a self) -> &Config {
+        self.config.get_or_init(|| { Config { greeting: "Hello" } })
+    }
+    pub fn user_service(&'a self) -> UserService {
+        UserService {
+            name: "Alice".to_string(),
+        }
+    }
+    pub async fn session_id(&'a self) -> u32 {
+        42
+    }
+}
+impl<'a> core::fmt::Debug for DependencyContainer<'a> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        #[allow(unused_mut)]
+        let mut debug = f.debug_struct("DependencyContainer");
+        debug
+            .field(
+                "config",
+                &format_args!(
+                    "{0}", if self.config.get().is_some() { "initialized" } else {
+                    "uninitialized" }
+                ),
+            );
+        debug.finish_non_exhaustive()
+    }
+}
+impl<'a> despatma::container::Provides<'a, &'a Config> for DependencyContainer<'a> {
+    fn provide(&'a self) -> &'a Config {
+        self.config()
+    }
+}
+impl<'a> despatma::container::Provides<'a, UserService> for DependencyContainer<'a> {
+    fn provide(&'a self) -> UserService {
+        self.user_service()
+    }
+}
+impl<'a> despatma::container::ProvidesAsync<'a, u32> for DependencyContainer<'a> {
+    fn provide(&'a self) -> impl std::future::Future<Output = u32> {
+        self.session_id()
+    }
+}
+struct TestContainer<'a> {
+    config: std::rc::Rc<std::cell::OnceCell<Config>>,
+    _phantom: std::marker::PhantomData<&'a ()>,
+}
+#[automatically_derived]
+impl<'a> ::core::clone::Clone for TestContainer<'a> {
+    #[inline]
+    fn clone(&self) -> TestContainer<'a> {
+        TestContainer {
+            config: ::core::clone::Clone::clone(&self.config),
+            _phantom: ::core::clone::Clone::clone(&self._phantom),
+        }
+    }
+}
+impl<'a> TestContainer<'a> {
+    pub fn new() -> Self {
+        Self {
+            config: Default::default(),
+            _phantom: Default::default(),
+        }
+    }
+    pub fn new_scope(&self) -> Self {
+        Self {
+            config: self.config.clone(),
+            _phantom: Default::default(),
+        }
+    }
+    /// The names of the singleton and scoped dependencies which have been created, without creating any others
+    pub fn initialized(&self) -> Vec<&'static str> {
+        #[allow(unused_mut)]
+        let mut initialized = Vec::new();
+        if self.config.get().is_some() {
+            initialized.push("config");
+        }
+        initialized
+    }
+    /// The dependencies of this container and how they are wired together in the DOT format
+    pub fn dependency_graph_dot() -> &'static str {
+        "digraph TestContainer {\n    node [shape=box];\n    \"config\" [label=\"config\\nSingleton\"];\n    \"user_service\" [label=\"user_service\\nTransient\"];\n}\n"
+    }
+    /// The dependencies of this container and how they are wired together as a Mermaid flowchart
+    pub fn dependency_graph_mermaid() -> &'static str {
+        "graph TD\n    config[\"config<br/>Singleton\"]\n    user_service[\"user_service<br/>Transient\"]\n"
+    }
+    /// Describes every dependency of this container so that tooling can check how it is wired together
+    pub const METADATA: &'static [despatma::container::DependencyInfo] = &[
+        despatma::container::DependencyInfo {
+            name: "config",
+            ty: "Config",
+            field_ty: "Config",
+            lifetime: despatma::container::Lifetime::Singleton,
+            is_async: false,
+            is_boxed: false,
+            dependencies: &[],
+        },
+        despatma::container::DependencyInfo {
+            name: "user_service",
+            ty: "UserService",
+            field_ty: "UserService",
+            lifetime: despatma::container::Lifetime::Transient,
+            is_async: false,
+            is_boxed: false,
+            dependencies: &[],
+        },
+    ];
+    pub fn config(&'a self) -> &Config {
+        self.config.get_or_init(|| { Config { greeting: "Hi" } })
+    }
+    pub fn user_service(&'a self) -> UserService {
+        UserService {
+            name: "Test".to_string(),
+        }
+    }
+}
+impl<'a> core::fmt::Debug for TestContainer<'a> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        #[allow(unused_mut)]
+        let mut debug = f.debug_struct("TestContainer");
+        debug
+            .field(
+                "config",
+                &format_args!(
+                    "{0}", if self.config.get().is_some() { "initialized" } else {
+                    "uninitialized" }
+                ),
+            );
+        debug.finish_non_exhaustive()
+    }
+}
+impl<'a> despatma::container::Provides<'a, &'a Config> for TestContainer<'a> {
+    fn provide(&'a self) -> &'a Config {
+        self.config()
+    }
+}
+impl<'a> despatma::container::Provides<'a, UserService> for TestContainer<'a> {
+    fn provide(&'a self) -> UserService {
+        self.user_service()
+    }
+}
+fn greet<'a, C>(container: &'a C) -> String
+where
+    C: Provides<'a, &'a Config> + Provides<'a, UserService>,
+{
+    let config: &Config = container.provide();
+    let user_service: UserService = container.provide();
+    ::alloc::__export::must_use({
+        ::alloc::fmt::format(format_args!("{0} {1}", config.greeting, user_service.name))
+    })
+}
+async fn session<'a>(container: &'a impl ProvidesAsync<'a, u32>) -> u32 {
+    container.provide().await
+}
+fn main() {
+    let body = async {
+        match (&greet(&DependencyContainer::new()), &"Hello Alice") {
+            (left_val, right_val) => {
+                if !(*left_val == *right_val) {
+                    let kind = ::core::panicking::AssertKind::Eq;
+                    ::core::panicking::assert_failed(
+                        kind,
+                        &*left_val,
+                        &*right_val,
+                        ::core::option::Option::None,
+                    );
+                }
+            }
+        };
+        match (&greet(&TestContainer::new()), &"Hi Test") {
+            (left_val, right_val) => {
+                if !(*left_val == *right_val) {
+                    let kind = ::core::panicking::AssertKind::Eq;
+                    ::core::panicking::assert_failed(
+                        kind,
+                        &*left_val,
+                        &*right_val,
+                        ::core::option::Option::None,
+                    );
+                }
+            }
+        };
+        match (&session(&DependencyContainer::new()).await, &42) {
+            (left_val, right_val) => {
+                if !(*left_val == *right_val) {
+                    let kind = ::core::panicking::AssertKind::Eq;
+                    ::core::panicking::assert_failed(
+                        kind,
+                        &*left_val,
+                        &*right_val,
+                        ::core::option::Option::None,
+                    );
+                }
+            }
+        };
+    };
+    let body = {
+        if false {
+            let _: &dyn ::core::future::Future<Output = ()> = &body;
+        }
+        body
+    };
+    #[allow(
+        clippy::expect_used,
+        clippy::diverging_sub_expression,
+        clippy::needless_return,
+        clippy::unwrap_in_result
+    )]
+    {
+        use tokio::runtime::Builder;
+        return Builder::new_multi_thread()
+            .enable_all()
+            .build()
+            .expect("Failed building the Runtime")
+            .block_on(body);
+    }
+}
@@ -0,0 +1,62 @@
+use despatma::container::{Provides, ProvidesAsync};
+
+struct Config {
+    greeting: &'static str,
+}
+
+struct UserService {
+    name: String,
+}
+
+#[despatma::dependency_container(provides)]
+impl DependencyContainer {
+    #[Singleton]
+    fn config(&self) -> Config {
+        Config { greeting: "Hello" }
+    }
+
+    fn user_service(&self) -> UserService {
+        UserService {
+            name: "Alice".to_string(),
+        }
+    }
+
+    async fn session_id(&self) -> u32 {
+        42
+    }
+}
+
+#[despatma::dependency_container(provides)]
+impl TestContainer {
+    #[Singleton]
+    fn config(&self) -> Config {
+        Config { greeting: "Hi" }
+    }
+
+    fn user_service(&self) -> UserService {
+        UserService {
+            name: "Test".to_string(),
+        }
+    }
+}
+
+fn greet<'a, C>(container: &'a C) -> String
+where
+    C: Provides<'a, &'a Config> + Provides<'a, UserService>,
+{
+    let config: &Config = container.provide();
+    let user_service: UserService = container.provide();
+
+    format!("{} {}", config.greeting, user_service.name)
+}
+
+async fn session<'a>(container: &'a impl ProvidesAsync<'a, u32>) -> u32 {
+    container.provide().await
+}
+
+#[tokio::main]
+async fn main() {
+    assert_eq!(greet(&DependencyContainer::new()), "Hello Alice");
+    assert_eq!(greet(&TestContainer::new()), "Hi Test");
+    assert_eq!(session(&DependencyContainer::new()).await, 42);
+}
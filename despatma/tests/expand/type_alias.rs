@@ -0,0 +1,19 @@
+type Url = String;
+
+#[despatma::dependency_container]
+impl DependencyContainer {
+    fn name(&self) -> String {
+        "despatma".to_string()
+    }
+
+    fn url(&self) -> Url {
+        "https://github.com/chesedo/despatma".to_string()
+    }
+}
+
+fn main() {
+    let container = DependencyContainer::new();
+
+    assert_eq!(container.name(), "despatma");
+    assert_eq!(container.url(), "https://github.com/chesedo/despatma");
+}
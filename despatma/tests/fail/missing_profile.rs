@@ -0,0 +1,31 @@
+trait Repository {}
+
+struct InMemory;
+
+impl Repository for InMemory {}
+
+trait Mailer {}
+
+struct Smtp;
+
+impl Mailer for Smtp {}
+
+#[despatma::dependency_container]
+impl DependencyContainer {
+    fn new(profile: DependencyContainerProfile) {}
+
+    #[Profile(dev)]
+    fn repository(&self) -> Box<dyn Repository> {
+        Box::new(InMemory)
+    }
+
+    #[Profile(prod)]
+    fn mailer(&self) -> Box<dyn Mailer> {
+        Box::new(Smtp)
+    }
+}
+
+fn main() {
+    let container = DependencyContainer::new(DependencyContainerProfile::Dev);
+    let _repository = container.repository();
+}
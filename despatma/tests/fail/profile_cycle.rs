@@ -0,0 +1,48 @@
+trait Repository {}
+
+struct InMemory;
+
+impl Repository for InMemory {}
+
+struct Postgres;
+
+impl Postgres {
+    fn new(_service: Service) -> Self {
+        Self
+    }
+}
+
+impl Repository for Postgres {}
+
+struct Service;
+
+impl Service {
+    fn new(_repository: Box<dyn Repository>) -> Self {
+        Self
+    }
+}
+
+#[despatma::dependency_container]
+impl DependencyContainer {
+    fn new(profile: DependencyContainerProfile) {}
+
+    #[Profile(dev)]
+    fn repository(&self) -> Box<dyn Repository> {
+        Box::new(InMemory)
+    }
+
+    #[Profile(prod)]
+    fn repository(&self, service: Service) -> Box<dyn Repository> {
+        let _ = service;
+        Box::new(Postgres)
+    }
+
+    fn service(&self, repository: Box<dyn Repository>) -> Service {
+        Service::new(repository)
+    }
+}
+
+fn main() {
+    let container = DependencyContainer::new(DependencyContainerProfile::Prod);
+    let _service = container.service();
+}
@@ -0,0 +1,46 @@
+use std::fs;
+
+// Expands the containers using the types of `despatma::container`, which need the macro to be called through `despatma`
+#[test]
+pub fn expand() {
+    let t = trybuild::TestCases::new();
+
+    // Use glob to get all .rs files that don't end with .expanded.rs
+    // These files are used by macrotest
+    let pattern = "tests/expand";
+    for path in fs::read_dir(pattern)
+        .unwrap()
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|name| {
+            !name
+                .file_name()
+                .unwrap()
+                .to_str()
+                .unwrap()
+                .ends_with(".expanded.rs")
+        })
+    {
+        t.pass(path);
+    }
+
+    // Get any errors before we run the macrotest
+    // Else macrotest might fail and we won't know why
+    drop(t);
+
+    macrotest::expand("tests/expand/*.rs");
+}
+
+#[test]
+pub fn fail() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/fail/*.rs");
+}
+
+// Compiles the containers with spans around every resolver when the `tracing` feature is enabled
+#[cfg(feature = "tracing")]
+#[test]
+pub fn tracing() {
+    let t = trybuild::TestCases::new();
+    t.pass("tests/tracing/*.rs");
+}
@@ -0,0 +1,127 @@
+use std::sync::{Arc, Mutex};
+
+use tracing::{
+    span::{Attributes, Id, Record},
+    Subscriber,
+};
+use tracing_subscriber::{
+    layer::{Context, SubscriberExt},
+    registry::LookupSpan,
+    Layer,
+};
+
+struct Config;
+
+struct Service;
+
+impl Service {
+    fn new(_config: &Config) -> Self {
+        Self
+    }
+}
+
+#[despatma::dependency_container]
+impl DependencyContainer {
+    #[Singleton]
+    fn config(&self) -> Config {
+        Config
+    }
+
+    async fn service(&self, config: &Config) -> Service {
+        Service::new(config)
+    }
+}
+
+/// A `resolve` span along with the values recorded on it
+#[derive(Debug, PartialEq)]
+struct Resolve {
+    dependency: String,
+    cached: bool,
+    has_duration: bool,
+}
+
+impl tracing::field::Visit for Resolve {
+    fn record_str(&mut self, field: &tracing::field::Field, value: &str) {
+        if field.name() == "dependency" {
+            self.dependency = value.to_string();
+        }
+    }
+
+    fn record_bool(&mut self, field: &tracing::field::Field, value: bool) {
+        if field.name() == "cached" {
+            self.cached = value;
+        }
+    }
+
+    fn record_debug(&mut self, field: &tracing::field::Field, _value: &dyn std::fmt::Debug) {
+        if field.name() == "duration" {
+            self.has_duration = true;
+        }
+    }
+}
+
+/// Collects the resolve spans and the values recorded on them
+struct Resolves(Arc<Mutex<Vec<Resolve>>>);
+
+impl<S> Layer<S> for Resolves
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        if attrs.metadata().name() != "resolve" {
+            return;
+        }
+
+        let mut resolves = self.0.lock().unwrap();
+        let mut resolve = Resolve {
+            dependency: String::new(),
+            cached: false,
+            has_duration: false,
+        };
+
+        attrs.record(&mut resolve);
+        ctx.span(id)
+            .unwrap()
+            .extensions_mut()
+            .insert(resolves.len());
+        resolves.push(resolve);
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        let span = ctx.span(id).unwrap();
+        let extensions = span.extensions();
+
+        if let Some(index) = extensions.get::<usize>() {
+            values.record(&mut self.0.lock().unwrap()[*index]);
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    let resolves = Arc::new(Mutex::new(Vec::new()));
+    let _guard = tracing::subscriber::set_default(
+        tracing_subscriber::registry().with(Resolves(resolves.clone())),
+    );
+
+    let container = DependencyContainer::new();
+    let _service = container.service().await;
+    let _service = container.service().await;
+
+    let resolve = |dependency: &str, cached: bool, has_duration: bool| Resolve {
+        dependency: dependency.to_string(),
+        cached,
+        has_duration,
+    };
+
+    // Only the first resolve of the singleton creates it. So the second one is cached and does not record a duration
+    assert_eq!(
+        *resolves.lock().unwrap(),
+        vec![
+            resolve("service", false, true),
+            resolve("config", false, true),
+            resolve("service", false, true),
+            resolve("config", true, false),
+        ]
+    );
+}